@@ -0,0 +1,32 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use manger::common::*;
+use manger::config::Config;
+use manger::csv::CsvRecord;
+use manger::indent::Indented;
+use manger::{chars, Consumable};
+
+fn exercise<T: Consumable>(source: &str) {
+    let _ = T::consume_from(source);
+    let _ = T::consume_all(source);
+    let _ = T::consume_iter(source).take(64).count();
+    let _ = T::consume_all_diagnostics::<char>(source);
+}
+
+fuzz_target!(|source: &str| {
+    exercise::<u8>(source);
+    exercise::<i64>(source);
+    exercise::<u128>(source);
+    exercise::<f32>(source);
+    exercise::<f64>(source);
+    exercise::<char>(source);
+    exercise::<(Sign, OneOrMore<Digit>, Option<chars::Period>)>(source);
+    exercise::<SeparatedBy<i32, chars::Comma>>(source);
+    exercise::<Lines<Row<u16>>>(source);
+    exercise::<Indented<Line<u8>>>(source);
+    exercise::<Recovered<u8, (Vec<NoneOf<chars::category::Whitespace>>, Whitespace)>>(source);
+    exercise::<FromStrConsumer<f64>>(source);
+    exercise::<Config>(source);
+    exercise::<CsvRecord>(source);
+});
@@ -0,0 +1,17 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use manger::json::Json;
+use manger::limits::Limits;
+use manger::Consumable;
+
+const LIMITS: Limits = Limits {
+    max_depth: Some(512),
+    max_repetitions: Some(4096),
+    max_length: None,
+};
+
+fuzz_target!(|source: &str| {
+    let _ = Json::consume_all(source);
+    let _ = Json::consume_from_with_limits(source, LIMITS);
+});
@@ -0,0 +1,455 @@
+//! Parser for the small EBNF dialect of the `grammar` attribute.
+//!
+//! ```ebnf
+//! rule        = [ IDENT, ":=" ], alternative, { "|", alternative }, [ ";" ];
+//! alternative = [ IDENT, "=>" ], item, { item };
+//! item        = [ IDENT, ":" ], term, [ "*" | "+" | "?" ];
+//! term        = RUST_TYPE | CHAR_LITERAL | STRING_LITERAL | "(", term, { term }, ")";
+//! ```
+
+use std::fmt;
+
+/// An error within the grammar at a utf-8 character index.
+#[derive(Debug, PartialEq)]
+pub struct Error {
+    pub index: usize,
+    pub message: String,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at index {} of the grammar", self.message, self.index)
+    }
+}
+
+fn error<T>(index: usize, message: impl Into<String>) -> Result<T, Error> {
+    Err(Error {
+        index,
+        message: message.into(),
+    })
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Literal {
+    Char(char),
+    Str(String),
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Repetition {
+    /// `*`, zero or more.
+    Many,
+    /// `+`, one or more.
+    OneOrMore,
+    /// `?`, zero or one.
+    Optional,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Term {
+    Type(String),
+    Literal(Literal),
+    Group(Vec<Term>),
+    Repeat(Box<Term>, Repetition),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Item {
+    pub name: Option<String>,
+    pub term: Term,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Alternative {
+    pub label: Option<String>,
+    pub items: Vec<Item>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+enum Token {
+    Word(String),
+    Literal(Literal),
+    Define,
+    Arrow,
+    Colon,
+    Pipe,
+    Open,
+    Close,
+    Repetition(Repetition),
+    Semicolon,
+}
+
+/// Parse a rule of the grammar into its alternatives.
+pub fn parse(source: &str) -> Result<Vec<Alternative>, Error> {
+    let tokens = tokenize(source)?;
+    let mut parser = Parser {
+        tokens: &tokens,
+        position: 0,
+        end: source.chars().count(),
+    };
+
+    if let (Some(Token::Word(_)), Some(Token::Define)) = (parser.peek(0), parser.peek(1)) {
+        parser.position += 2;
+    }
+
+    let mut alternatives = vec![parser.alternative()?];
+    while parser.eat(&Token::Pipe) {
+        alternatives.push(parser.alternative()?);
+    }
+
+    parser.eat(&Token::Semicolon);
+    match parser.peek(0) {
+        None => Ok(alternatives),
+        Some(token) => error(parser.index(), format!("unexpected {}", describe(token))),
+    }
+}
+
+fn describe(token: &Token) -> String {
+    match token {
+        Token::Word(word) => format!("`{}`", word),
+        Token::Literal(Literal::Char(c)) => format!("{:?}", c),
+        Token::Literal(Literal::Str(s)) => format!("{:?}", s),
+        Token::Define => "`:=`".to_string(),
+        Token::Arrow => "`=>`".to_string(),
+        Token::Colon => "`:`".to_string(),
+        Token::Pipe => "`|`".to_string(),
+        Token::Open => "`(`".to_string(),
+        Token::Close => "`)`".to_string(),
+        Token::Repetition(Repetition::Many) => "`*`".to_string(),
+        Token::Repetition(Repetition::OneOrMore) => "`+`".to_string(),
+        Token::Repetition(Repetition::Optional) => "`?`".to_string(),
+        Token::Semicolon => "`;`".to_string(),
+    }
+}
+
+struct Parser<'t> {
+    tokens: &'t [(Token, usize)],
+    position: usize,
+    end: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self, ahead: usize) -> Option<&Token> {
+        self.tokens
+            .get(self.position + ahead)
+            .map(|(token, _)| token)
+    }
+
+    fn index(&self) -> usize {
+        self.tokens
+            .get(self.position)
+            .map_or(self.end, |(_, index)| *index)
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        let found = self.peek(0) == Some(token);
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    fn alternative(&mut self) -> Result<Alternative, Error> {
+        let label = match (self.peek(0), self.peek(1)) {
+            (Some(Token::Word(label)), Some(Token::Arrow)) => {
+                let label = label.clone();
+                self.position += 2;
+                Some(label)
+            }
+            _ => None,
+        };
+
+        let mut items = Vec::new();
+        while let Some(item) = self.item()? {
+            items.push(item);
+        }
+
+        if items.is_empty() {
+            return error(self.index(), "expected at least one item");
+        }
+
+        Ok(Alternative { label, items })
+    }
+
+    fn item(&mut self) -> Result<Option<Item>, Error> {
+        let name = match (self.peek(0), self.peek(1)) {
+            (Some(Token::Word(name)), Some(Token::Colon)) => {
+                let name = name.clone();
+                self.position += 2;
+                Some(name)
+            }
+            _ => None,
+        };
+
+        match self.term()? {
+            Some(term) => Ok(Some(Item { name, term })),
+            None if name.is_some() => error(self.index(), "expected a type after `:`"),
+            None => Ok(None),
+        }
+    }
+
+    fn term(&mut self) -> Result<Option<Term>, Error> {
+        let term = match self.peek(0).cloned() {
+            Some(Token::Word(word)) => {
+                self.position += 1;
+                Term::Type(word)
+            }
+            Some(Token::Literal(literal)) => {
+                self.position += 1;
+                Term::Literal(literal)
+            }
+            Some(Token::Open) => {
+                let opened_at = self.index();
+                self.position += 1;
+
+                let mut terms = Vec::new();
+                while !self.eat(&Token::Close) {
+                    match self.peek(0) {
+                        None => return error(opened_at, "unclosed `(`"),
+                        Some(Token::Word(_)) if self.peek(1) == Some(&Token::Colon) => {
+                            return error(self.index(), "items within a group cannot be named")
+                        }
+                        _ => {}
+                    }
+
+                    match self.term()? {
+                        Some(term) => terms.push(term),
+                        None => {
+                            let token = describe(self.peek(0).expect("a token is left"));
+                            return error(self.index(), format!("unexpected {} in group", token));
+                        }
+                    }
+                }
+
+                if terms.is_empty() {
+                    return error(opened_at, "expected at least one item in group");
+                }
+
+                Term::Group(terms)
+            }
+            _ => return Ok(None),
+        };
+
+        Ok(Some(match self.peek(0) {
+            Some(Token::Repetition(repetition)) => {
+                let repetition = *repetition;
+                self.position += 1;
+                Term::Repeat(Box::new(term), repetition)
+            }
+            _ => term,
+        }))
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<(Token, usize)>, Error> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut index = 0;
+
+    while index < chars.len() {
+        let start = index;
+        let next = chars.get(index + 1).copied();
+
+        let token = match chars[index] {
+            c if c.is_whitespace() => {
+                index += 1;
+                continue;
+            }
+            ':' if next == Some('=') => {
+                index += 2;
+                Token::Define
+            }
+            '=' if next == Some('>') => {
+                index += 2;
+                Token::Arrow
+            }
+            ':' => {
+                index += 1;
+                Token::Colon
+            }
+            '|' => {
+                index += 1;
+                Token::Pipe
+            }
+            '(' => {
+                index += 1;
+                Token::Open
+            }
+            ')' => {
+                index += 1;
+                Token::Close
+            }
+            '*' => {
+                index += 1;
+                Token::Repetition(Repetition::Many)
+            }
+            '+' => {
+                index += 1;
+                Token::Repetition(Repetition::OneOrMore)
+            }
+            '?' => {
+                index += 1;
+                Token::Repetition(Repetition::Optional)
+            }
+            ';' => {
+                index += 1;
+                Token::Semicolon
+            }
+            quote @ ('\'' | '"') => {
+                let (text, end) = quoted(&chars, index, quote)?;
+                index = end;
+
+                if quote == '"' {
+                    Token::Literal(Literal::Str(text))
+                } else {
+                    let mut text = text.chars();
+                    match (text.next(), text.next()) {
+                        (Some(c), None) => Token::Literal(Literal::Char(c)),
+                        _ => return error(start, "a character literal has exactly one character"),
+                    }
+                }
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                index = rust_type(&chars, index)?;
+                Token::Word(chars[start..index].iter().collect())
+            }
+            c => return error(index, format!("unexpected character {:?}", c)),
+        };
+
+        tokens.push((token, start));
+    }
+
+    Ok(tokens)
+}
+
+/// Read a quoted literal starting at `start`, returning its unescaped text and the index after
+/// the closing quote.
+fn quoted(chars: &[char], start: usize, quote: char) -> Result<(String, usize), Error> {
+    let mut text = String::new();
+    let mut index = start + 1;
+
+    loop {
+        match chars.get(index) {
+            None => return error(start, "unclosed literal"),
+            Some(c) if *c == quote => return Ok((text, index + 1)),
+            Some('\\') => {
+                text.push(match chars.get(index + 1) {
+                    Some('n') => '\n',
+                    Some('r') => '\r',
+                    Some('t') => '\t',
+                    Some('0') => '\0',
+                    Some(c @ ('\\' | '\'' | '"')) => *c,
+                    _ => return error(index, "unknown escape"),
+                });
+                index += 2;
+            }
+            Some(c) => {
+                text.push(*c);
+                index += 1;
+            }
+        }
+    }
+}
+
+/// Find the end of the rust type starting at `start`, which is a path with generic arguments.
+fn rust_type(chars: &[char], start: usize) -> Result<usize, Error> {
+    let mut index = start;
+
+    loop {
+        while chars
+            .get(index)
+            .is_some_and(|c| c.is_alphanumeric() || *c == '_')
+        {
+            index += 1;
+        }
+
+        if chars.get(index) == Some(&'<') {
+            let mut depth = 0;
+            loop {
+                match chars.get(index) {
+                    None => return error(start, "unclosed `<` in type"),
+                    Some('<') => depth += 1,
+                    Some('>') => depth -= 1,
+                    _ => {}
+                }
+                index += 1;
+
+                if depth == 0 {
+                    break;
+                }
+            }
+        }
+
+        if chars.get(index) == Some(&':') && chars.get(index + 1) == Some(&':') {
+            index += 2;
+        } else {
+            return Ok(index);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            parse("expr := left: Term ('+' Term)* ;"),
+            Ok(vec![Alternative {
+                label: None,
+                items: vec![
+                    Item {
+                        name: Some("left".to_string()),
+                        term: Term::Type("Term".to_string()),
+                    },
+                    Item {
+                        name: None,
+                        term: Term::Repeat(
+                            Box::new(Term::Group(vec![
+                                Term::Literal(Literal::Char('+')),
+                                Term::Type("Term".to_string()),
+                            ])),
+                            Repetition::Many
+                        ),
+                    },
+                ],
+            }])
+        );
+        assert_eq!(
+            parse(r#"A => "\"a" | B => value: Vec<Option<u8>>?"#),
+            Ok(vec![
+                Alternative {
+                    label: Some("A".to_string()),
+                    items: vec![Item {
+                        name: None,
+                        term: Term::Literal(Literal::Str("\"a".to_string())),
+                    }],
+                },
+                Alternative {
+                    label: Some("B".to_string()),
+                    items: vec![Item {
+                        name: Some("value".to_string()),
+                        term: Term::Repeat(
+                            Box::new(Term::Type("Vec<Option<u8>>".to_string())),
+                            Repetition::Optional
+                        ),
+                    }],
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let index = |source| parse(source).unwrap_err().index;
+
+        assert_eq!(index("a: "), 3);
+        assert_eq!(index("('a' b"), 0);
+        assert_eq!(index("(x: u8)"), 1);
+        assert_eq!(index("'ab'"), 0);
+        assert_eq!(index("u8 | "), 5);
+        assert_eq!(index("u8 ) "), 3);
+        assert_eq!(index("u8 $"), 3);
+    }
+}
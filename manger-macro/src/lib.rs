@@ -0,0 +1,206 @@
+//! Procedural macros for [__manger__](https://docs.rs/manger).
+//!
+//! These are used through the re-exports within manger with the `grammar` feature, and generate
+//! invocations of the `consume_struct` and `consume_enum` macros of manger.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, Ident, LitChar, LitStr, Token, Type};
+
+mod ebnf;
+
+use ebnf::{Alternative, Item, Literal, Repetition, Term};
+
+/// Generate the `Consumable` implementation of a `struct` or `enum` from an EBNF rule.
+///
+/// See the documentation of the re-export within manger for the supported dialect.
+#[proc_macro_attribute]
+pub fn grammar(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let arguments = parse_macro_input!(attr as Arguments);
+    let input = parse_macro_input!(item as DeriveInput);
+
+    let expanded = match expand(&arguments, &input) {
+        Ok(expanded) => expanded,
+        Err(err) => err.to_compile_error(),
+    };
+
+    quote!( #input #expanded ).into()
+}
+
+/// The arguments of the attribute, which are the rule followed by options such as `render`.
+struct Arguments {
+    rule: LitStr,
+    options: Vec<Ident>,
+}
+
+impl Parse for Arguments {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let rule = input.parse()?;
+        let mut options = Vec::new();
+
+        if input.parse::<Option<Token![,]>>()?.is_some() {
+            for option in Punctuated::<Ident, Token![,]>::parse_terminated(input)? {
+                if option != "render" && option != "ebnf" && option != "info" {
+                    return Err(syn::Error::new(
+                        option.span(),
+                        "unknown option, expected `render`, `ebnf` or `info`",
+                    ));
+                }
+                options.push(option);
+            }
+        }
+
+        Ok(Arguments { rule, options })
+    }
+}
+
+fn expand(arguments: &Arguments, input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let span = arguments.rule.span();
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "generic types are not supported by `grammar`",
+        ));
+    }
+
+    let alternatives = ebnf::parse(&arguments.rule.value())
+        .map_err(|err| syn::Error::new(span, err.to_string()))?;
+
+    let name = &input.ident;
+    let options = arguments.options.iter().map(|option| quote!( #[#option] ));
+
+    match &input.data {
+        Data::Struct(data) => {
+            let alternative = match &alternatives[..] {
+                [alternative] if alternative.label.is_none() => alternative,
+                _ => {
+                    return Err(syn::Error::new(
+                        span,
+                        "a struct has exactly one alternative without a variant label",
+                    ))
+                }
+            };
+            let body = sequence(alternative, &data.fields, span)?;
+
+            Ok(quote! {
+                ::manger::consume_struct!( #( #options )* #name => #body );
+            })
+        }
+        Data::Enum(data) => {
+            let variants = alternatives
+                .iter()
+                .map(|alternative| {
+                    let label = alternative.label.as_ref().ok_or_else(|| {
+                        syn::Error::new(span, "every alternative of an enum needs `Variant =>`")
+                    })?;
+                    let variant = data
+                        .variants
+                        .iter()
+                        .find(|variant| variant.ident == label)
+                        .ok_or_else(|| {
+                            syn::Error::new(span, format!("no variant named `{}`", label))
+                        })?;
+
+                    let ident = &variant.ident;
+                    let body = sequence(alternative, &variant.fields, span)?;
+                    Ok(quote!( #ident => #body ))
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+
+            Ok(quote! {
+                ::manger::consume_enum!( #( #options )* #name { #( #variants ),* } );
+            })
+        }
+        Data::Union(_) => Err(syn::Error::new(
+            span,
+            "unions are not supported by `grammar`",
+        )),
+    }
+}
+
+/// Generate the body of a `consume_struct` or a variant of `consume_enum` for the `alternative`,
+/// which fills `fields`.
+fn sequence(alternative: &Alternative, fields: &Fields, span: Span) -> syn::Result<TokenStream2> {
+    let instructions = alternative
+        .items
+        .iter()
+        .map(|item| instruction(item, span))
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let mapping = match fields {
+        Fields::Unnamed(_) => {
+            let names = alternative
+                .items
+                .iter()
+                .filter_map(|item| item.name.as_deref())
+                .map(|name| Ident::new(name, span));
+            quote!( ( #( #names ),* ) )
+        }
+        Fields::Named(_) | Fields::Unit => quote!(),
+    };
+
+    Ok(quote!( [ #( #instructions ),* ; #mapping ] ))
+}
+
+fn instruction(item: &Item, span: Span) -> syn::Result<TokenStream2> {
+    match (&item.name, &item.term) {
+        (None, Term::Literal(Literal::Char(c))) => {
+            let c = LitChar::new(*c, span);
+            Ok(quote!( > #c ))
+        }
+        (None, Term::Literal(Literal::Str(s))) => {
+            let s = LitStr::new(s, span);
+            Ok(quote!( > #s ))
+        }
+        (name, term) => {
+            let name = name.as_deref().map(|name| Ident::new(name, span));
+            let ty = term_type(term, span)?;
+            Ok(quote!( #name: #ty ))
+        }
+    }
+}
+
+fn term_type(term: &Term, span: Span) -> syn::Result<TokenStream2> {
+    Ok(match term {
+        Term::Type(text) => {
+            let ty = syn::parse_str::<Type>(text).map_err(|err| {
+                syn::Error::new(span, format!("invalid type `{}`: {}", text, err))
+            })?;
+            quote!( #ty )
+        }
+        Term::Literal(Literal::Char(c)) => {
+            let c = LitChar::new(*c, span);
+            quote!( ::manger::chars::Char<#c> )
+        }
+        Term::Literal(Literal::Str(s)) => {
+            return Err(syn::Error::new(
+                span,
+                format!(
+                    "the string literal {:?} can only be used outside of groups and repetitions",
+                    s
+                ),
+            ))
+        }
+        Term::Group(terms) => match &terms[..] {
+            [term] => term_type(term, span)?,
+            terms => {
+                let types = terms
+                    .iter()
+                    .map(|term| term_type(term, span))
+                    .collect::<syn::Result<Vec<_>>>()?;
+                quote!( ( #( #types ),* ) )
+            }
+        },
+        Term::Repeat(term, repetition) => {
+            let ty = term_type(term, span)?;
+            match repetition {
+                Repetition::Many => quote!( ::std::vec::Vec<#ty> ),
+                Repetition::OneOrMore => quote!( ::manger::common::OneOrMore<#ty> ),
+                Repetition::Optional => quote!( ::std::option::Option<#ty> ),
+            }
+        }
+    })
+}
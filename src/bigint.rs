@@ -0,0 +1,89 @@
+use crate::common::{FromDigits, OverflowMode};
+use crate::integers::consume_integer_item;
+use crate::{Consumable, ConsumeError, Expectation, Render, SelfConsumable};
+use num_bigint::{BigInt, BigUint};
+
+macro_rules! impl_consume_bigint {
+    ( $type:ty, $signed:literal ) => {
+        impl FromDigits for $type {
+            const SIGNED: bool = $signed;
+
+            fn zero() -> Self {
+                <$type>::default()
+            }
+
+            // Big integers never overflow, so every mode behaves the same.
+            fn push_digit(self, digit: u8, negative: bool, _mode: OverflowMode) -> Option<Self> {
+                let shifted = self * 10u8;
+
+                Some(if negative {
+                    shifted - digit
+                } else {
+                    shifted + digit
+                })
+            }
+        }
+
+        impl Consumable for $type {
+            fn expecting() -> Expectation {
+                Expectation::new("an integer")
+            }
+
+            fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+                <$type>::consume_digits(source, OverflowMode::Checked)
+            }
+        }
+
+        impl SelfConsumable for $type {
+            fn consume_item<'a>(source: &'a str, item: &'_ Self) -> Result<&'a str, ConsumeError> {
+                consume_integer_item(source, item)
+            }
+        }
+
+        impl Render for $type {
+            fn render_to(&self, out: &mut String) {
+                out.push_str(&self.to_string());
+            }
+        }
+    };
+}
+
+impl_consume_bigint!(BigUint, false);
+impl_consume_bigint!(BigInt, true);
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::{BigInt, BigUint};
+
+    use crate::error::ConsumeErrorType::*;
+    use crate::{Consumable, ConsumeError, Render, SelfConsumable};
+
+    #[test]
+    fn test_bigint() {
+        let huge = "-3402823669209384634633746074317682114560000";
+        let source = format!("{};", huge);
+        let (value, unconsumed) = BigInt::consume_from(&source).unwrap();
+
+        assert_eq!(value, huge.parse::<BigInt>().unwrap());
+        assert_eq!(unconsumed, ";");
+        assert_eq!(value.render(), huge);
+        assert_eq!(BigInt::consume_item("12x", &BigInt::from(12)), Ok("x"));
+    }
+
+    #[test]
+    fn test_biguint() {
+        let huge = "340282366920938463463374607431768211456";
+
+        assert_eq!(
+            BigUint::consume_all(huge).unwrap(),
+            huge.parse::<BigUint>().unwrap()
+        );
+        assert_eq!(
+            BigUint::consume_from("-1").unwrap_err(),
+            ConsumeError::new_with(UnexpectedToken {
+                index: 0,
+                token: '-'
+            })
+        );
+    }
+}
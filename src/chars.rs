@@ -1,22 +1,26 @@
 //! Types for consuming individual __utf-8 characters__.
 //!
-//! This module contains common ASCII characters,
-//! latin alphabetic letters and decimals numeric digits.
+//! This module contains the generic [`Char`] and [`CharRange`] consumers,
+//! aliases for common ASCII characters, latin alphabetic letters,
+//! decimals numeric digits and unicode character categories.
 
-use crate::error::ConsumeError;
+use crate::common::{AnyOf, CharSet};
 use crate::error::ConsumeErrorType::*;
-use crate::{Consumable, SelfConsumable};
+use crate::error::{ConsumeError, ConsumeErrorType};
+use crate::strs::split_first_char;
+use crate::{Consumable, Expectation, LiteralConsumable, Render, SelfConsumable};
+use std::marker::PhantomData;
 
 // Trait implementations for `char`
 // --------------------------------
 
 impl SelfConsumable for char {
     fn consume_item<'a>(source: &'a str, item: &'_ Self) -> Result<&'a str, ConsumeError> {
-        source.chars().next().map_or(
+        split_first_char(source).map_or(
             Err(ConsumeError::new_with(InsufficientTokens { index: 0 })),
-            |token| {
+            |(token, unconsumed)| {
                 if token == *item {
-                    Ok(utf8_slice::from(source, 1))
+                    Ok(unconsumed)
                 } else {
                     Err(ConsumeError::new_with(UnexpectedToken { index: 0, token }))
                 }
@@ -26,9 +30,13 @@ impl SelfConsumable for char {
 }
 
 impl Consumable for char {
+    fn expecting() -> Expectation {
+        Expectation::new("a character")
+    }
+
     fn consume_from(s: &str) -> Result<(Self, &str), ConsumeError> {
-        if let Some(token) = s.chars().next() {
-            Ok((token, utf8_slice::from(s, 1)))
+        if let Some(split) = split_first_char(s) {
+            Ok(split)
         } else {
             Err(ConsumeError::new_with(InsufficientTokens { index: 0 }))
         }
@@ -37,30 +45,160 @@ impl Consumable for char {
 
 // --------------------------------
 
-macro_rules! declare_ascii {
-    ( $( $struct_name:ident => $char:literal ),+ ) => {
-        $(
-            /// Chars that represents the respective utf-8 character.
-            #[derive(Debug, PartialEq)]
-            pub struct $struct_name;
+/// Zero-sized consumer for the utf-8 character `C`.
+///
+/// Any character literal can be used as `C`, including non-ASCII characters. The most common
+/// characters have an alias in this module, such as [`Comma`] and [`OpenParenthese`].
+///
+/// # Examples
+///
+/// ```
+/// use manger::{Consumable, Render};
+/// use manger::chars::Char;
+///
+/// type Arrow = (Char<'→'>, u32);
+///
+/// let (arrow, unconsumed) = Arrow::consume_from("→42;")?;
+/// assert_eq!(unconsumed, ";");
+/// assert_eq!(arrow.render(), "→42");
+///
+/// assert!(<Char<'→'>>::consume_from("->").is_err());
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+pub struct Char<const C: char>;
 
-            impl From<$struct_name> for char {
-                fn from(_: $struct_name) -> char {
-                    $char
-                }
+impl<const C: char> Char<C> {
+    /// Create a new instance of the zero-sized consumer.
+    pub const fn new() -> Self {
+        Char
+    }
+
+    /// Consume the same as [`consume_from`][Consumable::consume_from], but in a `const` context.
+    ///
+    /// See the [`constant`][crate::constant] module for more information.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use manger::chars::Char;
+    ///
+    /// const _: () = assert!(Char::<'→'>::try_consume_const("→42").is_ok());
+    /// assert!(Char::<'→'>::try_consume_const("->").is_err());
+    /// ```
+    pub const fn try_consume_const(source: &str) -> Result<(Self, &str), ConsumeErrorType> {
+        match crate::constant::consume_char(source) {
+            Ok((token, unconsumed)) if token == C => Ok((Char, unconsumed)),
+            Ok((token, _)) => Err(UnexpectedToken { index: 0, token }),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+impl<const C: char> From<Char<C>> for char {
+    fn from(_: Char<C>) -> char {
+        C
+    }
+}
+
+impl<const C: char> std::convert::TryFrom<Char<C>> for u8 {
+    type Error = std::char::TryFromCharError;
+
+    fn try_from(_: Char<C>) -> Result<u8, Self::Error> {
+        u8::try_from(C)
+    }
+}
+
+impl<const C: char> Consumable for Char<C> {
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        char::consume_item(source, &C).map(|unconsumed| (Char, unconsumed))
+    }
+}
+
+impl<const C: char> LiteralConsumable for Char<C> {}
+
+impl<const C: char> Render for Char<C> {
+    fn render_to(&self, out: &mut String) {
+        out.push(C);
+    }
+}
+
+/// Consumer for one utf-8 character within the inclusive range `LO` to `HI`.
+///
+/// Holds the consumed character. Consuming a character outside of the range fails with an
+/// [`UnexpectedToken`][crate::ConsumeErrorType::UnexpectedToken].
+///
+/// # Examples
+///
+/// ```
+/// use manger::{Consumable, ConsumeErrorType};
+/// use manger::chars::CharRange;
+///
+/// type Lower = CharRange<'a', 'z'>;
+///
+/// let (letters, unconsumed) = <Vec<Lower>>::consume_from("abcD")?;
+///
+/// assert_eq!(letters.iter().map(|letter| letter.token()).collect::<String>(), "abc");
+/// assert_eq!(unconsumed, "D");
+///
+/// assert_eq!(
+///     Lower::consume_from("D").unwrap_err().causes(),
+///     vec![&ConsumeErrorType::UnexpectedToken { index: 0, token: 'D' }]
+/// );
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct CharRange<const LO: char, const HI: char>(char);
+
+impl<const LO: char, const HI: char> CharRange<LO, HI> {
+    /// Get the utf-8 character that was consumed.
+    pub fn token(&self) -> char {
+        self.0
+    }
+
+    /// Consume the same as [`consume_from`][Consumable::consume_from], but in a `const` context.
+    ///
+    /// See the [`constant`][crate::constant] module for more information.
+    pub const fn try_consume_const(source: &str) -> Result<(Self, &str), ConsumeErrorType> {
+        match crate::constant::consume_char(source) {
+            Ok((token, unconsumed)) if LO <= token && token <= HI => {
+                Ok((CharRange(token), unconsumed))
             }
+            Ok((token, _)) => Err(UnexpectedToken { index: 0, token }),
+            Err(err) => Err(err),
+        }
+    }
+}
 
-            impl From<$struct_name> for u8 {
-                fn from(_: $struct_name) -> u8 {
-                    $char as u8
-                }
+impl<const LO: char, const HI: char> From<CharRange<LO, HI>> for char {
+    fn from(range: CharRange<LO, HI>) -> char {
+        range.0
+    }
+}
+
+impl<const LO: char, const HI: char> Consumable for CharRange<LO, HI> {
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        match split_first_char(source) {
+            Some((token, unconsumed)) if (LO..=HI).contains(&token) => {
+                Ok((CharRange(token), unconsumed))
             }
+            Some((token, _)) => Err(ConsumeError::new_with(UnexpectedToken { index: 0, token })),
+            None => Err(ConsumeError::new_with(InsufficientTokens { index: 0 })),
+        }
+    }
+}
+
+impl<const LO: char, const HI: char> Render for CharRange<LO, HI> {
+    fn render_to(&self, out: &mut String) {
+        out.push(self.0);
+    }
+}
 
-            crate::consume_struct!(
-                $struct_name => [
-                    > $char;
-                ]
-            );
+macro_rules! declare_ascii {
+    ( $( $alias:ident => $char:literal ),+ ) => {
+        $(
+            #[doc = concat!("Consumes the character `", stringify!($char), "`.")]
+            pub type $alias = $crate::chars::Char<$char>;
         )+
     };
 }
@@ -131,7 +269,7 @@ pub mod alpha {
                 ///
                 /// Upper-case letters gets turned into `Uppercase` and lower-case letters
                 /// gets turned into `Lowercase`.
-                #[derive(Debug, PartialEq)]
+                #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
                 pub enum $letter {
                     /// Lowercase variant of corresponding letter.
                     Lowercase,
@@ -145,24 +283,20 @@ pub mod alpha {
                         use $letter::*;
 
                         match letter {
-                            Lowercase => char::from( lower::$letter ),
-                            Uppercase => char::from( upper::$letter ),
+                            Lowercase => $lower_char,
+                            Uppercase => $upper_char,
                         }
                     }
                 }
 
                 impl From<$letter> for u8 {
                     fn from(letter: $letter) -> u8 {
-                        use $letter::*;
-
-                        match letter {
-                            Lowercase => u8::from( lower::$letter ),
-                            Uppercase => u8::from( upper::$letter ),
-                        }
+                        char::from(letter) as u8
                     }
                 }
 
                 crate::consume_enum!(
+                    #[render]
                     $letter {
                         Lowercase => [ : lower::$letter; ],
                         Uppercase => [ : upper::$letter; ]
@@ -211,3 +345,143 @@ pub mod alpha {
         Z => [ 'z', 'Z' ]
     ];
 }
+
+/// Zero-sized consumer for one utf-8 character within the category `C`.
+///
+/// The categories are defined within the [`category`] module, but any [`CharSet`] can be used as a
+/// category. The most common categories have an alias in this module, such as [`Alphabetic`] and
+/// [`Numeric`]. Use [`AnyOf`] instead to keep the consumed character.
+///
+/// # Examples
+///
+/// ```
+/// use manger::Consumable;
+/// use manger::chars::{Alphabetic, Alphanumeric};
+///
+/// type Identifier = (Alphabetic, Vec<Alphanumeric>);
+///
+/// let (_, unconsumed) = Identifier::consume_from("größe2 = 5")?;
+/// assert_eq!(unconsumed, " = 5");
+///
+/// assert!(Identifier::consume_from("2nd").is_err());
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq)]
+pub struct CharCategory<C>(PhantomData<C>);
+
+// These are implemented manually, so they do not require `C` to implement them as well.
+impl<C> Clone for CharCategory<C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C> Copy for CharCategory<C> {}
+
+impl<C> std::hash::Hash for CharCategory<C> {
+    fn hash<H: std::hash::Hasher>(&self, _: &mut H) {}
+}
+
+impl<C> CharCategory<C> {
+    /// Create a new instance of the zero-sized consumer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use manger::chars::{Alphabetic, Char};
+    ///
+    /// const LETTER: Alphabetic = Alphabetic::new();
+    /// const COMMA: Char<','> = Char::new();
+    ///
+    /// assert_eq!(LETTER, Alphabetic::default());
+    /// assert_eq!(char::from(COMMA), ',');
+    /// ```
+    pub const fn new() -> Self {
+        CharCategory(PhantomData)
+    }
+}
+
+impl<C> Default for CharCategory<C> {
+    fn default() -> Self {
+        CharCategory::new()
+    }
+}
+
+impl<C: CharSet> Consumable for CharCategory<C> {
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        <AnyOf<C>>::consume_from(source).map(|(_, unconsumed)| (CharCategory::new(), unconsumed))
+    }
+}
+
+impl<C: CharSet> LiteralConsumable for CharCategory<C> {}
+
+/// Consumes a character for which [`char::is_alphabetic`] holds.
+pub type Alphabetic = CharCategory<category::Alphabetic>;
+
+/// Consumes a character for which [`char::is_alphanumeric`] holds.
+pub type Alphanumeric = CharCategory<category::Alphanumeric>;
+
+/// Consumes a character for which [`char::is_numeric`] holds.
+pub type Numeric = CharCategory<category::Numeric>;
+
+/// Consumes a character for which [`char::is_control`] holds.
+pub type Control = CharCategory<category::Control>;
+
+/// Consumes a character for which [`char::is_lowercase`] holds.
+pub type Lowercase = CharCategory<category::Lowercase>;
+
+/// Consumes a character for which [`char::is_uppercase`] holds.
+pub type Uppercase = CharCategory<category::Uppercase>;
+
+/// Consumes a character for which [`char::is_ascii_punctuation`] holds.
+pub type Punctuation = CharCategory<category::Punctuation>;
+
+/// Character categories driven by the classification methods of [`char`].
+///
+/// All categories implement [`CharSet`], so they can be used with [`CharCategory`],
+/// [`AnyOf`] and [`NoneOf`][crate::common::NoneOf].
+///
+/// # Examples
+///
+/// ```
+/// use manger::Consumable;
+/// use manger::chars::category;
+/// use manger::common::{AnyOf, NoneOf};
+///
+/// let (digits, unconsumed) = <Vec<AnyOf<category::Numeric>>>::consume_from("٣4x")?;
+/// assert_eq!(digits.len(), 2);
+///
+/// let (letter, _) = <NoneOf<category::Uppercase>>::consume_from(unconsumed)?;
+/// assert_eq!(letter.token(), 'x');
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+pub mod category {
+    use crate::common::CharSet;
+
+    macro_rules! declare_category {
+        ( $( $category:ident => $method:ident ),+ ) => {
+            $(
+                /// Category of the characters for which the respective method on `char` holds.
+                #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+                pub struct $category;
+
+                impl CharSet for $category {
+                    fn contains(token: char) -> bool {
+                        token.$method()
+                    }
+                }
+            )+
+        };
+    }
+
+    declare_category![
+        Alphabetic => is_alphabetic,
+        Alphanumeric => is_alphanumeric,
+        Numeric => is_numeric,
+        Control => is_control,
+        Lowercase => is_lowercase,
+        Uppercase => is_uppercase,
+        Whitespace => is_whitespace,
+        Punctuation => is_ascii_punctuation
+    ];
+}
@@ -0,0 +1,223 @@
+use crate::context;
+use crate::{Consumable, ConsumeError, ConsumeErrorType, Expectation, LiteralConsumable, Render};
+
+/// Start of the input.
+///
+/// Will succeed in consumation if `source` is at the start of the input registered with
+/// [`context::scope`][crate::context::scope], which [`consume_all`][Consumable::consume_all],
+/// [`parse_prefix`][Consumable::parse_prefix] and [`consume_iter`][Consumable::consume_iter] do
+/// automatically. It does not consume any tokens.
+///
+/// # Without a registered input
+///
+/// A plain [`consume_from`][Consumable::consume_from] does not register an input, since it only
+/// receives the part of the input which is left to consume. If `source` is not part of a
+/// registered input, `source` itself is taken as the start of the input and `Start` always
+/// succeeds. Use [`parse_prefix`][Consumable::parse_prefix] instead of
+/// [`consume_from`][Consumable::consume_from] at the top level to have `Start` checked.
+///
+/// ```
+/// use manger::Consumable;
+/// use manger::common::Start;
+///
+/// let input = "ab";
+///
+/// // Without a registered input, the rest of the input is taken as the start.
+/// assert!(<(char, Start)>::consume_from(input).is_ok());
+/// assert!(<(char, Start)>::parse_prefix(input).is_err());
+/// ```
+///
+/// # Examples
+///
+/// ```
+/// use manger::Consumable;
+/// use manger::common::{Start, Whitespace};
+///
+/// type Header = (Start, char);
+///
+/// assert_eq!(<Vec<Header>>::consume_all("#")?.len(), 1);
+/// assert!(<(Whitespace, Header)>::consume_all(" #").is_err());
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+pub struct Start;
+
+/// Start of a line.
+///
+/// Will succeed in consumation if `source` is at the start of the input or directly after a
+/// `'\n'`. Just as [`Start`], this uses the input registered with
+/// [`context::scope`][crate::context::scope]. It does not consume any tokens.
+///
+/// Without a registered input, `source` itself is taken as the start of the input and
+/// `StartOfLine` always succeeds. See [`Start`] for how to register the input.
+///
+/// # Examples
+///
+/// ```
+/// use manger::Consumable;
+/// use manger::common::{StartOfLine, Whitespace};
+///
+/// type TwoLines = (u32, Whitespace, StartOfLine, u32);
+///
+/// assert!(TwoLines::consume_all("1\n2").is_ok());
+/// assert!(TwoLines::consume_all("1 2").is_err());
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+pub struct StartOfLine;
+
+/// End of a line.
+///
+/// Will succeed in consumation if the next tokens are `"\n"` or `"\r\n"`, or if the end of
+/// string has been reached. It does not consume any tokens.
+///
+/// # Examples
+///
+/// ```
+/// use manger::Consumable;
+/// use manger::common::EndOfLine;
+///
+/// let (_, unconsumed) = <(u32, EndOfLine)>::consume_from("42\r\n43")?;
+/// assert_eq!(unconsumed, "\r\n43");
+///
+/// assert!(<(u32, EndOfLine)>::consume_from("42").is_ok());
+/// assert!(<(u32, EndOfLine)>::consume_from("42 43").is_err());
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+pub struct EndOfLine;
+
+fn unexpected_token(source: &str) -> ConsumeError {
+    ConsumeError::new_with(match source.chars().next() {
+        Some(token) => ConsumeErrorType::UnexpectedToken { index: 0, token },
+        None => ConsumeErrorType::InsufficientTokens { index: 0 },
+    })
+}
+
+impl Consumable for Start {
+    fn expecting() -> Expectation {
+        Expectation::new("the start of the input")
+    }
+
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        if context::is_start(source) {
+            Ok((Start, source))
+        } else {
+            Err(unexpected_token(source))
+        }
+    }
+}
+
+impl LiteralConsumable for Start {}
+
+impl Consumable for StartOfLine {
+    fn expecting() -> Expectation {
+        Expectation::new("the start of a line")
+    }
+
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        if context::is_start_of_line(source) {
+            Ok((StartOfLine, source))
+        } else {
+            Err(unexpected_token(source))
+        }
+    }
+}
+
+impl LiteralConsumable for StartOfLine {}
+
+impl Consumable for EndOfLine {
+    fn expecting() -> Expectation {
+        Expectation::new("the end of a line")
+    }
+
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        if source.is_empty() || source.starts_with('\n') || source.starts_with("\r\n") {
+            Ok((EndOfLine, source))
+        } else {
+            Err(unexpected_token(source))
+        }
+    }
+}
+
+impl LiteralConsumable for EndOfLine {}
+
+macro_rules! render_nothing {
+    ( $( $anchor:ty ),+ ) => {
+        $(
+            impl Render for $anchor {
+                fn render_to(&self, _: &mut String) {}
+            }
+        )+
+    };
+}
+
+render_nothing!(Start, StartOfLine, EndOfLine);
+
+#[cfg(test)]
+mod tests {
+    use super::{EndOfLine, Start, StartOfLine};
+    use crate::error::ConsumeErrorType::*;
+    use crate::{context, Consumable, ConsumeError};
+
+    #[test]
+    fn test_start() {
+        let input = "ab";
+
+        assert!(Start::consume_from(&input[1..]).is_ok());
+        context::scope(input, || {
+            assert!(Start::consume_from(input).is_ok());
+            assert_eq!(
+                Start::consume_from(&input[1..]).unwrap_err(),
+                ConsumeError::new_with(UnexpectedToken {
+                    index: 0,
+                    token: 'b'
+                })
+            );
+            assert_eq!(
+                Start::consume_from(&input[2..]).unwrap_err(),
+                ConsumeError::new_with(InsufficientTokens { index: 0 })
+            );
+        });
+    }
+
+    #[test]
+    fn test_start_without_scope() {
+        let input = "ab";
+
+        // Without a registered input, every `source` is taken as the start.
+        assert!(<(char, Start)>::consume_from(input).is_ok());
+        assert!(<(char, StartOfLine)>::consume_from(input).is_ok());
+
+        // The entry points that register the input do check it.
+        assert!(<(char, Start)>::parse_prefix(input).is_err());
+        assert!(<(char, Start, char)>::consume_all(input).is_err());
+        assert_eq!(<(char, Start)>::consume_iter(input).count(), 0);
+        assert!(<(char, StartOfLine)>::parse_prefix(input).is_err());
+    }
+
+    #[test]
+    fn test_start_of_line() {
+        let input = "a\nb";
+
+        context::scope(input, || {
+            assert!(StartOfLine::consume_from(input).is_ok());
+            assert!(StartOfLine::consume_from(&input[1..]).is_err());
+            assert!(StartOfLine::consume_from(&input[2..]).is_ok());
+        });
+
+        assert_eq!(<(StartOfLine, char)>::consume_iter(input).count(), 1);
+        assert_eq!(
+            <Vec<(StartOfLine, char, char)>>::consume_all("a\nb\n")
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_end_of_line() {
+        assert_eq!(EndOfLine::consume_from("\n").unwrap().1, "\n");
+        assert!(EndOfLine::consume_from("").is_ok());
+        assert!(EndOfLine::consume_from("\r").is_err());
+    }
+}
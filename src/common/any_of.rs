@@ -0,0 +1,308 @@
+use crate::error::ConsumeErrorType::*;
+use crate::strs::split_first_char;
+use crate::{Consumable, ConsumeError, Render};
+use std::marker::PhantomData;
+
+/// Trait that defines a set of utf-8 characters.
+///
+/// This is used by [`AnyOf`] and [`NoneOf`] to decide which characters to consume. Most sets can
+/// be created with the [`char_set`][crate::char_set] macro, but the trait can also be implemented
+/// by hand for sets that are easier expressed with a function.
+///
+/// # Examples
+///
+/// ```
+/// use manger::Consumable;
+/// use manger::common::{AnyOf, CharSet};
+///
+/// struct Even;
+/// impl CharSet for Even {
+///     fn contains(token: char) -> bool {
+///         token.to_digit(10).map_or(false, |digit| digit % 2 == 0)
+///     }
+/// }
+///
+/// let (even, unconsumed) = <AnyOf<Even>>::consume_from("42")?;
+///
+/// assert_eq!(even.token(), '4');
+/// assert_eq!(unconsumed, "2");
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+pub trait CharSet {
+    /// Returns whether `token` is part of the set.
+    fn contains(token: char) -> bool;
+}
+
+/// Returns whether `token` is within the set described by `set`.
+///
+/// Every character in `set` is part of the set. Two characters separated by a `'-'` describe an
+/// inclusive range of characters. A `'\\'` escapes the next character, which allows a `'-'` or
+/// `'\\'` to be part of the set. A `'-'` at the start or the end of `set` is part of the set as
+/// well.
+///
+/// # Examples
+///
+/// ```
+/// use manger::common::char_set_contains;
+///
+/// assert!(char_set_contains("a-z_", 'q'));
+/// assert!(char_set_contains("a-z_", '_'));
+/// assert!(!char_set_contains("a-z_", 'Q'));
+///
+/// assert!(char_set_contains(r"+\-*/", '-'));
+/// assert!(!char_set_contains(r"+\-/", ','));
+/// ```
+pub fn char_set_contains(set: &str, token: char) -> bool {
+    fn unescape(character: char, rest: &mut std::str::Chars<'_>) -> (char, bool) {
+        if character == '\\' {
+            (rest.next().unwrap_or('\\'), true)
+        } else {
+            (character, false)
+        }
+    }
+
+    let mut characters = set.chars();
+    let mut range_start = None;
+
+    while let Some(character) = characters.next() {
+        let (character, escaped) = unescape(character, &mut characters);
+
+        if !escaped && character == '-' {
+            if let Some(start) = range_start {
+                let mut lookahead = characters.clone();
+
+                if let Some(end) = lookahead.next() {
+                    let (end, _) = unescape(end, &mut lookahead);
+                    characters = lookahead;
+
+                    if (start..=end).contains(&token) {
+                        return true;
+                    }
+
+                    range_start = None;
+                    continue;
+                }
+            }
+        }
+
+        if character == token {
+            return true;
+        }
+
+        range_start = Some(character);
+    }
+
+    false
+}
+
+/// A macro used to define sets of characters, which implement [`CharSet`][crate::common::CharSet].
+///
+/// The set is described with a string literal as explained in
+/// [`char_set_contains`][crate::common::char_set_contains].
+///
+/// # Examples
+///
+/// ```
+/// use manger::{char_set, Consumable};
+/// use manger::common::{AnyOf, NoneOf};
+///
+/// char_set! {
+///     /// Characters that can start an identifier.
+///     IdentStart => "a-zA-Z_";
+///     Operator => r"+\-*/"
+/// }
+///
+/// let (start, unconsumed) = <AnyOf<IdentStart>>::consume_from("_name")?;
+/// assert_eq!(start.token(), '_');
+///
+/// let (operator, _) = <AnyOf<Operator>>::consume_from("-1")?;
+/// assert_eq!(char::from(operator), '-');
+///
+/// assert!(<NoneOf<Operator>>::consume_from("*").is_err());
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[macro_export]
+macro_rules! char_set {
+    ( $( $( #[$attr:meta] )* $vis:vis $name:ident => $set:literal );+ $(;)? ) => {
+        $(
+            $( #[$attr] )*
+            #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+            $vis struct $name;
+
+            impl $crate::common::CharSet for $name {
+                fn contains(token: char) -> bool {
+                    $crate::common::char_set_contains($set, token)
+                }
+            }
+        )+
+    };
+}
+
+/// A macro used to define named character classes from a predicate.
+///
+/// Every class becomes a struct holding the consumed utf-8 character. It consumes one character
+/// for which the predicate returns `true` and fails with an
+/// [`UnexpectedToken`][crate::ConsumeErrorType::UnexpectedToken] otherwise. The struct
+/// implements [`Render`][crate::Render] and converts into a `char`. Unlike a filter closure
+/// within [`consume_struct`][crate::consume_struct], the class can be reused as a type within
+/// tuples, collections and enums.
+///
+/// The predicate is a closure without captures that takes a `char`.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{char_class, Consumable};
+///
+/// char_class! {
+///     /// A hexadecimal digit.
+///     pub HexDigit => |c| c.is_ascii_hexdigit();
+///     Lowercase => char::is_lowercase
+/// }
+///
+/// let (digits, unconsumed) = <Vec<HexDigit>>::consume_from("c0ffee!")?;
+///
+/// assert_eq!(digits.into_iter().map(char::from).collect::<String>(), "c0ffee");
+/// assert_eq!(unconsumed, "!");
+///
+/// assert_eq!(Lowercase::consume_from("aB")?.0.token(), 'a');
+/// assert!(Lowercase::consume_from("B").is_err());
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[macro_export]
+macro_rules! char_class {
+    ( $( $( #[$attr:meta] )* $vis:vis $name:ident => $predicate:expr );+ $(;)? ) => {
+        $(
+            $( #[$attr] )*
+            #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+            $vis struct $name(char);
+
+            impl $name {
+                /// Get the utf-8 character that was consumed.
+                pub fn token(&self) -> char {
+                    self.0
+                }
+            }
+
+            impl From<$name> for char {
+                fn from(consumed: $name) -> char {
+                    consumed.0
+                }
+            }
+
+            impl $crate::Render for $name {
+                fn render_to(&self, out: &mut String) {
+                    out.push(self.0);
+                }
+            }
+
+            impl $crate::Consumable for $name {
+                fn consume_from(
+                    source: &str,
+                ) -> Result<(Self, &str), $crate::ConsumeError> {
+                    let predicate: fn(char) -> bool = $predicate;
+                    let mut characters = source.chars();
+
+                    match characters.next() {
+                        Some(token) if predicate(token) => Ok(($name(token), characters.as_str())),
+                        Some(token) => Err($crate::ConsumeError::new_with(
+                            $crate::ConsumeErrorType::UnexpectedToken { index: 0, token },
+                        )),
+                        None => Err($crate::ConsumeError::new_with(
+                            $crate::ConsumeErrorType::InsufficientTokens { index: 0 },
+                        )),
+                    }
+                }
+            }
+        )+
+    };
+}
+
+/// Consumes one utf-8 character which is part of the set `S`.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{char_set, Consumable};
+/// use manger::common::AnyOf;
+///
+/// char_set!(Vowel => "aeiou");
+///
+/// let (vowels, unconsumed) = <Vec<AnyOf<Vowel>>>::consume_from("aeixyz")?;
+///
+/// assert_eq!(vowels.len(), 3);
+/// assert_eq!(unconsumed, "xyz");
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct AnyOf<S> {
+    token: char,
+    phantom: PhantomData<S>,
+}
+
+/// Consumes one utf-8 character which is __not__ part of the set `S`.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{char_set, Consumable};
+/// use manger::common::NoneOf;
+///
+/// char_set!(Quote => "\"'");
+///
+/// let (text, unconsumed) = <Vec<NoneOf<Quote>>>::consume_from("it's")?;
+///
+/// assert_eq!(text.into_iter().map(char::from).collect::<String>(), "it");
+/// assert_eq!(unconsumed, "'s");
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct NoneOf<S> {
+    token: char,
+    phantom: PhantomData<S>,
+}
+
+macro_rules! impl_char_set_consumer {
+    ( $type:ident, $is_allowed:expr ) => {
+        impl<S> $type<S> {
+            /// Get the utf-8 character that was consumed.
+            pub fn token(&self) -> char {
+                self.token
+            }
+        }
+
+        impl<S> From<$type<S>> for char {
+            fn from(consumed: $type<S>) -> char {
+                consumed.token
+            }
+        }
+
+        impl<S> Render for $type<S> {
+            fn render_to(&self, out: &mut String) {
+                out.push(self.token);
+            }
+        }
+
+        impl<S: CharSet> Consumable for $type<S> {
+            fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+                let (token, unconsumed) = split_first_char(source)
+                    .ok_or_else(|| ConsumeError::new_with(InsufficientTokens { index: 0 }))?;
+
+                if !($is_allowed)(S::contains(token)) {
+                    return Err(ConsumeError::new_with(UnexpectedToken { index: 0, token }));
+                }
+
+                Ok((
+                    $type {
+                        token,
+                        phantom: PhantomData,
+                    },
+                    unconsumed,
+                ))
+            }
+        }
+    };
+}
+
+impl_char_set_consumer!(AnyOf, |contained: bool| contained);
+impl_char_set_consumer!(NoneOf, |contained: bool| !contained);
@@ -0,0 +1,116 @@
+use crate::error::ConsumeErrorType::*;
+use crate::{Consumable, ConsumeError, Expectation, LiteralConsumable, Render};
+
+/// Consume the ASCII byte at the start of `source` for which `is_allowed` holds.
+///
+/// Only the first byte is looked at, so this is faster than consuming a `char`. Any other utf-8
+/// character results in an [`UnexpectedToken`][crate::ConsumeErrorType::UnexpectedToken].
+fn consume_ascii(
+    source: &str,
+    is_allowed: impl Fn(u8) -> bool,
+) -> Result<(u8, &str), ConsumeError> {
+    match source.as_bytes().first() {
+        Some(&byte) if is_allowed(byte) => {
+            debug_assert!(byte.is_ascii(), "only ASCII bytes may be allowed");
+            Ok((byte, &source[1..]))
+        }
+        Some(_) => Err(ConsumeError::new_with(UnexpectedToken {
+            index: 0,
+            token: source.chars().next().unwrap_or_default(),
+        })),
+        None => Err(ConsumeError::new_with(InsufficientTokens { index: 0 })),
+    }
+}
+
+/// An ASCII decimal digit, which is consumed by looking at one byte.
+///
+/// This is a faster alternative to [`Digit`][crate::common::Digit] for grammars that only contain
+/// ASCII digits.
+///
+/// # Examples
+///
+/// ```
+/// use manger::Consumable;
+/// use manger::common::AsciiDigit;
+///
+/// let (digits, unconsumed) = <Vec<AsciiDigit>>::consume_from("2021-06")?;
+/// let year = digits.iter().fold(0, |year, digit| year * 10 + u32::from(digit.value()));
+///
+/// assert_eq!(year, 2021);
+/// assert_eq!(unconsumed, "-06");
+/// assert!(AsciiDigit::consume_from("٣").is_err());
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct AsciiDigit(u8);
+
+impl AsciiDigit {
+    /// Get the value of the digit, from `0` to `9`.
+    pub fn value(&self) -> u8 {
+        self.0 - b'0'
+    }
+}
+
+impl From<AsciiDigit> for char {
+    fn from(digit: AsciiDigit) -> char {
+        char::from(digit.0)
+    }
+}
+
+impl Consumable for AsciiDigit {
+    fn expecting() -> Expectation {
+        Expectation::new("a digit")
+    }
+
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        consume_ascii(source, |byte| byte.is_ascii_digit())
+            .map(|(byte, unconsumed)| (AsciiDigit(byte), unconsumed))
+    }
+}
+
+impl Render for AsciiDigit {
+    fn render_to(&self, out: &mut String) {
+        out.push(char::from(self.0));
+    }
+}
+
+/// An ASCII whitespace character, which is consumed by looking at one byte.
+///
+/// This consumes the characters for which [`u8::is_ascii_whitespace`] holds. It is a faster
+/// alternative to [`Whitespace`][crate::common::Whitespace] for grammars that only contain ASCII
+/// whitespace.
+///
+/// # Examples
+///
+/// ```
+/// use manger::Consumable;
+/// use manger::common::AsciiWhitespace;
+///
+/// let (_, unconsumed) = <Vec<AsciiWhitespace>>::consume_from(" \t\r\nkey")?;
+///
+/// assert_eq!(unconsumed, "key");
+/// assert!(AsciiWhitespace::consume_from("\u{a0}").is_err());
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+pub struct AsciiWhitespace;
+
+impl Consumable for AsciiWhitespace {
+    fn expecting() -> Expectation {
+        Expectation::new("whitespace")
+    }
+
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        consume_ascii(source, |byte| byte.is_ascii_whitespace())
+            .map(|(_, unconsumed)| (AsciiWhitespace, unconsumed))
+    }
+}
+
+impl LiteralConsumable for AsciiWhitespace {}
+
+/// Renders a `' '`.
+impl Render for AsciiWhitespace {
+    fn render_to(&self, out: &mut String) {
+        out.push(' ');
+    }
+}
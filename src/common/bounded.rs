@@ -0,0 +1,158 @@
+use crate::error::ConsumeErrorType::*;
+use crate::{Consumable, ConsumeError, Render};
+use std::convert::TryInto;
+
+/// Consume items of `T` until that fails or `max` items have been consumed.
+///
+/// Returns the items, the unconsumed part of `source`, the utf-8 character length of the items
+/// and the error of the item that failed, if one failed.
+fn consume_up_to<T: Consumable>(
+    source: &str,
+    max: usize,
+) -> (Vec<T>, &str, usize, Option<ConsumeError>) {
+    let mut items = Vec::with_capacity(max);
+    let mut unconsumed = source;
+    let mut offset = 0;
+
+    while items.len() < max {
+        match T::consume_how_many_from(unconsumed) {
+            Ok((item, rest, by)) => {
+                items.push(item);
+                unconsumed = rest;
+                offset += by;
+            }
+            Err(err) => return (items, unconsumed, offset, Some(err.offset(offset))),
+        }
+    }
+
+    (items, unconsumed, offset, None)
+}
+
+/// Collection struct which stores exactly `N` items of type `T`.
+///
+/// This would be equivalent to the `{N}` operator in
+/// [RegEx](https://en.wikipedia.org/wiki/Regular_expression). When fewer than `N` items can be
+/// consumed, the error starts with a [`MissingItems`][crate::ConsumeErrorType::MissingItems]
+/// cause, which states how many items were found, followed by the causes of the item that failed.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{Consumable, ConsumeErrorType};
+/// use manger::common::{Digit, Exactly};
+///
+/// type Year = Exactly<Digit, 4>;
+///
+/// let (year, unconsumed) = Year::consume_from("20211")?;
+/// let year = year.iter().fold(0, |year, digit| year * 10 + digit.value::<u32>());
+///
+/// assert_eq!(year, 2021);
+/// assert_eq!(unconsumed, "1");
+///
+/// let error = Year::consume_from("20x1").map(|_| ()).unwrap_err();
+/// assert_eq!(
+///     error.causes()[0],
+///     &ConsumeErrorType::MissingItems { index: 2, found: 2, required: 4 }
+/// );
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Exactly<T, const N: usize>([T; N]);
+
+impl<T, const N: usize> Exactly<T, N> {
+    /// Unwrap to fetch the array of consumed items.
+    pub fn into_inner(self) -> [T; N] {
+        self.0
+    }
+}
+
+impl<T, const N: usize> std::ops::Deref for Exactly<T, N> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.0
+    }
+}
+
+impl<T: Consumable, const N: usize> Consumable for Exactly<T, N> {
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let (items, unconsumed, offset, err) = consume_up_to(source, N);
+
+        if let Some(err) = err {
+            let mut missing = ConsumeError::new_with(MissingItems {
+                index: offset,
+                found: items.len(),
+                required: N,
+            });
+            missing.add_causes(err);
+
+            return Err(missing);
+        }
+
+        match items.try_into() {
+            Ok(items) => Ok((Exactly(items), unconsumed)),
+            Err(_) => unreachable!("exactly `N` items were consumed"),
+        }
+    }
+}
+
+impl<T: Render, const N: usize> Render for Exactly<T, N> {
+    fn render_to(&self, out: &mut String) {
+        self.0.iter().for_each(|item| item.render_to(out));
+    }
+}
+
+/// Collection struct which stores up to `N` items of type `T`.
+///
+/// This would be equivalent to the `{0,N}` operator in
+/// [RegEx](https://en.wikipedia.org/wiki/Regular_expression). Consuming stops after `N` items,
+/// even when more items follow, and never fails.
+///
+/// # Examples
+///
+/// ```
+/// use manger::Consumable;
+/// use manger::common::AtMost;
+///
+/// let (code, unconsumed) = <AtMost<char, 3>>::consume_from("ABCDE")?;
+///
+/// assert_eq!(code.iter().collect::<String>(), "ABC");
+/// assert_eq!(unconsumed, "DE");
+///
+/// let (numbers, unconsumed) = <AtMost<u32, 3>>::consume_from("x")?;
+///
+/// assert!(numbers.is_empty());
+/// assert_eq!(unconsumed, "x");
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct AtMost<T, const N: usize>(Vec<T>);
+
+impl<T, const N: usize> AtMost<T, N> {
+    /// Unwrap to fetch the vector of consumed items, which contains at most `N` items.
+    pub fn into_vec(self) -> Vec<T> {
+        self.0
+    }
+}
+
+impl<T, const N: usize> std::ops::Deref for AtMost<T, N> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.0
+    }
+}
+
+impl<T: Consumable, const N: usize> Consumable for AtMost<T, N> {
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let (items, unconsumed, _, _) = consume_up_to(source, N);
+
+        Ok((AtMost(items), unconsumed))
+    }
+}
+
+impl<T: Render, const N: usize> Render for AtMost<T, N> {
+    fn render_to(&self, out: &mut String) {
+        self.0.render_to(out);
+    }
+}
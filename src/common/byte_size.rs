@@ -0,0 +1,170 @@
+use crate::error::ConsumeErrorType::*;
+use crate::{Consumable, ConsumeError, Expectation, Render};
+use std::convert::TryFrom;
+
+/// The units of a [`ByteSize`], ordered from large to small, with their size in bytes.
+const UNITS: [(&str, u64); 13] = [
+    ("EiB", 1 << 60),
+    ("EB", 1_000_000_000_000_000_000),
+    ("PiB", 1 << 50),
+    ("PB", 1_000_000_000_000_000),
+    ("TiB", 1 << 40),
+    ("TB", 1_000_000_000_000),
+    ("GiB", 1 << 30),
+    ("GB", 1_000_000_000),
+    ("MiB", 1 << 20),
+    ("MB", 1_000_000),
+    ("KiB", 1 << 10),
+    ("KB", 1_000),
+    ("B", 1),
+];
+
+/// The maximum amount of digits after the period that are taken into account.
+const MAX_FRACTION_DIGITS: u32 = 19;
+
+/// An amount of bytes, such as `10KB` or `3.5GiB`.
+///
+/// Consumes a number, optionally with digits after a period, directly followed by an optional
+/// unit. The decimal units `KB` (or `kB`), `MB`, `GB`, `TB`, `PB` and `EB` are powers of 1000
+/// and the binary units `KiB`, `MiB`, `GiB`, `TiB`, `PiB` and `EiB` are powers of 1024. A number
+/// without a unit or with the unit `B` is an amount of bytes. Fractions of a byte are truncated.
+///
+/// An amount that does not fit a `u64` results in an
+/// [`InvalidValue`][crate::ConsumeErrorType::InvalidValue] at the index of the unit.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{Consumable, Render};
+/// use manger::common::ByteSize;
+///
+/// assert_eq!(ByteSize::consume_all("10KB")?.bytes(), 10_000);
+/// assert_eq!(ByteSize::consume_all("3.5GiB")?.bytes(), 3_758_096_384);
+/// assert_eq!(ByteSize::consume_all("512")?.bytes(), 512);
+///
+/// assert!(ByteSize::consume_all("16EiB").is_err());
+///
+/// assert_eq!(ByteSize::from(1536 * 1024).render(), "1536KiB");
+/// assert_eq!(ByteSize::from(2_000_000).render(), "2MB");
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Default)]
+pub struct ByteSize(u64);
+
+impl ByteSize {
+    /// Get the amount of bytes.
+    pub fn bytes(&self) -> u64 {
+        self.0
+    }
+}
+
+impl From<u64> for ByteSize {
+    fn from(bytes: u64) -> Self {
+        ByteSize(bytes)
+    }
+}
+
+impl From<ByteSize> for u64 {
+    fn from(size: ByteSize) -> Self {
+        size.0
+    }
+}
+
+impl Consumable for ByteSize {
+    fn expecting() -> Expectation {
+        Expectation::new("a byte size")
+    }
+
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let (integer, mut unconsumed) = u64::consume_from(source)?;
+
+        let mut numerator: u128 = 0;
+        let mut denominator: u128 = 1;
+        let bytes = unconsumed.as_bytes();
+        if bytes.first() == Some(&b'.') && bytes.get(1).is_some_and(u8::is_ascii_digit) {
+            let digits = unconsumed[1..]
+                .bytes()
+                .take_while(u8::is_ascii_digit)
+                .collect::<Vec<_>>();
+
+            for digit in digits.iter().take(MAX_FRACTION_DIGITS as usize) {
+                numerator = numerator * 10 + u128::from(digit - b'0');
+                denominator *= 10;
+            }
+
+            unconsumed = &unconsumed[1 + digits.len()..];
+        }
+
+        // All consumed characters are ASCII, so byte indices equal utf-8 character indices.
+        let unit_index = source.len() - unconsumed.len();
+        let (unit, unconsumed) = UNITS
+            .iter()
+            .chain(&[("kB", 1_000)])
+            .find_map(|(suffix, unit)| {
+                unconsumed
+                    .strip_prefix(suffix)
+                    .map(|rest| (u128::from(*unit), rest))
+            })
+            .unwrap_or((1, unconsumed));
+
+        let size = u128::from(integer) * unit + numerator * unit / denominator;
+
+        u64::try_from(size)
+            .map(|size| (ByteSize(size), unconsumed))
+            .map_err(|_| ConsumeError::new_with(InvalidValue { index: unit_index }))
+    }
+}
+
+/// Renders the amount with the largest unit that divides it exactly.
+impl Render for ByteSize {
+    fn render_to(&self, out: &mut String) {
+        let (suffix, unit) = UNITS
+            .iter()
+            .find(|(_, unit)| self.0 > 0 && self.0 % unit == 0)
+            .unwrap_or(&("B", 1));
+
+        out.push_str(&(self.0 / unit).to_string());
+        out.push_str(suffix);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ByteSize;
+    use crate::error::ConsumeErrorType::*;
+    use crate::{Consumable, ConsumeError, Render};
+
+    #[test]
+    fn test_byte_size() {
+        assert_eq!(
+            ByteSize::consume_from("1.5kB/s").unwrap(),
+            (ByteSize(1500), "/s")
+        );
+        assert_eq!(ByteSize::consume_from("0.3KiB").unwrap().0.bytes(), 307);
+        assert_eq!(
+            ByteSize::consume_from("2.GB").unwrap(),
+            (ByteSize(2), ".GB")
+        );
+        assert_eq!(
+            ByteSize::consume_all("15.99999999999999999999EiB")
+                .unwrap()
+                .bytes(),
+            u64::MAX
+        );
+        assert_eq!(
+            ByteSize::consume_from("20EB").unwrap_err(),
+            ConsumeError::new_with(InvalidValue { index: 2 })
+        );
+        assert_eq!(
+            ByteSize::consume_from("-1B").unwrap_err(),
+            ConsumeError::new_with(UnexpectedToken {
+                index: 0,
+                token: '-'
+            })
+        );
+
+        for source in &["0B", "1B", "1KiB", "999KB", "3EiB"] {
+            assert_eq!(ByteSize::consume_all(source).unwrap().render(), *source);
+        }
+    }
+}
@@ -1,13 +1,5 @@
-use crate::consume_struct;
+use crate::common::Empty;
 
-/// A catch-all clause for consuming.
-///
-/// Most often used with Enums and with the `Either<L, R>` struct.
-#[derive(Debug, PartialEq)]
-pub struct CatchAll;
-
-consume_struct!(
-    CatchAll => [
-        > "";
-    ]
-);
+/// A catch-all clause for consuming, which is the same as [`Empty`].
+#[deprecated(note = "use `common::Empty` instead")]
+pub type CatchAll = Empty;
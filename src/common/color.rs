@@ -0,0 +1,255 @@
+use super::{AtMost, Ws};
+use crate::chars::{CloseParenthese, Comma};
+use crate::error::ConsumeErrorType::*;
+use crate::{char_class, lit, Consumable, ConsumeError, ConsumeSource, Expectation, Render};
+
+/// A color with 8-bit red, green, blue and alpha channels.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+pub struct Rgba {
+    /// The red channel.
+    pub r: u8,
+    /// The green channel.
+    pub g: u8,
+    /// The blue channel.
+    pub b: u8,
+    /// The alpha channel, where `255` is opaque.
+    pub a: u8,
+}
+
+char_class!(HexDigit => |c| c.is_ascii_hexdigit());
+
+lit! {
+    "rgb(" => Rgb;
+    "rgba(" => RgbaOpen
+}
+
+impl HexDigit {
+    fn value(&self) -> u8 {
+        self.token().to_digit(16).unwrap_or_default() as u8
+    }
+}
+
+/// An alpha channel written as a number from `0` to `1`.
+struct Alpha(u8);
+
+impl Consumable for Alpha {
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let (alpha, unconsumed) = f32::consume_from(source)?;
+
+        if !(0.0..=1.0).contains(&alpha) {
+            return Err(ConsumeError::new_with(InvalidValue { index: 0 }));
+        }
+
+        Ok((Alpha((alpha * 255.0).round() as u8), unconsumed))
+    }
+}
+
+type RgbNotation = Ws<(Rgb, u8, Comma, u8, Comma, u8, CloseParenthese)>;
+type RgbaNotation = Ws<(
+    RgbaOpen,
+    u8,
+    Comma,
+    u8,
+    Comma,
+    u8,
+    Comma,
+    Alpha,
+    CloseParenthese,
+)>;
+
+fn consume_hex_notation(source: &str) -> Result<(Rgba, &str), ConsumeError> {
+    let mut unconsumed = source;
+
+    unconsumed.mut_consume_lit(&'#')?;
+    let digits = unconsumed
+        .mut_consume::<AtMost<HexDigit, 8>>()?
+        .iter()
+        .map(HexDigit::value)
+        .collect::<Vec<_>>();
+
+    let channel = |index: usize| digits[index] << 4 | digits[index + 1];
+    let rgba = match digits.len() {
+        3 => Rgba {
+            r: digits[0] * 0x11,
+            g: digits[1] * 0x11,
+            b: digits[2] * 0x11,
+            a: 255,
+        },
+        6 => Rgba {
+            r: channel(0),
+            g: channel(2),
+            b: channel(4),
+            a: 255,
+        },
+        8 => Rgba {
+            r: channel(0),
+            g: channel(2),
+            b: channel(4),
+            a: channel(6),
+        },
+        found => {
+            return Err(ConsumeError::new_with(match unconsumed.chars().next() {
+                Some(token) if found != 8 => UnexpectedToken {
+                    index: found + 1,
+                    token,
+                },
+                Some(_) => InvalidValue { index: found + 1 },
+                None => InsufficientTokens { index: found + 1 },
+            }))
+        }
+    };
+
+    if unconsumed.starts_with(|token: char| token.is_ascii_hexdigit()) {
+        return Err(ConsumeError::new_with(InvalidValue {
+            index: digits.len() + 1,
+        }));
+    }
+
+    Ok((rgba, unconsumed))
+}
+
+/// A color literal as used in stylesheets.
+///
+/// Consumes the hexadecimal notations `#RGB`, `#RRGGBB` and `#RRGGBBAA` and the functional
+/// notations `rgb(r, g, b)` and `rgba(r, g, b, alpha)`. The channels of the functional notations
+/// are integers from `0` to `255` and the alpha is a number from `0` to `1`. Whitespace is allowed
+/// around the numbers.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{Consumable, Render};
+/// use manger::common::{Color, Rgba};
+///
+/// let (color, unconsumed) = Color::consume_from("#f80;")?;
+///
+/// assert_eq!(color.rgba(), Rgba { r: 0xff, g: 0x88, b: 0x00, a: 0xff });
+/// assert_eq!(unconsumed, ";");
+///
+/// assert_eq!(
+///     Color::consume_all("rgba(255, 128, 0, 0.5)")?.rgba(),
+///     Rgba { r: 255, g: 128, b: 0, a: 128 }
+/// );
+/// assert_eq!(Color::consume_all("rgb( 0,0 , 255 )")?.render(), "#0000ff");
+///
+/// assert!(Color::consume_all("#ff80").is_err());
+/// assert!(Color::consume_all("rgb(256, 0, 0)").is_err());
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+pub struct Color(Rgba);
+
+impl Color {
+    /// Get the channels of the color.
+    pub fn rgba(&self) -> Rgba {
+        self.0
+    }
+}
+
+impl From<Rgba> for Color {
+    fn from(rgba: Rgba) -> Self {
+        Color(rgba)
+    }
+}
+
+impl From<Color> for Rgba {
+    fn from(color: Color) -> Self {
+        color.0
+    }
+}
+
+impl Consumable for Color {
+    fn expecting() -> Expectation {
+        Expectation::new("a color")
+    }
+
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        if source.starts_with('#') {
+            return consume_hex_notation(source)
+                .map(|(rgba, unconsumed)| (Color(rgba), unconsumed));
+        }
+
+        let mut error = ConsumeError::new();
+
+        match RgbNotation::consume_from(source) {
+            Ok((rgb, unconsumed)) => {
+                let (_, r, _, g, _, b, _) = rgb.into_inner();
+                return Ok((Color(Rgba { r, g, b, a: 255 }), unconsumed));
+            }
+            Err(err) => error.add_causes(err),
+        }
+
+        match RgbaNotation::consume_from(source) {
+            Ok((rgba, unconsumed)) => {
+                let (_, r, _, g, _, b, _, Alpha(a), _) = rgba.into_inner();
+                Ok((Color(Rgba { r, g, b, a }), unconsumed))
+            }
+            Err(err) => {
+                error.add_causes(err);
+                Err(error)
+            }
+        }
+    }
+}
+
+/// Renders `#rrggbb`, or `#rrggbbaa` when the color is not opaque.
+impl Render for Color {
+    fn render_to(&self, out: &mut String) {
+        let Rgba { r, g, b, a } = self.0;
+
+        out.push_str(&format!("#{:02x}{:02x}{:02x}", r, g, b));
+        if a != 255 {
+            out.push_str(&format!("{:02x}", a));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Color, Rgba};
+    use crate::error::ConsumeErrorType::*;
+    use crate::{Consumable, ConsumeError, Render};
+
+    #[test]
+    fn test_hex_notation() {
+        assert_eq!(
+            Color::consume_all("#11223380").unwrap().rgba(),
+            Rgba {
+                r: 0x11,
+                g: 0x22,
+                b: 0x33,
+                a: 0x80
+            }
+        );
+        assert_eq!(
+            Color::consume_from("#12g").unwrap_err(),
+            ConsumeError::new_with(UnexpectedToken {
+                index: 3,
+                token: 'g'
+            })
+        );
+        assert_eq!(
+            Color::consume_from("#123456789").unwrap_err(),
+            ConsumeError::new_with(InvalidValue { index: 9 })
+        );
+        assert_eq!(
+            Color::consume_from("#1234").unwrap_err(),
+            ConsumeError::new_with(InsufficientTokens { index: 5 })
+        );
+    }
+
+    #[test]
+    fn test_functional_notation() {
+        assert_eq!(
+            Color::consume_from("rgba(1,2,3,1.5)")
+                .unwrap_err()
+                .causes()
+                .last(),
+            Some(&&InvalidValue { index: 11 })
+        );
+
+        for source in &["#000000", "#ffffff00", "#0a0b0c0d"] {
+            assert_eq!(Color::consume_all(source).unwrap().render(), *source);
+        }
+    }
+}
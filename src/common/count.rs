@@ -0,0 +1,62 @@
+use crate::{Consumable, ConsumeError, Render};
+use std::marker::PhantomData;
+
+/// Consumes zero or more items of type `T`, like a `Vec<T>`, but only keeps how many were consumed.
+///
+/// This avoids allocating a `Vec<T>` when only its length is needed, such as when counting the
+/// spaces of an indentation. Rendering a `Count<T>` renders the default item of `T` that many
+/// times.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{Consumable, Render};
+/// use manger::chars::Space;
+/// use manger::common::Count;
+///
+/// let (indentation, unconsumed) = <Count<Space>>::consume_from("    return;")?;
+///
+/// assert_eq!(indentation.count(), 4);
+/// assert_eq!(unconsumed, "return;");
+/// assert_eq!(indentation.render(), "    ");
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Count<T> {
+    count: usize,
+    phantom: PhantomData<T>,
+}
+
+impl<T> Count<T> {
+    /// Get the amount of items that were consumed.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Unwrap to fetch the amount of items that were consumed.
+    pub fn into_inner(self) -> usize {
+        self.count
+    }
+}
+
+impl<T: Consumable> Consumable for Count<T> {
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let mut count = 0;
+        let unconsumed = crate::limits::repeat_each(source, 0, |_| true, |_: T| count += 1)?;
+
+        Ok((
+            Count {
+                count,
+                phantom: PhantomData,
+            },
+            unconsumed,
+        ))
+    }
+}
+
+impl<T: Render + Default> Render for Count<T> {
+    fn render_to(&self, out: &mut String) {
+        let item = T::default();
+        (0..self.count).for_each(|_| item.render_to(out));
+    }
+}
@@ -0,0 +1,202 @@
+use crate::{Consumable, ConsumeError, Render};
+use std::mem::ManuallyDrop;
+use std::ops::{Deref, DerefMut};
+
+/// Amount of stack that has to be left before a [`DeepRecursion`] is consumed or dropped on the
+/// current stack.
+const RED_ZONE: usize = 128 * 1024;
+
+/// Size of the stack segments which are allocated when less than [`RED_ZONE`] is left.
+const SEGMENT_STACK_SIZE: usize = 4 * 1024 * 1024;
+
+/// Run `f` on the current stack if enough of it is left, or on a new heap allocated stack segment
+/// of the current thread otherwise.
+fn segmented<R>(f: impl FnOnce() -> R) -> R {
+    stacker::maybe_grow(RED_ZONE, SEGMENT_STACK_SIZE, f)
+}
+
+/// Heap allocated item for recursive grammars, which can be nested arbitrarily deep.
+///
+/// A [`Box<T>`] that is consumed recursively uses the call stack for every level of nesting.
+/// Therefore, a `source` with many levels of nesting, such as a hundred thousand `'('`s, can
+/// overflow the stack. A `DeepRecursion<T>` is consumed as a [`Box<T>`], but continues on a new
+/// heap allocated stack segment when the current stack is about to run out. Dropping a
+/// `DeepRecursion<T>` is handled in the same way. The segments belong to the current thread, so
+/// thread local state, such as the [`context`][crate::context], the [`limits`][crate::limits] and
+/// the [`MergePolicy`][crate::MergePolicy], is unaffected.
+///
+/// Other recursive functions, such as derived implementations of [`Debug`] or [`PartialEq`], still
+/// use the call stack for every level of nesting.
+///
+/// This type is only available with the `deep-recursion` feature.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{consume_enum, Consumable};
+/// use manger::common::DeepRecursion;
+///
+/// enum Nested {
+///     Leaf,
+///     Group(DeepRecursion<Nested>),
+/// }
+/// consume_enum!(
+///     Nested {
+///         Leaf => [ > 'x'; ],
+///         Group => [ > '(', inner: DeepRecursion<Nested>, > ')'; (inner) ]
+///     }
+/// );
+///
+/// let depth = 20_000;
+/// let source = format!("{}x{}", "(".repeat(depth), ")".repeat(depth));
+///
+/// let mut nested = &Nested::consume_all(&source)?;
+/// let mut count = 0;
+/// while let Nested::Group(inner) = nested {
+///     nested = inner;
+///     count += 1;
+/// }
+///
+/// assert_eq!(count, depth);
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct DeepRecursion<T>(ManuallyDrop<Box<T>>);
+
+impl<T> DeepRecursion<T> {
+    /// Allocate `item` on the heap.
+    pub fn new(item: T) -> Self {
+        DeepRecursion(ManuallyDrop::new(Box::new(item)))
+    }
+
+    /// Unwrap to fetch the item.
+    pub fn into_inner(self) -> T {
+        let mut this = ManuallyDrop::new(self);
+
+        // SAFETY: `this` is never used or dropped again.
+        *unsafe { ManuallyDrop::take(&mut this.0) }
+    }
+}
+
+impl<T> Deref for DeepRecursion<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for DeepRecursion<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T> Drop for DeepRecursion<T> {
+    fn drop(&mut self) {
+        // SAFETY: `self.0` is never used again, since `self` is being dropped.
+        let item = unsafe { ManuallyDrop::take(&mut self.0) };
+
+        segmented(move || drop(item));
+    }
+}
+
+impl<T: Consumable> Consumable for DeepRecursion<T> {
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        segmented(|| T::consume_from(source))
+            .map(|(item, unconsumed)| (DeepRecursion::new(item), unconsumed))
+    }
+}
+
+impl<T: Render> Render for DeepRecursion<T> {
+    fn render_to(&self, out: &mut String) {
+        (**self).render_to(out);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DeepRecursion;
+    use crate::Consumable;
+
+    #[derive(Debug, PartialEq)]
+    enum Nested {
+        Leaf,
+        Group(DeepRecursion<Nested>),
+    }
+    crate::consume_enum!(
+        Nested {
+            Leaf => [ > 'x'; ],
+            Group => [ > '(', inner: DeepRecursion<Nested>, > ')'; (inner) ]
+        }
+    );
+
+    #[test]
+    fn test_deep_nesting() {
+        let depth = 100_000;
+        let source = format!("{}x{}", "(".repeat(depth), ")".repeat(depth));
+
+        assert!(Nested::consume_all(&source).is_ok());
+
+        let unclosed = format!("{}x{}", "(".repeat(depth), ")".repeat(depth - 1));
+        assert!(Nested::consume_all(&unclosed).is_err());
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum Tree {
+        Group(DeepRecursion<Tree>),
+        Leaf,
+    }
+    crate::consume_enum!(
+        Tree {
+            Group => [ > '(', inner: DeepRecursion<Tree>, > ')'; (inner) ],
+            Leaf => [ > "xy"; ]
+        }
+    );
+
+    #[test]
+    fn test_merge_policy_at_depth() {
+        let depth = 10_000;
+        let source = format!("{}x", "(".repeat(depth));
+
+        let error = crate::MergePolicy::Deepest.scope(|| Tree::consume_all(&source).unwrap_err());
+        assert!(error
+            .causes()
+            .iter()
+            .all(|cause| *cause.index() == depth + 1));
+
+        let error = Tree::consume_all(&source).unwrap_err();
+        assert!(error.causes().iter().any(|cause| *cause.index() == 0));
+    }
+
+    #[cfg(feature = "debug-trace")]
+    #[test]
+    fn test_debug_trace_at_depth() {
+        let depth = 1_000;
+        let source = format!("{}x{}", "(".repeat(depth), ")".repeat(depth));
+
+        let (result, trace) = Nested::consume_debug_from(&source);
+        assert!(result.is_ok());
+
+        let mut trace = &trace;
+        let mut count = 0;
+        while let Some(child) = trace.children().first() {
+            trace = child;
+            count += 1;
+        }
+        assert!(count >= depth);
+    }
+
+    #[test]
+    fn test_into_inner() {
+        let group = Nested::consume_all("((x))").unwrap();
+
+        match group {
+            Nested::Group(inner) => assert_eq!(
+                inner.into_inner(),
+                Nested::Group(DeepRecursion::new(Nested::Leaf))
+            ),
+            Nested::Leaf => panic!("expected a group"),
+        }
+    }
+}
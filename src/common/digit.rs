@@ -1,4 +1,7 @@
-use crate::consume_enum;
+use crate::error::ConsumeErrorType::*;
+use crate::strs::split_first_char;
+use crate::{Consumable, ConsumeError, ConsumeErrorType, Expectation, Render};
+use std::convert::TryFrom;
 
 /// Enum representing a decimal digit.
 ///
@@ -19,7 +22,7 @@ use crate::consume_enum;
 /// );
 /// # Ok::<(), manger::ConsumeError>(())
 /// ```
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
 pub enum Digit {
     /// Consuming found a '0'.
     Zero,
@@ -52,22 +55,99 @@ pub enum Digit {
     Nine,
 }
 
-consume_enum!(
-    Digit {
-        Zero => [ > '0'; ],
-        One => [ > '1'; ],
-        Two => [ > '2'; ],
-        Three => [ > '3'; ],
-        Four => [ > '4'; ],
-        Five => [ > '5'; ],
-        Six => [ > '6'; ],
-        Seven => [ > '7'; ],
-        Eight => [ > '8'; ],
-        Nine => [ > '9'; ]
+/// Converts the utf-8 characters `'0'` to `'9'` into the corresponding `Digit`.
+///
+/// Any other character results in an [`UnexpectedToken`][ConsumeErrorType::UnexpectedToken] at
+/// index `0`.
+///
+/// # Examples
+///
+/// ```
+/// use manger::common::Digit;
+/// use std::convert::TryFrom;
+///
+/// assert_eq!(Digit::try_from('7'), Ok(Digit::Seven));
+/// assert!(Digit::try_from('x').is_err());
+/// assert_eq!(char::from(&Digit::Seven), '7');
+/// ```
+impl TryFrom<char> for Digit {
+    type Error = ConsumeErrorType;
+
+    fn try_from(token: char) -> Result<Self, Self::Error> {
+        use Digit::*;
+
+        Ok(match token {
+            '0' => Zero,
+            '1' => One,
+            '2' => Two,
+            '3' => Three,
+            '4' => Four,
+            '5' => Five,
+            '6' => Six,
+            '7' => Seven,
+            '8' => Eight,
+            '9' => Nine,
+            _ => return Err(UnexpectedToken { index: 0, token }),
+        })
+    }
+}
+
+impl From<&Digit> for char {
+    fn from(digit: &Digit) -> Self {
+        char::from(b'0' + digit.value::<u8>())
+    }
+}
+
+impl Consumable for Digit {
+    fn expecting() -> Expectation {
+        Expectation::new("a digit")
+    }
+
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let (token, unconsumed) = split_first_char(source)
+            .ok_or_else(|| ConsumeError::new_with(InsufficientTokens { index: 0 }))?;
+
+        Digit::try_from(token)
+            .map(|digit| (digit, unconsumed))
+            .map_err(ConsumeError::new_with)
     }
-);
+}
+
+impl Render for Digit {
+    fn render_to(&self, out: &mut String) {
+        out.push(char::from(self));
+    }
+}
 
 impl Digit {
+    /// Consume the same as [`consume_from`][Consumable::consume_from], but in a `const` context.
+    ///
+    /// See the [`constant`][crate::constant] module for more information.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use manger::common::Digit;
+    ///
+    /// const SEVEN: Digit = match Digit::try_consume_const("7") {
+    ///     Ok((digit, _)) => digit,
+    ///     Err(_) => panic!(),
+    /// };
+    /// assert_eq!(SEVEN, Digit::Seven);
+    /// ```
+    pub const fn try_consume_const(source: &str) -> Result<(Self, &str), ConsumeErrorType> {
+        use Digit::*;
+        const DIGITS: [Digit; 10] = [Zero, One, Two, Three, Four, Five, Six, Seven, Eight, Nine];
+
+        match crate::constant::consume_char(source) {
+            Ok((token, unconsumed)) if token.is_ascii_digit() => {
+                Ok((DIGITS[(token as u32 - '0' as u32) as usize], unconsumed))
+            }
+            Ok((token, _)) => Err(UnexpectedToken { index: 0, token }),
+            Err(err) => Err(err),
+        }
+    }
+
     /// Get the value of the [`Digit`] as primitive type.
     ///
     /// # Examples
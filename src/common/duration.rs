@@ -0,0 +1,356 @@
+use crate::error::ConsumeErrorType::*;
+use crate::{Consumable, ConsumeError, ConsumeSource, Expectation, Render};
+use std::convert::TryFrom;
+use std::marker::PhantomData;
+use std::time::Duration;
+
+const NANOS_PER_SECOND: u128 = 1_000_000_000;
+
+/// The suffixes of the suffix style with their length in nanoseconds. A suffix has to come
+/// before the suffixes that are a prefix of it.
+const SUFFIXES: [(&str, u128); 5] = [
+    ("ms", 1_000_000),
+    ("us", 1_000),
+    ("h", 3600 * NANOS_PER_SECOND),
+    ("m", 60 * NANOS_PER_SECOND),
+    ("s", NANOS_PER_SECOND),
+];
+
+/// Trait that defines which notations of a duration are allowed by [`StyledDuration`].
+pub trait DurationPolicy {
+    /// Whether the suffix style is allowed. This is one or more amounts, each followed by one of
+    /// the units `h`, `m`, `s`, `ms` or `us`, such as `1h30m` or `250ms`.
+    const SUFFIX: bool;
+
+    /// Whether the clock style is allowed. This is hours, followed by two digit minutes and two
+    /// digit seconds separated by a `':'`, such as `01:30:00`.
+    const CLOCK: bool;
+}
+
+/// Policy that allows both the suffix style and the clock style, which is what [`Duration`]
+/// allows itself.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+pub struct AnyDurationStyle;
+
+impl DurationPolicy for AnyDurationStyle {
+    const SUFFIX: bool = true;
+    const CLOCK: bool = true;
+}
+
+/// Policy that only allows the suffix style, such as `1h30m`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+pub struct SuffixStyle;
+
+impl DurationPolicy for SuffixStyle {
+    const SUFFIX: bool = true;
+    const CLOCK: bool = false;
+}
+
+/// Policy that only allows the clock style, such as `01:30:00`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+pub struct ClockStyle;
+
+impl DurationPolicy for ClockStyle {
+    const SUFFIX: bool = false;
+    const CLOCK: bool = true;
+}
+
+/// Convert an amount of nanoseconds into a duration, failing with an
+/// [`InvalidValue`][crate::ConsumeErrorType::InvalidValue] at `index` when it does not fit.
+fn duration_from_nanos(nanos: u128, index: usize) -> Result<Duration, ConsumeError> {
+    let seconds = u64::try_from(nanos / NANOS_PER_SECOND)
+        .map_err(|_| ConsumeError::new_with(InvalidValue { index }))?;
+
+    Ok(Duration::new(seconds, (nanos % NANOS_PER_SECOND) as u32))
+}
+
+fn consume_suffix_style(source: &str) -> Result<(Duration, &str), ConsumeError> {
+    let mut unconsumed = source;
+    let mut offset = 0;
+    let mut nanos: u128 = 0;
+    let mut components = 0;
+
+    loop {
+        let (amount, after_amount) = match u64::consume_from(unconsumed) {
+            Ok(split) => split,
+            Err(_) if components > 0 => break,
+            Err(err) => return Err(err.offset(offset)),
+        };
+        let unit_index = offset + (unconsumed.len() - after_amount.len());
+
+        let unit = SUFFIXES.iter().find_map(|(suffix, unit)| {
+            after_amount
+                .strip_prefix(suffix)
+                .map(|rest| (*unit, suffix.len(), rest))
+        });
+        let (unit, unit_length, rest) = match unit {
+            Some(unit) => unit,
+            None if components > 0 => break,
+            None => {
+                return Err(ConsumeError::new_with(match after_amount.chars().next() {
+                    Some(token) => UnexpectedToken {
+                        index: unit_index,
+                        token,
+                    },
+                    None => InsufficientTokens { index: unit_index },
+                }))
+            }
+        };
+
+        nanos = nanos
+            .checked_add(u128::from(amount) * unit)
+            .ok_or_else(|| ConsumeError::new_with(InvalidValue { index: offset }))?;
+        duration_from_nanos(nanos, offset)?;
+
+        offset = unit_index + unit_length;
+        unconsumed = rest;
+        components += 1;
+    }
+
+    Ok((duration_from_nanos(nanos, 0)?, unconsumed))
+}
+
+/// Consume exactly two digits as a value below 60, for the minutes or seconds of the clock style.
+fn consume_sixtieths(unconsumed: &mut &str, offset: usize) -> Result<u64, ConsumeError> {
+    let (digits, _) = unconsumed
+        .mut_consume_by::<super::Exactly<super::Digit, 2>>()
+        .map_err(|err| err.offset(offset))?;
+    let value = digits[0].value::<u64>() * 10 + digits[1].value::<u64>();
+
+    if value >= 60 {
+        return Err(ConsumeError::new_with(InvalidValue { index: offset }));
+    }
+
+    Ok(value)
+}
+
+fn consume_clock_style(source: &str) -> Result<(Duration, &str), ConsumeError> {
+    let mut unconsumed = source;
+
+    let (hours, mut offset) = unconsumed.mut_consume_by::<u64>()?;
+    offset += unconsumed
+        .mut_consume_lit(&':')
+        .map_err(|err| err.offset(offset))?;
+    let minutes = consume_sixtieths(&mut unconsumed, offset)?;
+    offset += 2;
+    offset += unconsumed
+        .mut_consume_lit(&':')
+        .map_err(|err| err.offset(offset))?;
+    let seconds = consume_sixtieths(&mut unconsumed, offset)?;
+
+    let seconds = hours
+        .checked_mul(3600)
+        .and_then(|hours| hours.checked_add(minutes * 60 + seconds))
+        .ok_or_else(|| ConsumeError::new_with(InvalidValue { index: 0 }))?;
+
+    Ok((Duration::from_secs(seconds), unconsumed))
+}
+
+fn consume_duration<Policy: DurationPolicy>(
+    source: &str,
+) -> Result<(Duration, &str), ConsumeError> {
+    let mut error = ConsumeError::new();
+
+    if Policy::CLOCK {
+        match consume_clock_style(source) {
+            Ok(split) => return Ok(split),
+            Err(err) => error.add_causes(err),
+        }
+    }
+
+    if Policy::SUFFIX {
+        match consume_suffix_style(source) {
+            Ok(split) => return Ok(split),
+            Err(err) => error.add_causes(err),
+        }
+    }
+
+    Err(error)
+}
+
+/// Consumes a duration in the suffix style, such as `1h30m` or `250ms`, or in the clock style,
+/// such as `01:30:00`. See [`DurationPolicy`] for the exact notations and [`StyledDuration`] to
+/// only allow one of them.
+///
+/// A duration that does not fit results in an
+/// [`InvalidValue`][crate::ConsumeErrorType::InvalidValue].
+///
+/// # Examples
+///
+/// ```
+/// use manger::Consumable;
+/// use std::time::Duration;
+///
+/// assert_eq!(Duration::consume_all("1h30m")?, Duration::from_secs(5400));
+/// assert_eq!(Duration::consume_all("01:30:00")?, Duration::from_secs(5400));
+/// assert_eq!(Duration::consume_all("1s250ms")?, Duration::from_millis(1250));
+///
+/// assert!(Duration::consume_all("1:60:00").is_err());
+/// assert!(Duration::consume_all("10").is_err());
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+impl Consumable for Duration {
+    fn expecting() -> Expectation {
+        Expectation::new("a duration")
+    }
+
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        consume_duration::<AnyDurationStyle>(source)
+    }
+}
+
+/// Renders the duration in the suffix style, such as `1h30m`. Parts smaller than a microsecond
+/// are not rendered.
+impl Render for Duration {
+    fn render_to(&self, out: &mut String) {
+        let seconds = self.as_secs();
+        let nanos = self.subsec_nanos();
+
+        let components = [
+            (seconds / 3600, "h"),
+            (seconds % 3600 / 60, "m"),
+            (seconds % 60, "s"),
+            (u64::from(nanos / 1_000_000), "ms"),
+            (u64::from(nanos % 1_000_000 / 1_000), "us"),
+        ];
+
+        let mut rendered = false;
+        for (amount, suffix) in components.iter().filter(|(amount, _)| *amount > 0) {
+            out.push_str(&amount.to_string());
+            out.push_str(suffix);
+            rendered = true;
+        }
+
+        if !rendered {
+            out.push_str("0s");
+        }
+    }
+}
+
+/// A [`Duration`] whose notation is restricted by the [`DurationPolicy`] `Policy`.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{Consumable, Render};
+/// use manger::common::{ClockStyle, StyledDuration, SuffixStyle};
+/// use std::time::Duration;
+///
+/// let (timeout, _) = <StyledDuration<SuffixStyle>>::consume_from("250ms")?;
+/// assert_eq!(timeout.into_inner(), Duration::from_millis(250));
+/// assert!(<StyledDuration<SuffixStyle>>::consume_from("00:00:01").is_err());
+///
+/// let (length, _) = <StyledDuration<ClockStyle>>::consume_from("2:05:09")?;
+/// assert_eq!(*length, Duration::from_secs(2 * 3600 + 5 * 60 + 9));
+/// assert_eq!(length.render(), "02:05:09");
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+pub struct StyledDuration<Policy = AnyDurationStyle> {
+    duration: Duration,
+    phantom: PhantomData<Policy>,
+}
+
+impl<Policy> StyledDuration<Policy> {
+    /// Unwrap to fetch the consumed duration.
+    pub fn into_inner(self) -> Duration {
+        self.duration
+    }
+}
+
+impl<Policy> std::ops::Deref for StyledDuration<Policy> {
+    type Target = Duration;
+
+    fn deref(&self) -> &Duration {
+        &self.duration
+    }
+}
+
+impl<Policy: DurationPolicy> Consumable for StyledDuration<Policy> {
+    fn expecting() -> Expectation {
+        Duration::expecting()
+    }
+
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        consume_duration::<Policy>(source).map(|(duration, unconsumed)| {
+            (
+                StyledDuration {
+                    duration,
+                    phantom: PhantomData,
+                },
+                unconsumed,
+            )
+        })
+    }
+}
+
+/// Renders the duration in the suffix style if `Policy` allows it and in the clock style
+/// otherwise.
+impl<Policy: DurationPolicy> Render for StyledDuration<Policy> {
+    fn render_to(&self, out: &mut String) {
+        if Policy::SUFFIX {
+            self.duration.render_to(out);
+        } else {
+            let seconds = self.duration.as_secs();
+
+            out.push_str(&format!(
+                "{:02}:{:02}:{:02}",
+                seconds / 3600,
+                seconds % 3600 / 60,
+                seconds % 60
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::ConsumeErrorType::*;
+    use crate::{Consumable, ConsumeError, Render};
+    use std::time::Duration;
+
+    #[test]
+    fn test_suffix_style() {
+        assert_eq!(
+            Duration::consume_from("2m3us!").unwrap(),
+            (Duration::new(120, 3_000), "!")
+        );
+        assert_eq!(
+            Duration::consume_from("1h30").unwrap(),
+            (Duration::from_secs(3600), "30")
+        );
+        assert_eq!(
+            Duration::consume_from("18446744073709551615h").unwrap_err(),
+            ConsumeError::new_from(vec![
+                UnexpectedToken {
+                    index: 20,
+                    token: 'h'
+                },
+                InvalidValue { index: 0 }
+            ])
+        );
+    }
+
+    #[test]
+    fn test_clock_style() {
+        assert_eq!(
+            Duration::consume_from("1:02:03.5").unwrap(),
+            (Duration::from_secs(3723), ".5")
+        );
+        assert_eq!(
+            Duration::consume_from("1:2:03").unwrap_err().causes()[0],
+            &MissingItems {
+                index: 3,
+                found: 1,
+                required: 2
+            }
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        for source in &["1h30m", "250ms", "1m1s1ms1us", "0s"] {
+            assert_eq!(Duration::consume_all(source).unwrap().render(), *source);
+        }
+    }
+}
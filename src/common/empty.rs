@@ -0,0 +1,49 @@
+use crate::{Consumable, ConsumeError, Expectation, LiteralConsumable, Render};
+
+/// Zero-width consumer, which always succeeds and consumes nothing.
+///
+/// This is most often used as the last alternative of an `enum` or within an
+/// [`Either<L, R>`][either::Either], to fall back to when nothing else can be consumed. It renders
+/// as nothing.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{consume_enum, Consumable};
+/// use manger::common::Empty;
+///
+/// #[derive(PartialEq, Debug)]
+/// enum Visibility {
+///     Public,
+///     Private,
+/// }
+/// consume_enum!(
+///     Visibility {
+///         Public => [ > "pub "; ],
+///         Private => [ : Empty; ]
+///     }
+/// );
+///
+/// assert_eq!(Visibility::consume_from("pub fn")?, (Visibility::Public, "fn"));
+/// assert_eq!(Visibility::consume_from("fn")?, (Visibility::Private, "fn"));
+/// assert_eq!(Empty::consume_from("")?, (Empty, ""));
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+pub struct Empty;
+
+impl Consumable for Empty {
+    fn expecting() -> Expectation {
+        Expectation::new("nothing")
+    }
+
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        Ok((Empty, source))
+    }
+}
+
+impl LiteralConsumable for Empty {}
+
+impl Render for Empty {
+    fn render_to(&self, _: &mut String) {}
+}
@@ -0,0 +1,216 @@
+use crate::error::ConsumeErrorType::*;
+use crate::{Consumable, ConsumeError, ConsumeSource, Render};
+use std::marker::PhantomData;
+
+/// Struct representing an item of type `T` enclosed by an opening `Open` and a closing `Close`.
+///
+/// Consuming an `Enclosed<Open, T, Close>` consumes the same as the tuple `(Open, T, Close)`.
+/// However, when the `Close` is missing, the error starts with an
+/// [`UnclosedDelimiter`][crate::ConsumeErrorType::UnclosedDelimiter] cause, which contains the
+/// index at which the `Open` was consumed.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{Consumable, ConsumeErrorType};
+/// use manger::chars;
+/// use manger::common::Enclosed;
+///
+/// type Parenthesized = Enclosed<chars::OpenParenthese, u32, chars::CloseParenthese>;
+///
+/// let (number, unconsumed) = Parenthesized::consume_from("(42) + 1")?;
+///
+/// assert_eq!(number.into_inner(), 42);
+/// assert_eq!(unconsumed, " + 1");
+///
+/// let error = <(chars::Plus, Parenthesized)>::consume_from("+(42 + 1").unwrap_err();
+///
+/// assert_eq!(
+///     error.causes()[0],
+///     &ConsumeErrorType::UnclosedDelimiter { index: 4, opened_at: 1, token: '(' }
+/// );
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Enclosed<Open, T, Close> {
+    item: T,
+    phantom: PhantomData<(Open, Close)>,
+}
+
+impl<Open, T, Close> Enclosed<Open, T, Close> {
+    /// Get a reference to the enclosed item.
+    pub fn item(&self) -> &T {
+        &self.item
+    }
+
+    /// Unwrap to fetch the enclosed item.
+    pub fn into_inner(self) -> T {
+        self.item
+    }
+}
+
+impl<Open: Consumable, T: Consumable, Close: Consumable> Consumable for Enclosed<Open, T, Close> {
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let mut unconsumed = source;
+
+        let (_, mut offset) = unconsumed.mut_consume_by::<Open>()?;
+        let (item, by) = unconsumed
+            .mut_consume_by::<T>()
+            .map_err(|err| err.offset(offset))?;
+        offset += by;
+
+        match Close::consume_from(unconsumed) {
+            Ok((_, unconsumed)) => Ok((
+                Enclosed {
+                    item,
+                    phantom: PhantomData,
+                },
+                unconsumed,
+            )),
+            Err(err) => {
+                let mut error = ConsumeError::new_with(UnclosedDelimiter {
+                    index: offset,
+                    opened_at: 0,
+                    token: source.chars().next().unwrap_or_default(),
+                });
+                error.add_causes(err.offset(offset));
+
+                Err(error)
+            }
+        }
+    }
+}
+
+/// Renders the default `Open`, followed by the item and the default `Close`.
+impl<Open: Render + Default, T: Render, Close: Render + Default> Render
+    for Enclosed<Open, T, Close>
+{
+    fn render_to(&self, out: &mut String) {
+        Open::default().render_to(out);
+        self.item.render_to(out);
+        Close::default().render_to(out);
+    }
+}
+
+/// Get the closing bracket for the opening bracket `token`.
+fn closing_bracket(token: char) -> Option<char> {
+    match token {
+        '(' => Some(')'),
+        '[' => Some(']'),
+        '{' => Some('}'),
+        _ => None,
+    }
+}
+
+fn is_closing_bracket(token: char) -> bool {
+    matches!(token, ')' | ']' | '}')
+}
+
+/// A bracketed group with any kind of bracket, where all nested brackets are matched.
+///
+/// Consuming a `BalancedBrackets` consumes one of the opening brackets `'('`, `'['` or `'{'`,
+/// followed by everything up to and including the matching closing bracket. Every bracket
+/// that is opened within has to be closed by the same kind of bracket. A closing bracket of the
+/// wrong kind results in an [`UnexpectedToken`][crate::ConsumeErrorType::UnexpectedToken] and a
+/// missing closing bracket results in an
+/// [`UnclosedDelimiter`][crate::ConsumeErrorType::UnclosedDelimiter] for the innermost unclosed
+/// bracket. Other characters, including quotes, have no special meaning.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{Consumable, ConsumeErrorType};
+/// use manger::common::BalancedBrackets;
+///
+/// let (group, unconsumed) = BalancedBrackets::consume_from("[a, (b), {c: [d]}] rest")?;
+///
+/// assert_eq!(group.open(), '[');
+/// assert_eq!(group.inner(), "a, (b), {c: [d]}");
+/// assert_eq!(unconsumed, " rest");
+///
+/// assert_eq!(
+///     BalancedBrackets::consume_from("(a[b)").unwrap_err().causes(),
+///     vec![&ConsumeErrorType::UnexpectedToken { index: 4, token: ')' }]
+/// );
+/// assert_eq!(
+///     BalancedBrackets::consume_from("(a[b]").unwrap_err().causes(),
+///     vec![&ConsumeErrorType::UnclosedDelimiter { index: 5, opened_at: 0, token: '(' }]
+/// );
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct BalancedBrackets {
+    open: char,
+    inner: String,
+}
+
+impl BalancedBrackets {
+    /// Get the outermost opening bracket.
+    pub fn open(&self) -> char {
+        self.open
+    }
+
+    /// Get the outermost closing bracket.
+    pub fn close(&self) -> char {
+        closing_bracket(self.open).unwrap_or_default()
+    }
+
+    /// Get everything between the outermost brackets.
+    pub fn inner(&self) -> &str {
+        &self.inner
+    }
+
+    /// Unwrap to fetch everything between the outermost brackets.
+    pub fn into_inner(self) -> String {
+        self.inner
+    }
+}
+
+impl Consumable for BalancedBrackets {
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let open = match source.chars().next() {
+            Some(token) if closing_bracket(token).is_some() => token,
+            Some(token) => return Err(ConsumeError::new_with(UnexpectedToken { index: 0, token })),
+            None => return Err(ConsumeError::new_with(InsufficientTokens { index: 0 })),
+        };
+
+        // The opening brackets along with the utf-8 character index at which they were opened.
+        let mut opened = vec![(open, 0)];
+
+        for (index, (byte_index, token)) in source.char_indices().enumerate().skip(1) {
+            if closing_bracket(token).is_some() {
+                opened.push((token, index));
+            } else if is_closing_bracket(token) {
+                match opened.pop() {
+                    Some((bracket, _)) if closing_bracket(bracket) == Some(token) => {}
+                    _ => return Err(ConsumeError::new_with(UnexpectedToken { index, token })),
+                }
+
+                if opened.is_empty() {
+                    return Ok((
+                        BalancedBrackets {
+                            open,
+                            inner: source[open.len_utf8()..byte_index].to_string(),
+                        },
+                        &source[byte_index + token.len_utf8()..],
+                    ));
+                }
+            }
+        }
+
+        let (token, opened_at) = opened.pop().unwrap_or((open, 0));
+        Err(ConsumeError::new_with(UnclosedDelimiter {
+            index: source.chars().count(),
+            opened_at,
+            token,
+        }))
+    }
+}
+
+impl Render for BalancedBrackets {
+    fn render_to(&self, out: &mut String) {
+        out.push(self.open);
+        out.push_str(&self.inner);
+        out.push(self.close());
+    }
+}
@@ -0,0 +1,328 @@
+use crate::error::ConsumeErrorType::*;
+use crate::{Consumable, ConsumeError, Expectation, Render};
+use std::marker::PhantomData;
+
+/// Trait that defines the alphabet and padding of a [`Base64`] payload.
+pub trait Base64Policy {
+    /// The two characters for the values 62 and 63.
+    const EXTRA: [char; 2];
+
+    /// Whether the payload has to be padded with `'='` to a multiple of four characters.
+    const PADDED: bool;
+}
+
+/// Policy for the standard alphabet with `'+'` and `'/'`, padded with `'='`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+pub struct StandardBase64;
+
+impl Base64Policy for StandardBase64 {
+    const EXTRA: [char; 2] = ['+', '/'];
+    const PADDED: bool = true;
+}
+
+/// Policy for the URL-safe alphabet with `'-'` and `'_'`, without padding.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+pub struct UrlSafeBase64;
+
+impl Base64Policy for UrlSafeBase64 {
+    const EXTRA: [char; 2] = ['-', '_'];
+    const PADDED: bool = false;
+}
+
+/// Get the value of a base64 digit, or `None` if `token` is not in the alphabet.
+fn base64_value(token: char, extra: [char; 2]) -> Option<u8> {
+    match token {
+        'A'..='Z' => Some(token as u8 - b'A'),
+        'a'..='z' => Some(token as u8 - b'a' + 26),
+        '0'..='9' => Some(token as u8 - b'0' + 52),
+        _ if token == extra[0] => Some(62),
+        _ if token == extra[1] => Some(63),
+        _ => None,
+    }
+}
+
+/// Get the base64 digit for the lowest six bits of `value`.
+fn base64_digit(value: u8, extra: [char; 2]) -> char {
+    match value & 0x3f {
+        value @ 0..=25 => char::from(b'A' + value),
+        value @ 26..=51 => char::from(b'a' + value - 26),
+        value @ 52..=61 => char::from(b'0' + value - 52),
+        62 => extra[0],
+        _ => extra[1],
+    }
+}
+
+/// Fail with an error for the missing `expected` token at `index` in `source`.
+fn missing_token(source: &str, index: usize) -> ConsumeError {
+    ConsumeError::new_with(match source[index..].chars().next() {
+        Some(token) => UnexpectedToken { index, token },
+        None => InsufficientTokens { index },
+    })
+}
+
+/// A base64 encoded payload, decoded into bytes.
+///
+/// Consumes the longest run of characters in the alphabet of `Policy`, followed by the padding
+/// if the policy requires it. The run may be empty. Since every character in the alphabet is one
+/// byte long, all indices are byte indices as well.
+///
+/// A run that leaves a single character in its last group, or whose last character has bits set
+/// that are not part of the payload, results in an
+/// [`InvalidValue`][crate::ConsumeErrorType::InvalidValue] at that character. Missing padding
+/// results in an error at the index where the padding should be.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{Consumable, Render};
+/// use manger::common::{Base64, UrlSafeBase64};
+///
+/// let (payload, unconsumed) = <Base64>::consume_from("aGVsbG8=;")?;
+/// assert_eq!(payload.bytes(), b"hello");
+/// assert_eq!(unconsumed, ";");
+/// assert_eq!(payload.render(), "aGVsbG8=");
+///
+/// assert!(<Base64>::consume_from("aGVsbG8;").is_err());
+///
+/// let (payload, _) = <Base64<UrlSafeBase64>>::consume_from("-_8")?;
+/// assert_eq!(payload.bytes(), &[0xfb, 0xff]);
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Base64<Policy = StandardBase64> {
+    bytes: Vec<u8>,
+    phantom: PhantomData<Policy>,
+}
+
+/// A hexadecimal encoded payload, decoded into bytes.
+///
+/// Consumes the longest run of hexadecimal digits, in either case. The run may be empty. A run
+/// of an odd length results in an error at the index after the run, where a digit is missing.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{Consumable, Render};
+/// use manger::common::HexString;
+///
+/// let (payload, unconsumed) = HexString::consume_from("00fFa0 rest")?;
+/// assert_eq!(payload.bytes(), &[0x00, 0xff, 0xa0]);
+/// assert_eq!(unconsumed, " rest");
+/// assert_eq!(payload.render(), "00ffa0");
+///
+/// assert!(HexString::consume_from("abc").is_err());
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Default)]
+pub struct HexString(Vec<u8>);
+
+impl<Policy> Base64<Policy> {
+    /// Get the decoded bytes.
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Take ownership of the decoded bytes.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+impl HexString {
+    /// Get the decoded bytes.
+    pub fn bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Take ownership of the decoded bytes.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.0
+    }
+}
+
+impl<Policy> From<Vec<u8>> for Base64<Policy> {
+    fn from(bytes: Vec<u8>) -> Self {
+        Base64 {
+            bytes,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl From<Vec<u8>> for HexString {
+    fn from(bytes: Vec<u8>) -> Self {
+        HexString(bytes)
+    }
+}
+
+impl<Policy: Base64Policy> Consumable for Base64<Policy> {
+    fn expecting() -> Expectation {
+        Expectation::new("a base64 payload")
+    }
+
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let values = source
+            .chars()
+            .map_while(|token| base64_value(token, Policy::EXTRA))
+            .collect::<Vec<_>>();
+        let length = values.len();
+
+        let (padding, unused_bits) = match length % 4 {
+            0 => (0, 0),
+            1 => {
+                return Err(ConsumeError::new_with(InvalidValue { index: length - 1 }));
+            }
+            2 => (2, 4),
+            _ => (1, 2),
+        };
+
+        if values
+            .last()
+            .is_some_and(|value| value & ((1 << unused_bits) - 1) != 0)
+        {
+            return Err(ConsumeError::new_with(InvalidValue { index: length - 1 }));
+        }
+
+        let mut end = length;
+        if Policy::PADDED {
+            for _ in 0..padding {
+                if !source[end..].starts_with('=') {
+                    return Err(missing_token(source, end));
+                }
+                end += 1;
+            }
+        }
+
+        let bytes: Vec<u8> = values
+            .chunks(4)
+            .flat_map(|chunk| {
+                let group = chunk
+                    .iter()
+                    .enumerate()
+                    .fold(0u32, |group, (index, value)| {
+                        group | u32::from(*value) << (18 - 6 * index)
+                    });
+                group.to_be_bytes()[1..chunk.len()].to_vec()
+            })
+            .collect();
+
+        Ok((Base64::from(bytes), &source[end..]))
+    }
+}
+
+impl Consumable for HexString {
+    fn expecting() -> Expectation {
+        Expectation::new("a hexadecimal payload")
+    }
+
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let length = source
+            .bytes()
+            .take_while(|byte| byte.is_ascii_hexdigit())
+            .count();
+
+        if length % 2 == 1 {
+            return Err(missing_token(source, length));
+        }
+
+        let bytes = source.as_bytes()[..length]
+            .chunks(2)
+            .map(|pair| {
+                let digits = std::str::from_utf8(pair).expect("hexadecimal digits are ASCII");
+                u8::from_str_radix(digits, 16).expect("a pair of digits fits a byte")
+            })
+            .collect();
+
+        Ok((HexString(bytes), &source[length..]))
+    }
+}
+
+impl<Policy: Base64Policy> Render for Base64<Policy> {
+    fn render_to(&self, out: &mut String) {
+        for chunk in self.bytes.chunks(3) {
+            let mut group = [0u8; 4];
+            group[1..=chunk.len()].copy_from_slice(chunk);
+            let group = u32::from_be_bytes(group);
+
+            for index in 0..=chunk.len() {
+                out.push(base64_digit(
+                    (group >> (18 - 6 * index)) as u8,
+                    Policy::EXTRA,
+                ));
+            }
+
+            if Policy::PADDED {
+                (chunk.len()..3).for_each(|_| out.push('='));
+            }
+        }
+    }
+}
+
+impl Render for HexString {
+    fn render_to(&self, out: &mut String) {
+        for byte in &self.0 {
+            out.push_str(&format!("{:02x}", byte));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Base64, HexString, UrlSafeBase64};
+    use crate::error::ConsumeErrorType::*;
+    use crate::{Consumable, ConsumeError, Render};
+
+    #[test]
+    fn test_base64() {
+        for (encoded, decoded) in [
+            ("", &b""[..]),
+            ("Zg==", b"f"),
+            ("Zm8=", b"fo"),
+            ("Zm9v", b"foo"),
+            ("Zm9vYg==", b"foob"),
+        ] {
+            let payload = <Base64>::consume_all(encoded).unwrap();
+            assert_eq!(payload.bytes(), decoded);
+            assert_eq!(payload.render(), encoded);
+        }
+
+        assert_eq!(
+            <Base64>::consume_from("Zg=x").unwrap_err(),
+            ConsumeError::new_with(UnexpectedToken {
+                index: 3,
+                token: 'x'
+            })
+        );
+        assert_eq!(
+            <Base64>::consume_from("Zm9vY").unwrap_err(),
+            ConsumeError::new_with(InvalidValue { index: 4 })
+        );
+        assert_eq!(
+            <Base64>::consume_from("Zh==").unwrap_err(),
+            ConsumeError::new_with(InvalidValue { index: 1 })
+        );
+        assert_eq!(
+            <Base64<UrlSafeBase64>>::consume_from("Zg==").unwrap(),
+            (Base64::from(b"f".to_vec()), "==")
+        );
+    }
+
+    #[test]
+    fn test_hex_string() {
+        assert_eq!(
+            HexString::consume_from("").unwrap(),
+            (HexString::default(), "")
+        );
+        assert_eq!(
+            HexString::consume_from("abc").unwrap_err(),
+            ConsumeError::new_with(InsufficientTokens { index: 3 })
+        );
+        assert_eq!(
+            HexString::consume_from("a;").unwrap_err(),
+            ConsumeError::new_with(UnexpectedToken {
+                index: 1,
+                token: ';'
+            })
+        );
+    }
+}
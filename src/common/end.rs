@@ -1,4 +1,4 @@
-use crate::Consumable;
+use crate::{Consumable, LiteralConsumable, Render};
 use crate::{ConsumeError, ConsumeErrorType};
 
 /// End of stream of tokens.
@@ -27,18 +27,27 @@ use crate::{ConsumeError, ConsumeErrorType};
 /// assert!(EncasedInteger::consume_from("(42)").is_ok());
 /// assert!(EncasedInteger::consume_from("(42) some leftover tokens").is_err());
 /// ```
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
 pub struct End;
 
 impl Consumable for End {
+    fn expecting() -> crate::Expectation {
+        crate::Expectation::new("the end of the source")
+    }
+
     fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
-        if source.is_empty() {
-            Ok((End, ""))
-        } else {
-            Err(ConsumeError::new_with(ConsumeErrorType::UnexpectedToken {
+        match source.chars().next() {
+            None => Ok((End, "")),
+            Some(token) => Err(ConsumeError::new_with(ConsumeErrorType::UnexpectedToken {
                 index: 0,
-                token: source.chars().next().unwrap(),
-            }))
+                token,
+            })),
         }
     }
 }
+
+impl LiteralConsumable for End {}
+
+impl Render for End {
+    fn render_to(&self, _: &mut String) {}
+}
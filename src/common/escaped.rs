@@ -0,0 +1,197 @@
+use super::CharSet;
+use crate::error::ConsumeErrorType::*;
+use crate::{Consumable, ConsumeError, Render};
+use std::marker::PhantomData;
+
+/// Trait that defines which utf-8 characters can be escaped and what they stand for.
+///
+/// This is used by [`Escaped`] to turn the character after an escape character into the
+/// character it represents.
+///
+/// # Examples
+///
+/// ```
+/// use manger::common::Unescape;
+///
+/// /// Only allows escaping quotes.
+/// struct Quotes;
+/// impl Unescape for Quotes {
+///     fn unescape(token: char) -> Option<char> {
+///         match token {
+///             '"' | '\'' => Some(token),
+///             _ => None,
+///         }
+///     }
+/// }
+///
+/// assert_eq!(Quotes::unescape('"'), Some('"'));
+/// assert_eq!(Quotes::unescape('n'), None);
+/// ```
+pub trait Unescape {
+    /// Returns the character represented by the escaped `token`, or `None` if `token` cannot be
+    /// escaped.
+    fn unescape(token: char) -> Option<char>;
+}
+
+/// The escape sequences of C and many other languages.
+///
+/// These are `n`, `r`, `t` and `0` for a newline, carriage return, tab and null character, and
+/// `\\`, `'` and `"` for themselves.
+///
+/// # Examples
+///
+/// ```
+/// use manger::common::{CEscapes, Unescape};
+///
+/// assert_eq!(CEscapes::unescape('n'), Some('\n'));
+/// assert_eq!(CEscapes::unescape('\\'), Some('\\'));
+/// assert_eq!(CEscapes::unescape('q'), None);
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+pub struct CEscapes;
+
+impl Unescape for CEscapes {
+    fn unescape(token: char) -> Option<char> {
+        match token {
+            'n' => Some('\n'),
+            'r' => Some('\r'),
+            't' => Some('\t'),
+            '0' => Some('\0'),
+            '\\' | '\'' | '"' => Some(token),
+            _ => None,
+        }
+    }
+}
+
+/// Text consisting of normal characters interleaved with escape sequences.
+///
+/// Consuming an `Escaped<Normal, Escape, Escapee>` consumes characters for as long as they are
+/// either part of the set `Normal` or an escape sequence. An escape sequence consists of a
+/// character from the set `Escape`, followed by a character that can be unescaped with
+/// `Escapee`. Escape characters are never treated as normal characters. Consuming stops at the
+/// first character that is neither, which makes it possible to consume no characters at all.
+///
+/// An escape character followed by a character that cannot be unescaped results in an
+/// [`UnexpectedToken`][crate::ConsumeErrorType::UnexpectedToken] for the latter character. Both
+/// the raw text and the unescaped text are kept.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{char_set, consume_struct, Consumable};
+/// use manger::common::{CEscapes, CharSet, Escaped};
+///
+/// char_set!(Backslash => r"\\");
+///
+/// #[derive(Debug)]
+/// struct NotQuote;
+/// impl CharSet for NotQuote {
+///     fn contains(token: char) -> bool {
+///         token != '"'
+///     }
+/// }
+///
+/// #[derive(Debug)]
+/// struct Quoted(Escaped<NotQuote, Backslash, CEscapes>);
+/// consume_struct!(
+///     Quoted => [
+///         > '"',
+///         text: Escaped<NotQuote, Backslash, CEscapes>,
+///         > '"';
+///         (text)
+///     ]
+/// );
+///
+/// let (Quoted(text), unconsumed) = Quoted::consume_from(r#""say \"hi\"\n" rest"#)?;
+///
+/// assert_eq!(text.raw(), r#"say \"hi\"\n"#);
+/// assert_eq!(text.unescaped(), "say \"hi\"\n");
+/// assert_eq!(unconsumed, " rest");
+///
+/// assert_eq!(
+///     Quoted::consume_from(r#""\q""#).unwrap_err().causes()[0].index(),
+///     &2
+/// );
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Escaped<Normal, Escape, Escapee> {
+    raw: String,
+    unescaped: String,
+    phantom: PhantomData<(Normal, Escape, Escapee)>,
+}
+
+impl<Normal, Escape, Escapee> Escaped<Normal, Escape, Escapee> {
+    /// Get the text as it was consumed, including the escape characters.
+    pub fn raw(&self) -> &str {
+        &self.raw
+    }
+
+    /// Get the text where every escape sequence is replaced by the character it represents.
+    pub fn unescaped(&self) -> &str {
+        &self.unescaped
+    }
+
+    /// Unwrap to fetch the text as it was consumed, including the escape characters.
+    pub fn into_raw(self) -> String {
+        self.raw
+    }
+
+    /// Unwrap to fetch the text where every escape sequence is replaced by the character it
+    /// represents.
+    pub fn into_unescaped(self) -> String {
+        self.unescaped
+    }
+}
+
+impl<Normal: CharSet, Escape: CharSet, Escapee: Unescape> Consumable
+    for Escaped<Normal, Escape, Escapee>
+{
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let mut unescaped = String::new();
+        let mut tokens = source.char_indices().enumerate();
+        let mut end = source.len();
+
+        while let Some((index, (byte_index, token))) = tokens.next() {
+            if Escape::contains(token) {
+                match tokens.next() {
+                    Some((_, (_, escapee))) => match Escapee::unescape(escapee) {
+                        Some(escapee) => unescaped.push(escapee),
+                        None => {
+                            return Err(ConsumeError::new_with(UnexpectedToken {
+                                index: index + 1,
+                                token: escapee,
+                            }))
+                        }
+                    },
+                    None => {
+                        return Err(ConsumeError::new_with(InsufficientTokens {
+                            index: index + 1,
+                        }))
+                    }
+                }
+            } else if Normal::contains(token) {
+                unescaped.push(token);
+            } else {
+                end = byte_index;
+                break;
+            }
+        }
+
+        Ok((
+            Escaped {
+                raw: source[..end].to_string(),
+                unescaped,
+                phantom: PhantomData,
+            },
+            &source[end..],
+        ))
+    }
+}
+
+/// Renders the raw text, including the escape characters.
+impl<Normal, Escape, Escapee> Render for Escaped<Normal, Escape, Escapee> {
+    fn render_to(&self, out: &mut String) {
+        out.push_str(&self.raw);
+    }
+}
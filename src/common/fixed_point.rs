@@ -0,0 +1,158 @@
+use crate::common::{FromDigits, OverflowMode};
+use crate::error::ConsumeErrorType::*;
+use crate::{Consumable, ConsumeError, Expectation, Render};
+
+/// A decimal number with exactly `SCALE` digits after the period, such as an amount of money.
+///
+/// Consumes numbers in the form of `[+-]digits[.digits]` without any loss of precision, unlike
+/// the floating point types. The number is stored as an `i128` amount of units of
+/// `10^-SCALE`, so `SCALE` can be at most `38`. A period is only part of the number if it is
+/// followed by a digit.
+///
+/// Consuming fails with an [`InvalidValue`][crate::ConsumeErrorType::InvalidValue] at the digit
+/// where the number overflows, or at the first digit after the period that cannot be represented
+/// with `SCALE` digits.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{Consumable, ConsumeErrorType, Render};
+/// use manger::common::FixedPoint;
+///
+/// type Money = FixedPoint<2>;
+///
+/// let (price, unconsumed) = Money::consume_from("19.99 EUR")?;
+///
+/// assert_eq!(price.units(), 1999);
+/// assert_eq!(unconsumed, " EUR");
+///
+/// assert_eq!(Money::consume_all("-0.5")?.render(), "-0.50");
+/// assert_eq!(Money::consume_from("7.")?, (Money::from_units(700), "."));
+///
+/// assert_eq!(
+///     Money::consume_from("0.125").unwrap_err().causes(),
+///     vec![&ConsumeErrorType::InvalidValue { index: 4 }]
+/// );
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Default)]
+pub struct FixedPoint<const SCALE: u32> {
+    units: i128,
+}
+
+impl<const SCALE: u32> FixedPoint<SCALE> {
+    /// Create a number from an amount of units of `10^-SCALE`.
+    pub const fn from_units(units: i128) -> Self {
+        FixedPoint { units }
+    }
+
+    /// Get the amount of units of `10^-SCALE`.
+    pub const fn units(&self) -> i128 {
+        self.units
+    }
+
+    /// Get the value of one `10^0`, in units.
+    fn scale() -> i128 {
+        10i128
+            .checked_pow(SCALE)
+            .expect("the scale of a `FixedPoint` is at most 38")
+    }
+}
+
+impl<const SCALE: u32> Consumable for FixedPoint<SCALE> {
+    fn expecting() -> Expectation {
+        Expectation::new("a decimal number")
+    }
+
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let negative = source.starts_with('-');
+        let (integer, mut unconsumed) = i128::consume_digits(source, OverflowMode::Checked)?;
+
+        // All consumed characters are ASCII, so byte indices equal utf-8 character indices.
+        let mut index = source.len() - unconsumed.len();
+        let mut units = integer
+            .checked_mul(Self::scale())
+            .ok_or_else(|| ConsumeError::new_with(InvalidValue { index: index - 1 }))?;
+
+        let bytes = unconsumed.as_bytes();
+        if bytes.first() == Some(&b'.') && bytes.get(1).is_some_and(u8::is_ascii_digit) {
+            let mut factor = Self::scale();
+            unconsumed = &unconsumed[1..];
+            index += 1;
+
+            while let Some(digit) = unconsumed
+                .as_bytes()
+                .first()
+                .filter(|byte| byte.is_ascii_digit())
+                .map(|byte| i128::from(byte - b'0'))
+            {
+                factor /= 10;
+
+                let part = digit * factor;
+                units = match factor {
+                    0 => None,
+                    _ if negative => units.checked_sub(part),
+                    _ => units.checked_add(part),
+                }
+                .ok_or_else(|| ConsumeError::new_with(InvalidValue { index }))?;
+
+                unconsumed = &unconsumed[1..];
+                index += 1;
+            }
+        }
+
+        Ok((FixedPoint { units }, unconsumed))
+    }
+}
+
+/// Renders the number with exactly `SCALE` digits after the period.
+impl<const SCALE: u32> Render for FixedPoint<SCALE> {
+    fn render_to(&self, out: &mut String) {
+        let scale = Self::scale().unsigned_abs();
+        let magnitude = self.units.unsigned_abs();
+
+        if self.units < 0 {
+            out.push('-');
+        }
+
+        out.push_str(&(magnitude / scale).to_string());
+
+        if SCALE > 0 {
+            out.push_str(&format!(
+                ".{:0width$}",
+                magnitude % scale,
+                width = SCALE as usize
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FixedPoint;
+    use crate::error::ConsumeErrorType::*;
+    use crate::{Consumable, ConsumeError, Render};
+
+    #[test]
+    fn test_fixed_point() {
+        assert_eq!(<FixedPoint<3>>::consume_all("+1.5").unwrap().units(), 1500);
+        assert_eq!(<FixedPoint<0>>::consume_from("42.0").unwrap_err(), {
+            ConsumeError::new_with(InvalidValue { index: 3 })
+        });
+        assert_eq!(<FixedPoint<0>>::from_units(-42).render(), "-42");
+        assert_eq!(<FixedPoint<4>>::from_units(-5).render(), "-0.0005");
+
+        let max = format!("{}", i128::MAX / 100);
+        assert!(<FixedPoint<2>>::consume_all(&max).is_ok());
+        assert_eq!(
+            <FixedPoint<2>>::consume_all(&format!("{}.99", max)).unwrap_err(),
+            ConsumeError::new_with(InvalidValue {
+                index: max.len() + 1
+            })
+        );
+        assert_eq!(
+            <FixedPoint<2>>::consume_all(&format!("{}0", max)).unwrap_err(),
+            ConsumeError::new_with(InvalidValue { index: max.len() })
+        );
+    }
+}
@@ -0,0 +1,219 @@
+use crate::{Consumable, ConsumeError, Render};
+use std::marker::PhantomData;
+
+/// Trait that defines the separators used to format a number.
+///
+/// This is used by [`FormattedNumber`] to consume numbers such as `1,234.56` or `1.234,56`.
+///
+/// # Examples
+///
+/// ```
+/// use manger::Consumable;
+/// use manger::common::{FormattedNumber, NumberStyle};
+///
+/// /// Groups separated by underscores, as in Rust.
+/// struct Underscores;
+/// impl NumberStyle for Underscores {
+///     const GROUP_SEPARATOR: Option<char> = Some('_');
+///     const DECIMAL_SEPARATOR: char = '.';
+/// }
+///
+/// let (number, _) = <FormattedNumber<u32, Underscores>>::consume_from("1_000_000")?;
+/// assert_eq!(number.into_inner(), 1_000_000);
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+pub trait NumberStyle {
+    /// The character between groups of three digits before the decimal separator, if any.
+    const GROUP_SEPARATOR: Option<char>;
+
+    /// The character between the integer part and the fractional part.
+    const DECIMAL_SEPARATOR: char;
+}
+
+macro_rules! number_style {
+    ( $( $( #[$attr:meta] )* $name:ident => $group:expr, $decimal:expr; )+ ) => {
+        $(
+            $( #[$attr] )*
+            #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+            pub struct $name;
+
+            impl NumberStyle for $name {
+                const GROUP_SEPARATOR: Option<char> = $group;
+                const DECIMAL_SEPARATOR: char = $decimal;
+            }
+        )+
+    };
+}
+
+number_style! {
+    /// Numbers without group separators and with a decimal point, such as `1234.56`.
+    PlainStyle => None, '.';
+    /// Numbers with comma group separators and a decimal point, such as `1,234.56`.
+    CommaGroupStyle => Some(','), '.';
+    /// Numbers with point group separators and a decimal comma, such as `1.234,56`.
+    PointGroupStyle => Some('.'), ',';
+    /// Numbers with space group separators and a decimal comma, such as `1 234,56`.
+    SpaceGroupStyle => Some(' '), ',';
+    /// Numbers with apostrophe group separators and a decimal point, such as `1'234.56`.
+    ApostropheGroupStyle => Some('\''), '.';
+}
+
+/// A number of type `T` that is formatted according to the [`NumberStyle`] `Style`.
+///
+/// Consuming a `FormattedNumber<T, Style>` consumes an optional sign, the integer part and an
+/// optional fractional part. Within the integer part, the group separator may follow the first
+/// one to three digits and has to be followed by exactly three digits every time. A group
+/// separator or a decimal separator that does not fit this format is not consumed. The number is
+/// then consumed as a `T` in its plain form, such as `-1234.56`, and the indices of errors are
+/// moved to their position within the formatted number.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{Consumable, Render};
+/// use manger::common::{CommaGroupStyle, FormattedNumber, PointGroupStyle};
+///
+/// let (amount, unconsumed) = <FormattedNumber<f64, PointGroupStyle>>::consume_from("1.234,56 EUR")?;
+///
+/// assert_eq!(amount.into_inner(), 1234.56);
+/// assert_eq!(unconsumed, " EUR");
+///
+/// let (count, unconsumed) = <FormattedNumber<u32, CommaGroupStyle>>::consume_from("12,345,678, 9")?;
+///
+/// assert_eq!(count.into_inner(), 12_345_678);
+/// assert_eq!(unconsumed, ", 9");
+///
+/// let (balance, _) = <FormattedNumber<i64, PointGroupStyle>>::consume_from("-1234567")?;
+/// assert_eq!(balance.render(), "-1.234.567");
+///
+/// let error = <FormattedNumber<u8, CommaGroupStyle>>::consume_from("1,000").unwrap_err();
+/// assert_eq!(error.causes()[0].index(), &4);
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct FormattedNumber<T, Style> {
+    item: T,
+    phantom: PhantomData<Style>,
+}
+
+impl<T, Style> FormattedNumber<T, Style> {
+    /// Get a reference to the consumed number.
+    pub fn item(&self) -> &T {
+        &self.item
+    }
+
+    /// Unwrap to fetch the consumed number.
+    pub fn into_inner(self) -> T {
+        self.item
+    }
+}
+
+/// Get the amount of ASCII digits at the start of `tokens`.
+fn count_digits(tokens: &[char]) -> usize {
+    tokens
+        .iter()
+        .take_while(|token| token.is_ascii_digit())
+        .count()
+}
+
+impl<T: Consumable, Style: NumberStyle> Consumable for FormattedNumber<T, Style> {
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        // Only the characters that can be part of the number are collected.
+        let tokens: Vec<char> = source
+            .chars()
+            .take_while(|token| {
+                token.is_ascii_digit()
+                    || matches!(token, '+' | '-')
+                    || Some(*token) == Style::GROUP_SEPARATOR
+                    || *token == Style::DECIMAL_SEPARATOR
+            })
+            .collect();
+
+        // The plain number along with the utf-8 character index of every character in `source`.
+        let mut plain = String::new();
+        let mut indices = Vec::new();
+        let mut index = 0;
+
+        if let Some(sign @ ('+' | '-')) = tokens.first() {
+            plain.push(*sign);
+            indices.push(0);
+            index += 1;
+        }
+
+        let push_digits = |plain: &mut String, indices: &mut Vec<usize>, start, amount| {
+            for (offset, token) in tokens.iter().enumerate().skip(start).take(amount) {
+                plain.push(*token);
+                indices.push(offset);
+            }
+        };
+
+        let first_group = count_digits(&tokens[index..]);
+        push_digits(&mut plain, &mut indices, index, first_group);
+        index += first_group;
+
+        if (1..=3).contains(&first_group) {
+            while Style::GROUP_SEPARATOR.is_some()
+                && tokens.get(index).copied() == Style::GROUP_SEPARATOR
+                && count_digits(&tokens[index + 1..]) == 3
+            {
+                push_digits(&mut plain, &mut indices, index + 1, 3);
+                index += 4;
+            }
+        }
+
+        if tokens.get(index) == Some(&Style::DECIMAL_SEPARATOR)
+            && count_digits(&tokens[index + 1..]) > 0
+        {
+            plain.push('.');
+            indices.push(index);
+            index += 1;
+
+            let fraction = count_digits(&tokens[index..]);
+            push_digits(&mut plain, &mut indices, index, fraction);
+            index += fraction;
+        }
+
+        indices.push(index);
+
+        let ((item, _), _) = <(T, super::End)>::consume_from(&plain).map_err(|err| {
+            err.map_indices(|plain_index| indices[plain_index.min(indices.len() - 1)])
+        })?;
+
+        let length: usize = tokens[..index].iter().map(|token| token.len_utf8()).sum();
+        Ok((
+            FormattedNumber {
+                item,
+                phantom: PhantomData,
+            },
+            &source[length..],
+        ))
+    }
+}
+
+/// Renders the item and formats it according to `Style`.
+impl<T: Render, Style: NumberStyle> Render for FormattedNumber<T, Style> {
+    fn render_to(&self, out: &mut String) {
+        let plain = self.item.render();
+        let (sign, unsigned) = match plain.strip_prefix('-') {
+            Some(unsigned) => ("-", unsigned),
+            None => ("", plain.as_str()),
+        };
+        let (integer, fraction) = match unsigned.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (unsigned, None),
+        };
+
+        out.push_str(sign);
+        for (index, digit) in integer.chars().enumerate() {
+            if index > 0 && (integer.len() - index) % 3 == 0 {
+                out.extend(Style::GROUP_SEPARATOR);
+            }
+            out.push(digit);
+        }
+
+        if let Some(fraction) = fraction {
+            out.push(Style::DECIMAL_SEPARATOR);
+            out.push_str(fraction);
+        }
+    }
+}
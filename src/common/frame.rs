@@ -0,0 +1,276 @@
+use crate::error::ConsumeErrorType::*;
+use crate::limits::Limit;
+use crate::{Consumable, ConsumeError, Render};
+use std::marker::PhantomData;
+
+/// Frame of at most `MAX` utf-8 characters, which starts with its length.
+///
+/// The length is written as a decimal number followed by a `':'`, similar to a
+/// [netstring](https://en.wikipedia.org/wiki/Netstring). The `T` has to consume the whole body of
+/// the frame.
+///
+/// A frame with a length of more than `MAX` results in a
+/// [`LimitExceeded`][crate::ConsumeErrorType::LimitExceeded] error for the
+/// [`Length`][crate::limits::Limit::Length] limit, without waiting for the body. A body which ends
+/// before its length results in an
+/// [`InsufficientTokens`][crate::ConsumeErrorType::InsufficientTokens] error at the end of the
+/// `source`, so a streaming consumer waits for more data.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{Consumable, ConsumeErrorType};
+/// use manger::common::LengthPrefixed;
+/// use manger::limits::Limit;
+///
+/// type Frame = LengthPrefixed<Vec<char>, 16>;
+///
+/// let (frame, unconsumed) = Frame::consume_from("5:hello3:abc")?;
+///
+/// assert_eq!(frame.item().iter().collect::<String>(), "hello");
+/// assert_eq!(unconsumed, "3:abc");
+///
+/// assert_eq!(
+///     Frame::consume_from("5:hel").unwrap_err().causes(),
+///     vec![&ConsumeErrorType::InsufficientTokens { index: 5 }]
+/// );
+/// assert_eq!(
+///     Frame::consume_from("100:").unwrap_err().causes(),
+///     vec![&ConsumeErrorType::LimitExceeded { index: 0, limit: Limit::Length }]
+/// );
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct LengthPrefixed<T, const MAX: usize>(T);
+
+impl<T, const MAX: usize> LengthPrefixed<T, MAX> {
+    /// Get a reference to the item within the frame.
+    pub fn item(&self) -> &T {
+        &self.0
+    }
+
+    /// Unwrap to fetch the item within the frame.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: Consumable, const MAX: usize> Consumable for LengthPrefixed<T, MAX> {
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let (length, unconsumed) = <(usize, crate::chars::Char<':'>)>::consume_from(source)
+            .map(|((length, _), unconsumed)| (length, unconsumed))?;
+
+        if length > MAX {
+            return Err(ConsumeError::new_with(LimitExceeded {
+                index: 0,
+                limit: Limit::Length,
+            }));
+        }
+
+        let header = crate::strs::consumed_length(source, unconsumed);
+        let body = utf8_slice::till(unconsumed, length);
+
+        if body.chars().count() < length {
+            return Err(ConsumeError::new_with(InsufficientTokens {
+                index: header + body.chars().count(),
+            }));
+        }
+
+        let item = T::consume_all(body).map_err(|err| err.offset(header))?;
+        Ok((LengthPrefixed(item), &unconsumed[body.len()..]))
+    }
+}
+
+/// Renders the length of the rendered item and a `':'`, followed by the item.
+impl<T: Render, const MAX: usize> Render for LengthPrefixed<T, MAX> {
+    fn render_to(&self, out: &mut String) {
+        let body = self.0.render();
+        out.push_str(&body.chars().count().to_string());
+        out.push(':');
+        out.push_str(&body);
+    }
+}
+
+/// Frame of at most `MAX` utf-8 characters, which ends at the first `Delim`.
+///
+/// The body of the frame is everything before the first place where `Delim` can be consumed, and
+/// `T` has to consume the whole body. The `Delim` itself is consumed as well, but not stored.
+///
+/// When no `Delim` is found within the first `MAX` characters, this results in a
+/// [`LimitExceeded`][crate::ConsumeErrorType::LimitExceeded] error for the
+/// [`Length`][crate::limits::Limit::Length] limit at the index `MAX`. When the `source` ends
+/// before that, this results in an
+/// [`InsufficientTokens`][crate::ConsumeErrorType::InsufficientTokens] error at the end of the
+/// `source`, so a streaming consumer waits for more data.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{Consumable, ConsumeErrorType};
+/// use manger::chars::Char;
+/// use manger::common::{DelimitedFrame, NewlineToken};
+/// use manger::limits::Limit;
+///
+/// type Frame = DelimitedFrame<(u32, Char<' '>, u32), NewlineToken, 8>;
+///
+/// let (frame, unconsumed) = Frame::consume_from("1 2\r\n3 4\n")?;
+///
+/// assert_eq!(frame.item().0, 1);
+/// assert_eq!(unconsumed, "3 4\n");
+///
+/// assert_eq!(
+///     Frame::consume_from("1 2").unwrap_err().causes(),
+///     vec![&ConsumeErrorType::InsufficientTokens { index: 3 }]
+/// );
+/// assert_eq!(
+///     Frame::consume_from("123 45678\n").unwrap_err().causes(),
+///     vec![&ConsumeErrorType::LimitExceeded { index: 8, limit: Limit::Length }]
+/// );
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct DelimitedFrame<T, Delim, const MAX: usize> {
+    item: T,
+    phantom: PhantomData<Delim>,
+}
+
+impl<T, Delim, const MAX: usize> DelimitedFrame<T, Delim, MAX> {
+    /// Get a reference to the item within the frame.
+    pub fn item(&self) -> &T {
+        &self.item
+    }
+
+    /// Unwrap to fetch the item within the frame.
+    pub fn into_inner(self) -> T {
+        self.item
+    }
+}
+
+impl<T: Consumable, Delim: Consumable, const MAX: usize> Consumable
+    for DelimitedFrame<T, Delim, MAX>
+{
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let ends = source
+            .char_indices()
+            .map(|(byte_index, _)| byte_index)
+            .chain(std::iter::once(source.len()));
+
+        for (index, byte_index) in ends.enumerate() {
+            if let Ok((_, unconsumed)) = Delim::consume_from(&source[byte_index..]) {
+                let item = T::consume_all(&source[..byte_index])?;
+                return Ok((
+                    DelimitedFrame {
+                        item,
+                        phantom: PhantomData,
+                    },
+                    unconsumed,
+                ));
+            }
+
+            if index >= MAX && byte_index < source.len() {
+                return Err(ConsumeError::new_with(LimitExceeded {
+                    index: MAX,
+                    limit: Limit::Length,
+                }));
+            }
+        }
+
+        Err(ConsumeError::new_with(InsufficientTokens {
+            index: source.chars().count(),
+        }))
+    }
+}
+
+/// Renders the item, followed by the default `Delim`.
+impl<T: Render, Delim: Render + Default, const MAX: usize> Render
+    for DelimitedFrame<T, Delim, MAX>
+{
+    fn render_to(&self, out: &mut String) {
+        self.item.render_to(out);
+        Delim::default().render_to(out);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DelimitedFrame, LengthPrefixed};
+    use crate::chars::Char;
+    use crate::common::Empty;
+    use crate::error::ConsumeErrorType::*;
+    use crate::limits::Limit;
+    use crate::{Consumable, Render};
+
+    #[test]
+    fn test_length_prefixed() {
+        type Frame = LengthPrefixed<Vec<char>, 4>;
+
+        let (frame, unconsumed) = Frame::consume_from("3:ëab0:").unwrap();
+        assert_eq!(frame.item(), &vec!['ë', 'a', 'b']);
+        assert_eq!(unconsumed, "0:");
+        assert_eq!(frame.render(), "3:ëab");
+
+        let (frame, unconsumed) = Frame::consume_from(unconsumed).unwrap();
+        assert!(frame.into_inner().is_empty());
+        assert_eq!(unconsumed, "");
+
+        assert_eq!(
+            Frame::consume_from("4:abcd").map(|(frame, _)| frame.into_inner().len()),
+            Ok(4)
+        );
+        assert_eq!(
+            Frame::consume_from("5:abcde").unwrap_err().causes(),
+            vec![&LimitExceeded {
+                index: 0,
+                limit: Limit::Length
+            }]
+        );
+        assert_eq!(
+            Frame::consume_from("3").unwrap_err().causes(),
+            vec![&InsufficientTokens { index: 1 }]
+        );
+        assert_eq!(
+            <LengthPrefixed<u32, 8>>::consume_from("3:12x")
+                .unwrap_err()
+                .causes(),
+            vec![&UnexpectedToken {
+                index: 4,
+                token: 'x'
+            }]
+        );
+    }
+
+    #[test]
+    fn test_delimited_frame() {
+        type Frame = DelimitedFrame<Vec<char>, Char<';'>, 3>;
+
+        let (frame, unconsumed) = Frame::consume_from("ab;;c").unwrap();
+        assert_eq!(frame.item(), &vec!['a', 'b']);
+        assert_eq!(frame.render(), "ab;");
+
+        let (frame, unconsumed) = Frame::consume_from(unconsumed).unwrap();
+        assert!(frame.into_inner().is_empty());
+        assert_eq!(unconsumed, "c");
+
+        assert!(Frame::consume_from("abc;").is_ok());
+        assert_eq!(
+            Frame::consume_from("abc").unwrap_err().causes(),
+            vec![&InsufficientTokens { index: 3 }]
+        );
+        assert_eq!(
+            Frame::consume_from("abcd;").unwrap_err().causes(),
+            vec![&LimitExceeded {
+                index: 3,
+                limit: Limit::Length
+            }]
+        );
+        assert_eq!(
+            Frame::consume_from("ab").unwrap_err().causes(),
+            vec![&InsufficientTokens { index: 2 }]
+        );
+
+        // A delimiter which consumes nothing ends the frame directly.
+        let (frame, unconsumed) = <DelimitedFrame<Empty, Empty, 3>>::consume_from("ab").unwrap();
+        assert_eq!(frame.into_inner(), Empty);
+        assert_eq!(unconsumed, "ab");
+    }
+}
@@ -0,0 +1,83 @@
+use crate::chars::category;
+use crate::common::CharSet;
+use crate::error::ConsumeErrorType::*;
+use crate::{Consumable, ConsumeError, Render};
+use std::fmt::Display;
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+/// Adapter to consume any type `T` that implements [`FromStr`][std::str::FromStr].
+///
+/// The `source` is sliced up to the first character within the set `Delim`, or up to the end of
+/// the `source`. That slice is then given to [`T::from_str`][std::str::FromStr::from_str]. The
+/// delimiter itself is not consumed. When `from_str` fails, consuming fails with an
+/// [`InvalidValue`][crate::ConsumeErrorType::InvalidValue] at the start of the slice. By default,
+/// the slice ends at the first whitespace character.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{char_set, Consumable};
+/// use manger::common::FromStrConsumer;
+/// use std::net::Ipv4Addr;
+///
+/// char_set!(Colon => ":");
+///
+/// type Address = (FromStrConsumer<Ipv4Addr, Colon>, manger::chars::Colon, u16);
+///
+/// let ((ip, _, port), _) = Address::consume_from("127.0.0.1:8080")?;
+///
+/// assert_eq!(ip.into_inner(), Ipv4Addr::new(127, 0, 0, 1));
+/// assert_eq!(port, 8080);
+///
+/// let (address, unconsumed) = <FromStrConsumer<Ipv4Addr>>::consume_from("10.0.0.1 up")?;
+/// assert_eq!(address.item().octets(), [10, 0, 0, 1]);
+/// assert_eq!(unconsumed, " up");
+///
+/// assert!(<FromStrConsumer<Ipv4Addr>>::consume_from("10.0.0 up").is_err());
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct FromStrConsumer<T, Delim = category::Whitespace> {
+    item: T,
+    phantom: PhantomData<Delim>,
+}
+
+impl<T, Delim> FromStrConsumer<T, Delim> {
+    /// Get a reference to the consumed item.
+    pub fn item(&self) -> &T {
+        &self.item
+    }
+
+    /// Unwrap to fetch the consumed item.
+    pub fn into_inner(self) -> T {
+        self.item
+    }
+}
+
+/// Renders the item with its [`Display`] implementation.
+impl<T: Display, Delim> Render for FromStrConsumer<T, Delim> {
+    fn render_to(&self, out: &mut String) {
+        use std::fmt::Write;
+
+        write!(out, "{}", self.item).expect("writing to a String cannot fail");
+    }
+}
+
+impl<T: FromStr, Delim: CharSet> Consumable for FromStrConsumer<T, Delim> {
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let end = source.find(Delim::contains).unwrap_or(source.len());
+        let (slice, unconsumed) = source.split_at(end);
+
+        let item =
+            T::from_str(slice).map_err(|_| ConsumeError::new_with(InvalidValue { index: 0 }))?;
+
+        Ok((
+            FromStrConsumer {
+                item,
+                phantom: PhantomData,
+            },
+            unconsumed,
+        ))
+    }
+}
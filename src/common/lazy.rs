@@ -0,0 +1,74 @@
+use crate::{Consumable, ConsumeError, Render};
+use std::marker::PhantomData;
+
+/// Collection struct which stores zero or more items of type `T`, consumed up to the first
+/// position where `Until` would match.
+///
+/// A `Vec<T>` is greedy: it consumes as many items as possible. So `(Vec<char>, Until)` never
+/// consumes, because the `Vec<char>` also consumes the characters of `Until`. A `Lazy<T, Until>`
+/// checks whether `Until` can be consumed before every item and stops when it can. `Until` itself
+/// is not consumed, so it is normally followed by `Until` within a tuple. This would be equivalent
+/// to the `*?` operator in [RegEx](https://en.wikipedia.org/wiki/Regular_expression).
+///
+/// Consuming also stops when an item cannot be consumed, so `Until` is not required to follow.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{lit, Consumable};
+/// use manger::common::Lazy;
+///
+/// lit!("-->" => CommentEnd);
+///
+/// type Comment = (Lazy<char, CommentEnd>, CommentEnd);
+///
+/// let ((text, _), unconsumed) = Comment::consume_from(" a -> b --> rest")?;
+///
+/// assert_eq!(text.iter().collect::<String>(), " a -> b ");
+/// assert_eq!(unconsumed, " rest");
+///
+/// assert!(<(Vec<char>, CommentEnd)>::consume_from(" a --> rest").is_err());
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Lazy<T, Until> {
+    items: Vec<T>,
+    phantom: PhantomData<Until>,
+}
+
+impl<T, Until> Lazy<T, Until> {
+    /// Unwrap to fetch the vector of consumed items.
+    pub fn into_vec(self) -> Vec<T> {
+        self.items
+    }
+}
+
+impl<T, Until> std::ops::Deref for Lazy<T, Until> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.items
+    }
+}
+
+impl<T: Consumable, Until: Consumable> Consumable for Lazy<T, Until> {
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let (items, unconsumed) = crate::limits::repeat_while(source, 0, |unconsumed| {
+            Until::consume_from(unconsumed).is_err()
+        })?;
+
+        Ok((
+            Lazy {
+                items,
+                phantom: PhantomData,
+            },
+            unconsumed,
+        ))
+    }
+}
+
+impl<T: Render, Until> Render for Lazy<T, Until> {
+    fn render_to(&self, out: &mut String) {
+        self.items.render_to(out);
+    }
+}
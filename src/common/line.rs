@@ -0,0 +1,494 @@
+use crate::context;
+use crate::error::ConsumeErrorType::*;
+use crate::{Consumable, ConsumeError, ConsumeSource, Expectation, LiteralConsumable, Render};
+use thiserror::Error;
+
+fn is_blank(token: char) -> bool {
+    token == ' ' || token == '\t'
+}
+
+/// Split the `' '` and `'\t'` characters from the start of `source`.
+///
+/// Returns the amount of split characters and the rest of `source`.
+fn split_blanks(source: &str) -> (usize, &str) {
+    let rest = source.trim_start_matches(is_blank);
+    (source.len() - rest.len(), rest)
+}
+
+/// Enum representing the form of a line ending.
+///
+/// Consumes `"\r\n"`, `"\n"` or `"\r"` and reports which of these was found. Use
+/// [`NewlineToken`] to accept all forms without caring which one was found.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{Consumable, Render};
+/// use manger::common::LineEnding;
+///
+/// let (ending, unconsumed) = LineEnding::consume_from("\r\nnext")?;
+///
+/// assert_eq!(ending, LineEnding::CrLf);
+/// assert_eq!(unconsumed, "next");
+///
+/// assert_eq!(LineEnding::consume_from("\rnext")?.0, LineEnding::Cr);
+/// assert_eq!(LineEnding::Lf.render(), "\n");
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum LineEnding {
+    /// Consumed a `"\n"`.
+    Lf,
+
+    /// Consumed a `"\r\n"`.
+    CrLf,
+
+    /// Consumed a lone `"\r"`.
+    Cr,
+}
+
+impl LineEnding {
+    /// Get the characters of the line ending.
+    pub fn as_str(&self) -> &'static str {
+        use LineEnding::*;
+
+        match self {
+            Lf => "\n",
+            CrLf => "\r\n",
+            Cr => "\r",
+        }
+    }
+}
+
+impl Consumable for LineEnding {
+    fn expecting() -> Expectation {
+        Expectation::new("a line ending")
+    }
+
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        use LineEnding::*;
+
+        [CrLf, Lf, Cr]
+            .iter()
+            .find_map(|ending| {
+                source
+                    .strip_prefix(ending.as_str())
+                    .map(|unconsumed| (*ending, unconsumed))
+            })
+            .ok_or_else(|| {
+                ConsumeError::new_with(match source.chars().next() {
+                    Some(token) => UnexpectedToken { index: 0, token },
+                    None => InsufficientTokens { index: 0 },
+                })
+            })
+    }
+}
+
+impl Render for LineEnding {
+    fn render_to(&self, out: &mut String) {
+        out.push_str(self.as_str());
+    }
+}
+
+/// Struct representing any line ending.
+///
+/// Consumes `"\r\n"`, `"\n"` or `"\r"` uniformly, which allows grammars to accept input from
+/// all platforms. Renders a `'\n'`.
+///
+/// # Examples
+///
+/// ```
+/// use manger::Consumable;
+/// use manger::common::NewlineToken;
+///
+/// type Pair = (u32, NewlineToken, u32);
+///
+/// assert!(Pair::consume_all("1\n2").is_ok());
+/// assert!(Pair::consume_all("1\r\n2").is_ok());
+/// assert!(Pair::consume_all("1\r2").is_ok());
+/// assert!(Pair::consume_all("1 2").is_err());
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+pub struct NewlineToken;
+
+impl Consumable for NewlineToken {
+    fn expecting() -> Expectation {
+        LineEnding::expecting()
+    }
+
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        LineEnding::consume_from(source).map(|(_, unconsumed)| (NewlineToken, unconsumed))
+    }
+}
+
+impl LiteralConsumable for NewlineToken {}
+
+/// Renders a `'\n'`.
+impl Render for NewlineToken {
+    fn render_to(&self, out: &mut String) {
+        out.push('\n');
+    }
+}
+
+/// An item `T` that spans the rest of a line.
+///
+/// Consumes a `T`, optionally followed by `' '` and `'\t'` characters, followed by a
+/// [`LineEnding`] or the end of the `source`. The line ending is consumed as well, and its form
+/// is available through [`ending`][Line::ending].
+///
+/// # Examples
+///
+/// ```
+/// use manger::Consumable;
+/// use manger::common::{Line, LineEnding};
+///
+/// let (line, unconsumed) = <Line<u32>>::consume_from("42  \r\n43")?;
+///
+/// assert_eq!(*line.item(), 42);
+/// assert_eq!(line.ending(), Some(LineEnding::CrLf));
+/// assert_eq!(unconsumed, "43");
+///
+/// assert!(<Line<u32>>::consume_from("42 43").is_err());
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Line<T> {
+    item: T,
+    ending: Option<LineEnding>,
+}
+
+impl<T> Line<T> {
+    /// Get a reference to the item on the line.
+    pub fn item(&self) -> &T {
+        &self.item
+    }
+
+    /// Get the line ending that ended the line, or `None` if the line ended at the end of the
+    /// `source`.
+    pub fn ending(&self) -> Option<LineEnding> {
+        self.ending
+    }
+
+    /// Unwrap to fetch the item on the line.
+    pub fn into_inner(self) -> T {
+        self.item
+    }
+}
+
+/// Renders the item followed by its line ending, or a `'\n'` if it has none.
+impl<T: Render> Render for Line<T> {
+    fn render_to(&self, out: &mut String) {
+        self.item.render_to(out);
+        self.ending.unwrap_or(LineEnding::Lf).render_to(out);
+    }
+}
+
+impl<T: Consumable> Consumable for Line<T> {
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let mut unconsumed = source;
+
+        let (item, mut offset) = unconsumed.mut_consume_by::<T>()?;
+        let (blanks, rest) = split_blanks(unconsumed);
+        offset += blanks;
+        unconsumed = rest;
+
+        if unconsumed.is_empty() {
+            return Ok((Line { item, ending: None }, unconsumed));
+        }
+
+        let ending = unconsumed
+            .mut_consume::<LineEnding>()
+            .map_err(|err| err.offset(offset))?;
+
+        Ok((
+            Line {
+                item,
+                ending: Some(ending),
+            },
+            unconsumed,
+        ))
+    }
+}
+
+/// Collection of one item `T` per line until the end of the `source`.
+///
+/// Every line is consumed as a [`Line<T>`]. Consuming fails when any of the lines fails to
+/// consume. Lines may end in any [`LineEnding`]. [`consume_lines`][Lines::consume_lines] reports such a failure together with the line
+/// number at which it occured.
+///
+/// # Examples
+///
+/// ```
+/// use manger::Consumable;
+/// use manger::common::Lines;
+///
+/// let (numbers, unconsumed) = <Lines<i32>>::consume_from("1\n-2\r\n3\n")?;
+///
+/// assert_eq!(numbers.items(), &[1, -2, 3]);
+/// assert_eq!(unconsumed, "");
+///
+/// assert!(<Lines<i32>>::consume_from("1\ntwo\n3").is_err());
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Lines<T> {
+    items: Vec<T>,
+}
+
+/// Error for a line that failed to consume within [`Lines::consume_lines`].
+#[derive(Error, Debug, PartialEq, Eq, Clone)]
+#[error("Failed to consume line {line}")]
+pub struct LineError {
+    line: usize,
+    error: ConsumeError,
+}
+
+impl LineError {
+    /// Get the line number at which consuming failed, starting at 1.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Get the error of the line. The indices of the error are relative to the start of the line.
+    pub fn error(&self) -> &ConsumeError {
+        &self.error
+    }
+
+    /// Unwrap to fetch the error of the line.
+    pub fn into_error(self) -> ConsumeError {
+        self.error
+    }
+}
+
+impl<T> Lines<T> {
+    /// Get the items in order of the lines.
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+
+    /// Take ownership of the items in order of the lines.
+    pub fn into_vec(self) -> Vec<T> {
+        self.items
+    }
+}
+
+impl<T: Consumable> Lines<T> {
+    /// Consume an item `T` from every line of `source`.
+    ///
+    /// The `source` is registered as the input within [`context`][crate::context] while consuming.
+    /// If a line fails to consume, the line number and the error for that line are returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use manger::ConsumeErrorType::*;
+    /// use manger::common::Lines;
+    ///
+    /// let error = <Lines<u32>>::consume_lines("1\n2\n3x\n4").unwrap_err();
+    ///
+    /// assert_eq!(error.line(), 3);
+    /// assert_eq!(
+    ///     error.error().causes(),
+    ///     vec![&UnexpectedToken { index: 1, token: 'x' }]
+    /// );
+    /// ```
+    pub fn consume_lines(source: &str) -> Result<Self, LineError> {
+        context::scope(source, || {
+            let mut items = Vec::new();
+            let mut unconsumed = source;
+            let mut line = 1;
+
+            while !unconsumed.is_empty() {
+                let Line { item, .. } = unconsumed
+                    .mut_consume::<Line<T>>()
+                    .map_err(|error| LineError { line, error })?;
+
+                items.push(item);
+                line += 1;
+            }
+
+            Ok(Lines { items })
+        })
+    }
+}
+
+/// Renders every item followed by a `'\n'`.
+impl<T: Render> Render for Lines<T> {
+    fn render_to(&self, out: &mut String) {
+        for item in &self.items {
+            item.render_to(out);
+            out.push('\n');
+        }
+    }
+}
+
+impl<T: Consumable> Consumable for Lines<T> {
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let mut items = Vec::new();
+        let mut unconsumed = source;
+        let mut offset = 0;
+
+        while !unconsumed.is_empty() {
+            let (Line { item, .. }, by) = unconsumed
+                .mut_consume_by::<Line<T>>()
+                .map_err(|err| err.offset(offset))?;
+
+            items.push(item);
+            offset += by;
+        }
+
+        Ok((Lines { items }, unconsumed))
+    }
+}
+
+/// One or more items `T` on a single line, separated by `' '` and `'\t'` characters.
+///
+/// Blanks at the start of the row and blanks before the end of the line are consumed as well. The
+/// row ends before the first blanks that are not followed by a `T`. The line ending is not
+/// consumed, which allows for consuming tables with [`Lines<Row<T>>`][Lines].
+///
+/// # Examples
+///
+/// ```
+/// use manger::Consumable;
+/// use manger::common::{Lines, Row};
+///
+/// let (table, _) = <Lines<Row<u32>>>::consume_from("1 2  3\n\t4 5 6 ")?;
+///
+/// let sums: Vec<u32> = table
+///     .into_vec()
+///     .into_iter()
+///     .map(|row| row.into_vec().into_iter().sum())
+///     .collect();
+///
+/// assert_eq!(sums, vec![6, 15]);
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Row<T> {
+    items: Vec<T>,
+}
+
+impl<T> Row<T> {
+    /// Get the items of the row in order of appearance.
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+
+    /// Take ownership of the items of the row.
+    pub fn into_vec(self) -> Vec<T> {
+        self.items
+    }
+}
+
+/// Renders the items separated by a `' '`.
+impl<T: Render> Render for Row<T> {
+    fn render_to(&self, out: &mut String) {
+        for (index, item) in self.items.iter().enumerate() {
+            if index > 0 {
+                out.push(' ');
+            }
+            item.render_to(out);
+        }
+    }
+}
+
+impl<T: Consumable> Consumable for Row<T> {
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let (offset, mut unconsumed) = split_blanks(source);
+
+        let item = unconsumed
+            .mut_consume::<T>()
+            .map_err(|err| err.offset(offset))?;
+        let mut items = vec![item];
+
+        loop {
+            let (blanks, rest) = split_blanks(unconsumed);
+            if blanks == 0 {
+                break;
+            }
+
+            match T::consume_from(rest) {
+                Ok((item, rest)) => {
+                    items.push(item);
+                    unconsumed = rest;
+                }
+                Err(_) => {
+                    if rest.is_empty() || rest.starts_with(&['\n', '\r'][..]) {
+                        unconsumed = rest;
+                    }
+                    break;
+                }
+            }
+        }
+
+        Ok((Row { items }, unconsumed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Line, LineEnding, Lines, NewlineToken, Row};
+    use crate::error::ConsumeErrorType::*;
+    use crate::{Consumable, ConsumeError};
+
+    #[test]
+    fn test_line() {
+        assert_eq!(<Line<char>>::consume_from("a\r\nb").unwrap().1, "b");
+        assert_eq!(<Line<char>>::consume_from("a\t").unwrap().1, "");
+        assert_eq!(<Line<char>>::consume_from("a \rb").unwrap().1, "b");
+        assert_eq!(
+            <Line<char>>::consume_from("a \tb").unwrap_err(),
+            ConsumeError::new_with(UnexpectedToken {
+                index: 3,
+                token: 'b'
+            })
+        );
+    }
+
+    #[test]
+    fn test_line_ending() {
+        assert_eq!(
+            LineEnding::consume_from("\n\r").unwrap(),
+            (LineEnding::Lf, "\r")
+        );
+        assert_eq!(
+            LineEnding::consume_from("\r\r\n").unwrap(),
+            (LineEnding::Cr, "\r\n")
+        );
+        assert_eq!(
+            LineEnding::consume_from("").unwrap_err(),
+            ConsumeError::new_with(InsufficientTokens { index: 0 })
+        );
+        assert_eq!(
+            NewlineToken::consume_from("x").unwrap_err(),
+            ConsumeError::new_with(UnexpectedToken {
+                index: 0,
+                token: 'x'
+            })
+        );
+    }
+
+    #[test]
+    fn test_lines() {
+        assert!(<Lines<u8>>::consume_from("").unwrap().0.items().is_empty());
+        assert_eq!(
+            <Lines<u8>>::consume_from("1\n2\n300").unwrap_err(),
+            ConsumeError::new_with(InvalidValue { index: 6 })
+        );
+
+        let error = <Lines<u8>>::consume_lines("1\n\n3").unwrap_err();
+        assert_eq!(error.line(), 2);
+        assert_eq!(error.error().causes()[0].index(), &0);
+    }
+
+    #[test]
+    fn test_row() {
+        let (row, unconsumed) = <Row<u8>>::consume_from(" 1\t2  x").unwrap();
+        assert_eq!(row.items(), &[1, 2]);
+        assert_eq!(unconsumed, "  x");
+
+        assert_eq!(<Row<u8>>::consume_from("1 \n2").unwrap().1, "\n2");
+        assert!(<Row<u8>>::consume_from("  \n").is_err());
+    }
+}
@@ -0,0 +1,117 @@
+use crate::error::ConsumeErrorType::*;
+use crate::{ConsumeError, Render, SelfConsumable};
+
+/// A literal string that is matched regardless of case.
+///
+/// Consuming compares the lowercase forms of every utf-8 character, so `"SeLeCt"` matches
+/// `CaseInsensitive("select")`. The literal renders as written. Like every
+/// [`SelfConsumable`], it can be used within the `> EXPR` instruction of
+/// [`consume_struct`][crate::consume_struct] and [`consume_enum`][crate::consume_enum].
+///
+/// # Examples
+///
+/// ```
+/// use manger::ConsumeSource;
+/// use manger::common::CaseInsensitive;
+///
+/// assert_eq!("SELECT *".consume_lit(&CaseInsensitive("select"))?, " *");
+/// assert!("selekt".consume_lit(&CaseInsensitive("select")).is_err());
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct CaseInsensitive<'a>(pub &'a str);
+
+/// A keyword, which is a literal string that is not directly followed by an identifier
+/// character.
+///
+/// Identifier characters are alphanumeric characters and `'_'`, so `"letter"` does not start with
+/// `Keyword("let")`. In that case, consuming fails with an
+/// [`UnexpectedToken`][crate::ConsumeErrorType::UnexpectedToken] at the character after the
+/// keyword. The keyword renders as written.
+///
+/// # Examples
+///
+/// ```
+/// use manger::ConsumeSource;
+/// use manger::common::Keyword;
+///
+/// assert_eq!("let x".consume_lit(&Keyword("let"))?, " x");
+/// assert_eq!("let(".consume_lit(&Keyword("let"))?, "(");
+/// assert!("letter".consume_lit(&Keyword("let")).is_err());
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Keyword<'a>(pub &'a str);
+
+impl SelfConsumable for CaseInsensitive<'_> {
+    fn consume_item<'a>(source: &'a str, item: &'_ Self) -> Result<&'a str, ConsumeError> {
+        let mut characters = source.chars();
+
+        for (index, expected) in item.0.chars().enumerate() {
+            match characters.next() {
+                Some(token) if token.to_lowercase().eq(expected.to_lowercase()) => {}
+                Some(token) => {
+                    return Err(ConsumeError::new_with(UnexpectedToken { index, token }))
+                }
+                None => return Err(ConsumeError::new_with(InsufficientTokens { index })),
+            }
+        }
+
+        Ok(characters.as_str())
+    }
+}
+
+impl SelfConsumable for Keyword<'_> {
+    fn consume_item<'a>(source: &'a str, item: &'_ Self) -> Result<&'a str, ConsumeError> {
+        let unconsumed = <&str>::consume_item(source, &item.0)?;
+
+        match unconsumed.chars().next() {
+            Some(token) if token.is_alphanumeric() || token == '_' => {
+                Err(ConsumeError::new_with(UnexpectedToken {
+                    index: item.0.chars().count(),
+                    token,
+                }))
+            }
+            _ => Ok(unconsumed),
+        }
+    }
+}
+
+impl Render for CaseInsensitive<'_> {
+    fn render_to(&self, out: &mut String) {
+        out.push_str(self.0);
+    }
+}
+
+impl Render for Keyword<'_> {
+    fn render_to(&self, out: &mut String) {
+        out.push_str(self.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CaseInsensitive, Keyword};
+    use crate::error::ConsumeErrorType::*;
+    use crate::{ConsumeError, SelfConsumable};
+
+    #[test]
+    fn test_literal_wrappers() {
+        assert_eq!(
+            CaseInsensitive::consume_item("ÉtÉ!", &CaseInsensitive("été")),
+            Ok("!")
+        );
+        assert_eq!(
+            CaseInsensitive::consume_item("Hos", &CaseInsensitive("host")),
+            Err(ConsumeError::new_with(InsufficientTokens { index: 3 }))
+        );
+        assert_eq!(
+            Keyword::consume_item("if_x", &Keyword("if")),
+            Err(ConsumeError::new_with(UnexpectedToken {
+                index: 2,
+                token: '_'
+            }))
+        );
+        assert_eq!(Keyword::consume_item("if", &Keyword("if")), Ok(""));
+    }
+}
@@ -1,12 +1,28 @@
 //! Types for common structures within consuming.
 
+#[doc(inline)]
+pub use frame::{DelimitedFrame, LengthPrefixed};
+
+#[doc(inline)]
+pub use from_str::FromStrConsumer;
+
+#[doc(inline)]
+pub use line::{Line, LineEnding, LineError, Lines, NewlineToken, Row};
+
 #[doc(inline)]
 pub use one_or_more::OneOrMore;
 
+#[doc(inline)]
+pub use recovered::Recovered;
+
+#[doc(inline)]
+pub use separated_by::SeparatedBy;
+
 #[doc(inline)]
 pub use sign::Sign;
 
 #[doc(inline)]
+#[allow(deprecated)]
 pub use catch_all::CatchAll;
 
 #[doc(inline)]
@@ -18,9 +34,123 @@ pub use whitespace::Whitespace;
 #[doc(inline)]
 pub use end::End;
 
+#[doc(inline)]
+pub use anchor::{EndOfLine, Start, StartOfLine};
+
+#[doc(inline)]
+pub use any_of::{char_set_contains, AnyOf, CharSet, NoneOf};
+
+#[doc(inline)]
+pub use ascii::{AsciiDigit, AsciiWhitespace};
+
+#[doc(inline)]
+pub use bounded::{AtMost, Exactly};
+
+#[doc(inline)]
+pub use count::Count;
+
+#[cfg(feature = "deep-recursion")]
+#[doc(inline)]
+pub use deep_recursion::DeepRecursion;
+
+#[doc(inline)]
+pub use enclosed::{BalancedBrackets, Enclosed};
+
+#[doc(inline)]
+pub use escaped::{CEscapes, Escaped, Unescape};
+
+#[doc(inline)]
+pub use formatted_number::{
+    ApostropheGroupStyle, CommaGroupStyle, FormattedNumber, NumberStyle, PlainStyle,
+    PointGroupStyle, SpaceGroupStyle,
+};
+
+#[doc(inline)]
+pub use lazy::Lazy;
+
+#[doc(inline)]
+pub use overflow::{FromDigits, Integer, OverflowMode, Saturating, WrappingConsume};
+
+#[doc(inline)]
+pub use strict_int::{IntegerPolicy, PermissivePolicy, StrictInt, StrictPolicy};
+
+#[doc(inline)]
+pub use trim::{Trim, TrimEnd, TrimStart};
+
+#[doc(inline)]
+pub use ws::Ws;
+
+#[doc(inline)]
+pub use fixed_point::FixedPoint;
+
+#[doc(inline)]
+pub use unit::{Percent, WithUnit};
+
+#[doc(inline)]
+pub use byte_size::ByteSize;
+
+#[doc(inline)]
+pub use color::{Color, Rgba};
+
+#[doc(inline)]
+pub use duration::{AnyDurationStyle, ClockStyle, DurationPolicy, StyledDuration, SuffixStyle};
+
+#[doc(inline)]
+pub use path::{BackslashEscape, Glob, GlobPart, NoEscape, Path, PathPolicy};
+
+#[doc(inline)]
+pub use encoding::{Base64, Base64Policy, HexString, StandardBase64, UrlSafeBase64};
+
+#[doc(inline)]
+pub use pattern::pattern_match;
+
+#[doc(inline)]
+pub use literal::{CaseInsensitive, Keyword};
+
+#[doc(inline)]
+pub use shell_words::ShellWords;
+
+#[doc(inline)]
+pub use option_with_err::OptionWithErr;
+
+#[doc(inline)]
+pub use empty::Empty;
+
+mod anchor;
+mod any_of;
+mod ascii;
+mod bounded;
+mod byte_size;
 mod catch_all;
+mod color;
+mod count;
+#[cfg(feature = "deep-recursion")]
+mod deep_recursion;
 mod digit;
+mod duration;
+mod empty;
+mod enclosed;
+mod encoding;
 mod end;
+mod escaped;
+mod fixed_point;
+mod formatted_number;
+mod frame;
+mod from_str;
+mod lazy;
+mod line;
+mod literal;
 mod one_or_more;
+mod option_with_err;
+mod overflow;
+mod path;
+mod pattern;
+mod recovered;
+mod separated_by;
+mod shell_words;
 mod sign;
+mod strict_int;
+mod trim;
+mod unit;
 mod whitespace;
+mod ws;
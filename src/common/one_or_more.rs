@@ -1,4 +1,4 @@
-use crate::{Consumable, ConsumeError};
+use crate::{Consumable, ConsumeError, Render};
 
 /// Collection struct which stores one or more items of type `T`.
 ///
@@ -10,11 +10,9 @@ use crate::{Consumable, ConsumeError};
 ///
 /// # Note
 ///
-/// While `OneOrMore` is not iterable, the
-/// [`into_iter`][OneOrMore::into_iter], [`into_vec`][OneOrMore::into_vec],
-/// [`ref_vec`][OneOrMore::ref_vec] and [`mut_vec`][OneOrMore::mut_vec]
-/// can be used to iterate/create iterators over the items contained within the structs
-/// and do further data manipulation.
+/// The items are stored in a single `Vec<T>`, which never is empty. A `OneOrMore<T>` dereferences
+/// to a slice of its items, so slice methods such as `len`, `iter`, `iter_mut` and `first` can be
+/// used directly. It can also be indexed and iterated over by value or by reference.
 ///
 /// # Examples
 ///
@@ -43,14 +41,11 @@ use crate::{Consumable, ConsumeError};
 /// assert_eq!(product, 42);
 /// # Ok::<(), manger::ConsumeError>(())
 /// ```
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
 pub struct OneOrMore<T> {
-    /// The item that is guarenteed to be consumed
-    head: T,
-
-    /// Other items that were possibly consumed as well.
-    /// The items are in the order they were consumed by.
-    tail: Vec<T>,
+    /// The items in the order they were consumed by, which are guarenteed to contain at least
+    /// one item.
+    items: Vec<T>,
 }
 
 impl<T> OneOrMore<T> {
@@ -70,14 +65,14 @@ impl<T> OneOrMore<T> {
     /// # Ok::<(), manger::ConsumeError>(())
     /// ```
     pub fn head(&self) -> &T {
-        &self.head
+        &self.items[0]
     }
 
     /// Getter for the non-first items of a `OneOrMore<T>`.
     ///
     /// This will return references to the items that is were consumed
     /// after the first item and will be in order of they position within the
-    /// `source` string. The returned vector possibly has __NO_ items.
+    /// `source` string. The returned slice possibly has __NO__ items.
     ///
     /// # Examples
     /// ```
@@ -89,8 +84,27 @@ impl<T> OneOrMore<T> {
     /// assert_eq!(&items.tail().iter().collect::<String>(), "BcdEFg");
     /// # Ok::<(), manger::ConsumeError>(())
     /// ```
-    pub fn tail(&self) -> &Vec<T> {
-        &self.tail
+    pub fn tail(&self) -> &[T] {
+        &self.items[1..]
+    }
+
+    /// Get a slice of all the items, which always contains at least one item.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use manger::Consumable;
+    /// use manger::common::OneOrMore;
+    ///
+    /// let (items, _) = <OneOrMore<(u32, Option<char>)>>::consume_from("4,2")?;
+    ///
+    /// assert_eq!(items.as_slice().len(), 2);
+    /// assert_eq!(items[1], (2, None));
+    /// assert_eq!(items.iter().map(|(number, _)| number).sum::<u32>(), 6);
+    /// # Ok::<(), manger::ConsumeError>(())
+    /// ```
+    pub fn as_slice(&self) -> &[T] {
+        &self.items
     }
 
     /// Take ownership `self` of type `OneOrMore<T>` and return a `Vec<T>` owning all
@@ -117,12 +131,7 @@ impl<T> OneOrMore<T> {
     /// # Ok::<(), manger::ConsumeError>(())
     /// ```
     pub fn into_vec(self) -> Vec<T> {
-        let mut vec = Vec::with_capacity(self.tail().len() + 1);
-
-        vec.push(self.head);
-        self.tail.into_iter().for_each(|item| vec.push(item));
-
-        vec
+        self.items
     }
 
     /// Returns a vector with references to the items in the `OneOrMore<T>`.
@@ -146,12 +155,7 @@ impl<T> OneOrMore<T> {
     /// # Ok::<(), manger::ConsumeError>(())
     /// ```
     pub fn ref_vec(&self) -> Vec<&T> {
-        let mut vec = Vec::with_capacity(self.tail().len() + 1);
-
-        vec.push(&self.head);
-        self.tail.iter().for_each(|item| vec.push(&item));
-
-        vec
+        self.items.iter().collect()
     }
 
     /// Returns a vector with mutable references to the items in the `OneOrMore<T>`.
@@ -177,29 +181,81 @@ impl<T> OneOrMore<T> {
     /// # Ok::<(), manger::ConsumeError>(())
     /// ```
     pub fn mut_vec(&mut self) -> Vec<&mut T> {
-        let mut vec = Vec::with_capacity(self.tail().len() + 1);
+        self.items.iter_mut().collect()
+    }
+}
+
+impl<T> std::ops::Deref for OneOrMore<T> {
+    type Target = [T];
 
-        vec.push(&mut self.head);
-        self.tail.iter_mut().for_each(|item| vec.push(item));
+    fn deref(&self) -> &[T] {
+        &self.items
+    }
+}
 
-        vec
+impl<T> std::ops::DerefMut for OneOrMore<T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        &mut self.items
     }
 }
 
-impl<T: Consumable> IntoIterator for OneOrMore<T> {
+impl<T> std::ops::Index<usize> for OneOrMore<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        &self.items[index]
+    }
+}
+
+impl<T> std::ops::IndexMut<usize> for OneOrMore<T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        &mut self.items[index]
+    }
+}
+
+impl<T> IntoIterator for OneOrMore<T> {
     type Item = T;
     type IntoIter = std::vec::IntoIter<Self::Item>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.into_vec().into_iter()
+        self.items.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a OneOrMore<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut OneOrMore<T> {
+    type Item = &'a mut T;
+    type IntoIter = std::slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.iter_mut()
+    }
+}
+
+impl<T: Render> Render for OneOrMore<T> {
+    fn render_to(&self, out: &mut String) {
+        self.items.render_to(out);
     }
 }
 
 impl<T: Consumable> Consumable for OneOrMore<T> {
     fn consume_from(s: &str) -> Result<(Self, &str), ConsumeError> {
-        let (head, unconsumed) = T::consume_from(s)?;
-        let (tail, unconsumed) = <Vec<T>>::consume_from(unconsumed)?;
+        let (head, unconsumed, by) = T::consume_how_many_from(s)?;
+        let (tail, unconsumed) =
+            crate::limits::repeat(unconsumed, 1).map_err(|err| err.offset(by))?;
+
+        let mut items = Vec::with_capacity(tail.len() + 1);
+        items.push(head);
+        items.extend(tail);
 
-        Ok((OneOrMore { head, tail }, unconsumed))
+        Ok((OneOrMore { items }, unconsumed))
     }
 }
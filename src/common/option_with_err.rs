@@ -0,0 +1,88 @@
+use crate::{Consumable, ConsumeError, Render};
+
+/// Optional consumer, which keeps the error that occured when `T` could not be consumed.
+///
+/// Just like an [`Option<T>`], consuming an `OptionWithErr<T>` never fails and consumes nothing
+/// when `T` cannot be consumed. However, the error of `T` is kept instead of thrown away. This
+/// helps with reporting why an optional part was left out, when it was almost right.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{lit, Consumable, ConsumeErrorType};
+/// use manger::common::OptionWithErr;
+///
+/// lit!("e" => E);
+/// type Exponent = OptionWithErr<(E, i32)>;
+///
+/// let (exponent, unconsumed) = Exponent::consume_from("e5")?;
+/// assert_eq!(exponent.item(), &Some((E, 5)));
+/// assert_eq!(unconsumed, "");
+///
+/// let (exponent, unconsumed) = Exponent::consume_from("e+")?;
+/// assert!(exponent.item().is_none());
+/// assert_eq!(
+///     exponent.error().unwrap().causes(),
+///     vec![&ConsumeErrorType::InsufficientTokens { index: 2 }]
+/// );
+/// assert_eq!(unconsumed, "e+");
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct OptionWithErr<T> {
+    item: Option<T>,
+    error: Option<ConsumeError>,
+}
+
+impl<T> OptionWithErr<T> {
+    /// Get the consumed `T`, or `None` when it could not be consumed.
+    pub fn item(&self) -> &Option<T> {
+        &self.item
+    }
+
+    /// Get the error that occured while consuming `T`, or `None` when `T` was consumed.
+    pub fn error(&self) -> Option<&ConsumeError> {
+        self.error.as_ref()
+    }
+
+    /// Take ownership of the consumed `T` and the error that occured while consuming it.
+    pub fn into_parts(self) -> (Option<T>, Option<ConsumeError>) {
+        (self.item, self.error)
+    }
+}
+
+impl<T> From<OptionWithErr<T>> for Option<T> {
+    fn from(option: OptionWithErr<T>) -> Option<T> {
+        option.item
+    }
+}
+
+impl<T: Consumable> Consumable for OptionWithErr<T> {
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        crate::trace::attempt::<Self, _>(source, || {
+            Ok(match T::consume_from(source) {
+                Ok((item, unconsumed)) => (
+                    OptionWithErr {
+                        item: Some(item),
+                        error: None,
+                    },
+                    unconsumed,
+                ),
+                Err(err) => (
+                    OptionWithErr {
+                        item: None,
+                        error: Some(err),
+                    },
+                    source,
+                ),
+            })
+        })
+    }
+}
+
+/// Renders the consumed `T`, or nothing when it could not be consumed.
+impl<T: Render> Render for OptionWithErr<T> {
+    fn render_to(&self, out: &mut String) {
+        self.item.render_to(out);
+    }
+}
@@ -0,0 +1,216 @@
+use crate::{Consumable, ConsumeError, ConsumeErrorType, Render};
+
+/// What happens when an integer does not fit its type while consuming.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum OverflowMode {
+    /// Consuming fails with an [`InvalidValue`][crate::ConsumeErrorType::InvalidValue] at the
+    /// digit which overflows. This is what the integer types do themselves.
+    Checked,
+
+    /// The integer is clamped to the minimum or maximum value of its type.
+    Saturating,
+
+    /// The integer wraps around at the boundary of its type.
+    Wrapping,
+}
+
+/// Trait for numeric types that can be built from decimal digits.
+///
+/// The primitive integer types consume through this trait. Other numeric types, such as
+/// integers of another crate, can implement it to be consumed in the same way. Every type that
+/// implements `FromDigits` and [`Consumable`] is an [`Integer`], so it also works with
+/// [`Saturating`] and [`WrappingConsume`].
+///
+/// # Examples
+///
+/// ```
+/// use manger::{Consumable, ConsumeError};
+/// use manger::common::{FromDigits, OverflowMode, Saturating};
+///
+/// /// A percentage from 0 to 100.
+/// #[derive(Debug, PartialEq)]
+/// struct Percentage(u8);
+///
+/// impl FromDigits for Percentage {
+///     const SIGNED: bool = false;
+///
+///     fn zero() -> Self {
+///         Percentage(0)
+///     }
+///
+///     fn push_digit(self, digit: u8, _negative: bool, mode: OverflowMode) -> Option<Self> {
+///         match self.0.checked_mul(10).and_then(|value| value.checked_add(digit)) {
+///             Some(value) if value <= 100 => Some(Percentage(value)),
+///             _ if mode == OverflowMode::Checked => None,
+///             _ => Some(Percentage(100)),
+///         }
+///     }
+/// }
+///
+/// impl Consumable for Percentage {
+///     fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+///         Self::consume_digits(source, OverflowMode::Checked)
+///     }
+/// }
+///
+/// assert_eq!(Percentage::consume_from("42%")?, (Percentage(42), "%"));
+/// assert!(Percentage::consume_from("101").is_err());
+/// assert_eq!(<Saturating<Percentage>>::consume_from("250")?.0.into_inner(), Percentage(100));
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+pub trait FromDigits: Sized {
+    /// Whether the type has negative values. A leading `'-'` or `'+'` is only consumed for signed
+    /// types.
+    const SIGNED: bool;
+
+    /// Get the value without any digits.
+    fn zero() -> Self;
+
+    /// Append a decimal `digit` to the end of `self`. For negative numbers, `negative` is `true`
+    /// and the digit should be subtracted instead of added.
+    ///
+    /// Returns `None` when the result does not fit the type, which only happens in the
+    /// [`Checked`][OverflowMode::Checked] mode.
+    fn push_digit(self, digit: u8, negative: bool, mode: OverflowMode) -> Option<Self>;
+
+    /// Consume a number from the decimal digits at the start of `source`, handling overflow
+    /// according to `mode`.
+    ///
+    /// Fails with an [`InvalidValue`][ConsumeErrorType::InvalidValue] at the digit for which
+    /// [`push_digit`][FromDigits::push_digit] returns `None`.
+    fn consume_digits(source: &str, mode: OverflowMode) -> Result<(Self, &str), ConsumeError> {
+        let (negative, start) = match source.as_bytes().first() {
+            Some(b'-') if Self::SIGNED => (true, 1),
+            Some(b'+') if Self::SIGNED => (false, 1),
+            _ => (false, 0),
+        };
+
+        let mut number = Self::zero();
+        let mut index = start;
+
+        while let Some(byte) = source
+            .as_bytes()
+            .get(index)
+            .filter(|byte| byte.is_ascii_digit())
+        {
+            number = number
+                .push_digit(byte - b'0', negative, mode)
+                .ok_or_else(|| ConsumeError::new_with(ConsumeErrorType::InvalidValue { index }))?;
+            index += 1;
+        }
+
+        if index == start {
+            return Err(ConsumeError::new_with(
+                match source[start..].chars().next() {
+                    Some(token) => ConsumeErrorType::UnexpectedToken {
+                        index: start,
+                        token,
+                    },
+                    None => ConsumeErrorType::InsufficientTokens { index: start },
+                },
+            ));
+        }
+
+        Ok((number, &source[index..]))
+    }
+}
+
+/// Trait for integer types, which can be consumed with any [`OverflowMode`].
+///
+/// All digits are consumed in every mode, so the unconsumed part of the `source` does not depend
+/// on the mode. This is implemented for every [`FromDigits`] type that is [`Consumable`].
+///
+/// # Examples
+///
+/// ```
+/// use manger::common::{Integer, OverflowMode};
+///
+/// assert!(u8::consume_with("300", OverflowMode::Checked).is_err());
+/// assert_eq!(u8::consume_with("300", OverflowMode::Saturating)?, (255, ""));
+/// assert_eq!(u8::consume_with("300", OverflowMode::Wrapping)?, (44, ""));
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+pub trait Integer: Consumable {
+    /// Consume an integer from the start of `source`, handling overflow according to `mode`.
+    fn consume_with(source: &str, mode: OverflowMode) -> Result<(Self, &str), ConsumeError>;
+}
+
+impl<T: FromDigits + Consumable> Integer for T {
+    fn consume_with(source: &str, mode: OverflowMode) -> Result<(Self, &str), ConsumeError> {
+        T::consume_digits(source, mode)
+    }
+}
+
+/// An integer of type `T` which is clamped to the bounds of `T` when it overflows.
+///
+/// # Examples
+///
+/// ```
+/// use manger::Consumable;
+/// use manger::common::Saturating;
+///
+/// let (readings, _) = <Vec<(Saturating<i8>, Option<char>)>>::consume_from("12,-300,400")?;
+/// let readings: Vec<i8> = readings
+///     .into_iter()
+///     .map(|(reading, _)| reading.into_inner())
+///     .collect();
+///
+/// assert_eq!(readings, vec![12, -128, 127]);
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Saturating<T>(T);
+
+/// An integer of type `T` which wraps around at the bounds of `T` when it overflows.
+///
+/// This is named after consuming, to avoid confusion with [`std::num::Wrapping`].
+///
+/// # Examples
+///
+/// ```
+/// use manger::Consumable;
+/// use manger::common::WrappingConsume;
+///
+/// let (hash, _) = <WrappingConsume<u32>>::consume_from("4294967297")?;
+///
+/// assert_eq!(hash.into_inner(), 1);
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct WrappingConsume<T>(T);
+
+macro_rules! impl_overflow_wrapper {
+    ( $( $wrapper:ident => $mode:ident ),+ ) => {
+        $(
+            impl<T> $wrapper<T> {
+                /// Unwrap to fetch the consumed integer.
+                pub fn into_inner(self) -> T {
+                    self.0
+                }
+            }
+
+            impl<T> std::ops::Deref for $wrapper<T> {
+                type Target = T;
+
+                fn deref(&self) -> &T {
+                    &self.0
+                }
+            }
+
+            impl<T: Integer> Consumable for $wrapper<T> {
+                fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+                    T::consume_with(source, OverflowMode::$mode)
+                        .map(|(item, unconsumed)| ($wrapper(item), unconsumed))
+                }
+            }
+
+            impl<T: Render> Render for $wrapper<T> {
+                fn render_to(&self, out: &mut String) {
+                    self.0.render_to(out);
+                }
+            }
+        )+
+    };
+}
+
+impl_overflow_wrapper!(Saturating => Saturating, WrappingConsume => Wrapping);
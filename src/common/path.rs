@@ -0,0 +1,442 @@
+use crate::error::ConsumeErrorType::*;
+use crate::{Consumable, ConsumeError, Expectation, Render};
+use std::marker::PhantomData;
+
+/// Trait that defines how special characters are escaped within a [`Path`].
+pub trait PathPolicy {
+    /// The character that makes the next character part of a segment, even if it is a `'/'` or
+    /// whitespace. `None` disables escaping.
+    const ESCAPE: Option<char>;
+}
+
+/// Policy that escapes characters with a `'\\'`, as POSIX shells do.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+pub struct BackslashEscape;
+
+impl PathPolicy for BackslashEscape {
+    const ESCAPE: Option<char> = Some('\\');
+}
+
+/// Policy without escaping, so a `'\\'` is an ordinary character.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+pub struct NoEscape;
+
+impl PathPolicy for NoEscape {
+    const ESCAPE: Option<char> = None;
+}
+
+/// Split `source` into characters, resolving escapes with `escape`, until the first unescaped
+/// whitespace.
+///
+/// Returns the characters with whether they were escaped, the index in `source` of every
+/// character followed by the amount of consumed characters, and the unconsumed part of `source`.
+#[allow(clippy::type_complexity)]
+fn split_escaped(
+    source: &str,
+    escape: Option<char>,
+) -> Result<(Vec<(char, bool)>, Vec<usize>, &str), ConsumeError> {
+    let mut tokens = Vec::new();
+    let mut indices = Vec::new();
+    let mut characters = source.char_indices();
+    let mut index = 0;
+
+    while let Some((byte_index, token)) = characters.next() {
+        if token.is_whitespace() {
+            indices.push(index);
+            return Ok((tokens, indices, &source[byte_index..]));
+        }
+
+        indices.push(index);
+        if Some(token) == escape {
+            match characters.next() {
+                Some((_, escaped)) => tokens.push((escaped, true)),
+                None => {
+                    return Err(ConsumeError::new_with(InsufficientTokens {
+                        index: index + 1,
+                    }))
+                }
+            }
+            index += 2;
+        } else {
+            tokens.push((token, false));
+            index += 1;
+        }
+    }
+
+    indices.push(index);
+    Ok((tokens, indices, ""))
+}
+
+/// Fail with an error for the start of `source`, which contains no path or glob.
+fn empty_error(source: &str) -> ConsumeError {
+    ConsumeError::new_with(match source.chars().next() {
+        Some(token) => UnexpectedToken { index: 0, token },
+        None => InsufficientTokens { index: 0 },
+    })
+}
+
+/// A POSIX-style path of segments separated by `'/'`.
+///
+/// The path ends at the first whitespace that is not escaped according to `Policy`. Empty
+/// segments, such as in `a//b`, are skipped. The segments are unescaped.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{Consumable, Render};
+/// use manger::common::{NoEscape, Path};
+///
+/// let (path, unconsumed) = <Path>::consume_from(r"/home/my\ files/notes.txt rest")?;
+///
+/// assert!(path.is_absolute());
+/// assert_eq!(path.segments(), &["home", "my files", "notes.txt"]);
+/// assert_eq!(unconsumed, " rest");
+/// assert_eq!(path.render(), r"/home/my\ files/notes.txt");
+///
+/// let (path, _) = <Path<NoEscape>>::consume_from(r"C:\dir/file")?;
+/// assert!(!path.is_absolute());
+/// assert_eq!(path.segments(), &[r"C:\dir", "file"]);
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Path<Policy = BackslashEscape> {
+    absolute: bool,
+    segments: Vec<String>,
+    phantom: PhantomData<Policy>,
+}
+
+impl<Policy> Path<Policy> {
+    /// Returns whether the path starts with a `'/'`.
+    pub fn is_absolute(&self) -> bool {
+        self.absolute
+    }
+
+    /// Get the unescaped segments of the path.
+    pub fn segments(&self) -> &[String] {
+        &self.segments
+    }
+
+    /// Take ownership of the unescaped segments of the path.
+    pub fn into_segments(self) -> Vec<String> {
+        self.segments
+    }
+}
+
+impl<Policy: PathPolicy> Consumable for Path<Policy> {
+    fn expecting() -> Expectation {
+        Expectation::new("a path")
+    }
+
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let (tokens, _, unconsumed) = split_escaped(source, Policy::ESCAPE)?;
+        if tokens.is_empty() {
+            return Err(empty_error(source));
+        }
+
+        let mut segments = vec![String::new()];
+        for (token, escaped) in &tokens {
+            match (token, escaped) {
+                ('/', false) => segments.push(String::new()),
+                _ => segments
+                    .last_mut()
+                    .into_iter()
+                    .for_each(|segment| segment.push(*token)),
+            }
+        }
+        segments.retain(|segment| !segment.is_empty());
+
+        Ok((
+            Path {
+                absolute: tokens[0] == ('/', false),
+                segments,
+                phantom: PhantomData,
+            },
+            unconsumed,
+        ))
+    }
+}
+
+/// Renders the path with the special characters escaped according to `Policy`.
+impl<Policy: PathPolicy> Render for Path<Policy> {
+    fn render_to(&self, out: &mut String) {
+        if self.absolute {
+            out.push('/');
+        }
+
+        for (index, segment) in self.segments.iter().enumerate() {
+            if index > 0 {
+                out.push('/');
+            }
+
+            for token in segment.chars() {
+                if let Some(escape) = Policy::ESCAPE {
+                    if token == escape || token == '/' || token.is_whitespace() {
+                        out.push(escape);
+                    }
+                }
+                out.push(token);
+            }
+        }
+    }
+}
+
+/// One part of a segment of a [`Glob`].
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub enum GlobPart {
+    /// Characters that have to match literally.
+    Literal(String),
+
+    /// A `'?'`, which matches any one character.
+    AnyChar,
+
+    /// A `'*'`, which matches any amount of characters within a segment.
+    AnyString,
+
+    /// A `"**"` that forms a whole segment, which matches any amount of segments.
+    AnySegments,
+
+    /// A character class, such as `[a-z_]` or `[!0-9]`, which matches one character that is
+    /// within one of the inclusive `ranges`, or that is in none of them if `negated` is `true`.
+    Class {
+        /// Whether the class starts with a `'!'` or `'^'`.
+        negated: bool,
+        /// The inclusive ranges of the class. A single character is a range from and to itself.
+        ranges: Vec<(char, char)>,
+    },
+}
+
+/// A glob pattern, such as `src/**/*.rs` or `[!.]*.toml`.
+///
+/// The pattern ends at the first whitespace that is not escaped with a `'\\'`. It is split into
+/// segments at every `'/'` and every segment is a list of [`GlobPart`]s. An unclosed character
+/// class results in an [`UnclosedDelimiter`][crate::ConsumeErrorType::UnclosedDelimiter].
+///
+/// # Examples
+///
+/// ```
+/// use manger::{Consumable, Render};
+/// use manger::common::{Glob, GlobPart};
+///
+/// let (glob, _) = Glob::consume_from("src/**/[!_]*.rs")?;
+///
+/// assert_eq!(glob.segments().len(), 3);
+/// assert_eq!(glob.segments()[1], vec![GlobPart::AnySegments]);
+/// assert_eq!(
+///     glob.segments()[2],
+///     vec![
+///         GlobPart::Class { negated: true, ranges: vec![('_', '_')] },
+///         GlobPart::AnyString,
+///         GlobPart::Literal(".rs".to_string()),
+///     ]
+/// );
+/// assert_eq!(glob.render(), "src/**/[!_]*.rs");
+///
+/// assert!(Glob::consume_from("[a-z").is_err());
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Glob {
+    absolute: bool,
+    segments: Vec<Vec<GlobPart>>,
+}
+
+impl Glob {
+    /// Returns whether the pattern starts with a `'/'`.
+    pub fn is_absolute(&self) -> bool {
+        self.absolute
+    }
+
+    /// Get the segments of the pattern.
+    pub fn segments(&self) -> &[Vec<GlobPart>] {
+        &self.segments
+    }
+}
+
+/// Push `token` to the literal at the end of `segment`, or start a new literal.
+fn push_literal(segment: &mut Vec<GlobPart>, token: char) {
+    match segment.last_mut() {
+        Some(GlobPart::Literal(literal)) => literal.push(token),
+        _ => segment.push(GlobPart::Literal(token.to_string())),
+    }
+}
+
+/// Consume a character class from `tokens`, which start after the `'['` at `opened_at`.
+fn consume_class(
+    tokens: &[(char, bool)],
+    opened_at: usize,
+) -> Result<(GlobPart, usize), ConsumeError> {
+    let negated = matches!(tokens.first(), Some(('!' | '^', false)));
+    let mut index = usize::from(negated);
+    let mut ranges = Vec::new();
+
+    loop {
+        match tokens.get(index) {
+            // A `']'` directly after the opening is part of the class.
+            Some((']', false)) if !ranges.is_empty() => break,
+            Some((start, _)) => match (tokens.get(index + 1), tokens.get(index + 2)) {
+                (Some(('-', false)), Some((end, escaped))) if *end != ']' || *escaped => {
+                    ranges.push((*start, *end));
+                    index += 3;
+                }
+                _ => {
+                    ranges.push((*start, *start));
+                    index += 1;
+                }
+            },
+            None => {
+                return Err(ConsumeError::new_with(UnclosedDelimiter {
+                    index: opened_at + 1 + index,
+                    opened_at,
+                    token: '[',
+                }))
+            }
+        }
+    }
+
+    Ok((GlobPart::Class { negated, ranges }, index + 1))
+}
+
+impl Consumable for Glob {
+    fn expecting() -> Expectation {
+        Expectation::new("a glob pattern")
+    }
+
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let (tokens, indices, unconsumed) = split_escaped(source, Some('\\'))?;
+        if tokens.is_empty() {
+            return Err(empty_error(source));
+        }
+
+        let mut segments = vec![Vec::new()];
+        let mut index = 0;
+
+        while let Some(&(token, escaped)) = tokens.get(index) {
+            let segment = segments.last_mut().expect("there is always a segment");
+            index += 1;
+
+            match (token, escaped) {
+                ('/', false) => segments.push(Vec::new()),
+                ('?', false) => segment.push(GlobPart::AnyChar),
+                ('*', false) if tokens.get(index) == Some(&('*', false)) && segment.is_empty() => {
+                    index += 1;
+                    segment.push(GlobPart::AnySegments);
+                }
+                ('*', false) => segment.push(GlobPart::AnyString),
+                ('[', false) => {
+                    // Escapes take two characters in `source`, so map the token indices.
+                    let (class, length) = consume_class(&tokens[index..], index - 1)
+                        .map_err(|err| err.map_indices(|position| indices[position]))?;
+                    segment.push(class);
+                    index += length;
+                }
+                _ => push_literal(segment, token),
+            }
+        }
+
+        let absolute = segments.len() > 1 && segments[0].is_empty();
+        segments.retain(|segment| !segment.is_empty());
+
+        Ok((Glob { absolute, segments }, unconsumed))
+    }
+}
+
+/// Escape the characters in `literal` that have a special meaning within a glob.
+fn render_glob_literal(literal: &str, out: &mut String) {
+    for token in literal.chars() {
+        if "\\/?*[".contains(token) || token.is_whitespace() {
+            out.push('\\');
+        }
+        out.push(token);
+    }
+}
+
+impl Render for Glob {
+    fn render_to(&self, out: &mut String) {
+        if self.absolute {
+            out.push('/');
+        }
+
+        for (index, segment) in self.segments.iter().enumerate() {
+            if index > 0 {
+                out.push('/');
+            }
+
+            for part in segment {
+                match part {
+                    GlobPart::Literal(literal) => render_glob_literal(literal, out),
+                    GlobPart::AnyChar => out.push('?'),
+                    GlobPart::AnyString => out.push('*'),
+                    GlobPart::AnySegments => out.push_str("**"),
+                    GlobPart::Class { negated, ranges } => {
+                        out.push('[');
+                        if *negated {
+                            out.push('!');
+                        }
+                        for (start, end) in ranges {
+                            out.push(*start);
+                            if start != end {
+                                out.push('-');
+                                out.push(*end);
+                            }
+                        }
+                        out.push(']');
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Glob, GlobPart, Path};
+    use crate::error::ConsumeErrorType::*;
+    use crate::{Consumable, ConsumeError, Render};
+
+    #[test]
+    fn test_path() {
+        let (path, unconsumed) = <Path>::consume_from("a//b/\n").unwrap();
+        assert_eq!(path.segments(), &["a", "b"]);
+        assert_eq!(unconsumed, "\n");
+
+        assert_eq!(<Path>::consume_all("/").unwrap().render(), "/");
+        assert_eq!(
+            <Path>::consume_from(r"ab\").unwrap_err(),
+            ConsumeError::new_with(InsufficientTokens { index: 3 })
+        );
+        assert_eq!(
+            <Path>::consume_from(" a").unwrap_err(),
+            ConsumeError::new_with(UnexpectedToken {
+                index: 0,
+                token: ' '
+            })
+        );
+    }
+
+    #[test]
+    fn test_glob() {
+        let glob = Glob::consume_all(r"/a\*[]a-]?").unwrap();
+        assert!(glob.is_absolute());
+        assert_eq!(
+            glob.segments(),
+            &[vec![
+                GlobPart::Literal("a*".to_string()),
+                GlobPart::Class {
+                    negated: false,
+                    ranges: vec![(']', ']'), ('a', 'a'), ('-', '-')]
+                },
+                GlobPart::AnyChar,
+            ]]
+        );
+        assert_eq!(glob.render(), r"/a\*[]a-]?");
+
+        assert_eq!(
+            Glob::consume_from(r"\?[ab").unwrap_err(),
+            ConsumeError::new_with(UnclosedDelimiter {
+                index: 5,
+                opened_at: 2,
+                token: '['
+            })
+        );
+    }
+}
@@ -0,0 +1,112 @@
+use crate::error::ConsumeErrorType::*;
+use crate::ConsumeError;
+
+/// Match the start of `source` against `pattern`, where a `'#'` matches any ASCII digit and every
+/// other character matches itself.
+///
+/// Returns the unconsumed part of `source`. This is used by [`pattern`][crate::pattern].
+///
+/// # Examples
+///
+/// ```
+/// use manger::common::pattern_match;
+///
+/// assert_eq!(pattern_match("##:##", "12:30 PM")?, " PM");
+/// assert!(pattern_match("##:##", "12.30").is_err());
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+pub fn pattern_match<'a>(pattern: &str, source: &'a str) -> Result<&'a str, ConsumeError> {
+    let mut characters = source.chars();
+
+    for (index, expected) in pattern.chars().enumerate() {
+        match characters.next() {
+            Some(token) if token == expected || (expected == '#' && token.is_ascii_digit()) => {}
+            Some(token) => return Err(ConsumeError::new_with(UnexpectedToken { index, token })),
+            None => return Err(ConsumeError::new_with(InsufficientTokens { index })),
+        }
+    }
+
+    Ok(characters.as_str())
+}
+
+/// A macro used to define types for fixed-format strings, such as phone or serial numbers.
+///
+/// Every pattern is a string where a `'#'` means an ASCII digit and every other character is
+/// literal. Every type becomes a struct holding the consumed string, which consumes exactly the
+/// pattern and renders the consumed string again. The pattern is also available through the
+/// associated `PATTERN` constant. This is a middle ground between a regular expression and a
+/// hand-written type.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{pattern, Consumable, Render};
+///
+/// pattern! {
+///     /// A social security number.
+///     "###-##-####" => pub Ssn;
+///     "(###) ###-####" => PhoneNumber
+/// }
+///
+/// let (ssn, unconsumed) = Ssn::consume_from("078-05-1120 rest")?;
+/// assert_eq!(ssn.as_str(), "078-05-1120");
+/// assert_eq!(ssn.digits(), vec![0, 7, 8, 0, 5, 1, 1, 2, 0]);
+/// assert_eq!(unconsumed, " rest");
+/// assert_eq!(ssn.render(), "078-05-1120");
+///
+/// assert!(Ssn::consume_from("078-5-1120").is_err());
+/// assert!(PhoneNumber::consume_all("(555) 010-4477").is_ok());
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[macro_export]
+macro_rules! pattern {
+    ( $( $( #[$attr:meta] )* $pattern:literal => $vis:vis $name:ident );+ $(;)? ) => {
+        $(
+            $( #[$attr] )*
+            #[derive(Debug, PartialEq, Eq, Clone, Hash)]
+            $vis struct $name(String);
+
+            #[allow(dead_code)]
+            impl $name {
+                /// The pattern, where a `'#'` means an ASCII digit.
+                pub const PATTERN: &'static str = $pattern;
+
+                /// Get the string that was consumed.
+                pub fn as_str(&self) -> &str {
+                    &self.0
+                }
+
+                /// Get the values of the digits at the `'#'` positions of the pattern.
+                pub fn digits(&self) -> Vec<u8> {
+                    $pattern
+                        .chars()
+                        .zip(self.0.chars())
+                        .filter(|(expected, _)| *expected == '#')
+                        .map(|(_, token)| token as u8 - b'0')
+                        .collect()
+                }
+            }
+
+            impl $crate::Consumable for $name {
+                fn expecting() -> $crate::Expectation {
+                    $crate::Expectation::new(concat!("`", $pattern, "`"))
+                }
+
+                fn consume_from(
+                    source: &str,
+                ) -> ::std::result::Result<(Self, &str), $crate::ConsumeError> {
+                    let unconsumed = $crate::common::pattern_match($pattern, source)?;
+                    let consumed = &source[..source.len() - unconsumed.len()];
+
+                    Ok(($name(consumed.to_string()), unconsumed))
+                }
+            }
+
+            impl $crate::Render for $name {
+                fn render_to(&self, out: &mut String) {
+                    out.push_str(&self.0);
+                }
+            }
+        )+
+    };
+}
@@ -0,0 +1,86 @@
+use crate::{Consumable, ConsumeError};
+use std::marker::PhantomData;
+
+/// Error-tolerant consumer, which recovers from a failure to consume `T` by consuming `R`.
+///
+/// Consuming a `Recovered<T, R>` first attempts to consume a `T`. When that fails, the error is
+/// recorded and a recovery type `R` is consumed from the same `source` instead. This is often a
+/// type that skips to a synchronization point, such as the next `';'`. Consuming only fails when
+/// both `T` and `R` fail, in which case the errors of both are returned.
+///
+/// This allows for consuming a sequence of items, while collecting the errors for all the items
+/// that were malformed.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{char_set, Consumable};
+/// use manger::chars;
+/// use manger::common::{NoneOf, Recovered};
+///
+/// char_set!(Semicolon => ";");
+///
+/// // Skip everything up to and including the next `;`.
+/// type SkipStatement = (Vec<NoneOf<Semicolon>>, chars::Semicolon);
+/// type Statement = Recovered<(u32, chars::Semicolon), SkipStatement>;
+///
+/// let (statements, unconsumed) = <Vec<Statement>>::consume_from("1;x;3;")?;
+///
+/// assert_eq!(unconsumed, "");
+/// assert_eq!(statements.len(), 3);
+/// assert!(statements[0].result().is_ok());
+/// assert!(statements[1].is_recovered());
+/// assert_eq!(statements[1].result().as_ref().unwrap_err().causes()[0].index(), &0);
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Recovered<T, R> {
+    result: Result<T, ConsumeError>,
+    phantom: PhantomData<R>,
+}
+
+impl<T, R> Recovered<T, R> {
+    /// Get the consumed `T` or the error that occured while consuming it.
+    pub fn result(&self) -> &Result<T, ConsumeError> {
+        &self.result
+    }
+
+    /// Take ownership of the consumed `T` or the error that occured while consuming it.
+    pub fn into_result(self) -> Result<T, ConsumeError> {
+        self.result
+    }
+
+    /// Returns whether consuming `T` failed and `R` was consumed instead.
+    pub fn is_recovered(&self) -> bool {
+        self.result.is_err()
+    }
+}
+
+impl<T, R> From<Recovered<T, R>> for Result<T, ConsumeError> {
+    fn from(recovered: Recovered<T, R>) -> Result<T, ConsumeError> {
+        recovered.result
+    }
+}
+
+impl<T: Consumable, R: Consumable> Consumable for Recovered<T, R> {
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let (result, unconsumed) = match T::consume_from(source) {
+            Ok((item, unconsumed)) => (Ok(item), unconsumed),
+            Err(mut err) => match R::consume_from(source) {
+                Ok((_, unconsumed)) => (Err(err), unconsumed),
+                Err(recovery_err) => {
+                    err.add_causes(recovery_err);
+                    return Err(err);
+                }
+            },
+        };
+
+        Ok((
+            Recovered {
+                result,
+                phantom: PhantomData,
+            },
+            unconsumed,
+        ))
+    }
+}
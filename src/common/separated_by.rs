@@ -0,0 +1,134 @@
+use crate::{Consumable, ConsumeError, Render};
+use std::marker::PhantomData;
+
+/// Collection struct which stores zero or more items of type `T` that were separated by `Sep`.
+///
+/// Consuming a `SeparatedBy<T, Sep>` consumes a `T`, followed by any number of `Sep` and `T`
+/// pairs. A trailing `Sep` which is not followed by a `T` is not consumed. Just as a
+/// [`Vec<T>`][std::vec::Vec], consuming a `SeparatedBy` can never fail itself.
+///
+/// Within the [`consume_struct`][crate::consume_struct] and
+/// [`consume_enum`][crate::consume_enum] macros, a literal separator can be given directly
+/// with the `name: TYPE [ SEPARATOR ]` instruction.
+///
+/// # Examples
+///
+/// ```
+/// use manger::Consumable;
+/// use manger::chars;
+/// use manger::common::SeparatedBy;
+///
+/// let (numbers, unconsumed) = <SeparatedBy<u32, chars::Comma>>::consume_from("1,2,3,")?;
+///
+/// assert_eq!(numbers.items(), &[1, 2, 3]);
+/// assert_eq!(unconsumed, ",");
+///
+/// let (numbers, unconsumed) = <SeparatedBy<u32, chars::Comma>>::consume_from("none")?;
+///
+/// assert!(numbers.into_vec().is_empty());
+/// assert_eq!(unconsumed, "none");
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct SeparatedBy<T, Sep> {
+    items: Vec<T>,
+    phantom: PhantomData<Sep>,
+}
+
+impl<T, Sep> SeparatedBy<T, Sep> {
+    /// Getter for the items of a `SeparatedBy<T, Sep>` in the order they were consumed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use manger::Consumable;
+    /// use manger::chars;
+    /// use manger::common::SeparatedBy;
+    ///
+    /// let (letters, _) = <SeparatedBy<char, chars::Semicolon>>::consume_from("a;b;c")?;
+    ///
+    /// assert_eq!(letters.items().iter().collect::<String>(), "abc");
+    /// # Ok::<(), manger::ConsumeError>(())
+    /// ```
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+
+    /// Take ownership `self` of type `SeparatedBy<T, Sep>` and return a `Vec<T>` owning all
+    /// the items `self` used to contain.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use manger::Consumable;
+    /// use manger::chars;
+    /// use manger::common::SeparatedBy;
+    ///
+    /// let (numbers, _) = <SeparatedBy<i32, chars::Plus>>::consume_from("1+-2+3")?;
+    ///
+    /// assert_eq!(numbers.into_vec().into_iter().sum::<i32>(), 2);
+    /// # Ok::<(), manger::ConsumeError>(())
+    /// ```
+    pub fn into_vec(self) -> Vec<T> {
+        self.items
+    }
+}
+
+impl<T, Sep> From<SeparatedBy<T, Sep>> for Vec<T> {
+    fn from(separated: SeparatedBy<T, Sep>) -> Vec<T> {
+        separated.items
+    }
+}
+
+impl<T, Sep> IntoIterator for SeparatedBy<T, Sep> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.into_iter()
+    }
+}
+
+/// Renders the items with the default separator between each pair of items.
+impl<T: Render, Sep: Render + Default> Render for SeparatedBy<T, Sep> {
+    fn render_to(&self, out: &mut String) {
+        let separator = Sep::default();
+
+        for (index, item) in self.items.iter().enumerate() {
+            if index > 0 {
+                separator.render_to(out);
+            }
+            item.render_to(out);
+        }
+    }
+}
+
+impl<T: Consumable, Sep: Consumable> Consumable for SeparatedBy<T, Sep> {
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let mut items = Vec::new();
+        let mut last_unconsumed = source;
+
+        if let Ok((item, unconsumed)) = T::consume_from(last_unconsumed) {
+            items.push(item);
+            last_unconsumed = unconsumed;
+
+            while let Ok((item, unconsumed)) = <(Sep, T)>::consume_from(last_unconsumed) {
+                // A separator and item that consume nothing would be consumed forever.
+                if unconsumed.len() == last_unconsumed.len() {
+                    break;
+                }
+
+                items.push(item.1);
+                last_unconsumed = unconsumed;
+            }
+        }
+
+        Ok((
+            SeparatedBy {
+                items,
+                phantom: PhantomData,
+            },
+            last_unconsumed,
+        ))
+    }
+}
@@ -0,0 +1,190 @@
+use crate::error::ConsumeErrorType::*;
+use crate::{Consumable, ConsumeError, Expectation, Render};
+
+/// A command line split into arguments, like a POSIX shell does.
+///
+/// Arguments are separated by spaces and tabs and the command line ends at an unquoted newline,
+/// which is not consumed. Within an argument:
+///
+/// - characters between `'\''`s are literal,
+/// - characters between `'"'`s are literal, except that a `'\\'` escapes a `'"'`, `'\\'`, `'$'`,
+///   `` '`' `` or newline,
+/// - and otherwise a `'\\'` escapes any character. An escaped newline continues the line and is
+///   removed.
+///
+/// Adjacent quoted and unquoted parts form a single argument. A quote without its closing quote
+/// results in an [`UnclosedDelimiter`][crate::ConsumeErrorType::UnclosedDelimiter]. Variables,
+/// globs and other expansions are not performed.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{Consumable, Render};
+/// use manger::common::ShellWords;
+///
+/// let (words, unconsumed) = ShellWords::consume_from(r#"git commit -m "fix \"it\"" 'a b'c\ d"#)?;
+///
+/// assert_eq!(words.words(), &["git", "commit", "-m", "fix \"it\"", "a bc d"]);
+/// assert_eq!(unconsumed, "");
+/// assert_eq!(words.render(), r#"git commit -m 'fix "it"' 'a bc d'"#);
+///
+/// assert!(ShellWords::consume_from("echo 'open").is_err());
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Default)]
+pub struct ShellWords(Vec<String>);
+
+impl ShellWords {
+    /// Get the arguments in order of appearance.
+    pub fn words(&self) -> &[String] {
+        &self.0
+    }
+
+    /// Take ownership of the arguments.
+    pub fn into_vec(self) -> Vec<String> {
+        self.0
+    }
+}
+
+impl From<ShellWords> for Vec<String> {
+    fn from(words: ShellWords) -> Self {
+        words.0
+    }
+}
+
+impl From<Vec<String>> for ShellWords {
+    fn from(words: Vec<String>) -> Self {
+        ShellWords(words)
+    }
+}
+
+impl Consumable for ShellWords {
+    fn expecting() -> Expectation {
+        Expectation::new("a command line")
+    }
+
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let mut words = Vec::new();
+        let mut word: Option<String> = None;
+        let mut characters = source.char_indices().peekable();
+        let mut index = 0;
+        let mut end = source.len();
+
+        while let Some((byte_index, token)) = characters.next() {
+            let start = index;
+            index += 1;
+
+            match token {
+                '\n' => {
+                    end = byte_index;
+                    break;
+                }
+                ' ' | '\t' => words.extend(word.take()),
+                '\\' => match characters.next() {
+                    Some((_, '\n')) => index += 1,
+                    Some((_, escaped)) => {
+                        index += 1;
+                        word.get_or_insert_with(String::new).push(escaped);
+                    }
+                    None => return Err(ConsumeError::new_with(InsufficientTokens { index })),
+                },
+                '\'' | '"' => {
+                    let word = word.get_or_insert_with(String::new);
+
+                    loop {
+                        let (_, quoted) = characters.next().ok_or_else(|| {
+                            ConsumeError::new_with(UnclosedDelimiter {
+                                index,
+                                opened_at: start,
+                                token,
+                            })
+                        })?;
+                        index += 1;
+
+                        if quoted == token {
+                            break;
+                        }
+
+                        match characters.peek() {
+                            Some((_, escaped))
+                                if token == '"'
+                                    && quoted == '\\'
+                                    && "\"\\$`\n".contains(*escaped) =>
+                            {
+                                if *escaped != '\n' {
+                                    word.push(*escaped);
+                                }
+                                characters.next();
+                                index += 1;
+                            }
+                            _ => word.push(quoted),
+                        }
+                    }
+                }
+                _ => word.get_or_insert_with(String::new).push(token),
+            }
+        }
+
+        words.extend(word);
+
+        Ok((ShellWords(words), &source[end..]))
+    }
+}
+
+/// Renders the arguments separated by spaces, with single quotes around the arguments that need
+/// quoting.
+impl Render for ShellWords {
+    fn render_to(&self, out: &mut String) {
+        for (index, word) in self.0.iter().enumerate() {
+            if index > 0 {
+                out.push(' ');
+            }
+
+            let plain = !word.is_empty()
+                && word
+                    .chars()
+                    .all(|token| token.is_alphanumeric() || "-_./=:,@%+".contains(token));
+
+            if plain {
+                out.push_str(word);
+            } else {
+                out.push('\'');
+                out.push_str(&word.replace('\'', r"'\''"));
+                out.push('\'');
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ShellWords;
+    use crate::error::ConsumeErrorType::*;
+    use crate::{Consumable, ConsumeError, Render};
+
+    #[test]
+    fn test_shell_words() {
+        let (words, unconsumed) =
+            ShellWords::consume_from("  a\\\nb \"\\a\\$\" '' x'\\'\nnext").unwrap();
+        assert_eq!(words.words(), &["ab", "\\a$", "", "x\\"]);
+        assert_eq!(unconsumed, "\nnext");
+        assert_eq!(words.render(), r"ab '\a$' '' 'x\'");
+
+        assert_eq!(
+            ShellWords::consume_all("it's").unwrap_err(),
+            ConsumeError::new_with(UnclosedDelimiter {
+                index: 4,
+                opened_at: 2,
+                token: '\''
+            })
+        );
+        assert_eq!(
+            ShellWords::consume_all("a\\").unwrap_err(),
+            ConsumeError::new_with(InsufficientTokens { index: 2 })
+        );
+        assert_eq!(
+            ShellWords::consume_all(&ShellWords::from(vec!["it's".to_string()]).render()),
+            Ok(ShellWords::from(vec!["it's".to_string()]))
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use crate::consume_enum;
+use crate::{consume_enum, Render};
 
 /// Enum that represents parsing a number sign.
 ///
@@ -27,7 +27,7 @@ use crate::consume_enum;
 /// );
 /// # Ok::<(), manger::ConsumeError>(())
 /// ```
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
 pub enum Sign {
     /// Consumed either a '+' or nothing.
     Positive,
@@ -50,7 +50,7 @@ consume_enum!(
             : chars::Plus;
         ],
         Empty => [
-            : common::CatchAll;
+            : common::Empty;
         ]
     }
 );
@@ -94,6 +94,41 @@ impl Sign {
     }
 }
 
+/// Converts `'+'` into `Positive` and `'-'` into `Negative`.
+///
+/// Any other character results in an
+/// [`UnexpectedToken`][crate::ConsumeErrorType::UnexpectedToken] at index `0`.
+///
+/// # Examples
+///
+/// ```
+/// use manger::common::Sign;
+/// use std::convert::TryFrom;
+///
+/// assert_eq!(Sign::try_from('-'), Ok(Sign::Negative));
+/// assert!(Sign::try_from('~').is_err());
+/// ```
+impl std::convert::TryFrom<char> for Sign {
+    type Error = crate::ConsumeErrorType;
+
+    fn try_from(token: char) -> Result<Self, Self::Error> {
+        match token {
+            '+' => Ok(Sign::Positive),
+            '-' => Ok(Sign::Negative),
+            _ => Err(crate::ConsumeErrorType::UnexpectedToken { index: 0, token }),
+        }
+    }
+}
+
+/// Renders `'-'` for `Negative` and nothing for `Positive`.
+impl Render for Sign {
+    fn render_to(&self, out: &mut String) {
+        if self.is_negative() {
+            out.push('-');
+        }
+    }
+}
+
 macro_rules! from_sign_int {
     ( $( $type:ty ),* ) => {
         $(
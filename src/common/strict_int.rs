@@ -0,0 +1,143 @@
+use crate::error::ConsumeErrorType::*;
+use crate::{Consumable, ConsumeError, Render};
+use std::marker::PhantomData;
+
+/// Trait that defines which notations of an integer are allowed by [`StrictInt`].
+///
+/// # Examples
+///
+/// ```
+/// use manger::Consumable;
+/// use manger::common::{IntegerPolicy, StrictInt};
+///
+/// /// Allows a leading `+`, but no leading zeros.
+/// struct Signed;
+/// impl IntegerPolicy for Signed {
+///     const LEADING_PLUS: bool = true;
+///     const LEADING_ZEROS: bool = false;
+/// }
+///
+/// assert!(<StrictInt<i32, Signed>>::consume_from("+42").is_ok());
+/// assert!(<StrictInt<i32, Signed>>::consume_from("+042").is_err());
+/// ```
+pub trait IntegerPolicy {
+    /// Whether a `'+'` may precede the digits.
+    const LEADING_PLUS: bool;
+
+    /// Whether the digits may start with a `'0'` that is followed by more digits.
+    const LEADING_ZEROS: bool;
+}
+
+/// Policy that allows neither a leading `'+'` nor leading zeros, as in JSON.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+pub struct StrictPolicy;
+
+impl IntegerPolicy for StrictPolicy {
+    const LEADING_PLUS: bool = false;
+    const LEADING_ZEROS: bool = false;
+}
+
+/// Policy that allows a leading `'+'` and leading zeros, which is what the integer types allow
+/// themselves.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+pub struct PermissivePolicy;
+
+impl IntegerPolicy for PermissivePolicy {
+    const LEADING_PLUS: bool = true;
+    const LEADING_ZEROS: bool = true;
+}
+
+/// An integer of type `T` whose notation is restricted by the [`IntegerPolicy`] `Policy`.
+///
+/// Consuming a `StrictInt<T, Policy>` checks the notation before consuming a `T`. A `'+'` or a
+/// leading zero that is not allowed results in an
+/// [`InvalidValue`][crate::ConsumeErrorType::InvalidValue] at its index. A sign that is not
+/// followed by a digit results in an `InvalidValue` at the index of the sign. A `'+'` that is
+/// allowed is also accepted for unsigned integer types.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{Consumable, ConsumeErrorType};
+/// use manger::common::{PermissivePolicy, StrictInt};
+///
+/// let (number, unconsumed) = <StrictInt<i32>>::consume_from("-10, 7")?;
+///
+/// assert_eq!(number.into_inner(), -10);
+/// assert_eq!(unconsumed, ", 7");
+///
+/// assert_eq!(
+///     <StrictInt<u8>>::consume_from("+1").unwrap_err().causes(),
+///     vec![&ConsumeErrorType::InvalidValue { index: 0 }]
+/// );
+/// assert_eq!(
+///     <StrictInt<i8>>::consume_from("-007").unwrap_err().causes(),
+///     vec![&ConsumeErrorType::InvalidValue { index: 1 }]
+/// );
+/// assert_eq!(
+///     <StrictInt<i8>>::consume_from("- 7").unwrap_err().causes(),
+///     vec![&ConsumeErrorType::InvalidValue { index: 0 }]
+/// );
+///
+/// assert!(<StrictInt<u8>>::consume_from("0").is_ok());
+/// assert_eq!(<StrictInt<u8, PermissivePolicy>>::consume_from("+007")?.0.into_inner(), 7);
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct StrictInt<T, Policy = StrictPolicy> {
+    item: T,
+    phantom: PhantomData<Policy>,
+}
+
+impl<T, Policy> StrictInt<T, Policy> {
+    /// Get a reference to the consumed integer.
+    pub fn item(&self) -> &T {
+        &self.item
+    }
+
+    /// Unwrap to fetch the consumed integer.
+    pub fn into_inner(self) -> T {
+        self.item
+    }
+}
+
+impl<T: Consumable, Policy: IntegerPolicy> Consumable for StrictInt<T, Policy> {
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let bytes = source.as_bytes();
+        let invalid = |index| Err(ConsumeError::new_with(InvalidValue { index }));
+
+        let sign = match bytes.first() {
+            Some(b'+') if !Policy::LEADING_PLUS => return invalid(0),
+            Some(b'+' | b'-') => 1,
+            _ => 0,
+        };
+
+        match (bytes.get(sign), bytes.get(sign + 1)) {
+            (Some(digit), _) if sign > 0 && !digit.is_ascii_digit() => return invalid(0),
+            (None, _) if sign > 0 => return invalid(0),
+            (Some(b'0'), Some(digit)) if !Policy::LEADING_ZEROS && digit.is_ascii_digit() => {
+                return invalid(sign)
+            }
+            _ => {}
+        }
+
+        // A `'+'` is skipped, so it is also accepted for unsigned integer types.
+        let skipped = if bytes.first() == Some(&b'+') { 1 } else { 0 };
+        let (item, unconsumed) =
+            T::consume_from(&source[skipped..]).map_err(|err| err.offset(skipped))?;
+
+        Ok((
+            StrictInt {
+                item,
+                phantom: PhantomData,
+            },
+            unconsumed,
+        ))
+    }
+}
+
+impl<T: Render, Policy> Render for StrictInt<T, Policy> {
+    fn render_to(&self, out: &mut String) {
+        self.item.render_to(out);
+    }
+}
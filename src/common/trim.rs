@@ -0,0 +1,122 @@
+use super::{Count, Whitespace};
+use crate::{Consumable, ConsumeError, ConsumeSource, Expectation, Render};
+
+macro_rules! trim_wrapper {
+    ( $( #[$attr:meta] )* $name:ident, $leading:expr, $trailing:expr ) => {
+        $( #[$attr] )*
+        #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+        pub struct $name<T>(T);
+
+        impl<T> $name<T> {
+            /// Unwrap to fetch the consumed item.
+            pub fn into_inner(self) -> T {
+                self.0
+            }
+        }
+
+        impl<T> std::ops::Deref for $name<T> {
+            type Target = T;
+
+            fn deref(&self) -> &T {
+                &self.0
+            }
+        }
+
+        impl<T: Consumable> Consumable for $name<T> {
+            fn expecting() -> Expectation {
+                T::expecting()
+            }
+
+            fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+                let mut unconsumed = source;
+                let mut offset = 0;
+
+                if $leading {
+                    offset += unconsumed.mut_consume_by::<Count<Whitespace>>()?.1;
+                }
+
+                let item = unconsumed
+                    .mut_consume::<T>()
+                    .map_err(|err| err.offset(offset))?;
+
+                if $trailing {
+                    unconsumed.mut_consume::<Count<Whitespace>>()?;
+                }
+
+                Ok(($name(item), unconsumed))
+            }
+        }
+
+        /// Renders only the inner item.
+        impl<T: Render> Render for $name<T> {
+            fn render_to(&self, out: &mut String) {
+                self.0.render_to(out);
+            }
+        }
+    };
+}
+
+trim_wrapper!(
+    /// Struct which consumes `T` surrounded by any amount of [`Whitespace`].
+    ///
+    /// This is useful for parsing single values provided by users, which often contain stray
+    /// whitespace. Use [`TrimStart`] or [`TrimEnd`] to only skip the whitespace on one side.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use manger::Consumable;
+    /// use manger::common::Trim;
+    ///
+    /// let (value, unconsumed) = <Trim<u32>>::consume_from("  42 \n")?;
+    ///
+    /// assert_eq!(value.into_inner(), 42);
+    /// assert_eq!(unconsumed, "");
+    ///
+    /// assert_eq!(*<Trim<u32>>::consume_all("7")?, 7);
+    /// # Ok::<(), manger::ConsumeError>(())
+    /// ```
+    Trim,
+    true,
+    true
+);
+
+trim_wrapper!(
+    /// Struct which consumes any amount of leading [`Whitespace`] followed by `T`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use manger::Consumable;
+    /// use manger::common::TrimStart;
+    ///
+    /// let (value, unconsumed) = <TrimStart<i32>>::consume_from("\t-3 ")?;
+    ///
+    /// assert_eq!(value.into_inner(), -3);
+    /// assert_eq!(unconsumed, " ");
+    /// # Ok::<(), manger::ConsumeError>(())
+    /// ```
+    TrimStart,
+    true,
+    false
+);
+
+trim_wrapper!(
+    /// Struct which consumes `T` followed by any amount of trailing [`Whitespace`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use manger::Consumable;
+    /// use manger::common::TrimEnd;
+    ///
+    /// let (value, unconsumed) = <TrimEnd<char>>::consume_from("a  b")?;
+    ///
+    /// assert_eq!(value.into_inner(), 'a');
+    /// assert_eq!(unconsumed, "b");
+    /// # Ok::<(), manger::ConsumeError>(())
+    /// ```
+    TrimEnd,
+    false,
+    true
+);
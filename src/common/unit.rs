@@ -0,0 +1,130 @@
+use crate::{Consumable, ConsumeError, ConsumeSource, Render};
+
+/// A number `T` followed by a `'%'`.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{Consumable, Render};
+/// use manger::common::Percent;
+///
+/// let (discount, unconsumed) = <Percent<f32>>::consume_from("12.5% off")?;
+///
+/// assert_eq!(discount.value(), &12.5);
+/// assert_eq!(discount.fraction(), 0.125);
+/// assert_eq!(unconsumed, " off");
+///
+/// assert!(<Percent<u8>>::consume_from("12").is_err());
+/// assert_eq!(<Percent<u8>>::consume_all("50%")?.render(), "50%");
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+pub struct Percent<T>(T);
+
+impl<T> Percent<T> {
+    /// Get the number before the `'%'`.
+    pub fn value(&self) -> &T {
+        &self.0
+    }
+
+    /// Unwrap to fetch the number before the `'%'`.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: Copy + Into<f64>> Percent<T> {
+    /// Get the percentage as a fraction, where `100%` is `1.0`.
+    pub fn fraction(&self) -> f64 {
+        self.0.into() / 100.0
+    }
+}
+
+impl<T: Consumable> Consumable for Percent<T> {
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let mut unconsumed = source;
+
+        let (value, offset) = unconsumed.mut_consume_by::<T>()?;
+        unconsumed
+            .mut_consume_lit(&'%')
+            .map_err(|err| err.offset(offset))?;
+
+        Ok((Percent(value), unconsumed))
+    }
+}
+
+impl<T: Render> Render for Percent<T> {
+    fn render_to(&self, out: &mut String) {
+        self.0.render_to(out);
+        out.push('%');
+    }
+}
+
+/// A number `T` directly followed by a unit `U`.
+///
+/// The unit is usually an enum created with [`consume_enum`][crate::consume_enum], which
+/// consumes the accepted unit suffixes. Both the value and the unit are returned.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{consume_enum, Consumable};
+/// use manger::common::WithUnit;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Time {
+///     Milliseconds,
+///     Seconds,
+/// }
+/// consume_enum!(
+///     Time {
+///         Milliseconds => [ > "ms"; ],
+///         Seconds => [ > 's'; ]
+///     }
+/// );
+///
+/// let (timeout, unconsumed) = <WithUnit<u32, Time>>::consume_from("10ms;")?;
+///
+/// assert_eq!(timeout.value(), &10);
+/// assert_eq!(timeout.unit(), &Time::Milliseconds);
+/// assert_eq!(unconsumed, ";");
+///
+/// assert_eq!(<WithUnit<u32, Time>>::consume_all("3s")?.into_parts(), (3, Time::Seconds));
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+pub struct WithUnit<T, U> {
+    value: T,
+    unit: U,
+}
+
+impl<T, U> WithUnit<T, U> {
+    /// Get the number before the unit.
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// Get the unit.
+    pub fn unit(&self) -> &U {
+        &self.unit
+    }
+
+    /// Take ownership of the number and the unit.
+    pub fn into_parts(self) -> (T, U) {
+        (self.value, self.unit)
+    }
+}
+
+impl<T: Consumable, U: Consumable> Consumable for WithUnit<T, U> {
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        <(T, U)>::consume_from(source)
+            .map(|((value, unit), unconsumed)| (WithUnit { value, unit }, unconsumed))
+    }
+}
+
+impl<T: Render, U: Render> Render for WithUnit<T, U> {
+    fn render_to(&self, out: &mut String) {
+        self.value.render_to(out);
+        self.unit.render_to(out);
+    }
+}
@@ -1,11 +1,32 @@
+use crate::error::ConsumeErrorType::*;
+use crate::strs::split_first_char;
+use crate::{Consumable, ConsumeError, Expectation, LiteralConsumable, Render};
+
 /// Struct representing a Whitespace utf-8 character.
 ///
 /// Will consume all characters which return true on [`char::is_whitespace`].
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
 pub struct Whitespace;
 
-crate::consume_struct!(
-    Whitespace => [
-        : char { |token: char| token.is_whitespace() };
-    ]
-);
+impl Consumable for Whitespace {
+    fn expecting() -> Expectation {
+        Expectation::new("whitespace")
+    }
+
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        match split_first_char(source) {
+            Some((token, unconsumed)) if token.is_whitespace() => Ok((Whitespace, unconsumed)),
+            Some(_) => Err(ConsumeError::new_with(InvalidValue { index: 0 })),
+            None => Err(ConsumeError::new_with(InsufficientTokens { index: 0 })),
+        }
+    }
+}
+
+impl LiteralConsumable for Whitespace {}
+
+/// Renders a `' '`.
+impl Render for Whitespace {
+    fn render_to(&self, out: &mut String) {
+        out.push(' ');
+    }
+}
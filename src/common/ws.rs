@@ -0,0 +1,98 @@
+use super::{Count, Whitespace};
+use crate::{Consumable, ConsumeError, ConsumeSource, Render};
+
+/// A tuple of type `T` whose elements may be separated by whitespace.
+///
+/// A tuple such as `(A, B, C)` requires its elements to follow each other directly. Consuming a
+/// `Ws<(A, B, C)>` consumes zero or more [`Whitespace`] characters between the elements, but not
+/// before the first or after the last element. Rendering puts a single space between the
+/// elements.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{Consumable, Render};
+/// use manger::chars::Equals;
+/// use manger::common::Ws;
+///
+/// type Assignment = Ws<(char, Equals, i32)>;
+///
+/// let (assignment, unconsumed) = Assignment::consume_from("x  =\t-5 ")?;
+/// let (name, _, value) = assignment.into_inner();
+///
+/// assert_eq!((name, value), ('x', -5));
+/// assert_eq!(unconsumed, " ");
+///
+/// assert_eq!(Assignment::consume_all("y=1")?.render(), "y = 1");
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+pub struct Ws<T>(T);
+
+impl<T> Ws<T> {
+    /// Unwrap to fetch the tuple of consumed elements.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> std::ops::Deref for Ws<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+macro_rules! ws_concat {
+    ( $first_ident:ident => $first_index:tt $( , $type_ident:ident => $index:tt )+ ) => {
+        impl<$first_ident, $( $type_ident ),+> Consumable for Ws<($first_ident, $( $type_ident ),+)>
+        where
+            $first_ident: Consumable,
+            $( $type_ident: Consumable ),+
+        {
+            fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+                let mut unconsumed = source;
+                let mut offset = 0;
+
+                let tuple = (
+                    unconsumed
+                        .mut_consume_by::<$first_ident>()
+                        .map(|(item, by)| { offset += by; item })?,
+                    $(
+                        unconsumed
+                            .mut_consume_by::<(Count<Whitespace>, $type_ident)>()
+                            .map_err(|err| err.offset(offset))
+                            .map(|((_, item), by)| { offset += by; item })?
+                    ),+
+                );
+
+                Ok((Ws(tuple), unconsumed))
+            }
+        }
+
+        impl<$first_ident, $( $type_ident ),+> Render for Ws<($first_ident, $( $type_ident ),+)>
+        where
+            $first_ident: Render,
+            $( $type_ident: Render ),+
+        {
+            fn render_to(&self, out: &mut String) {
+                (self.0).$first_index.render_to(out);
+                $(
+                    out.push(' ');
+                    (self.0).$index.render_to(out);
+                )+
+            }
+        }
+    };
+}
+
+ws_concat!(A => 0, B => 1);
+ws_concat!(A => 0, B => 1, C => 2);
+ws_concat!(A => 0, B => 1, C => 2, D => 3);
+ws_concat!(A => 0, B => 1, C => 2, D => 3, E => 4);
+ws_concat!(A => 0, B => 1, C => 2, D => 3, E => 4, F => 5);
+ws_concat!(A => 0, B => 1, C => 2, D => 3, E => 4, F => 5, G => 6);
+ws_concat!(A => 0, B => 1, C => 2, D => 3, E => 4, F => 5, G => 6, H => 7);
+ws_concat!(A => 0, B => 1, C => 2, D => 3, E => 4, F => 5, G => 6, H => 7, I => 8);
+ws_concat!(A => 0, B => 1, C => 2, D => 3, E => 4, F => 5, G => 6, H => 7, I => 8, J => 9);
@@ -0,0 +1,622 @@
+//! Types for consuming __INI-style configuration__ files.
+//!
+//! A configuration consists of lines, which can be:
+//!
+//! - a `key = value` pair, which is a [`KeyValue`],
+//! - a `[section]` header, which is a [`SectionHeader`],
+//! - a comment starting with `';'` or `'#'`, which is a [`Comment`],
+//! - or a blank line.
+//!
+//! The [`Config`] type consumes a whole configuration into its global entries and its
+//! [`Section`]s, skipping comments and blank lines.
+//!
+//! The [`FrontMatter`] type consumes the fenced block of `key: value` or `key = value` pairs at the
+//! start of a document, as used by static site generators, without needing a YAML or TOML parser.
+//!
+//! # Examples
+//!
+//! ```
+//! use manger::Consumable;
+//! use manger::config::Config;
+//!
+//! let source = "\
+//! ; global settings
+//! name = manger
+//!
+//! [owner]
+//! email = me@example.com
+//! ; trailing comment
+//! ";
+//!
+//! let config = Config::consume_all(source)?;
+//!
+//! assert_eq!(config.get("name"), Some("manger"));
+//! assert_eq!(
+//!     config.section("owner").and_then(|owner| owner.get("email")),
+//!     Some("me@example.com")
+//! );
+//! # Ok::<(), manger::ConsumeError>(())
+//! ```
+
+use crate::common::End;
+use crate::error::ConsumeErrorType::*;
+use crate::{consume_enum, consume_struct, Consumable, ConsumeError, ConsumeSource};
+use std::marker::PhantomData;
+
+/// Split `source` at the first character for which `predicate` does not hold.
+fn split_while(source: &str, predicate: impl Fn(char) -> bool) -> (&str, &str) {
+    let index = source
+        .char_indices()
+        .find(|(_, token)| !predicate(*token))
+        .map_or(source.len(), |(index, _)| index);
+
+    source.split_at(index)
+}
+
+fn is_blank(token: char) -> bool {
+    token == ' ' || token == '\t'
+}
+
+fn is_line_content(token: char) -> bool {
+    token != '\n' && token != '\r'
+}
+
+enum LineEnding {
+    CarriageReturnNewLine,
+    NewLine,
+    End,
+}
+
+consume_enum!(
+    LineEnding {
+        CarriageReturnNewLine => [ > "\r\n"; ],
+        NewLine => [ > '\n'; ],
+        End => [ : End; ]
+    }
+);
+
+/// A comment that starts with `';'` or `'#'` and spans the rest of the line.
+///
+/// The text of the comment excludes the starting character and the line ending. The line ending is
+/// not consumed.
+///
+/// # Examples
+///
+/// ```
+/// use manger::Consumable;
+/// use manger::config::Comment;
+///
+/// let (comment, unconsumed) = Comment::consume_from("; a comment\nkey = value")?;
+///
+/// assert_eq!(comment.text(), " a comment");
+/// assert_eq!(unconsumed, "\nkey = value");
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Comment(String);
+
+impl Comment {
+    /// Get the text of the comment.
+    pub fn text(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Consumable for Comment {
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let (_, unconsumed) = char::consume_from(source).and_then(|(token, unconsumed)| {
+            if token == ';' || token == '#' {
+                Ok((token, unconsumed))
+            } else {
+                Err(ConsumeError::new_with(UnexpectedToken { index: 0, token }))
+            }
+        })?;
+
+        let (text, unconsumed) = split_while(unconsumed, is_line_content);
+
+        Ok((Comment(text.to_string()), unconsumed))
+    }
+}
+
+/// A section header of the form `[name]`.
+///
+/// Blanks around the name are ignored. The line ending is not consumed.
+///
+/// # Examples
+///
+/// ```
+/// use manger::Consumable;
+/// use manger::config::SectionHeader;
+///
+/// let (header, _) = SectionHeader::consume_from("[ database ]")?;
+///
+/// assert_eq!(header.name(), "database");
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct SectionHeader(String);
+
+impl SectionHeader {
+    /// Get the name of the section.
+    pub fn name(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Consumable for SectionHeader {
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let mut unconsumed = source.consume_lit(&'[')?;
+
+        let (name, rest) = split_while(unconsumed, |token| token != ']' && is_line_content(token));
+        let offset = 1 + utf8_slice::len(name);
+        unconsumed = rest.consume_lit(&']').map_err(|err| err.offset(offset))?;
+
+        Ok((SectionHeader(name.trim().to_string()), unconsumed))
+    }
+}
+
+/// A `key = value` pair.
+///
+/// Blanks around the key and the value are ignored. The value spans the rest of the line and the
+/// line ending is not consumed.
+///
+/// # Examples
+///
+/// ```
+/// use manger::Consumable;
+/// use manger::config::KeyValue;
+///
+/// let (entry, unconsumed) = KeyValue::consume_from("timeout = 30 s\n")?;
+///
+/// assert_eq!(entry.key(), "timeout");
+/// assert_eq!(entry.value(), "30 s");
+/// assert_eq!(unconsumed, "\n");
+///
+/// // Values can be consumed further with any `Consumable` type.
+/// let (seconds, _) = entry.value().consume::<u32>()?;
+/// assert_eq!(seconds, 30);
+/// # use manger::ConsumeSource;
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct KeyValue {
+    key: String,
+    value: String,
+}
+
+impl KeyValue {
+    /// Get the key of the pair.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Get the value of the pair.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+}
+
+impl Consumable for KeyValue {
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let (leading, unconsumed) = split_while(source, is_blank);
+        let mut offset = utf8_slice::len(leading);
+
+        let (key, unconsumed) =
+            split_while(unconsumed, |token| token != '=' && is_line_content(token));
+
+        match key.chars().next() {
+            None => {
+                return Err(ConsumeError::new_with(match unconsumed.chars().next() {
+                    Some(token) => UnexpectedToken {
+                        index: offset,
+                        token,
+                    },
+                    None => InsufficientTokens { index: offset },
+                }))
+            }
+            Some(token) if token == '[' || token == ';' || token == '#' => {
+                return Err(ConsumeError::new_with(UnexpectedToken {
+                    index: offset,
+                    token,
+                }))
+            }
+            _ => {}
+        }
+
+        offset += utf8_slice::len(key);
+        let unconsumed = unconsumed
+            .consume_lit(&'=')
+            .map_err(|err| err.offset(offset))?;
+
+        let (value, unconsumed) = split_while(unconsumed, is_line_content);
+
+        Ok((
+            KeyValue {
+                key: key.trim_end().to_string(),
+                value: value.trim().to_string(),
+            },
+            unconsumed,
+        ))
+    }
+}
+
+/// A named section with its entries.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Section {
+    name: String,
+    entries: Vec<KeyValue>,
+}
+
+impl Section {
+    /// Get the name of the section.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the entries of the section in order of appearance.
+    pub fn entries(&self) -> &[KeyValue] {
+        &self.entries
+    }
+
+    /// Get the value of the last entry with `key` within the section.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        find_value(&self.entries, key)
+    }
+}
+
+/// A configuration with global entries and sections.
+///
+/// Global entries are the entries before the first section header. Comments and blank lines are
+/// skipped. Every line has to end with a line ending (`"\n"` or `"\r\n"`) or the end of the
+/// `source`.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Config {
+    global: Vec<KeyValue>,
+    sections: Vec<Section>,
+}
+
+impl Config {
+    /// Get the entries before the first section header in order of appearance.
+    pub fn global(&self) -> &[KeyValue] {
+        &self.global
+    }
+
+    /// Get the sections in order of appearance.
+    pub fn sections(&self) -> &[Section] {
+        &self.sections
+    }
+
+    /// Get the first section called `name`.
+    pub fn section(&self, name: &str) -> Option<&Section> {
+        self.sections.iter().find(|section| section.name == name)
+    }
+
+    /// Get the value of the last global entry with `key`.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        find_value(&self.global, key)
+    }
+}
+
+fn find_value<'a>(entries: &'a [KeyValue], key: &str) -> Option<&'a str> {
+    entries
+        .iter()
+        .rev()
+        .find(|entry| entry.key == key)
+        .map(KeyValue::value)
+}
+
+struct Blanks;
+
+impl Consumable for Blanks {
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        Ok((Blanks, split_while(source, is_blank).1))
+    }
+}
+
+enum Line {
+    Comment,
+    Section(SectionHeader),
+    Entry(KeyValue),
+    Blank,
+}
+
+consume_enum!(
+    Line {
+        Comment => [
+            : Blanks,
+            : Comment;
+        ],
+        Section => [
+            : Blanks,
+            header: SectionHeader,
+            : Blanks;
+            (header)
+        ],
+        Entry => [
+            entry: KeyValue;
+            (entry)
+        ],
+        Blank => [
+            : Blanks;
+        ]
+    }
+);
+
+struct TerminatedLine(Line);
+
+consume_struct!(
+    TerminatedLine => [
+        line: Line,
+        : LineEnding;
+        (line)
+    ]
+);
+
+impl Consumable for Config {
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let mut config = Config {
+            global: Vec::new(),
+            sections: Vec::new(),
+        };
+        let mut unconsumed = source;
+
+        while !unconsumed.is_empty() {
+            let offset = utf8_slice::len(source) - utf8_slice::len(unconsumed);
+            let TerminatedLine(line) = unconsumed
+                .mut_consume::<TerminatedLine>()
+                .map_err(|err| err.offset(offset))?;
+
+            match line {
+                Line::Section(header) => config.sections.push(Section {
+                    name: header.0,
+                    entries: Vec::new(),
+                }),
+                Line::Entry(entry) => match config.sections.last_mut() {
+                    Some(section) => section.entries.push(entry),
+                    None => config.global.push(entry),
+                },
+                Line::Comment | Line::Blank => {}
+            }
+        }
+
+        Ok((config, unconsumed))
+    }
+}
+
+/// Trait that defines the fences and separator of a [`FrontMatter`] block.
+pub trait FrontMatterStyle {
+    /// The line that opens and closes the block.
+    const FENCE: &'static str;
+
+    /// The character between a key and its value.
+    const SEPARATOR: char;
+}
+
+/// Style of YAML front matter, with `---` fences and `key: value` pairs.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+pub struct YamlStyle;
+
+impl FrontMatterStyle for YamlStyle {
+    const FENCE: &'static str = "---";
+    const SEPARATOR: char = ':';
+}
+
+/// Style of TOML front matter, with `+++` fences and `key = value` pairs.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+pub struct TomlStyle;
+
+impl FrontMatterStyle for TomlStyle {
+    const FENCE: &'static str = "+++";
+    const SEPARATOR: char = '=';
+}
+
+/// A front matter block of flat key-value pairs between two fence lines.
+///
+/// Blanks around keys and values are ignored and a value between matching `'"'` or `'\''`
+/// quotes is unquoted. Blank lines and lines starting with a `'#'` are skipped. Nested values,
+/// lists and escapes are not supported. The document after the closing fence is left unconsumed.
+/// A block without a closing fence results in an
+/// [`UnclosedDelimiter`][crate::ConsumeErrorType::UnclosedDelimiter].
+///
+/// # Examples
+///
+/// ```
+/// use manger::Consumable;
+/// use manger::config::{FrontMatter, TomlStyle};
+///
+/// let source = "---\ntitle: \"Hello: world\"\n# draft: true\ntags: a, b\n---\n# Body";
+/// let (front, body) = <FrontMatter>::consume_from(source)?;
+///
+/// assert_eq!(front.get("title"), Some("Hello: world"));
+/// assert_eq!(front.get("tags"), Some("a, b"));
+/// assert_eq!(front.get("draft"), None);
+/// assert_eq!(body, "# Body");
+///
+/// let (front, _) = <FrontMatter<TomlStyle>>::consume_from("+++\ndate = 2021-01-01\n+++\n")?;
+/// assert_eq!(front.get("date"), Some("2021-01-01"));
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct FrontMatter<Style = YamlStyle> {
+    entries: Vec<KeyValue>,
+    phantom: PhantomData<Style>,
+}
+
+impl<Style> FrontMatter<Style> {
+    /// Get the entries in order of appearance.
+    pub fn entries(&self) -> &[KeyValue] {
+        &self.entries
+    }
+
+    /// Get the value of the last entry with `key`.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        find_value(&self.entries, key)
+    }
+}
+
+/// Remove matching quotes around `value`.
+fn unquote(value: &str) -> &str {
+    ['"', '\'']
+        .iter()
+        .find_map(|quote| {
+            value
+                .strip_prefix(*quote)
+                .and_then(|inner| inner.strip_suffix(*quote))
+        })
+        .unwrap_or(value)
+}
+
+/// Consume the line ending at the start of `unconsumed`, which is at `offset`, and return its
+/// length. The end of the `source` is only accepted if `allow_end` is `true`.
+fn consume_line_ending(
+    unconsumed: &mut &str,
+    offset: usize,
+    allow_end: bool,
+) -> Result<usize, ConsumeError> {
+    match unconsumed.mut_consume_by::<LineEnding>() {
+        Ok((LineEnding::End, _)) if !allow_end => {
+            Err(ConsumeError::new_with(InsufficientTokens { index: offset }))
+        }
+        Ok((_, by)) => Ok(by),
+        Err(err) => Err(err.offset(offset)),
+    }
+}
+
+impl<Style: FrontMatterStyle> Consumable for FrontMatter<Style> {
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let mut unconsumed = source;
+        let mut entries = Vec::new();
+
+        let mut offset = unconsumed.mut_consume_lit(&Style::FENCE)?;
+        offset += unconsumed.mut_consume_by::<Blanks>()?.1;
+        offset += consume_line_ending(&mut unconsumed, offset, false)?;
+
+        loop {
+            if unconsumed.is_empty() {
+                return Err(ConsumeError::new_with(UnclosedDelimiter {
+                    index: offset,
+                    opened_at: 0,
+                    token: Style::FENCE.chars().next().unwrap_or_default(),
+                }));
+            }
+
+            let (line, rest) = split_while(unconsumed, is_line_content);
+            unconsumed = rest;
+            let line_length = utf8_slice::len(line);
+
+            if line.trim_end() == Style::FENCE {
+                consume_line_ending(&mut unconsumed, offset + line_length, true)?;
+                break;
+            }
+
+            let content = line.trim_start_matches(is_blank);
+            if !content.is_empty() && !content.starts_with('#') {
+                let (key, value) = split_while(content, |token| token != Style::SEPARATOR);
+                let key_offset = offset + line_length - utf8_slice::len(content);
+
+                if key.trim_end().is_empty() || value.is_empty() {
+                    let index = key_offset + utf8_slice::len(key);
+                    return Err(ConsumeError::new_with(match content.chars().next() {
+                        Some(token) if key.trim_end().is_empty() => UnexpectedToken {
+                            index: key_offset,
+                            token,
+                        },
+                        _ => match unconsumed.chars().next() {
+                            Some(token) => UnexpectedToken { index, token },
+                            None => InsufficientTokens { index },
+                        },
+                    }));
+                }
+
+                entries.push(KeyValue {
+                    key: key.trim_end().to_string(),
+                    value: unquote(value[1..].trim_matches(is_blank)).to_string(),
+                });
+            }
+
+            offset += line_length;
+            offset += consume_line_ending(&mut unconsumed, offset, true)?;
+        }
+
+        Ok((
+            FrontMatter {
+                entries,
+                phantom: PhantomData,
+            },
+            unconsumed,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Config, FrontMatter, KeyValue, TomlStyle};
+    use crate::error::ConsumeErrorType::*;
+    use crate::{Consumable, ConsumeError};
+
+    #[test]
+    fn test_key_value() {
+        let (entry, _) = KeyValue::consume_from("  a b = = c ").unwrap();
+        assert_eq!((entry.key(), entry.value()), ("a b", "= c"));
+
+        let (entry, _) = KeyValue::consume_from("empty=").unwrap();
+        assert_eq!((entry.key(), entry.value()), ("empty", ""));
+
+        assert_eq!(
+            KeyValue::consume_from(" = value").unwrap_err(),
+            ConsumeError::new_with(UnexpectedToken {
+                index: 1,
+                token: '='
+            })
+        );
+        assert_eq!(
+            KeyValue::consume_from("key value").unwrap_err(),
+            ConsumeError::new_with(InsufficientTokens { index: 9 })
+        );
+    }
+
+    #[test]
+    fn test_config() {
+        let config = Config::consume_all("a = 1\r\n\n[s]\r\nb = 2\n[t]\na = 3").unwrap();
+
+        assert_eq!(config.get("a"), Some("1"));
+        assert_eq!(config.get("b"), None);
+        assert_eq!(config.section("s").and_then(|s| s.get("b")), Some("2"));
+        assert_eq!(config.section("t").and_then(|t| t.get("a")), Some("3"));
+        assert_eq!(config.sections().len(), 2);
+
+        assert!(Config::consume_all("a = 1\n[unclosed\n").is_err());
+        assert!(Config::consume_all("just some text").is_err());
+    }
+
+    #[test]
+    fn test_front_matter() {
+        let (front, body) = <FrontMatter>::consume_from("--- \r\n a : '1'\r\n\r\n---").unwrap();
+        assert_eq!(front.get("a"), Some("1"));
+        assert_eq!(body, "");
+
+        assert_eq!(
+            <FrontMatter>::consume_from("---\na: 1\n").unwrap_err(),
+            ConsumeError::new_with(UnclosedDelimiter {
+                index: 9,
+                opened_at: 0,
+                token: '-'
+            })
+        );
+        assert_eq!(
+            <FrontMatter>::consume_from("--- \nkey\n---\n").unwrap_err(),
+            ConsumeError::new_with(UnexpectedToken {
+                index: 8,
+                token: '\n'
+            })
+        );
+        assert_eq!(
+            <FrontMatter<TomlStyle>>::consume_from("+++\n = 1\n+++\n").unwrap_err(),
+            ConsumeError::new_with(UnexpectedToken {
+                index: 5,
+                token: '='
+            })
+        );
+        assert!(<FrontMatter>::consume_from("---").is_err());
+    }
+}
@@ -0,0 +1,283 @@
+//! Consuming in __`const`__ contexts.
+//!
+//! Methods of traits cannot be called in `const` contexts, so the [`Consumable`][crate::Consumable]
+//! implementations cannot be used to check a `source` at compile-time. The simplest consumers
+//! therefore also have a `const fn try_consume_const`, which consumes the same as
+//! [`consume_from`][crate::Consumable::consume_from]. These are:
+//!
+//! - [`Char<C>`][crate::chars::Char] and [`CharRange<LO, HI>`][crate::chars::CharRange],
+//! - [`Digit`][crate::common::Digit],
+//! - types for literal strings defined with [`lit`][crate::lit].
+//!
+//! This module contains the `const` functions these are built on, which can be combined into
+//! `const` consumers for other grammars. Instead of a [`ConsumeError`][crate::ConsumeError], they
+//! return the [`ConsumeErrorType`] of the failure, since it can be created in a `const` context.
+//!
+//! # Examples
+//!
+//! ```
+//! use manger::constant;
+//! use manger::chars::Char;
+//!
+//! /// Consume a port such as `:8080` at compile-time.
+//! const fn port(source: &str) -> u32 {
+//!     let source = match Char::<':'>::try_consume_const(source) {
+//!         Ok((_, unconsumed)) => unconsumed,
+//!         Err(_) => panic!("a port starts with a `:`"),
+//!     };
+//!
+//!     match constant::consume_u32(source) {
+//!         Ok((port, unconsumed)) if unconsumed.is_empty() && port <= 65535 => port,
+//!         _ => panic!("a port is a number up to 65535"),
+//!     }
+//! }
+//!
+//! const PORT: u32 = port(":8080");
+//! assert_eq!(PORT, 8080);
+//!
+//! // A static sanity check, which fails to compile when the grammar changes.
+//! const _: () = assert!(constant::consume_literal("let x", "let").is_ok());
+//! ```
+
+use crate::error::ConsumeErrorType::{self, *};
+
+/// Consume the first utf-8 character of `source`.
+///
+/// # Errors
+///
+/// Returns an `InsufficientTokens` error at index `0` when `source` is empty.
+///
+/// # Examples
+///
+/// ```
+/// use manger::constant::consume_char;
+///
+/// const FIRST: char = match consume_char("ëa") {
+///     Ok((token, _)) => token,
+///     Err(_) => panic!(),
+/// };
+/// assert_eq!(FIRST, 'ë');
+/// ```
+pub const fn consume_char(source: &str) -> Result<(char, &str), ConsumeErrorType> {
+    let bytes = source.as_bytes();
+
+    if bytes.is_empty() {
+        return Err(InsufficientTokens { index: 0 });
+    }
+
+    let (length, mut code) = match bytes[0] {
+        byte if byte < 0x80 => (1, byte as u32),
+        byte if byte < 0xE0 => (2, (byte & 0x1F) as u32),
+        byte if byte < 0xF0 => (3, (byte & 0x0F) as u32),
+        byte => (4, (byte & 0x07) as u32),
+    };
+
+    let mut index = 1;
+    while index < length {
+        code = (code << 6) | (bytes[index] & 0x3F) as u32;
+        index += 1;
+    }
+
+    let (_, rest) = bytes.split_at(length);
+    // SAFETY: `length` is the length of the first utf-8 character of `source`, so `rest` starts at
+    // a character boundary.
+    let rest = unsafe { std::str::from_utf8_unchecked(rest) };
+
+    match char::from_u32(code) {
+        Some(token) => Ok((token, rest)),
+        None => unreachable!(),
+    }
+}
+
+/// Consume the `literal` from the start of `source`, like [`SelfConsumable`][crate::SelfConsumable]
+/// does for a `&str`.
+///
+/// # Errors
+///
+/// Returns an `UnexpectedToken` error at the first token that differs, or an
+/// `InsufficientTokens` error when `source` ends before the `literal`.
+///
+/// # Examples
+///
+/// ```
+/// use manger::constant::consume_literal;
+/// use manger::ConsumeErrorType;
+///
+/// assert_eq!(consume_literal("let x", "let"), Ok(" x"));
+/// assert_eq!(
+///     consume_literal("lex", "let"),
+///     Err(ConsumeErrorType::UnexpectedToken { index: 2, token: 'x' })
+/// );
+/// assert_eq!(
+///     consume_literal("le", "let"),
+///     Err(ConsumeErrorType::InsufficientTokens { index: 2 })
+/// );
+/// ```
+pub const fn consume_literal<'a>(
+    source: &'a str,
+    literal: &str,
+) -> Result<&'a str, ConsumeErrorType> {
+    let mut unconsumed = source;
+    let mut literal = literal;
+    let mut index = 0;
+
+    while !literal.is_empty() {
+        let (expected, rest) = match consume_char(literal) {
+            Ok(split) => split,
+            Err(_) => unreachable!(),
+        };
+        literal = rest;
+
+        match consume_char(unconsumed) {
+            Ok((token, rest)) if token == expected => unconsumed = rest,
+            Ok((token, _)) => return Err(UnexpectedToken { index, token }),
+            Err(_) => return Err(InsufficientTokens { index }),
+        }
+
+        index += 1;
+    }
+
+    Ok(unconsumed)
+}
+
+/// Consume one or more ascii digits as a decimal `u32`.
+///
+/// Unlike consuming a `u32` with [`consume_from`][crate::Consumable::consume_from], this does not
+/// accept a sign.
+///
+/// # Errors
+///
+/// Returns an `UnexpectedToken` or `InsufficientTokens` error at index `0` when `source` does not
+/// start with a digit, and an `InvalidValue` error at index `0` when the number overflows.
+///
+/// # Examples
+///
+/// ```
+/// use manger::constant::consume_u32;
+/// use manger::ConsumeErrorType;
+///
+/// assert_eq!(consume_u32("42;"), Ok((42, ";")));
+/// assert_eq!(
+///     consume_u32("4294967296"),
+///     Err(ConsumeErrorType::InvalidValue { index: 0 })
+/// );
+/// ```
+pub const fn consume_u32(source: &str) -> Result<(u32, &str), ConsumeErrorType> {
+    let mut value: u32 = match consume_char(source) {
+        Ok((token, _)) if token.is_ascii_digit() => 0,
+        Ok((token, _)) => return Err(UnexpectedToken { index: 0, token }),
+        Err(err) => return Err(err),
+    };
+    let mut unconsumed = source;
+
+    while let Ok((token, rest)) = consume_char(unconsumed) {
+        if !token.is_ascii_digit() {
+            break;
+        }
+
+        value = match value.checked_mul(10) {
+            Some(value) => match value.checked_add(token as u32 - '0' as u32) {
+                Some(value) => value,
+                None => return Err(InvalidValue { index: 0 }),
+            },
+            None => return Err(InvalidValue { index: 0 }),
+        };
+        unconsumed = rest;
+    }
+
+    Ok((value, unconsumed))
+}
+
+/// Returns whether the strings `a` and `b` are equal, which is not possible with `==` in a `const`
+/// context.
+///
+/// # Examples
+///
+/// ```
+/// use manger::constant::str_eq;
+///
+/// const _: () = assert!(str_eq("abc", "abc") && !str_eq("abc", "ab"));
+/// ```
+pub const fn str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let mut index = 0;
+    while index < a.len() {
+        if a[index] != b[index] {
+            return false;
+        }
+        index += 1;
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{consume_char, consume_literal, consume_u32};
+    use crate::error::ConsumeErrorType::*;
+    use crate::Consumable;
+
+    #[test]
+    fn test_const_consume_char() {
+        for source in ["a", "ë", "→x", "𝄞", "\u{7F}\u{80}"] {
+            let expected = char::consume_from(source).map_err(|err| err.into_causes()[0]);
+            assert_eq!(consume_char(source), expected);
+        }
+
+        assert_eq!(consume_char(""), Err(InsufficientTokens { index: 0 }));
+    }
+
+    #[test]
+    fn test_const_consumers() {
+        use crate::chars::{Char, CharRange};
+        use crate::common::Digit;
+
+        fn runtime<T: Consumable>(source: &str) -> Result<(T, &str), crate::ConsumeErrorType> {
+            T::consume_from(source).map_err(|err| err.into_causes()[0])
+        }
+
+        for source in ["", "0", "7x", "9", "a", "ë"] {
+            assert_eq!(Digit::try_consume_const(source), runtime::<Digit>(source));
+            assert_eq!(
+                <CharRange<'a', 'z'>>::try_consume_const(source),
+                runtime::<CharRange<'a', 'z'>>(source)
+            );
+            assert_eq!(
+                <Char<'ë'>>::try_consume_const(source),
+                runtime::<Char<'ë'>>(source)
+            );
+        }
+    }
+
+    #[test]
+    fn test_const_consume_literal() {
+        assert_eq!(consume_literal("→ë→x", "→ë→"), Ok("x"));
+        assert_eq!(consume_literal("abc", ""), Ok("abc"));
+        assert_eq!(
+            consume_literal("→ëx", "→ë→"),
+            Err(UnexpectedToken {
+                index: 2,
+                token: 'x'
+            })
+        );
+    }
+
+    #[test]
+    fn test_const_consume_u32() {
+        assert_eq!(consume_u32("4294967295"), Ok((u32::MAX, "")));
+        assert_eq!(consume_u32("007x"), Ok((7, "x")));
+        assert_eq!(consume_u32(""), Err(InsufficientTokens { index: 0 }));
+        assert_eq!(
+            consume_u32("-1"),
+            Err(UnexpectedToken {
+                index: 0,
+                token: '-'
+            })
+        );
+    }
+}
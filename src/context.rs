@@ -0,0 +1,201 @@
+//! Tracking of the __position__ of a `source` within the input that is being consumed.
+//!
+//! [`Consumable::consume_from`][crate::Consumable::consume_from] only receives the part of the input which is left to consume.
+//! Consumers that need to know where they are within the whole input, such as
+//! [`Start`][crate::common::Start] and [`StartOfLine`][crate::common::StartOfLine], use the input
+//! registered with [`scope`]. [`consume_all`][crate::Consumable::consume_all],
+//! [`parse_prefix`][crate::Consumable::parse_prefix] and
+//! [`consume_iter`][crate::Consumable::consume_iter] register their `source` automatically, a
+//! plain [`consume_from`][crate::Consumable::consume_from] does not.
+//!
+//! # Examples
+//!
+//! ```
+//! use manger::{context, Consumable, ConsumeSource};
+//!
+//! let input = "first\nsecond line";
+//!
+//! context::scope(input, || {
+//!     let unconsumed = input.consume_lit(&"first\nsec")?;
+//!     let position = context::position(unconsumed).unwrap();
+//!
+//!     assert_eq!(position.index(), 9);
+//!     assert_eq!((position.line(), position.column()), (2, 4));
+//!     Ok::<(), manger::ConsumeError>(())
+//! })?;
+//! # Ok::<(), manger::ConsumeError>(())
+//! ```
+
+use std::cell::Cell;
+
+thread_local! {
+    /// The address and byte length of the input registered with `scope`.
+    static INPUT: Cell<Option<(usize, usize)>> = const { Cell::new(None) };
+}
+
+/// Restores the previously registered input, also when unwinding.
+struct ScopeGuard(Option<(usize, usize)>);
+
+impl Drop for ScopeGuard {
+    fn drop(&mut self) {
+        INPUT.with(|input| input.set(self.0));
+    }
+}
+
+/// Register `input` as the whole input for the duration of `f`.
+///
+/// Scopes can be nested, in which case the innermost `input` is used. The previously registered
+/// input is restored once `f` returns.
+///
+/// # Examples
+///
+/// ```
+/// use manger::context;
+///
+/// let input = "abc";
+///
+/// assert_eq!(context::position(&input[1..]), None);
+/// context::scope(input, || {
+///     assert_eq!(context::position(&input[1..]).map(|p| p.index()), Some(1));
+/// });
+/// ```
+pub fn scope<R>(input: &str, f: impl FnOnce() -> R) -> R {
+    let previous =
+        INPUT.with(|registered| registered.replace(Some((input.as_ptr() as usize, input.len()))));
+    let _guard = ScopeGuard(previous);
+
+    f()
+}
+
+/// Call `f` with the registered input and the byte offset of `source` within that input.
+///
+/// Returns `None` if no input is registered or if `source` is not part of it.
+pub(crate) fn with_input<R>(source: &str, f: impl FnOnce(&str, usize) -> R) -> Option<R> {
+    let (address, length) = INPUT.with(Cell::get)?;
+    let source_address = source.as_ptr() as usize;
+
+    if source_address < address || source_address + source.len() > address + length {
+        return None;
+    }
+
+    // SAFETY: The registered input is borrowed by `scope` for as long as it is registered, and
+    // the reference does not escape `f`.
+    let input = unsafe {
+        std::str::from_utf8_unchecked(std::slice::from_raw_parts(address as *const u8, length))
+    };
+
+    Some(f(input, source_address - address))
+}
+
+/// Position of a utf-8 character within a `source`.
+///
+/// The `index` counts utf-8 characters from the start of the `source`, just as the indices of
+/// [`ConsumeError`][crate::ConsumeError]s do. The `line` and `column` start counting at 1.
+///
+/// # Examples
+///
+/// ```
+/// use manger::context::Position;
+///
+/// let position = Position::from_index("ab\ncdé\nf", 6);
+///
+/// assert_eq!(position.index(), 6);
+/// assert_eq!(position.line(), 2);
+/// assert_eq!(position.column(), 4);
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Position {
+    index: usize,
+    line: usize,
+    column: usize,
+}
+
+impl Position {
+    /// Get the position of the utf-8 character at `index` within `source`.
+    ///
+    /// Indices past the end of `source` continue on the last line.
+    pub fn from_index(source: &str, index: usize) -> Position {
+        let mut line = 1;
+        let mut column = 1;
+
+        for token in source.chars().take(index) {
+            if token == '\n' {
+                line += 1;
+                column = 1;
+            } else {
+                column += 1;
+            }
+        }
+
+        let length = utf8_slice::len(source);
+        if index > length {
+            column += index - length;
+        }
+
+        Position {
+            index,
+            line,
+            column,
+        }
+    }
+
+    /// Get the amount of utf-8 characters before the position.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Get the line of the position, starting at 1.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Get the column of the position in utf-8 characters, starting at 1.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+}
+
+/// Get the position of `source` within the input registered with [`scope`].
+///
+/// Returns `None` if no input is registered or if `source` is not part of it.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{context, Consumable};
+///
+/// let input = "12\n345";
+///
+/// let line = context::scope(input, || {
+///     let (_, unconsumed) = <(u32, char)>::consume_from(input)?;
+///     Ok::<_, manger::ConsumeError>(context::position(unconsumed).map(|p| p.line()))
+/// })?;
+///
+/// assert_eq!(line, Some(2));
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+pub fn position(source: &str) -> Option<Position> {
+    with_input(source, |input, offset| {
+        let consumed = &input[..offset];
+        Position::from_index(consumed, utf8_slice::len(consumed))
+    })
+}
+
+/// Returns whether `source` is at the start of a line within the registered input.
+///
+/// When `source` is not part of the registered input, `source` is taken as the start of the
+/// input.
+pub(crate) fn is_start_of_line(source: &str) -> bool {
+    with_input(source, |input, offset| {
+        offset == 0 || input.as_bytes()[offset - 1] == b'\n'
+    })
+    .unwrap_or(true)
+}
+
+/// Returns whether `source` is at the start of the registered input.
+///
+/// When `source` is not part of the registered input, `source` is taken as the start of the
+/// input.
+pub(crate) fn is_start(source: &str) -> bool {
+    with_input(source, |_, offset| offset == 0).unwrap_or(true)
+}
@@ -0,0 +1,294 @@
+//! __Coverage__ of the grammar defined with the macros during a test run.
+//!
+//! With the `coverage` feature enabled, the implementations generated by
+//! [`consume_struct`][crate::consume_struct!] and [`consume_enum`][crate::consume_enum!] record
+//! which of their parts were consumed. For a `struct`, these are the items of its sequence and
+//! for an `enum`, these are its variants. The [`report`] then shows how often every part was
+//! consumed, which helps to find alternatives that are never tested.
+//!
+//! A type shows up in the [`report`] once it has been attempted to be consumed. Types that were
+//! never attempted are not known and therefore do not show up at all. Without the `coverage`
+//! feature, nothing is recorded and the [`report`] is always empty.
+//!
+//! # Examples
+//!
+//! ```
+//! use manger::{consume_enum, coverage, Consumable};
+//!
+//! enum Answer {
+//!     Yes,
+//!     No,
+//! }
+//! consume_enum!(
+//!     Answer {
+//!         Yes => [ > "yes"; ],
+//!         No => [ > "no"; ]
+//!     }
+//! );
+//!
+//! assert!(Answer::consume_from("yes").is_ok());
+//!
+//! let report = coverage::report();
+//! # #[cfg(feature = "coverage")]
+//! # {
+//! let rule = report.rule(std::any::type_name::<Answer>()).unwrap();
+//!
+//! assert_eq!(rule.points(), &[("Yes", 1), ("No", 0)]);
+//! assert_eq!(rule.uncovered().collect::<Vec<_>>(), vec!["No"]);
+//! # }
+//!
+//! // Prints the coverage of every rule along with the total coverage.
+//! println!("{}", report);
+//! ```
+
+use std::fmt;
+
+#[cfg(feature = "coverage")]
+use std::{collections::BTreeMap, sync::Mutex};
+
+/// The points of every rule along with how often they were consumed, by the name of the rule.
+#[cfg(feature = "coverage")]
+type Points = BTreeMap<&'static str, Vec<(&'static str, usize)>>;
+
+#[cfg(feature = "coverage")]
+static POINTS: Mutex<Points> = Mutex::new(BTreeMap::new());
+
+#[cfg(feature = "coverage")]
+fn with_points<R>(f: impl FnOnce(&mut Points) -> R) -> R {
+    let mut points = POINTS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    f(&mut points)
+}
+
+/// Register the `points` of the rule `T`, if that has not happened yet.
+///
+/// This is used by the implementations generated by the macros.
+#[doc(hidden)]
+#[inline(always)]
+#[allow(unused_variables, clippy::extra_unused_type_parameters)]
+pub fn register<T: ?Sized>(points: &[&'static str]) {
+    #[cfg(feature = "coverage")]
+    with_points(|registered| {
+        registered
+            .entry(std::any::type_name::<T>())
+            .or_insert_with(|| points.iter().map(|point| (*point, 0)).collect());
+    });
+}
+
+/// Record that the `point`-th point of the rule `T` was consumed.
+///
+/// This is used by the implementations generated by the macros.
+#[doc(hidden)]
+#[inline(always)]
+#[allow(unused_variables, clippy::extra_unused_type_parameters)]
+pub fn hit<T: ?Sized>(point: usize) {
+    #[cfg(feature = "coverage")]
+    with_points(|registered| {
+        if let Some((_, hits)) = registered
+            .get_mut(std::any::type_name::<T>())
+            .and_then(|points| points.get_mut(point))
+        {
+            *hits += 1;
+        }
+    });
+}
+
+/// Get the coverage recorded so far.
+pub fn report() -> Report {
+    #[cfg(feature = "coverage")]
+    let rules = with_points(|registered| {
+        registered
+            .iter()
+            .map(|(name, points)| RuleCoverage {
+                name,
+                points: points.clone(),
+            })
+            .collect()
+    });
+
+    #[cfg(not(feature = "coverage"))]
+    let rules = Vec::new();
+
+    Report { rules }
+}
+
+/// Forget all coverage recorded so far.
+pub fn reset() {
+    #[cfg(feature = "coverage")]
+    with_points(Points::clear);
+}
+
+/// The coverage of all rules, which is returned by [`report`].
+///
+/// The rules are sorted by their name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    rules: Vec<RuleCoverage>,
+}
+
+impl Report {
+    /// Get the coverage of every rule.
+    pub fn rules(&self) -> &[RuleCoverage] {
+        &self.rules
+    }
+
+    /// Get the coverage of the rule with the type name `name`.
+    pub fn rule(&self, name: &str) -> Option<&RuleCoverage> {
+        self.rules.iter().find(|rule| rule.name == name)
+    }
+
+    /// Get the amount of points of all rules that were consumed at least once.
+    pub fn covered(&self) -> usize {
+        self.rules.iter().map(RuleCoverage::covered).sum()
+    }
+
+    /// Get the amount of points of all rules.
+    pub fn total(&self) -> usize {
+        self.rules.iter().map(|rule| rule.points.len()).sum()
+    }
+}
+
+/// Shows the coverage of every rule, followed by how often every point was consumed, and the
+/// total coverage.
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for rule in &self.rules {
+            writeln!(f, "{} {}/{}", rule.name, rule.covered(), rule.points.len())?;
+
+            for (point, hits) in &rule.points {
+                writeln!(f, "  {:>6} {}", hits, point)?;
+            }
+        }
+
+        write!(f, "Total {}/{}", self.covered(), self.total())
+    }
+}
+
+/// The coverage of a single rule, which is a type implemented with one of the macros.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleCoverage {
+    name: &'static str,
+    points: Vec<(&'static str, usize)>,
+}
+
+impl RuleCoverage {
+    /// Get the type name of the rule.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Get the points of the rule in order, along with how often they were consumed.
+    ///
+    /// These are the variants of an `enum` or the items of the sequence of a `struct`.
+    pub fn points(&self) -> &[(&'static str, usize)] {
+        &self.points
+    }
+
+    /// Get the amount of points that were consumed at least once.
+    pub fn covered(&self) -> usize {
+        self.points.iter().filter(|(_, hits)| *hits > 0).count()
+    }
+
+    /// Get the points that were never consumed.
+    pub fn uncovered(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.points
+            .iter()
+            .filter(|(_, hits)| *hits == 0)
+            .map(|(point, _)| *point)
+    }
+}
+
+#[cfg(all(test, feature = "coverage"))]
+mod tests {
+    use super::report;
+    use crate::{consume_enum, consume_struct, Consumable};
+
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    enum Token {
+        Number(u32),
+        Plus,
+        Minus,
+    }
+    consume_enum!(
+        Token {
+            Number => [ value: u32; (value) ],
+            Plus => [ > '+'; ],
+            Minus => [ > '-'; ]
+        }
+    );
+
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Pair {
+        left: u32,
+        right: u32,
+    }
+    consume_struct!(
+        Pair => [
+            left: u32,
+            > ',',
+            right: u32,
+            > ',';
+        ]
+    );
+
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    enum Expr {
+        Number(u32),
+        Sub(Box<Expr>, Box<Expr>),
+        Mul(Box<Expr>, Box<Expr>),
+    }
+    consume_enum!(
+        Expr {
+            Number => [ value: u32; (value) ],
+            Sub => { left_assoc, precedence = 1 } [ lhs: Self, > '-', rhs: Self; (Box::new(lhs), Box::new(rhs)) ],
+            Mul => { left_assoc, precedence = 2 } [ lhs: Self, > '*', rhs: Self; (Box::new(lhs), Box::new(rhs)) ]
+        }
+    );
+
+    fn points<T>() -> Vec<(&'static str, usize)> {
+        report()
+            .rule(std::any::type_name::<T>())
+            .map(|rule| rule.points().to_vec())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn test_coverage_enum() {
+        assert!(<Vec<Token>>::consume_all("1+2+").is_ok());
+        assert_eq!(
+            points::<Token>(),
+            vec![("Number", 2), ("Plus", 2), ("Minus", 0)]
+        );
+
+        let report = report();
+        let rule = report.rule(std::any::type_name::<Token>()).unwrap();
+        assert_eq!(rule.covered(), 2);
+        assert_eq!(rule.uncovered().collect::<Vec<_>>(), vec!["Minus"]);
+        assert!(report
+            .to_string()
+            .contains(&format!("{} 2/3\n", std::any::type_name::<Token>())));
+    }
+
+    #[test]
+    fn test_coverage_struct() {
+        assert!(Pair::consume_from("1,2").is_err());
+        assert!(Pair::consume_from("1,x").is_err());
+        assert_eq!(
+            points::<Pair>(),
+            vec![("left: u32", 2), ("','", 2), ("right: u32", 1), ("','", 0)]
+        );
+    }
+
+    #[test]
+    fn test_coverage_operators() {
+        assert!(Expr::consume_all("1-2-3").is_ok());
+        assert_eq!(
+            points::<Expr>(),
+            vec![("Number", 3), ("Sub", 2), ("Mul", 0)]
+        );
+    }
+}
@@ -0,0 +1,252 @@
+//! Types for consuming __comma-separated values__.
+//!
+//! This module contains a [`CsvField`] and a [`CsvRecord`], which can be used to consume
+//! tabular text. Both are generic over the separator, which defaults to
+//! [`chars::Comma`][crate::chars::Comma]. Any [`Consumable`] type can be used as a separator.
+//!
+//! # Examples
+//!
+//! ```
+//! use manger::Consumable;
+//! use manger::csv::CsvRecord;
+//!
+//! let source = "name,age\n\"Doe, John\",42\n";
+//!
+//! let rows: Vec<Vec<String>> = <CsvRecord>::consume_iter(source)
+//!     .map(|record| record.into_fields())
+//!     .collect();
+//!
+//! assert_eq!(rows, vec![
+//!     vec!["name".to_string(), "age".to_string()],
+//!     vec!["Doe, John".to_string(), "42".to_string()],
+//! ]);
+//! ```
+
+use crate::chars;
+use crate::error::ConsumeErrorType::*;
+use crate::{Consumable, ConsumeError, ConsumeSource};
+use std::marker::PhantomData;
+
+/// A single field within a CSV record.
+///
+/// A field is either quoted or unquoted. Quoted fields start and end with a `'"'` and can contain
+/// the separator, newlines and escaped quotes (`""`). Unquoted fields contain everything up to the
+/// next separator `Sep`, newline or the end of the `source`. A field may be empty.
+///
+/// # Examples
+///
+/// ```
+/// use manger::Consumable;
+/// use manger::chars;
+/// use manger::csv::CsvField;
+///
+/// let (field, unconsumed) = <CsvField>::consume_from("\"say \"\"hi\"\"\",next")?;
+/// assert_eq!(field.value(), "say \"hi\"");
+/// assert_eq!(unconsumed, ",next");
+///
+/// let (field, unconsumed) = <CsvField<chars::Semicolon>>::consume_from("a,b;c")?;
+/// assert_eq!(field.value(), "a,b");
+/// assert_eq!(unconsumed, ";c");
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct CsvField<Sep = chars::Comma> {
+    value: String,
+    phantom: PhantomData<Sep>,
+}
+
+impl<Sep> CsvField<Sep> {
+    /// Get the unescaped value of the field.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Take ownership of the unescaped value of the field.
+    pub fn into_string(self) -> String {
+        self.value
+    }
+}
+
+impl<Sep> From<CsvField<Sep>> for String {
+    fn from(field: CsvField<Sep>) -> String {
+        field.into_string()
+    }
+}
+
+impl<Sep: Consumable> Consumable for CsvField<Sep> {
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let mut unconsumed = source;
+        let mut value = String::new();
+
+        if unconsumed.mut_consume_lit(&'"').is_ok() {
+            let mut index = 1;
+
+            loop {
+                match unconsumed.chars().next() {
+                    None => return Err(ConsumeError::new_with(InsufficientTokens { index })),
+                    Some('"') => {
+                        unconsumed = utf8_slice::from(unconsumed, 1);
+                        index += 1;
+
+                        if unconsumed.mut_consume_lit(&'"').is_err() {
+                            break;
+                        }
+
+                        index += 1;
+                        value.push('"');
+                    }
+                    Some(token) => {
+                        unconsumed = utf8_slice::from(unconsumed, 1);
+                        index += 1;
+                        value.push(token);
+                    }
+                }
+            }
+        } else {
+            while let Some(token) = unconsumed.chars().next() {
+                if token == '\n' || token == '\r' || Sep::consume_from(unconsumed).is_ok() {
+                    break;
+                }
+
+                unconsumed = utf8_slice::from(unconsumed, 1);
+                value.push(token);
+            }
+        }
+
+        Ok((
+            CsvField {
+                value,
+                phantom: PhantomData,
+            },
+            unconsumed,
+        ))
+    }
+}
+
+/// A record, or row, of CSV fields separated by `Sep`.
+///
+/// A record consumes one or more [`CsvField`]s followed by a line ending (`"\n"` or `"\r\n"`) or
+/// the end of the `source`. Consuming a record from an empty `source` fails, which makes
+/// [`consume_iter`][Consumable::consume_iter] stop after the last row.
+///
+/// # Examples
+///
+/// ```
+/// use manger::Consumable;
+/// use manger::chars;
+/// use manger::csv::CsvRecord;
+///
+/// let (record, unconsumed) = <CsvRecord<chars::Tab>>::consume_from("1\t2\t3\r\n4\t5\t6")?;
+///
+/// assert_eq!(record.fields(), &["1", "2", "3"]);
+/// assert_eq!(unconsumed, "4\t5\t6");
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct CsvRecord<Sep = chars::Comma> {
+    fields: Vec<String>,
+    phantom: PhantomData<Sep>,
+}
+
+impl<Sep> CsvRecord<Sep> {
+    /// Get the values of the fields in this record in order of appearance.
+    pub fn fields(&self) -> &[String] {
+        &self.fields
+    }
+
+    /// Get the value of the field at `index`, if it exists.
+    pub fn get(&self, index: usize) -> Option<&str> {
+        self.fields.get(index).map(String::as_str)
+    }
+
+    /// Take ownership of the values of the fields in this record.
+    pub fn into_fields(self) -> Vec<String> {
+        self.fields
+    }
+}
+
+impl<Sep: Consumable> Consumable for CsvRecord<Sep> {
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        if source.is_empty() {
+            return Err(ConsumeError::new_with(InsufficientTokens { index: 0 }));
+        }
+
+        let mut unconsumed = source;
+        let mut fields = Vec::new();
+        let mut offset = 0;
+
+        loop {
+            let (field, by) = unconsumed
+                .mut_consume_by::<CsvField<Sep>>()
+                .map_err(|err| err.offset(offset))?;
+            offset += by;
+            fields.push(field.into_string());
+
+            match unconsumed.mut_consume_by::<Sep>() {
+                Ok((_, by)) => offset += by,
+                Err(_) => break,
+            }
+        }
+
+        if unconsumed.mut_consume_lit(&"\r\n").is_err()
+            && unconsumed.mut_consume_lit(&'\n').is_err()
+        {
+            if let Some(token) = unconsumed.chars().next() {
+                return Err(ConsumeError::new_with(UnexpectedToken {
+                    index: offset,
+                    token,
+                }));
+            }
+        }
+
+        Ok((
+            CsvRecord {
+                fields,
+                phantom: PhantomData,
+            },
+            unconsumed,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CsvField, CsvRecord};
+    use crate::error::ConsumeErrorType::*;
+    use crate::{Consumable, ConsumeError};
+
+    #[test]
+    fn test_csv_field() {
+        assert_eq!(
+            <CsvField>::consume_from("abc,def").unwrap().0.value(),
+            "abc"
+        );
+        assert_eq!(<CsvField>::consume_from(",def").unwrap().1, ",def");
+        assert_eq!(
+            <CsvField>::consume_from("\"a\nb\"\n").unwrap().0.value(),
+            "a\nb"
+        );
+        assert_eq!(
+            <CsvField>::consume_from("\"abc").unwrap_err(),
+            ConsumeError::new_with(InsufficientTokens { index: 4 })
+        );
+    }
+
+    #[test]
+    fn test_csv_record() {
+        assert_eq!(
+            <CsvRecord>::consume_from("a,,c").unwrap().0.fields(),
+            &["a", "", "c"]
+        );
+        assert_eq!(<CsvRecord>::consume_from("a,b\nc").unwrap().1, "c");
+        assert_eq!(
+            <CsvRecord>::consume_from("\"a\"b,c").unwrap_err(),
+            ConsumeError::new_with(UnexpectedToken {
+                index: 3,
+                token: 'b'
+            })
+        );
+        assert!(<CsvRecord>::consume_from("").is_err());
+        assert_eq!(<CsvRecord>::consume_iter("a\nb\n\nc").count(), 4);
+    }
+}
@@ -0,0 +1,124 @@
+use crate::{Consumable, ConsumeError, ConsumeSource, SelfConsumable};
+
+/// A `source` that keeps track of its utf-8 character position within the whole input.
+///
+/// `Cursor` implements [`ConsumeSource`] like `&str` does, but the indices of all errors are
+/// positions within the whole input instead of within the unconsumed part. This is useful for
+/// hand-written consumers, which otherwise have to add the amount of consumed characters to every
+/// error themselves. The position can also be used to build spans of consumed items.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{ConsumeErrorType, ConsumeSource, Cursor};
+///
+/// let mut cursor = Cursor::new("let x = 4_2;");
+///
+/// cursor.mut_consume_lit(&"let ")?;
+/// let start = cursor.position();
+/// let name = cursor.mut_consume::<char>()?;
+/// let span = start..cursor.position();
+///
+/// assert_eq!((name, span), ('x', 4..5));
+///
+/// cursor.mut_consume_lit(&" = ")?;
+/// let error = cursor.attempt(|cursor| {
+///     let value = cursor.mut_consume::<u32>()?;
+///     cursor.mut_consume_lit(&';')?;
+///     Ok(value)
+/// }).unwrap_err();
+///
+/// assert_eq!(
+///     error.causes(),
+///     vec![&ConsumeErrorType::UnexpectedToken { index: 9, token: '_' }]
+/// );
+/// assert_eq!(cursor.position(), 8);
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Cursor<'s> {
+    unconsumed: &'s str,
+    position: usize,
+}
+
+impl<'s> Cursor<'s> {
+    /// Create a cursor at the start of `input`.
+    pub fn new(input: &'s str) -> Self {
+        Cursor::at(input, 0)
+    }
+
+    /// Create a cursor for `source`, which starts at the utf-8 character index `position` of the
+    /// whole input.
+    pub fn at(source: &'s str, position: usize) -> Self {
+        Cursor {
+            unconsumed: source,
+            position,
+        }
+    }
+
+    /// Get the utf-8 character index of the cursor within the whole input.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Get the part of the input that is left to consume.
+    pub fn unconsumed(&self) -> &'s str {
+        self.unconsumed
+    }
+
+    /// Returns whether there is nothing left to consume.
+    pub fn is_empty(&self) -> bool {
+        self.unconsumed.is_empty()
+    }
+
+    /// Move the cursor to `unconsumed`, which is the end of the current unconsumed part.
+    fn advance(self, unconsumed: &'s str) -> Self {
+        Cursor {
+            unconsumed,
+            position: self.position + crate::strs::consumed_length(self.unconsumed, unconsumed),
+        }
+    }
+}
+
+impl<'s> ConsumeSource for Cursor<'s> {
+    fn consume_lit<T: SelfConsumable>(self, literal: &T) -> Result<Self, ConsumeError> {
+        T::consume_item(self.unconsumed, literal)
+            .map(|unconsumed| self.advance(unconsumed))
+            .map_err(|err| err.offset(self.position))
+    }
+
+    fn consume<T: Consumable>(self) -> Result<(T, Self), ConsumeError> {
+        T::consume_from(self.unconsumed)
+            .map(|(item, unconsumed)| (item, self.advance(unconsumed)))
+            .map_err(|err| err.offset(self.position))
+    }
+
+    fn mut_consume_lit<T: SelfConsumable>(&mut self, literal: &T) -> Result<usize, ConsumeError> {
+        let start = self.position;
+        *self = self.consume_lit(literal)?;
+
+        Ok(self.position - start)
+    }
+
+    fn mut_consume<T: Consumable>(&mut self) -> Result<T, ConsumeError> {
+        let (item, cursor) = self.consume()?;
+        *self = cursor;
+
+        Ok(item)
+    }
+
+    fn mut_consume_by<T: Consumable>(&mut self) -> Result<(T, usize), ConsumeError> {
+        let start = self.position;
+        let item = self.mut_consume()?;
+
+        Ok((item, self.position - start))
+    }
+
+    fn checkpoint(&self) -> Self {
+        *self
+    }
+
+    fn restore(&mut self, checkpoint: Self) {
+        *self = checkpoint;
+    }
+}
@@ -0,0 +1,304 @@
+//! A [__serde__](https://serde.rs) deserializer for the values of a runtime grammar.
+//!
+//! A [`Grammar`] consumes a `source` into a list of [`Value`]s. The [`Deserializer`] in this
+//! module fills a type implementing [`Deserialize`][serde::Deserialize] from such a list, where
+//! every field, tuple element or sequence item takes the next value. This allows for populating
+//! existing `#[derive(Deserialize)]` types from a custom textual format with [`from_grammar`].
+//!
+//! Values are converted to the type of the field where serde allows for it, so a
+//! [`Value::Int`] can fill any integer or floating point field. A [`Value::Str`] can also fill a
+//! unit variant of an `enum` with the same name. An [`Option`] field always takes a value and is
+//! never `None`.
+//!
+//! The deserializer only works on the flat list of values of a runtime [`Grammar`]. Types
+//! consumed with [`consume_struct`][crate::consume_struct] or
+//! [`consume_enum`][crate::consume_enum] are not deserialized through it. Every field is filled
+//! from a single value, so fields holding a nested struct, tuple or sequence are not supported.
+//!
+//! Only available with the `serde` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use manger::de::from_grammar;
+//! use manger::dynamic::GrammarBuilder;
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize, Debug, PartialEq)]
+//! enum Method {
+//!     GET,
+//!     POST,
+//! }
+//!
+//! #[derive(Deserialize, Debug, PartialEq)]
+//! struct Request {
+//!     id: u16,
+//!     method: Method,
+//!     path: String,
+//! }
+//!
+//! let grammar = GrammarBuilder::new().format("%d: %s %s").unwrap().build();
+//! let request: Request = from_grammar(&grammar, "42: GET /index.html")?;
+//!
+//! assert_eq!(
+//!     request,
+//!     Request {
+//!         id: 42,
+//!         method: Method::GET,
+//!         path: "/index.html".to_string(),
+//!     }
+//! );
+//! # Ok::<(), manger::de::DeserializeError>(())
+//! ```
+
+use crate::dynamic::{Grammar, Value};
+use crate::ConsumeError;
+use serde::de::{self, DeserializeOwned, IntoDeserializer, SeqAccess, Unexpected, Visitor};
+use serde::forward_to_deserialize_any;
+use std::fmt;
+use thiserror::Error;
+
+/// Error which occurs while consuming or deserializing with [`from_grammar`].
+#[derive(Error, Debug, PartialEq)]
+pub enum DeserializeError {
+    /// The `source` could not be consumed with the grammar.
+    #[error("{0}")]
+    Consume(ConsumeError),
+
+    /// Fewer values were deserialized than the grammar produced.
+    #[error("Found {remaining} values that were not deserialized!")]
+    TrailingValues {
+        /// The amount of values that were left over.
+        remaining: usize,
+    },
+
+    /// The values do not match the type that is deserialized, as reported by serde.
+    #[error("{0}")]
+    Custom(String),
+}
+
+impl From<ConsumeError> for DeserializeError {
+    fn from(err: ConsumeError) -> Self {
+        DeserializeError::Consume(err)
+    }
+}
+
+impl de::Error for DeserializeError {
+    fn custom<T: fmt::Display>(message: T) -> Self {
+        DeserializeError::Custom(message.to_string())
+    }
+}
+
+/// Consume the whole `source` with `grammar` and deserialize a `T` from the produced values.
+///
+/// Fails when the `source` cannot be consumed, when the values do not match `T` or when not all
+/// values were used.
+///
+/// # Examples
+///
+/// ```
+/// use manger::de::{from_grammar, DeserializeError};
+/// use manger::dynamic::GrammarBuilder;
+///
+/// let grammar = GrammarBuilder::new().format("%d-%d-%d").unwrap().build();
+///
+/// let date: (u16, u8, u8) = from_grammar(&grammar, "2021-04-01")?;
+/// assert_eq!(date, (2021, 4, 1));
+///
+/// let year_and_month = from_grammar::<(u16, u8)>(&grammar, "2021-04-01");
+/// assert_eq!(year_and_month, Err(DeserializeError::TrailingValues { remaining: 1 }));
+///
+/// assert!(from_grammar::<(u16, u8, u8)>(&grammar, "2021-04-1000").is_err());
+/// # Ok::<(), DeserializeError>(())
+/// ```
+pub fn from_grammar<T: DeserializeOwned>(
+    grammar: &Grammar,
+    source: &str,
+) -> Result<T, DeserializeError> {
+    let mut deserializer = Deserializer::new(grammar.consume_all(source)?);
+    let item = T::deserialize(&mut deserializer)?;
+
+    match deserializer.values.len() {
+        0 => Ok(item),
+        remaining => Err(DeserializeError::TrailingValues { remaining }),
+    }
+}
+
+/// Deserializer of a sequence of [`Value`]s, such as the ones produced by a [`Grammar`].
+///
+/// The deserialized type is always filled from a sequence, so it should be a struct, a tuple or
+/// a collection.
+///
+/// # Examples
+///
+/// ```
+/// use manger::de::Deserializer;
+/// use manger::dynamic::Value;
+/// use serde::Deserialize;
+///
+/// let mut deserializer = Deserializer::new(vec![Value::Int(1), Value::Float(2.5)]);
+///
+/// assert_eq!(<Vec<f32>>::deserialize(&mut deserializer)?, vec![1.0, 2.5]);
+/// # Ok::<(), manger::de::DeserializeError>(())
+/// ```
+#[derive(Debug)]
+pub struct Deserializer {
+    values: std::vec::IntoIter<Value>,
+}
+
+impl Deserializer {
+    /// Create a deserializer over `values`.
+    pub fn new(values: Vec<Value>) -> Self {
+        Deserializer {
+            values: values.into_iter(),
+        }
+    }
+}
+
+impl<'de> de::Deserializer<'de> for &mut Deserializer {
+    type Error = DeserializeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_seq(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        option unit unit_struct newtype_struct seq tuple tuple_struct map struct enum identifier
+        ignored_any
+    }
+}
+
+impl<'de> SeqAccess<'de> for Deserializer {
+    type Error = DeserializeError;
+
+    fn next_element_seed<T: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        self.values
+            .next()
+            .map(|value| seed.deserialize(ValueDeserializer(value)))
+            .transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.values.len())
+    }
+}
+
+/// Deserializer of a single value within the sequence.
+struct ValueDeserializer(Value);
+
+impl<'de> de::Deserializer<'de> for ValueDeserializer {
+    type Error = DeserializeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.0 {
+            Value::Int(value) => visitor.visit_i64(value),
+            Value::Float(value) => visitor.visit_f64(value),
+            Value::Char(value) => visitor.visit_char(value),
+            Value::Str(value) => visitor.visit_string(value),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        let unexpected = match self.0 {
+            Value::Str(variant) => return visitor.visit_enum(variant.into_deserializer()),
+            Value::Int(value) => Unexpected::Signed(value),
+            Value::Float(value) => Unexpected::Float(value),
+            Value::Char(value) => Unexpected::Char(value),
+        };
+
+        Err(de::Error::invalid_type(unexpected, &visitor))
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        unit unit_struct seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{from_grammar, DeserializeError};
+    use crate::dynamic::GrammarBuilder;
+    use serde::Deserialize;
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Meters(f64);
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    enum Unit {
+        Meter,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Measurement {
+        label: Option<char>,
+        length: Meters,
+        unit: Unit,
+    }
+
+    #[test]
+    fn test_from_grammar() {
+        let grammar = GrammarBuilder::new().format("%c=%d %s").unwrap().build();
+
+        assert_eq!(
+            from_grammar::<Measurement>(&grammar, "x=5 Meter"),
+            Ok(Measurement {
+                label: Some('x'),
+                length: Meters(5.0),
+                unit: Unit::Meter,
+            })
+        );
+        assert!(matches!(
+            from_grammar::<Measurement>(&grammar, "x=5 Inch"),
+            Err(DeserializeError::Custom(_))
+        ));
+        assert!(matches!(
+            from_grammar::<Measurement>(&grammar, "x=5"),
+            Err(DeserializeError::Consume(_))
+        ));
+
+        let grammar = GrammarBuilder::new().format("%c=%d").unwrap().build();
+        assert!(matches!(
+            from_grammar::<Measurement>(&grammar, "x=5"),
+            Err(DeserializeError::Custom(_))
+        ));
+
+        let grammar = GrammarBuilder::new().format("%d").unwrap().build();
+        assert!(matches!(
+            from_grammar::<(Unit,)>(&grammar, "5"),
+            Err(DeserializeError::Custom(_))
+        ));
+    }
+
+    #[test]
+    fn test_float_precision() {
+        let grammar = GrammarBuilder::new().format("%f").unwrap().build();
+
+        assert_eq!(from_grammar::<(f64,)>(&grammar, "0.1"), Ok((0.1,)));
+        assert_eq!(
+            from_grammar::<(f64,)>(&grammar, "16777217"),
+            Ok((16777217.0,))
+        );
+    }
+}
@@ -0,0 +1,453 @@
+//! Types for composing a grammar at __runtime__.
+//!
+//! The [`consume_struct`][crate::consume_struct] and [`consume_enum`][crate::consume_enum] macros
+//! define a grammar at compile time. Sometimes the grammar is only known at runtime, for example
+//! when it is given as a log format like `"%d %s [%t]"`. A [`GrammarBuilder`] composes such a
+//! grammar from literals and boxed consumers into a [`Grammar`]. Consuming with a [`Grammar`]
+//! produces a [`Value`] for every consumer, while literals are only checked.
+//!
+//! # Examples
+//!
+//! ```
+//! use manger::dynamic::{GrammarBuilder, Value};
+//!
+//! let grammar = GrammarBuilder::new().format("%d: %s %s").unwrap().build();
+//!
+//! let values = grammar.consume_all("42: GET /index.html")?;
+//!
+//! assert_eq!(
+//!     values,
+//!     vec![
+//!         Value::Int(42),
+//!         Value::Str("GET".to_string()),
+//!         Value::Str("/index.html".to_string()),
+//!     ]
+//! );
+//! # Ok::<(), manger::ConsumeError>(())
+//! ```
+//!
+//! A `%s` consumes all characters up to the next whitespace. Use [`GrammarBuilder::specifier`] to
+//! define more specific consumers for a format.
+
+use crate::error::ConsumeErrorType::*;
+use crate::{Consumable, ConsumeError, ConsumeSource};
+use std::collections::HashMap;
+use std::fmt;
+use thiserror::Error;
+
+/// Value produced by a consumer of a [`Grammar`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    /// A consumed integer.
+    Int(i64),
+
+    /// A consumed floating point number.
+    Float(f64),
+
+    /// A consumed utf-8 character.
+    Char(char),
+
+    /// A consumed string.
+    Str(String),
+}
+
+impl Value {
+    /// Get the integer of a [`Value::Int`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use manger::dynamic::Value;
+    ///
+    /// assert_eq!(Value::Int(5).as_int(), Some(5));
+    /// assert_eq!(Value::Char('5').as_int(), None);
+    /// ```
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            Value::Int(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Get the floating point number of a [`Value::Float`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use manger::dynamic::Value;
+    ///
+    /// assert_eq!(Value::Float(0.5).as_float(), Some(0.5));
+    /// assert_eq!(Value::Int(5).as_float(), None);
+    /// ```
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            Value::Float(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Get the character of a [`Value::Char`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use manger::dynamic::Value;
+    ///
+    /// assert_eq!(Value::Char('x').as_char(), Some('x'));
+    /// assert_eq!(Value::Str("x".to_string()).as_char(), None);
+    /// ```
+    pub fn as_char(&self) -> Option<char> {
+        match self {
+            Value::Char(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Get the string of a [`Value::Str`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use manger::dynamic::Value;
+    ///
+    /// assert_eq!(Value::Str("x".to_string()).as_str(), Some("x"));
+    /// assert_eq!(Value::Char('x').as_str(), None);
+    /// ```
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::Str(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/// Boxed consumer that produces a [`Value`] at runtime.
+pub type DynConsumer = Box<dyn Fn(&str) -> Result<(Value, &str), ConsumeError>>;
+
+enum Part {
+    Literal(String),
+    Consumer(DynConsumer),
+}
+
+impl fmt::Debug for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::Literal(literal) => f.debug_tuple("Literal").field(literal).finish(),
+            Part::Consumer(_) => f.write_str("Consumer"),
+        }
+    }
+}
+
+/// Error for an invalid format within [`GrammarBuilder::format`].
+#[derive(Error, Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum FormatError {
+    /// The format contains a specifier that is not known to the builder.
+    #[error("Unknown specifier '%{specifier}' at index {index}")]
+    UnknownSpecifier {
+        /// Index of the `'%'` of the specifier within the format.
+        index: usize,
+        /// Character of the specifier.
+        specifier: char,
+    },
+
+    /// The format ends with a `'%'` without a specifier.
+    #[error("Missing specifier after '%' at index {index}")]
+    MissingSpecifier {
+        /// Index of the `'%'` within the format.
+        index: usize,
+    },
+}
+
+fn word(source: &str) -> Result<(Value, &str), ConsumeError> {
+    let end = source.find(char::is_whitespace).unwrap_or(source.len());
+
+    if end == 0 {
+        return Err(ConsumeError::new_with(match source.chars().next() {
+            Some(token) => UnexpectedToken { index: 0, token },
+            None => InsufficientTokens { index: 0 },
+        }));
+    }
+
+    Ok((Value::Str(source[..end].to_string()), &source[end..]))
+}
+
+/// Builder to compose a [`Grammar`] at runtime.
+///
+/// The parts of the grammar are consumed in the order they are added. Next to the built-in
+/// consumers, any [`Consumable`] type can be added with [`custom`][GrammarBuilder::custom] and any
+/// function with [`consumer`][GrammarBuilder::consumer].
+///
+/// # Examples
+///
+/// ```
+/// use manger::dynamic::{GrammarBuilder, Value};
+/// use manger::common::Sign;
+///
+/// let grammar = GrammarBuilder::new()
+///     .custom(|sign: Sign| Value::Char(if sign.is_negative() { '-' } else { '+' }))
+///     .float()
+///     .literal(" x ")
+///     .char()
+///     .build();
+///
+/// let values = grammar.consume_all("-2.5 x y")?;
+///
+/// assert_eq!(values[0].as_char(), Some('-'));
+/// assert_eq!(values[1].as_float(), Some(2.5));
+/// assert_eq!(values[2].as_char(), Some('y'));
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, Default)]
+pub struct GrammarBuilder {
+    parts: Vec<Part>,
+    specifiers: HashMap<char, fn() -> DynConsumer>,
+}
+
+impl GrammarBuilder {
+    /// Create a builder for an empty grammar.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a literal, which has to be present but does not produce a [`Value`].
+    pub fn literal(mut self, literal: impl Into<String>) -> Self {
+        self.parts.push(Part::Literal(literal.into()));
+        self
+    }
+
+    /// Add a boxed consumer, which produces a [`Value`].
+    pub fn consumer(
+        mut self,
+        consumer: impl Fn(&str) -> Result<(Value, &str), ConsumeError> + 'static,
+    ) -> Self {
+        self.parts.push(Part::Consumer(Box::new(consumer)));
+        self
+    }
+
+    /// Add a consumer for a [`Consumable`] type `T`, which is turned into a [`Value`] by `map`.
+    pub fn custom<T: Consumable>(self, map: impl Fn(T) -> Value + 'static) -> Self {
+        self.consumer(move |source| T::consume_from(source).map(|(item, rest)| (map(item), rest)))
+    }
+
+    /// Add a consumer for an [`i64`], which produces a [`Value::Int`].
+    pub fn int(self) -> Self {
+        self.custom(Value::Int)
+    }
+
+    /// Add a consumer for an [`f64`], which produces a [`Value::Float`].
+    pub fn float(self) -> Self {
+        self.custom(Value::Float)
+    }
+
+    /// Add a consumer for a [`char`], which produces a [`Value::Char`].
+    pub fn char(self) -> Self {
+        self.custom(Value::Char)
+    }
+
+    /// Add a consumer for one or more non-whitespace characters, which produces a [`Value::Str`].
+    pub fn word(self) -> Self {
+        self.consumer(word)
+    }
+
+    /// Define the consumer that is added for `%specifier` within [`format`][GrammarBuilder::format].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use manger::Consumable;
+    /// use manger::dynamic::{DynConsumer, GrammarBuilder, Value};
+    ///
+    /// fn bracketed() -> DynConsumer {
+    ///     Box::new(|source| {
+    ///         let end = source.find(']').unwrap_or(source.len());
+    ///         Ok((Value::Str(source[..end].to_string()), &source[end..]))
+    ///     })
+    /// }
+    ///
+    /// let grammar = GrammarBuilder::new()
+    ///     .specifier('t', bracketed)
+    ///     .format("%s [%t]")
+    ///     .unwrap()
+    ///     .build();
+    ///
+    /// let values = grammar.consume_all("INFO [12:00:01]")?;
+    /// assert_eq!(values[1].as_str(), Some("12:00:01"));
+    ///
+    /// assert!(GrammarBuilder::new().format("%t").is_err());
+    /// # Ok::<(), manger::ConsumeError>(())
+    /// ```
+    pub fn specifier(mut self, specifier: char, consumer: fn() -> DynConsumer) -> Self {
+        self.specifiers.insert(specifier, consumer);
+        self
+    }
+
+    /// Add the parts described by a `format`.
+    ///
+    /// The characters of the `format` are literals, except for the following specifiers:
+    ///
+    /// - `%d` adds an [`int`][GrammarBuilder::int],
+    /// - `%f` adds a [`float`][GrammarBuilder::float],
+    /// - `%c` adds a [`char`][GrammarBuilder::char],
+    /// - `%s` adds a [`word`][GrammarBuilder::word],
+    /// - `%%` adds the literal `'%'`,
+    /// - any specifier defined with [`specifier`][GrammarBuilder::specifier].
+    ///
+    /// Specifiers defined with [`specifier`][GrammarBuilder::specifier] take precedence over the
+    /// built-in specifiers.
+    pub fn format(mut self, format: &str) -> Result<Self, FormatError> {
+        let mut literal = String::new();
+        let mut chars = format.chars().enumerate();
+
+        while let Some((index, token)) = chars.next() {
+            if token != '%' {
+                literal.push(token);
+                continue;
+            }
+
+            let specifier = chars
+                .next()
+                .map(|(_, specifier)| specifier)
+                .ok_or(FormatError::MissingSpecifier { index })?;
+
+            if specifier == '%' {
+                literal.push('%');
+                continue;
+            }
+
+            if !literal.is_empty() {
+                self = self.literal(std::mem::take(&mut literal));
+            }
+
+            self = match (self.specifiers.get(&specifier), specifier) {
+                (Some(consumer), _) => {
+                    let consumer = consumer();
+                    self.consumer(consumer)
+                }
+                (None, 'd') => self.int(),
+                (None, 'f') => self.float(),
+                (None, 'c') => self.char(),
+                (None, 's') => self.word(),
+                (None, _) => return Err(FormatError::UnknownSpecifier { index, specifier }),
+            };
+        }
+
+        if !literal.is_empty() {
+            self = self.literal(literal);
+        }
+
+        Ok(self)
+    }
+
+    /// Finish the grammar.
+    pub fn build(self) -> Grammar {
+        Grammar { parts: self.parts }
+    }
+}
+
+/// Grammar composed at runtime with a [`GrammarBuilder`].
+///
+/// # Examples
+///
+/// ```
+/// use manger::dynamic::{GrammarBuilder, Value};
+///
+/// let grammar = GrammarBuilder::new().literal("(").int().literal(")").build();
+///
+/// let (values, unconsumed) = grammar.consume_from("(-7) rest")?;
+///
+/// assert_eq!(values, vec![Value::Int(-7)]);
+/// assert_eq!(unconsumed, " rest");
+///
+/// assert_eq!(grammar.consume_from("(-7").unwrap_err().causes()[0].index(), &3);
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug)]
+pub struct Grammar {
+    parts: Vec<Part>,
+}
+
+impl Grammar {
+    /// Consume the grammar from the start of `source`.
+    ///
+    /// Returns the produced values in the order of the consumers, along with the unconsumed part
+    /// of `source`.
+    pub fn consume_from<'a>(&self, source: &'a str) -> Result<(Vec<Value>, &'a str), ConsumeError> {
+        let mut values = Vec::new();
+        let mut unconsumed = source;
+        let mut offset = 0;
+
+        for part in &self.parts {
+            match part {
+                Part::Literal(literal) => {
+                    offset += unconsumed
+                        .mut_consume_lit(&literal.as_str())
+                        .map_err(|err| err.offset(offset))?;
+                }
+                Part::Consumer(consumer) => {
+                    let (value, rest) = consumer(unconsumed).map_err(|err| err.offset(offset))?;
+
+                    offset += unconsumed[..unconsumed.len() - rest.len()].chars().count();
+                    unconsumed = rest;
+                    values.push(value);
+                }
+            }
+        }
+
+        Ok((values, unconsumed))
+    }
+
+    /// Consume the grammar from the whole `source`.
+    ///
+    /// This is similar to [`Consumable::consume_all`], and fails when there are tokens left after
+    /// consuming the grammar.
+    pub fn consume_all(&self, source: &str) -> Result<Vec<Value>, ConsumeError> {
+        let (values, unconsumed) = self.consume_from(source)?;
+
+        match unconsumed.chars().next() {
+            None => Ok(values),
+            Some(token) => Err(ConsumeError::new_with(UnexpectedToken {
+                index: source.chars().count() - unconsumed.chars().count(),
+                token,
+            })),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FormatError, GrammarBuilder, Value};
+    use crate::error::ConsumeErrorType::*;
+    use crate::ConsumeError;
+
+    #[test]
+    fn test_format() {
+        let grammar = GrammarBuilder::new().format("%c%% é%d %f").unwrap().build();
+        assert_eq!(
+            grammar.consume_all("a% é-1x2.5").unwrap_err().causes()[0].index(),
+            &6
+        );
+        assert_eq!(
+            grammar.consume_all("a% é-1 2.5 ").unwrap_err(),
+            ConsumeError::new_with(UnexpectedToken {
+                index: 10,
+                token: ' '
+            })
+        );
+        assert_eq!(
+            grammar.consume_all("a% é-1 2.5").unwrap(),
+            vec![Value::Char('a'), Value::Int(-1), Value::Float(2.5)]
+        );
+
+        assert_eq!(
+            GrammarBuilder::new().format("ab%").unwrap_err(),
+            FormatError::MissingSpecifier { index: 2 }
+        );
+        assert_eq!(
+            GrammarBuilder::new().format("%d%x").unwrap_err(),
+            FormatError::UnknownSpecifier {
+                index: 2,
+                specifier: 'x'
+            }
+        );
+    }
+}
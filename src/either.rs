@@ -1,4 +1,4 @@
-use crate::error::ConsumeError;
+use crate::error::{ConsumeError, MergePolicy};
 use crate::Consumable;
 use either::Either;
 
@@ -8,24 +8,27 @@ where
     R: Consumable,
 {
     fn consume_from(s: &str) -> Result<(Self, &str), ConsumeError> {
-        let left = <L>::consume_from(s);
+        crate::trace::attempt::<Self, _>(s, || {
+            let left = <L>::consume_from(s);
 
-        match left {
-            Ok((left_item, unconsumed)) => Ok((Either::Left(left_item), unconsumed)),
-            Err(left_err) => {
-                let right = <R>::consume_from(s);
+            match left {
+                Ok((left_item, unconsumed)) => Ok((Either::Left(left_item), unconsumed)),
+                Err(left_err) => {
+                    let right = <R>::consume_from(s);
 
-                match right {
-                    Ok((right_item, unconsumed)) => Ok((Either::Right(right_item), unconsumed)),
-                    Err(right_err) => {
-                        let mut errors = ConsumeError::new();
-                        errors.add_causes(left_err);
-                        errors.add_causes(right_err);
+                    match right {
+                        Ok((right_item, unconsumed)) => Ok((Either::Right(right_item), unconsumed)),
+                        Err(right_err) => {
+                            let mut errors = ConsumeError::new();
+                            let policy = MergePolicy::current();
+                            errors.add_alternative(left_err, policy);
+                            errors.add_alternative(right_err, policy);
 
-                        Err(errors)
+                            Err(errors)
+                        }
                     }
                 }
             }
-        }
+        })
     }
 }
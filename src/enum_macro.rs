@@ -93,138 +93,1171 @@
 /// The ENBF syntax is as follows:
 /// > Please note that the syntax ignores interproduction rule
 /// ```enbf
-/// syntax = enum_name, "{",
+/// syntax = { option }, enum_name, [ generics ], "{",
 ///             {(variant_definition, ",")}*,
 ///             variant_definition,
 ///          "}";
 ///
-/// variant_definition = variant_name, "=>", "[",
+/// variant_definition = variant_name, "=>", [ operator ], "[",
 ///                         {(instruction, ",")}*,
 ///                         instruction, ";",
-///                         [ "(", RUST_EXPR*, ")" ], # RUST_EXPR is an arbitrary rust
-///                                                   # expression it can use all the RUST_IDENT
-///                                                   # defined in the previous section.
+///                         [ tuple_mapping | named_mapping ],
 ///                      "]";
 ///
+/// operator = "{", ( "left_assoc" | "right_assoc" ), ",", "precedence", "=", INTEGER, "}";
+///
+/// tuple_mapping = "(", RUST_EXPR*, ")"; # RUST_EXPR is an arbitrary rust expression it can use
+///                                       # all the RUST_IDENT defined in the previous section.
+///
+/// named_mapping = "{", { RUST_IDENT, [ ":", RUST_EXPR ] }*, "}"; # A RUST_IDENT without
+///                                                                # RUST_EXPR uses the
+///                                                                # RUST_IDENT defined in the
+///                                                                # previous section.
+///
+///
+/// option = "#[", ( "render" | "ebnf" | "info" ), "]";
+///
 /// instruction = expr_instruction | type_instruction;
 ///
 /// expr_instruction = ">", RUST_EXPR;    # RUST_EXPR is an arbitrary rust expression. It should
 ///                                       # return a instance of a type that has the `Consumable`
 ///                                       # trait.
 ///
-/// type_instruction = [ RUST_IDENT ], ":", RUST_TYPE; # RUST_IDENT is an arbitrary rust identity
-///                                                    # an it will assigned to that property if no
-///                                                    # tuple syntax is defined.
-///                                                    # RUST_TYPE is an arbitrary rust type that
-///                                                    # implements `Consumable`.
+/// type_instruction = [ RUST_IDENT ], ":", RUST_TYPE, [ separator ]; # RUST_IDENT is an arbitrary
+///                                                                   # rust identity an it will
+///                                                                   # assigned to that property
+///                                                                   # if no tuple syntax is
+///                                                                   # defined.
+///                                                                   # RUST_TYPE is an arbitrary
+///                                                                   # rust type that implements
+///                                                                   # `Consumable`.
+///
+/// separator = "[", RUST_EXPR, "]"; # RUST_EXPR is a constant rust expression. It should return a
+///                                  # instance of a type that has the `SelfConsumable` trait.
+///
+/// generics = "<", RUST_IDENT, { ",", RUST_IDENT }, ">",
+///            [ "where", RUST_WHERE_PREDICATES ]; # RUST_WHERE_PREDICATES are arbitrary rust where
+///                                                # predicates, which are added to the
+///                                                # implementation.
+/// ```
+///
+/// # Mappings
+///
+/// A variant is constructed from the saved data in one of three ways:
+///
+/// 1. A tuple mapping, such as `(left * right)`, constructs a tuple variant.
+/// 2. A named mapping, such as `{ left, total: left + right }`, constructs a struct-like variant.
+///    Fields without an expression take the saved data with the same name.
+/// 3. Without a mapping, a struct-like variant is constructed from all the saved data. Variants
+///    without fields are constructed when no data was saved.
+///
+/// ```
+/// use manger::{ consume_enum, Consumable };
+///
+/// #[derive(PartialEq, Debug)]
+/// enum Operation {
+///     Sum { left: i32, right: i32, total: i32 },
+///     Negate { value: i32 },
+/// }
+/// consume_enum!(
+///     Operation {
+///         Sum => [
+///             left: i32,
+///             > '+',
+///             right: i32;
+///             { left, right, total: left + right }
+///         ],
+///         Negate => [
+///             > '-',
+///             value: i32;
+///         ]
+///     }
+/// );
+///
+/// assert_eq!(
+///     Operation::consume_from("3+4")?.0,
+///     Operation::Sum { left: 3, right: 4, total: 7 }
+/// );
+/// assert_eq!(
+///     Operation::consume_from("-5")?.0,
+///     Operation::Negate { value: 5 }
+/// );
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+///
+/// # Rendering
+///
+/// Just as with [`consume_struct`][crate::consume_struct], prefixing the definition with
+/// `#[render]` also implements [`Render`][crate::Render] for the `enum`. Every variant is rendered
+/// with its own instructions. Fields of a named mapping that are given a value are not rendered.
+///
+/// ```
+/// use manger::{ consume_enum, Consumable, Render };
+///
+/// #[derive(PartialEq, Debug)]
+/// enum Size {
+///     Auto,
+///     Pixels(u32),
+///     Ratio { width: u32, height: u32 },
+/// }
+/// consume_enum!(
+///     #[render]
+///     Size {
+///         Auto => [ > "auto"; ],
+///         Pixels => [ pixels: u32, > "px"; (pixels) ],
+///         Ratio => [ width: u32, > ':', height: u32; ]
+///     }
+/// );
+///
+/// assert_eq!(Size::Pixels(20).render(), "20px");
+/// assert_eq!(Size::consume_all("16:9")?.render(), "16:9");
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+///
+/// # Grammar
+///
+/// Just as with [`consume_struct`][crate::consume_struct], prefixing the definition with `#[ebnf]`
+/// adds an associated constant `EBNF` to the `enum`. Every variant is an alternative within the
+/// rule. Likewise, `#[info]` implements [`GrammarInfo`][crate::info::GrammarInfo]. The options can
+/// be combined.
+///
+/// ```
+/// use manger::{ consume_enum, Render };
+///
+/// enum Size {
+///     Auto,
+///     Pixels(u32),
+/// }
+/// consume_enum!(
+///     #[ebnf]
+///     #[render]
+///     Size {
+///         Auto => [ > "auto"; ],
+///         Pixels => [ pixels: u32, > "px"; (pixels) ]
+///     }
+/// );
+///
+/// assert_eq!(Size::EBNF, r#"Size = "auto" | u32 , "px" ;"#);
+/// assert_eq!(Size::Auto.render(), "auto");
+/// ```
+///
+/// # Operators
+///
+/// Variants of binary operators are annotated with their associativity and precedence, such as
+/// `{ left_assoc, precedence = 1 }`. Their instructions start with `lhs: Self` and end with
+/// `rhs: Self`, with the instructions for the operator itself in between. The names of both
+/// operands can be chosen freely.
+///
+/// The other variants are the operands. These are consumed as alternatives, after which an operator
+/// and the next operand are consumed for as long as possible. The operands are then combined by
+/// the operators from the highest to the lowest precedence. Operators with equal precedence are
+/// combined from left to right with `left_assoc` and from right to left with `right_assoc`.
+/// An operator that is not followed by an operand is left unconsumed.
+///
+/// ```
+/// use manger::{ consume_enum, Consumable };
+///
+/// #[derive(PartialEq, Debug)]
+/// enum Expr {
+///     Add(Box<Expr>, Box<Expr>),
+///     Sub(Box<Expr>, Box<Expr>),
+///     Mul(Box<Expr>, Box<Expr>),
+///     Pow(Box<Expr>, Box<Expr>),
+///     Num(u32),
+/// }
+/// consume_enum!(
+///     Expr {
+///         Add => { left_assoc, precedence = 1 } [ lhs: Self, > '+', rhs: Self; (Box::new(lhs), Box::new(rhs)) ],
+///         Sub => { left_assoc, precedence = 1 } [ lhs: Self, > '-', rhs: Self; (Box::new(lhs), Box::new(rhs)) ],
+///         Mul => { left_assoc, precedence = 2 } [ lhs: Self, > '*', rhs: Self; (Box::new(lhs), Box::new(rhs)) ],
+///         Pow => { right_assoc, precedence = 3 } [ lhs: Self, > '^', rhs: Self; (Box::new(lhs), Box::new(rhs)) ],
+///         Num => [ value: u32; (value) ]
+///     }
+/// );
+///
+/// impl Expr {
+///     fn eval(&self) -> u32 {
+///         match self {
+///             Expr::Add(lhs, rhs) => lhs.eval() + rhs.eval(),
+///             Expr::Sub(lhs, rhs) => lhs.eval() - rhs.eval(),
+///             Expr::Mul(lhs, rhs) => lhs.eval() * rhs.eval(),
+///             Expr::Pow(lhs, rhs) => lhs.eval().pow(rhs.eval()),
+///             Expr::Num(value) => *value,
+///         }
+///     }
+/// }
+///
+/// assert_eq!(Expr::consume_all("1+2*3")?.eval(), 7);
+/// assert_eq!(Expr::consume_all("9-3-2")?.eval(), 4);
+/// assert_eq!(Expr::consume_all("2^3^2")?.eval(), 512);
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+///
+/// Rendering is not generated for an `enum` with operators.
+///
+/// # Error reporting
+///
+/// When every variant fails, the error contains the causes of all of them by default. Prefixing
+/// the definition with `#[merge(Deepest)]` or `#[merge(First)]` only keeps the causes of the
+/// variants that got the furthest or of the first variant, see
+/// [`MergePolicy`][crate::MergePolicy]. Without this option, the
+/// [`current`][crate::MergePolicy::current] policy is used.
+///
+/// ```
+/// use manger::{ consume_enum, Consumable, ConsumeErrorType };
+///
+/// enum Statement {
+///     Assign(char, u32),
+///     Call(char),
+/// }
+/// consume_enum!(
+///     #[merge(Deepest)]
+///     Statement {
+///         Assign => [ name: char, > '=', value: u32; (name, value) ],
+///         Call => [ name: char, > "()"; (name) ]
+///     }
+/// );
+///
+/// let error = Statement::consume_from("f(").map(|_| ()).unwrap_err();
+/// assert_eq!(
+///     error.into_causes(),
+///     vec![ConsumeErrorType::InsufficientTokens { index: 2 }]
+/// );
+/// ```
+///
+/// # Generics
+///
+/// Generic `enum`s can be consumed by listing the generic parameters after the name of the
+/// `enum`. Any bounds on the parameters are given in a `where` clause.
+///
+/// ```
+/// use manger::{ consume_enum, Consumable };
+///
+/// #[derive(PartialEq, Debug)]
+/// enum Setting<T> {
+///     Default,
+///     Value(T),
+/// }
+/// consume_enum!(
+///     Setting<T> where T: Consumable {
+///         Default => [ > "default"; ],
+///         Value => [ value: T; (value) ]
+///     }
+/// );
+///
+/// assert_eq!(<Setting<i32>>::consume_from("default")?.0, Setting::Default);
+/// assert_eq!(<Setting<i32>>::consume_from("-5")?.0, Setting::Value(-5));
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+///
+/// # Common mistakes
+///
+/// Mistakes within the macro are reported at the token where the macro stopped matching. Every
+/// list of instructions has to end with a semicolon, even when no mapping follows it.
+///
+/// ```compile_fail
+/// use manger::consume_enum;
+///
+/// enum Answer { Yes, No }
+/// consume_enum!(
+///     Answer {
+///         Yes => [ > "yes" ],
+///         No => [ > "no" ]
+///     }
+/// );
+/// ```
+///
+/// The variants are given between braces, not brackets.
+///
+/// ```compile_fail
+/// use manger::consume_enum;
+///
+/// enum Answer { Yes, No }
+/// consume_enum!(
+///     Answer [
+///         Yes => [ > "yes"; ],
+///         No => [ > "no"; ]
+///     ]
+/// );
 /// ```
 ///
 /// # Note
 ///
 /// 1. Although this macro works without importing any __manger__ traits, they will also not be
-/// imported afterwards. Importing traits should still be done if methods of the trait
-/// are supposed to be used afterwards.
+///    imported afterwards. Importing traits should still be done if methods of the trait
+///    are supposed to be used afterwards.
 ///
 /// 2. This macro assumed that we are in the same module as the `enum` mentioned
-/// was defined. Some undefined behaviour might occur if this macro is called
-/// outside of the module the `enum` was created.
+///    was defined. Some undefined behaviour might occur if this macro is called
+///    outside of the module the `enum` was created.
 #[macro_export]
 macro_rules! consume_enum {
+    ( #[merge($policy:ident)] $( $rest:tt )* ) => {
+        $crate::consume_enum!( @policy [ $policy ] $( $rest )* );
+    };
+    ( @policy [ $policy:ident ] #[$option:ident] $( $rest:tt )* ) => {
+        $crate::consume_enum!( @policy [ $policy ] $( $rest )* );
+        $crate::consume_enum!( @entry $option [] $( $rest )* );
+    };
+    ( @policy [ $policy:ident ] $enum_name:ident $( $rest:tt )* ) => {
+        $crate::consume_enum!( @entry impl [ $policy ] $enum_name $( $rest )* );
+    };
+    ( #[$option:ident] $( $rest:tt )* ) => {
+        $crate::consume_enum!( $( $rest )* );
+        $crate::consume_enum!( @entry $option [] $( $rest )* );
+    };
+    ( $enum_name:ident $( $rest:tt )* ) => {
+        $crate::consume_enum!( @entry impl [] $enum_name $( $rest )* );
+    };
+
+    // The `$mode` selects whether `Consumable` (`impl`), `Render` (`render`) or the `EBNF`
+    // constant (`ebnf`) is implemented. Other options are skipped. The `$policy` is the
+    // `MergePolicy` given with `#[merge(...)]`, which is only used by `impl`.
+    ( @entry $mode:tt [ $( $policy:ident )? ] #[merge($_policy:ident)] $( $rest:tt )* ) => {
+        $crate::consume_enum!( @entry $mode [ $( $policy )? ] $( $rest )* );
+    };
+    ( @entry $mode:tt [ $( $policy:ident )? ] #[$option:ident] $( $rest:tt )* ) => {
+        $crate::consume_enum!( @entry $mode [ $( $policy )? ] $( $rest )* );
+    };
+    ( @entry $mode:tt [ $( $policy:ident )? ] $enum_name:ident $body:tt ) => {
+        $crate::consume_enum!( @$mode [ $( $policy )? ] [] $enum_name [] $body );
+    };
+    ( @entry $mode:tt [ $( $policy:ident )? ] $enum_name:ident < $( $generic:ident ),+ $(,)? > $body:tt ) => {
+        $crate::consume_enum!( @$mode [ $( $policy )? ] [ $( $generic ),+ ] $enum_name [] $body );
+    };
+    ( @entry $mode:tt [ $( $policy:ident )? ] $enum_name:ident < $( $generic:ident ),+ $(,)? > where $( $rest:tt )* ) => {
+        $crate::consume_enum!(
+            @where $mode [ $( $policy )? ] [ $( $generic ),+ ] $enum_name [] $( $rest )*
+        );
+    };
+
+    // Collect the tokens of the where clause up until the variants.
+    (
+        @where $mode:tt [ $( $policy:ident )? ] [ $( $generic:ident ),+ ] $enum_name:ident
+        [ $( $clause:tt )* ] { $( $body:tt )* }
+    ) => {
+        $crate::consume_enum!(
+            @$mode [ $( $policy )? ] [ $( $generic ),+ ] $enum_name [ $( $clause )* ] { $( $body )* }
+        );
+    };
+    (
+        @where $mode:tt [ $( $policy:ident )? ] [ $( $generic:ident ),+ ] $enum_name:ident
+        [ $( $clause:tt )* ] $next:tt $( $rest:tt )*
+    ) => {
+        $crate::consume_enum!(
+            @where $mode [ $( $policy )? ] [ $( $generic ),+ ] $enum_name [ $( $clause )* $next ] $( $rest )*
+        );
+    };
+
+    (
+        @impl [ $( $policy:ident )? ] [ $( $generic:ident ),* ] $enum_name:ident [ $( $clause:tt )* ] {
+            $(
+                $ident:ident => [
+                    $(
+                        $( $( $prop_name:ident )?: $cons_type:ty $( [ $separator:expr ] )? $( { $cons_condition:expr } )? )?
+                        $( > $cons_expr:expr )?
+                    ),*
+                    ;
+                    $(
+                        ( $( $prop:expr ),* )
+                    )?
+                    $(
+                        { $( $field:ident $( : $field_value:expr )? ),* $(,)? }
+                    )?
+                ]
+            ),+
+            $(,)?
+        }
+    ) => {
+        impl<$( $generic ),*> $crate::Consumable for $enum_name<$( $generic ),*>
+        where
+            $( $clause )*
+        {
+            fn expecting() -> $crate::Expectation {
+                $crate::Expectation::new($crate::consume_enum!(
+                    @ebnf_alternatives
+                    $(
+                        $crate::consume_struct!(
+                            @ebnf_sequence
+                            $( ( $( $cons_type $( , [ $separator ] )? )? $( > $cons_expr )? ) ),*
+                        )
+                    ),+
+                ))
+            }
+
+            fn consume_from(source: &str) -> Result<(Self, &str), $crate::ConsumeError> {
+                $crate::trace::attempt::<Self, _>(source, || $crate::limits::nest(|| {
+                    let policy = $crate::consume_enum!( @merge_policy $( $policy )? );
+                    let mut error = $crate::ConsumeError::new();
+                    $crate::coverage::register::<Self>(&[ $( stringify!($ident) ),+ ]);
+                    let mut point = 0;
+
+                    $(
+                        #[allow(unconditional_recursion)]
+                        match $crate::consume_enum!(
+                            @sequence source [
+                                $(
+                                    $( $( $prop_name )?: $cons_type $( [ $separator ] )? $( { $cons_condition } )? )?
+                                    $( > $cons_expr )?
+                                ),*
+                            ] => $crate::consume_enum!(
+                                @internal
+                                $enum_name,
+                                $ident,
+                                $(
+                                    $( $( $prop_name, )? )?
+                                )*
+                                $( => ( $( $prop ),* ) )?
+                                $( => { $( $field $( : $field_value )? ),* } )?
+                            )
+                        ) {
+                            Ok((item, unconsumed, _)) => {
+                                $crate::coverage::hit::<Self>(point);
+                                return Ok((item, unconsumed));
+                            }
+                            Err(err) => error.add_alternative(err, policy),
+                        }
+                        #[allow(unused_assignments)]
+                        { point += 1 };
+                    )+
+
+                    // Every alternative failed, so each of them was expected.
+                    $(
+                        error.add_expectation($crate::Expectation::new($crate::consume_struct!(
+                            @ebnf_sequence
+                            $( ( $( $cons_type $( , [ $separator ] )? )? $( > $cons_expr )? ) ),*
+                        )));
+                    )+
+
+                    Err(error)
+                })
+                .map_err(|err| err.tag_origin(::std::any::type_name::<Self>())))
+            }
+        }
+    };
+
+    // Without operator variants, the variants did not match the rule above. Report the variant
+    // that is malformed, instead of failing within the expansion for operators.
+    (
+        @impl [ $( $policy:ident )? ] [ $( $generic:ident ),* ] $enum_name:ident [ $( $clause:tt )* ] {
+            $(
+                $ident:ident => [ $( $body:tt )* ]
+            ),+
+            $(,)?
+        }
+    ) => {
+        $(
+            $crate::consume_enum!( @check_variant $ident [ $( $body )* ] );
+        )+
+    };
+    (
+        @check_variant $ident:ident [
+            $(
+                $( $( $prop_name:ident )?: $cons_type:ty $( [ $separator:expr ] )? $( { $cons_condition:expr } )? )?
+                $( > $cons_expr:expr )?
+            ),*
+            ;
+            $( ( $( $prop:expr ),* ) )?
+            $( { $( $field:ident $( : $field_value:expr )? ),* $(,)? } )?
+        ]
+    ) => {};
+    ( @check_variant $ident:ident [ $( $body:tt )* ] ) => {
+        $crate::consume_enum!( @check_semicolon $ident [ $( $body )* ] );
+    };
+    ( @check_semicolon $ident:ident [ ; $( $rest:tt )* ] ) => {
+        compile_error!(concat!(
+            "The variant `",
+            stringify!($ident),
+            "` has to be of the form `",
+            stringify!($ident),
+            " => [ instructions; mapping ]`"
+        ));
+    };
+    ( @check_semicolon $ident:ident [ $next:tt $( $rest:tt )* ] ) => {
+        $crate::consume_enum!( @check_semicolon $ident [ $( $rest )* ] );
+    };
+    ( @check_semicolon $ident:ident [] ) => {
+        compile_error!(concat!(
+            "The instructions of the variant `",
+            stringify!($ident),
+            "` have to end with a `;`"
+        ));
+    };
+
+    // An `enum` with operator variants is consumed as a list of operands separated by operators,
+    // which is then combined by precedence.
+    (
+        @impl [ $( $policy:ident )? ] [ $( $generic:ident ),* ] $enum_name:ident [ $( $clause:tt )* ] {
+            $(
+                $ident:ident => $( { $( $annotation:tt )* } )? [ $( $body:tt )* ]
+            ),+
+            $(,)?
+        }
+    ) => {
+        impl<$( $generic ),*> $crate::Consumable for $enum_name<$( $generic ),*>
+        where
+            $( $clause )*
+        {
+            fn expecting() -> $crate::Expectation {
+                $crate::Expectation::new($crate::consume_enum!(
+                    @ebnf_alternatives
+                    $(
+                        $crate::consume_enum!(
+                            @variant_ebnf $enum_name [ $( $( $annotation )* )? ] [ $( $body )* ]
+                        )
+                    ),+
+                ))
+            }
+
+            fn consume_from(source: &str) -> Result<(Self, &str), $crate::ConsumeError> {
+                type Build<'a, T> = Box<dyn FnOnce(T, T) -> T + 'a>;
+
+                $crate::trace::attempt::<Self, _>(source, || $crate::limits::nest(|| {
+                    let policy = $crate::consume_enum!( @merge_policy $( $policy )? );
+                    $crate::coverage::register::<Self>(&[ $( stringify!($ident) ),+ ]);
+
+                    let (mut current, mut unconsumed) = $crate::consume_enum!(
+                        @operand_alternatives source policy $enum_name [
+                            $( $ident [ $( $( $annotation )* )? ] [ $( $body )* ] )+
+                        ]
+                    )?;
+
+                    // The operands before `current` along with the operator that follows them.
+                    let mut pending: Vec<(Self, u32, Build<'_, Self>)> = Vec::new();
+
+                    loop {
+                        let mut operator: Option<(u32, bool, Build<'_, Self>, &str, usize)> = None;
+
+                        let mut point = 0;
+                        $(
+                            if operator.is_none() {
+                                operator = $crate::consume_enum!(
+                                    @operator [ $( $( $annotation )* )? ] unconsumed
+                                    $enum_name $ident [ $( $body )* ]
+                                );
+                                if operator.is_some() {
+                                    $crate::coverage::hit::<Self>(point);
+                                }
+                            }
+                            #[allow(unused_assignments)]
+                            { point += 1 };
+                        )+
+
+                        let (precedence, left_assoc, build, rest, _) = match operator {
+                            Some(consumed) => consumed,
+                            None => break,
+                        };
+
+                        // Without an operand after it, the operator is left unconsumed.
+                        let (operand, rest) = match $crate::consume_enum!(
+                            @operand_alternatives rest policy $enum_name [
+                                $( $ident [ $( $( $annotation )* )? ] [ $( $body )* ] )+
+                            ]
+                        ) {
+                            Ok(consumed) => consumed,
+                            Err(_) => break,
+                        };
+
+                        while let Some((lhs, top, top_build)) = pending.pop() {
+                            if top > precedence || (top == precedence && left_assoc) {
+                                current = top_build(lhs, current);
+                            } else {
+                                pending.push((lhs, top, top_build));
+                                break;
+                            }
+                        }
+
+                        pending.push((current, precedence, build));
+                        current = operand;
+                        unconsumed = rest;
+                    }
+
+                    while let Some((lhs, _, build)) = pending.pop() {
+                        current = build(lhs, current);
+                    }
+
+                    Ok((current, unconsumed))
+                })
+                .map_err(|err| err.tag_origin(::std::any::type_name::<Self>())))
+            }
+        }
+    };
+
+    // Consume one of the operands from `$source`, which gives the operand and the unconsumed part.
+    (
+        @operand_alternatives $source:ident $policy:ident $enum_name:ident [
+            $( $ident:ident [ $( $annotation:tt )* ] [ $( $body:tt )* ] )+
+        ]
+    ) => {{
+        let mut error = $crate::ConsumeError::new();
+        let mut operand: Option<(Self, &str, usize)> = None;
+
+        let mut point = 0;
+        $(
+            if operand.is_none() {
+                operand = $crate::consume_enum!(
+                    @operand [ $( $annotation )* ] $source error $policy
+                    $enum_name $ident [ $( $body )* ]
+                );
+                if operand.is_some() {
+                    $crate::coverage::hit::<Self>(point);
+                }
+            }
+            #[allow(unused_assignments)]
+            { point += 1 };
+        )+
+
+        match operand {
+            Some((item, rest, _)) => Ok((item, rest)),
+            None => {
+                // Every operand failed, so each of them was expected.
+                $(
+                    $crate::consume_enum!(
+                        @operand_expectation [ $( $annotation )* ] error
+                        $enum_name [ $( $body )* ]
+                    );
+                )+
+
+                Err(error)
+            }
+        }
+    }};
+
+    // Consume a list of instructions from `$source` into `$result`, which gives the result, the
+    // unconsumed part and the amount of consumed characters.
+    (
+        @sequence $source:ident [
+            $(
+                $( $( $prop_name:ident )?: $cons_type:ty $( [ $separator:expr ] )? $( { $cons_condition:expr } )? )?
+                $( > $cons_expr:expr )?
+            ),*
+        ] => $result:expr
+    ) => {
+        loop {
+            let mut unconsumed = $source;
+            let mut offset = 0;
+
+            $(
+                $(
+                    $( let $prop_name = )?
+                    match $crate::consume_enum!( @consume unconsumed, $cons_type $( , [ $separator ] )? )
+                    $(
+                        .and_then(
+                            |(item, by)| {
+                                if ($cons_condition)(item) {
+                                    Ok((item, by))
+                                } else {
+                                    Err(
+                                        $crate::ConsumeError::new_with(
+                                            $crate::ConsumeErrorType::InvalidValue { index: 0 }
+                                        )
+                                    )
+                                }
+                            }
+                        )
+                    )?
+                    {
+                        Err(err) => break Err(err.offset(offset)),
+                        Ok((prop, by)) => {
+                            #[allow(unused_assignments)]
+                            { offset += by };
+                            prop
+                        }
+                    };
+                )?
+
+                $(
+                    if let Err(err) = $crate::ConsumeSource::mut_consume_lit(&mut unconsumed, &$cons_expr)
+                        .map(|by| {
+                            #[allow(unused_assignments)]
+                            { offset += by };
+                        })
+                    {
+                        break Err(err.offset(offset));
+                    }
+                )?
+            )*
+
+            break Ok(($result, unconsumed, offset));
+        }
+    };
+
+    // The operands are the variants without an operator annotation.
+    ( @operand [] $source:ident $error:ident $policy:ident $enum_name:ident $ident:ident [ $( $body:tt )* ] ) => {
+        $crate::consume_enum!( @operand_sequence $source $error $policy $enum_name $ident [ $( $body )* ] )
+    };
+    ( @operand [ $( $annotation:tt )+ ] $( $rest:tt )* ) => {
+        None
+    };
+    (
+        @operand_sequence $source:ident $error:ident $policy:ident $enum_name:ident $ident:ident [
+            $(
+                $( $( $prop_name:ident )?: $cons_type:ty $( [ $separator:expr ] )? $( { $cons_condition:expr } )? )?
+                $( > $cons_expr:expr )?
+            ),*
+            ;
+            $( ( $( $prop:expr ),* ) )?
+            $( { $( $field:ident $( : $field_value:expr )? ),* $(,)? } )?
+        ]
+    ) => {
+        match $crate::consume_enum!(
+            @sequence $source [
+                $(
+                    $( $( $prop_name )?: $cons_type $( [ $separator ] )? $( { $cons_condition } )? )?
+                    $( > $cons_expr )?
+                ),*
+            ] => $crate::consume_enum!(
+                @internal
+                $enum_name,
+                $ident,
+                $(
+                    $( $( $prop_name, )? )?
+                )*
+                $( => ( $( $prop ),* ) )?
+                $( => { $( $field $( : $field_value )? ),* } )?
+            )
+        ) {
+            Ok(consumed) => Some(consumed),
+            Err(err) => {
+                $error.add_alternative(err, $policy);
+                None
+            }
+        }
+    };
+
+    ( @operand_expectation [] $error:ident $enum_name:ident [ $( $body:tt )* ] ) => {
+        $error.add_expectation($crate::Expectation::new(
+            $crate::consume_enum!( @variant_ebnf $enum_name [] [ $( $body )* ] )
+        ))
+    };
+    ( @operand_expectation [ $( $annotation:tt )+ ] $( $rest:tt )* ) => {};
+
+    // The operators are the variants with an operator annotation, which consume the instructions
+    // between `lhs: Self` and `rhs: Self`.
+    ( @operator [] $( $rest:tt )* ) => {
+        None
+    };
+    ( @operator [ $( $annotation:tt )+ ] $source:ident $enum_name:ident $ident:ident [ $( $body:tt )* ] ) => {
+        $crate::consume_enum!(
+            @operator_split [ operator_sequence $source $enum_name $ident [ $( $annotation )+ ] ]
+            [ $( $body )* ]
+        )
+    };
+    (
+        @operator_sequence $source:ident $enum_name:ident $ident:ident
+        [ $associativity:ident , precedence = $precedence:literal $(,)? ]
+        [ $lhs:ident $rhs:ident ]
+        [
+            $(
+                $( $( $prop_name:ident )?: $cons_type:ty $( [ $separator:expr ] )? $( { $cons_condition:expr } )? )?
+                $( > $cons_expr:expr )?
+            ),+
+        ]
+        [
+            $( ( $( $prop:expr ),* ) )?
+            $( { $( $field:ident $( : $field_value:expr )? ),* $(,)? } )?
+        ]
+    ) => {
+        match $crate::consume_enum!(
+            @sequence $source [
+                $(
+                    $( $( $prop_name )?: $cons_type $( [ $separator ] )? $( { $cons_condition } )? )?
+                    $( > $cons_expr )?
+                ),+
+            ] => {
+                let build: Box<dyn FnOnce(Self, Self) -> Self + '_> =
+                    Box::new(move |$lhs: Self, $rhs: Self| $crate::consume_enum!(
+                        @internal
+                        $enum_name,
+                        $ident,
+                        $lhs,
+                        $(
+                            $( $( $prop_name, )? )?
+                        )+
+                        $rhs,
+                        $( => ( $( $prop ),* ) )?
+                        $( => { $( $field $( : $field_value )? ),* } )?
+                    ));
+                build
+            }
+        ) {
+            Ok((build, unconsumed, by)) => Some((
+                $precedence,
+                $crate::consume_enum!( @left_assoc $associativity ),
+                build,
+                unconsumed,
+                by,
+            )),
+            Err(_) => None,
+        }
+    };
+    ( @operator_sequence $source:ident $enum_name:ident $ident:ident [ $( $annotation:tt )* ] $( $rest:tt )* ) => {
+        compile_error!(
+            "An operator is annotated as `{ left_assoc, precedence = 1 }` or `{ right_assoc, precedence = 1 }`"
+        )
+    };
+
+    ( @left_assoc left_assoc ) => {
+        true
+    };
+    ( @left_assoc right_assoc ) => {
+        false
+    };
+    ( @left_assoc $associativity:ident ) => {
+        compile_error!("The associativity of an operator is either `left_assoc` or `right_assoc`")
+    };
+
+    // Split an operator variant into its operands, the instructions in between and the mapping.
+    // These are passed on to the `$callback`.
+    ( @operator_split [ $( $context:tt )* ] [ $lhs:ident : Self , $( $rest:tt )* ] ) => {
+        $crate::consume_enum!( @operator_munch [ $( $context )* ] [ $lhs ] [] $( $rest )* )
+    };
+    (
+        @operator_munch [ $callback:ident $( $context:tt )* ] [ $lhs:ident ] [ $( $middle:tt )+ ]
+        , $rhs:ident : Self ; $( $mapping:tt )*
+    ) => {
+        $crate::consume_enum!(
+            @$callback $( $context )* [ $lhs $rhs ] [ $( $middle )+ ] [ $( $mapping )* ]
+        )
+    };
+    ( @operator_munch $context:tt $lhs:tt [ $( $middle:tt )* ] $next:tt $( $rest:tt )* ) => {
+        $crate::consume_enum!( @operator_munch $context $lhs [ $( $middle )* $next ] $( $rest )* )
+    };
+    ( @operator_munch $context:tt $lhs:tt [ $( $middle:tt )* ] ) => {
+        compile_error!(
+            "An operator variant starts with `lhs: Self,` and ends with `, rhs: Self;`, with instructions in between"
+        )
+    };
+    ( @operator_split $context:tt $body:tt ) => {
+        compile_error!(
+            "An operator variant starts with `lhs: Self,` and ends with `, rhs: Self;`, with instructions in between"
+        )
+    };
+
+    // The EBNF of a single variant, where an operator has its own `enum` as operands.
+    (
+        @variant_ebnf $enum_name:ident [] [
+            $(
+                $( $( $prop_name:ident )?: $cons_type:ty $( [ $separator:expr ] )? $( { $cons_condition:expr } )? )?
+                $( > $cons_expr:expr )?
+            ),*
+            ;
+            $( $mapping:tt )*
+        ]
+    ) => {
+        $crate::consume_struct!(
+            @ebnf_sequence
+            $( ( $( $cons_type $( , [ $separator ] )? )? $( > $cons_expr )? ) ),*
+        )
+    };
+    ( @variant_ebnf $enum_name:ident [ $( $annotation:tt )+ ] [ $( $body:tt )* ] ) => {
+        $crate::consume_enum!( @operator_split [ operator_ebnf $enum_name ] [ $( $body )* ] )
+    };
+    (
+        @operator_ebnf $enum_name:ident [ $lhs:ident $rhs:ident ]
+        [
+            $(
+                $( $( $prop_name:ident )?: $cons_type:ty $( [ $separator:expr ] )? $( { $cons_condition:expr } )? )?
+                $( > $cons_expr:expr )?
+            ),+
+        ]
+        [ $( $mapping:tt )* ]
+    ) => {
+        concat!(
+            stringify!($enum_name),
+            " , ",
+            $crate::consume_struct!(
+                @ebnf_sequence
+                $( ( $( $cons_type $( , [ $separator ] )? )? $( > $cons_expr )? ) ),+
+            ),
+            " , ",
+            stringify!($enum_name)
+        )
+    };
+
+    ( @merge_policy ) => {
+        $crate::MergePolicy::current()
+    };
+    ( @merge_policy $policy:ident ) => {
+        $crate::MergePolicy::$policy
+    };
+
+    ( @consume $unconsumed:ident, $cons_type:ty ) => {
+        $crate::ConsumeSource::mut_consume_by::<$cons_type>(&mut $unconsumed)
+    };
+    ( @consume $unconsumed:ident, $cons_type:ty, [ $separator:expr ] ) => {{
+        struct Separator;
+
+        impl $crate::Consumable for Separator {
+            fn consume_from(source: &str) -> Result<(Self, &str), $crate::ConsumeError> {
+                $crate::ConsumeSource::consume_lit(source, &$separator)
+                    .map(|unconsumed| (Separator, unconsumed))
+            }
+        }
+
+        $crate::ConsumeSource::mut_consume_by::<
+            $crate::common::SeparatedBy<$cons_type, Separator>
+        >(&mut $unconsumed)
+            .map(|(items, by)| (items.into_vec(), by))
+    }};
+
+    ( @internal $enum_name:ident, $ident:ident, $( $prop_name:ident, )* => ( $( $prop:expr ),* ) ) => {
+        $enum_name::$ident ( $( $prop ),* )
+    };
+    (
+        @internal $enum_name:ident, $ident:ident, $( $prop_name:ident, )*
+        => { $( $field:ident $( : $field_value:expr )? ),* }
+    ) => {
+        $enum_name::$ident { $( $field: $crate::consume_enum!( @field $field $( : $field_value )? ) ),* }
+    };
+    ( @internal $enum_name:ident, $ident:ident, $( $prop_name:ident, )* ) => {
+        $enum_name::$ident { $( $prop_name ),* }
+    };
+
+    ( @field $field:ident ) => {
+        $field
+    };
+    ( @field $field:ident : $field_value:expr ) => {
+        $field_value
+    };
+
     (
-        $enum_name:ident {
+        @render [ $( $policy:ident )? ] [ $( $generic:ident ),* ] $enum_name:ident [ $( $clause:tt )* ] {
             $(
                 $ident:ident => [
                     $(
-                        $( $( $prop_name:ident )?: $cons_type:ty $( { $cons_condition:expr } )? )?
+                        $( $( $prop_name:ident )?: $cons_type:ty $( [ $separator:expr ] )? $( { $cons_condition:expr } )? )?
                         $( > $cons_expr:expr )?
                     ),*
                     ;
                     $(
-                        ( $( $prop:expr ),* )
+                        ( $( $tuple_field:ident ),* $(,)? )
+                    )?
+                    $(
+                        { $( $field:ident $( : $field_value:expr )? ),* $(,)? }
                     )?
                 ]
             ),+
+            $(,)?
         }
     ) => {
-        impl $crate::Consumable for $enum_name {
-            fn consume_from(source: &str) -> Result<(Self, &str), $crate::ConsumeError> {
-                let mut error = $crate::ConsumeError::new();
-
-                $(
-                    #[allow(unconditional_recursion)]
-                    loop {
-                        let mut unconsumed = source;
-                        let mut offset = 0;
-
-                        $(
+        impl<$( $generic ),*> $crate::Render for $enum_name<$( $generic ),*>
+        where
+            $( $clause )*
+        {
+            #[allow(unused_variables)]
+            fn render_to(&self, out: &mut String) {
+                match self {
+                    $(
+                        $crate::consume_enum!(
+                            @render_pattern
+                            $enum_name,
+                            $ident,
+                            $(
+                                $( $( $prop_name, )? )?
+                            )*
+                            $( => ( $( $tuple_field ),* ) )?
+                            $( => { $( $field $( : $field_value )? ),* } )?
+                        ) => {
                             $(
-                                $( let $prop_name = )?
-                                match $crate::ConsumeSource::mut_consume_by::<$cons_type>(&mut unconsumed)
                                 $(
-                                    .and_then(
-                                        |(item, unconsumed)| {
-                                            if ($cons_condition)(item) {
-                                                Ok((item, unconsumed))
-                                            } else {
-                                                Err($crate::ConsumeErrorType::InvalidValue { index: offset })
-                                            }
-                                        }
-                                    )
+                                    $crate::consume_struct!(
+                                        @render_item out, [ $( $prop_name )? ] $cons_type $( , [ $separator ] )?
+                                    );
                                 )?
-                                {
-                                        Err(err) => {
-                                            error.add_causes(err.offset(offset));
-                                            break;
-                                        },
-                                        Ok((prop, by)) => {
-                                            #[allow(unused_assignments)]
-                                            { offset += by };
-                                            prop
-                                        }
-                                };
-                            )?
 
-                            $(
-                                if let Err(err) = $crate::ConsumeSource::mut_consume_lit(&mut unconsumed, &$cons_expr)
-                                    .map(|by| {
-                                        #[allow(unused_assignments)]
-                                        { offset += by };
-                                    }
-                                    )
-                                {
-                                    error.add_causes(err.offset(offset));
-                                    break;
-                                }
-                            )?
-                        )+
+                                $(
+                                    $crate::Render::render_to(&$cons_expr, out);
+                                )?
+                            )+
+                        }
+                    )+
+                    #[allow(unreachable_patterns)]
+                    _ => {}
+                }
+            }
+        }
+    };
+    ( @render $( $rest:tt )* ) => {
+        compile_error!(
+            "Render can only be generated without operators and when the mappings only list the names of properties"
+        );
+    };
 
-                        return Ok(
-                            (
-                                 $crate::consume_enum!(
-                                    @internal
-                                    $enum_name,
-                                    $ident,
-                                    $(
-                                        $( $( $prop_name, )? )?
-                                    )*
-                                    $( => ( $( $prop ),* ) )?
-                                ),
-                                unconsumed
-                            )
-                        );
-                    }
-                )+
+    ( @render_pattern $enum_name:ident, $ident:ident, $( $prop_name:ident, )* => ( $( $tuple_field:ident ),* ) ) => {
+        $enum_name::$ident ( $( $tuple_field ),* )
+    };
+    (
+        @render_pattern $enum_name:ident, $ident:ident, $( $prop_name:ident, )*
+        => { $( $field:ident $( : $field_value:expr )? ),* }
+    ) => {
+        $enum_name::$ident { $( $field: $crate::consume_enum!( @render_field $field $( : $field_value )? ) ),* }
+    };
+    ( @render_pattern $enum_name:ident, $ident:ident, $( $prop_name:ident, )* ) => {
+        $enum_name::$ident { $( $prop_name ),* }
+    };
 
-                Err(error)
+    (
+        @ebnf [ $( $policy:ident )? ] [ $( $generic:ident ),* ] $enum_name:ident [ $( $clause:tt )* ] {
+            $(
+                $ident:ident => [
+                    $(
+                        $( $( $prop_name:ident )?: $cons_type:ty $( [ $separator:expr ] )? $( { $cons_condition:expr } )? )?
+                        $( > $cons_expr:expr )?
+                    ),*
+                    ;
+                    $(
+                        ( $( $prop:expr ),* )
+                    )?
+                    $(
+                        { $( $field:ident $( : $field_value:expr )? ),* $(,)? }
+                    )?
+                ]
+            ),+
+            $(,)?
+        }
+    ) => {
+        impl<$( $generic ),*> $enum_name<$( $generic ),*>
+        where
+            $( $clause )*
+        {
+            /// The grammar that is consumed, as an EBNF rule.
+            pub const EBNF: &str = concat!(
+                stringify!($enum_name),
+                " = ",
+                $crate::consume_enum!(
+                    @ebnf_alternatives
+                    $(
+                        $crate::consume_struct!(
+                            @ebnf_sequence
+                            $( ( $( $cons_type $( , [ $separator ] )? )? $( > $cons_expr )? ) ),*
+                        )
+                    ),+
+                ),
+                " ;"
+            );
+        }
+    };
+    (
+        @ebnf [ $( $policy:ident )? ] [ $( $generic:ident ),* ] $enum_name:ident [ $( $clause:tt )* ] {
+            $(
+                $ident:ident => $( { $( $annotation:tt )* } )? [ $( $body:tt )* ]
+            ),+
+            $(,)?
+        }
+    ) => {
+        impl<$( $generic ),*> $enum_name<$( $generic ),*>
+        where
+            $( $clause )*
+        {
+            /// The grammar that is consumed, as an EBNF rule.
+            pub const EBNF: &str = concat!(
+                stringify!($enum_name),
+                " = ",
+                $crate::consume_enum!(
+                    @ebnf_alternatives
+                    $(
+                        $crate::consume_enum!(
+                            @variant_ebnf $enum_name [ $( $( $annotation )* )? ] [ $( $body )* ]
+                        )
+                    ),+
+                ),
+                " ;"
+            );
+        }
+    };
+
+    (
+        @info [ $( $policy:ident )? ] [ $( $generic:ident ),* ] $enum_name:ident [ $( $clause:tt )* ] {
+            $(
+                $ident:ident => $( { $( $annotation:tt )* } )? [ $( $body:tt )* ]
+            ),+
+            $(,)?
+        }
+    ) => {
+        impl<$( $generic ),*> $crate::info::GrammarInfo for $enum_name<$( $generic ),*>
+        where
+            $( $clause )*
+        {
+            fn grammar_info() -> $crate::info::RuleInfo {
+                $crate::info::RuleInfo::new(
+                    stringify!($enum_name),
+                    vec![
+                        $(
+                            $crate::consume_enum!(
+                                @variant_info $enum_name [ $( $( $annotation )* )? ] [ $( $body )* ]
+                            )
+                        ),+
+                    ],
+                )
             }
         }
     };
 
-    ( @internal $enum_name:ident, $ident:ident, $( $prop_name:ident ),*, => ( $( $prop:expr ),* ) ) => {
-        $enum_name::$ident ( $( $prop ),* )
+    // The items of a single variant, where an operator has its own `enum` as operands.
+    (
+        @variant_info $enum_name:ident [] [
+            $(
+                $( $( $prop_name:ident )?: $cons_type:ty $( [ $separator:expr ] )? $( { $cons_condition:expr } )? )?
+                $( > $cons_expr:expr )?
+            ),*
+            ;
+            $( $mapping:tt )*
+        ]
+    ) => {
+        $crate::consume_struct!(
+            @info_sequence
+            $(
+                (
+                    $( [ $( $prop_name )? ] $cons_type $( , [ $separator ] )? )?
+                    $( > $cons_expr )?
+                )
+            ),*
+        )
     };
-    ( @internal $enum_name:ident, $ident:ident, $( $prop_name:ident ),* ) => {
-        $enum_name::$ident { $( $prop_name ),* }
+    ( @variant_info $enum_name:ident [ $( $annotation:tt )+ ] [ $( $body:tt )* ] ) => {
+        $crate::consume_enum!( @operator_split [ operator_info $enum_name ] [ $( $body )* ] )
+    };
+    (
+        @operator_info $enum_name:ident [ $lhs:ident $rhs:ident ]
+        [
+            $(
+                $( $( $prop_name:ident )?: $cons_type:ty $( [ $separator:expr ] )? $( { $cons_condition:expr } )? )?
+                $( > $cons_expr:expr )?
+            ),+
+        ]
+        [ $( $mapping:tt )* ]
+    ) => {{
+        let operand = |name| $crate::info::GrammarItem::typed(
+            Some(name),
+            stringify!($enum_name),
+            |source| <Self as $crate::Consumable>::consume_from(source).map(|(_, unconsumed)| unconsumed),
+        );
+
+        let mut items = vec![operand(stringify!($lhs))];
+        items.extend($crate::consume_struct!(
+            @info_sequence
+            $(
+                (
+                    $( [ $( $prop_name )? ] $cons_type $( , [ $separator ] )? )?
+                    $( > $cons_expr )?
+                )
+            ),+
+        ));
+        items.push(operand(stringify!($rhs)));
+        items
+    }};
+
+    ( @ebnf_alternatives $first:expr $( , $rest:expr )* ) => {
+        concat!( $first $( , " | ", $rest )* )
+    };
+
+    // Fields with a computed value are not rendered, since they are not part of the `source`.
+    ( @render_field $field:ident ) => {
+        $field
+    };
+    ( @render_field $field:ident : $field_value:expr ) => {
+        _
     };
 }
 
@@ -475,6 +1508,7 @@ mod tests {
         }
 
         #[test]
+        #[allow(clippy::identity_op)]
         fn test_combination_parsing() {
             assert_eq!(
                 Expression::consume_from("+ *\n 123 321 456").unwrap(),
@@ -499,4 +1533,356 @@ mod tests {
             );
         }
     }
+
+    mod generics {
+        use crate::chars;
+        use crate::Consumable;
+
+        #[derive(Debug, PartialEq)]
+        enum Pair<L, R> {
+            Both(L, R),
+            Left(L),
+        }
+
+        consume_enum!(
+            Pair<L, R> where L: Consumable, R: Consumable + std::fmt::Debug {
+                Both => [
+                    left: L,
+                    : chars::Comma,
+                    right: R;
+                    (left, right)
+                ],
+                Left => [
+                    left: L;
+                    (left)
+                ]
+            }
+        );
+
+        #[test]
+        fn test_generic_parsing() {
+            assert_eq!(
+                <Pair<u8, char>>::consume_from("1,x").unwrap(),
+                (Pair::Both(1, 'x'), "")
+            );
+            assert_eq!(
+                <Pair<u8, char>>::consume_from("1;x").unwrap(),
+                (Pair::Left(1), ";x")
+            );
+            assert!(<Pair<u8, char>>::consume_from("x").is_err());
+        }
+    }
+
+    mod separated_lists {
+        use crate::chars;
+        use crate::Consumable;
+
+        #[derive(Debug, PartialEq)]
+        enum Set<T> {
+            Items(Vec<T>),
+            Pairs { keys: Vec<char>, values: Vec<T> },
+        }
+
+        consume_enum!(
+            Set<T> where T: Consumable {
+                Pairs => [
+                    > '{',
+                    keys: char [ '|' ],
+                    : chars::Colon,
+                    values: T [ '|' ],
+                    > '}';
+                ],
+                Items => [
+                    > '{',
+                    items: T [ ',' ],
+                    > '}';
+                    (items)
+                ]
+            }
+        );
+
+        #[test]
+        fn test_separated_list_parsing() {
+            assert_eq!(
+                <Set<u8>>::consume_from("{1,2,3}").unwrap(),
+                (Set::Items(vec![1, 2, 3]), "")
+            );
+            assert_eq!(
+                <Set<u8>>::consume_from("{}").unwrap(),
+                (Set::Items(vec![]), "")
+            );
+            assert_eq!(
+                <Set<u8>>::consume_from("{a|b:1|2}").unwrap(),
+                (
+                    Set::Pairs {
+                        keys: vec!['a', 'b'],
+                        values: vec![1, 2]
+                    },
+                    ""
+                )
+            );
+            assert!(<Set<u8>>::consume_from("{1,2,}").is_err());
+        }
+    }
+
+    mod named_mappings {
+        use crate::Consumable;
+
+        #[derive(Debug, PartialEq)]
+        enum Range {
+            Between { from: u32, to: u32 },
+            Single { value: u32, exact: bool },
+            Empty {},
+        }
+
+        consume_enum!(
+            Range {
+                Between => [
+                    from: u32,
+                    > "..",
+                    to: u32;
+                ],
+                Single => [
+                    > '=',
+                    value: u32;
+                    { value, exact: true, }
+                ],
+                Empty => [
+                    > "..";
+                    {}
+                ]
+            }
+        );
+
+        #[test]
+        fn test_named_mapping_parsing() {
+            assert_eq!(
+                Range::consume_from("1..5").unwrap(),
+                (Range::Between { from: 1, to: 5 }, "")
+            );
+            assert_eq!(
+                Range::consume_from("=3").unwrap(),
+                (
+                    Range::Single {
+                        value: 3,
+                        exact: true
+                    },
+                    ""
+                )
+            );
+            assert_eq!(Range::consume_from("..").unwrap(), (Range::Empty {}, ""));
+        }
+    }
+
+    mod grammar {
+        use crate::chars;
+        use crate::Consumable;
+
+        #[allow(dead_code)]
+        enum Pair<L, R> {
+            Both(L, Vec<R>),
+            Left(L),
+        }
+
+        consume_enum!(
+            #[ebnf]
+            Pair<L, R> where L: Consumable, R: Consumable {
+                Both => [
+                    left: L,
+                    : chars::Comma,
+                    right: R [ ';' ];
+                    (left, right)
+                ],
+                Left => [
+                    left: L;
+                    (left)
+                ]
+            }
+        );
+
+        #[test]
+        fn test_ebnf() {
+            assert_eq!(
+                <Pair<u8, char>>::EBNF,
+                "Pair = L , chars::Comma , [ R , { ';' , R } ] | L ;"
+            );
+            assert!(<Pair<u8, char>>::consume_from("1,").is_ok());
+        }
+    }
+
+    mod merge {
+        use crate::ConsumeErrorType::*;
+        use crate::{Consumable, MergePolicy};
+
+        #[allow(dead_code)]
+        enum Statement {
+            Assign(char, u32),
+            Call(char),
+        }
+
+        consume_enum!(
+            Statement {
+                Assign => [ name: char, > '=', value: u32; (name, value) ],
+                Call => [ name: char, > "()"; (name) ]
+            }
+        );
+
+        #[derive(Debug)]
+        enum DeepestStatement {
+            Assign(char, u32),
+            Call(char),
+        }
+
+        consume_enum!(
+            #[merge(Deepest)]
+            #[render]
+            DeepestStatement {
+                Assign => [ name: char, > '=', value: u32; (name, value) ],
+                Call => [ name: char, > "()"; (name) ]
+            }
+        );
+
+        #[test]
+        fn test_merge_policy() {
+            let causes =
+                |result: Result<(), crate::ConsumeError>| result.unwrap_err().into_causes();
+
+            assert_eq!(
+                causes(Statement::consume_from("x=").map(|_| ())),
+                vec![
+                    InsufficientTokens { index: 2 },
+                    UnexpectedToken {
+                        index: 1,
+                        token: '='
+                    }
+                ]
+            );
+            assert_eq!(
+                causes(DeepestStatement::consume_from("x=").map(|_| ())),
+                vec![InsufficientTokens { index: 2 }]
+            );
+            assert_eq!(
+                causes(MergePolicy::First.scope(|| Statement::consume_from("x=").map(|_| ()))),
+                vec![InsufficientTokens { index: 2 }]
+            );
+            assert_eq!(
+                causes(MergePolicy::First.scope(|| Statement::consume_from("x(").map(|_| ()))),
+                vec![UnexpectedToken {
+                    index: 1,
+                    token: '('
+                }]
+            );
+            assert_eq!(MergePolicy::current(), MergePolicy::All);
+        }
+    }
+
+    mod operators {
+        use crate::error::ConsumeErrorType::*;
+        use crate::Consumable;
+
+        #[derive(Debug, PartialEq)]
+        enum Expr {
+            Sum { left: Box<Expr>, right: Box<Expr> },
+            Product(Box<Expr>, Box<Expr>),
+            Compare(Box<Expr>, char, Box<Expr>),
+            Assign(Box<Expr>, Box<Expr>),
+            Group(Box<Expr>),
+            Num(u32),
+        }
+
+        consume_enum!(
+            #[ebnf]
+            Expr {
+                Sum => { left_assoc, precedence = 2 } [
+                    left: Self, > '+', right: Self;
+                    { left: Box::new(left), right: Box::new(right) }
+                ],
+                Product => { left_assoc, precedence = 3 } [
+                    left: Self, > '*', right: Self;
+                    (Box::new(left), Box::new(right))
+                ],
+                Compare => { left_assoc, precedence = 1 } [
+                    left: Self, op: char { |c| c == '<' || c == '>' }, right: Self;
+                    (Box::new(left), op, Box::new(right))
+                ],
+                Assign => { right_assoc, precedence = 0 } [
+                    left: Self, > '=', right: Self;
+                    (Box::new(left), Box::new(right))
+                ],
+                Group => [ > '(', inner: Expr, > ')'; (Box::new(inner)) ],
+                Num => [ value: u32; (value) ]
+            }
+        );
+
+        fn render(expr: &Expr) -> String {
+            match expr {
+                Expr::Sum { left, right } => format!("({}+{})", render(left), render(right)),
+                Expr::Product(left, right) => format!("({}*{})", render(left), render(right)),
+                Expr::Compare(left, op, right) => {
+                    format!("({}{}{})", render(left), op, render(right))
+                }
+                Expr::Assign(left, right) => format!("({}={})", render(left), render(right)),
+                Expr::Group(inner) => render(inner),
+                Expr::Num(value) => value.to_string(),
+            }
+        }
+
+        #[test]
+        fn test_operator_precedence() {
+            let parse = |source| Expr::consume_all(source).map(|expr| render(&expr));
+
+            assert_eq!(parse("1"), Ok("1".to_string()));
+            assert_eq!(parse("1+2*3+4"), Ok("((1+(2*3))+4)".to_string()));
+            assert_eq!(parse("1*(2+3)*4"), Ok("((1*(2+3))*4)".to_string()));
+            assert_eq!(parse("1<2+3>4"), Ok("((1<(2+3))>4)".to_string()));
+            assert_eq!(parse("1=2=3+4"), Ok("(1=(2=(3+4)))".to_string()));
+            assert_eq!(parse("1*2=3<4"), Ok("((1*2)=(3<4))".to_string()));
+        }
+
+        #[test]
+        fn test_operator_errors() {
+            assert_eq!(
+                Expr::consume_from("1+2-3").map(|(expr, unconsumed)| (render(&expr), unconsumed)),
+                Ok(("(1+2)".to_string(), "-3"))
+            );
+            assert_eq!(
+                Expr::consume_from("1+2*").map(|(expr, unconsumed)| (render(&expr), unconsumed)),
+                Ok(("(1+2)".to_string(), "*"))
+            );
+            assert_eq!(
+                Expr::consume_from("1+x").map(|(expr, unconsumed)| (render(&expr), unconsumed)),
+                Ok(("1".to_string(), "+x"))
+            );
+            assert_eq!(
+                Expr::consume_from("1+)").map(|(expr, unconsumed)| (render(&expr), unconsumed)),
+                Ok(("1".to_string(), "+)"))
+            );
+            assert_eq!(
+                <(Expr, crate::chars::Plus)>::consume_from("1+")
+                    .map(|((expr, _), unconsumed)| (render(&expr), unconsumed)),
+                Ok(("1".to_string(), ""))
+            );
+            assert_eq!(
+                Expr::consume_from("x")
+                    .map(|_| ())
+                    .unwrap_err()
+                    .into_causes(),
+                vec![
+                    UnexpectedToken {
+                        index: 0,
+                        token: 'x'
+                    },
+                    UnexpectedToken {
+                        index: 0,
+                        token: 'x'
+                    }
+                ]
+            );
+            assert_eq!(
+                Expr::EBNF,
+                "Expr = Expr , '+' , Expr | Expr , '*' , Expr | Expr , char , Expr | \
+                 Expr , '=' , Expr | '(' , Expr , ')' | u32 ;"
+            );
+        }
+    }
 }
@@ -1,3 +1,5 @@
+use std::cell::Cell;
+use std::fmt;
 use thiserror::Error;
 
 /// One item in [`ConsumeError`]. these can occur while consuming
@@ -6,7 +8,7 @@ use thiserror::Error;
 /// Multiple instances of this type can occur during one parsing.
 /// Especially, multiple instance of these error occur,
 /// when using `enum`'s or using the `Either<L, R>` struct.
-#[derive(Error, Debug, PartialEq, Clone, Copy)]
+#[derive(Error, Debug, PartialEq, Eq, Clone, Copy, Hash)]
 pub enum ConsumeErrorType {
     /// An error varient which occurs when while consuming more tokens
     /// where expected, but none were found.
@@ -38,30 +40,254 @@ pub enum ConsumeErrorType {
         /// formed.
         index: usize,
     },
+
+    /// An error varient which occurs when consuming exceeds one of the
+    /// [`Limits`][crate::limits::Limits].
+    #[error("Exceeded the {limit} limit at index `{index}`!")]
+    LimitExceeded {
+        /// The utf-8 character index within the `source` at which the limit was exceeded.
+        index: usize,
+        /// The limit which was exceeded.
+        limit: crate::limits::Limit,
+    },
+
+    /// An error varient which occurs when a delimiter, such as a bracket, was opened but no
+    /// matching closing delimiter was found.
+    #[error("Expected a closing delimiter at index `{index}` for the unclosed `{token}` opened at index `{opened_at}`!")]
+    UnclosedDelimiter {
+        /// The utf-8 character index within the `source` at which the closing delimiter was
+        /// expected.
+        index: usize,
+        /// The utf-8 character index within the `source` at which the delimiter was opened.
+        opened_at: usize,
+        /// The utf-8 character which opened the delimiter.
+        token: char,
+    },
+
+    /// An error varient which occurs when fewer items were consumed than a repetition, such as
+    /// [`Exactly`][crate::common::Exactly], requires.
+    #[error(
+        "Expected {required} items but found {found}, the next item failed at index `{index}`!"
+    )]
+    MissingItems {
+        /// The utf-8 character index within the `source` at which the next item was expected.
+        index: usize,
+        /// The amount of items that were consumed.
+        found: usize,
+        /// The amount of items that were required.
+        required: usize,
+    },
+
+    /// An error varient which occurs when a format string, such as the one of
+    /// [`scan`][crate::scan!], is malformed or has a different amount of placeholders than values
+    /// are consumed.
+    #[error("The format string could not be followed at index `{index}`!")]
+    InvalidFormat {
+        /// The utf-8 character index within the `source` at which the format string could not be
+        /// followed.
+        index: usize,
+    },
+}
+
+/// A human-readable description of what a [`Consumable`][crate::Consumable] consumes, such as
+/// "an integer" or "the keyword `let`".
+///
+/// This is returned by [`Consumable::expecting`][crate::Consumable::expecting] and is added to the
+/// errors of [`consume_enum`][crate::consume_enum] for every alternative that failed.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{Consumable, Expectation};
+///
+/// assert_eq!(u32::expecting(), Expectation::new("an integer"));
+/// assert_eq!(u32::expecting().to_string(), "an integer");
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Expectation(&'static str);
+
+impl Expectation {
+    /// Create an expectation with a `description`.
+    pub const fn new(description: &'static str) -> Self {
+        Expectation(description)
+    }
+
+    /// Get the description of the expectation.
+    pub fn description(&self) -> &'static str {
+        self.0
+    }
+}
+
+impl fmt::Display for Expectation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
 }
 
 /// A list of errors that occured while consuming from a `source`.
-#[derive(Debug, PartialEq)]
+///
+/// Next to its causes, an error can contain the [`Expectation`]s of what was expected instead and
+/// the origin of every cause, see [`tagged_causes`][ConsumeError::tagged_causes]. These are extra
+/// information and are therefore not compared or hashed when comparing or hashing errors.
+#[derive(Debug, Default, Clone)]
 pub struct ConsumeError {
     causes: Vec<ConsumeErrorType>,
+    #[cfg(feature = "origin")]
+    origins: Vec<Option<&'static str>>,
+    severities: Vec<Severity>,
+    expected: Vec<Expectation>,
+}
+
+/// How important a cause of a [`ConsumeError`] is.
+///
+/// Causes are errors by default. Secondary information, such as where a delimiter was opened, can
+/// be added as a note with [`ConsumeError::add_note`]. Notes are never picked as the
+/// [`primary`][ConsumeError::primary] cause while there are errors, and are prefixed with `note: `
+/// when displaying the [`ConsumeError`].
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Severity {
+    /// A cause of the failure.
+    #[default]
+    Error,
+    /// Extra information about the failure.
+    Note,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Error => "error",
+            Severity::Note => "note",
+        })
+    }
+}
+
+/// The strategy used to combine the errors of alternatives when all of them fail.
+///
+/// The policy used by [`Either`][either::Either] and [`consume_enum`][crate::consume_enum] is the
+/// [`current`][MergePolicy::current] one, which can be changed for the duration of a closure with
+/// [`scope`][MergePolicy::scope]. A single `enum` can also pick its own policy with the
+/// `#[merge(...)]` option of [`consume_enum`][crate::consume_enum].
+///
+/// # Examples
+///
+/// ```
+/// use manger::{ Consumable, ConsumeErrorType::*, MergePolicy };
+///
+/// type Pair = either::Either<(char, char), u32>;
+///
+/// let error = Pair::consume_from("a").map(|_| ()).unwrap_err();
+/// assert_eq!(error.causes().len(), 2);
+///
+/// let error = MergePolicy::Deepest.scope(|| Pair::consume_from("a").map(|_| ()).unwrap_err());
+/// assert_eq!(error.into_causes(), vec![InsufficientTokens { index: 1 }]);
+/// ```
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum MergePolicy {
+    /// Keep the causes of every alternative.
+    #[default]
+    All,
+    /// Keep only the causes of the alternatives that got the furthest into the `source`.
+    Deepest,
+    /// Keep only the causes of the first alternative.
+    First,
+}
+
+thread_local! {
+    /// The policy registered with `MergePolicy::scope`.
+    static POLICY: Cell<MergePolicy> = const { Cell::new(MergePolicy::All) };
+}
+
+/// Restores the previous policy, also when unwinding.
+struct PolicyGuard(MergePolicy);
+
+impl Drop for PolicyGuard {
+    fn drop(&mut self) {
+        POLICY.with(|policy| policy.set(self.0));
+    }
+}
+
+impl MergePolicy {
+    /// Get the policy of the current thread, which is [`All`][MergePolicy::All] outside of
+    /// [`scope`][MergePolicy::scope].
+    pub fn current() -> MergePolicy {
+        POLICY.with(Cell::get)
+    }
+
+    /// Use this policy on the current thread for the duration of `f`.
+    pub fn scope<R>(self, f: impl FnOnce() -> R) -> R {
+        let _guard = PolicyGuard(POLICY.with(|policy| policy.replace(self)));
+
+        f()
+    }
+}
+
+/// A cause of a [`ConsumeError`] together with the type that produced it.
+///
+/// Returned by [`ConsumeError::tagged_causes`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct TaggedCause<'a> {
+    cause: &'a ConsumeErrorType,
+    origin: Option<&'static str>,
+    severity: Severity,
+}
+
+impl<'a> TaggedCause<'a> {
+    /// Get the cause.
+    pub fn cause(&self) -> &'a ConsumeErrorType {
+        self.cause
+    }
+
+    /// Get the name of the type, as given by [`std::any::type_name`], that produced the cause.
+    ///
+    /// Causes are tagged by the [`Consumable`][crate::Consumable] implementations generated by
+    /// [`consume_struct`][crate::consume_struct] and [`consume_enum`][crate::consume_enum].
+    /// The innermost generated implementation tags a cause. This returns `None` for causes
+    /// outside of generated implementations and when the `origin` feature is disabled.
+    pub fn origin(&self) -> Option<&'static str> {
+        self.origin
+    }
+
+    /// Get the severity of the cause.
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+}
+
+impl PartialEq for ConsumeError {
+    fn eq(&self, other: &Self) -> bool {
+        self.causes == other.causes
+    }
+}
+
+impl Eq for ConsumeError {}
+
+impl std::hash::Hash for ConsumeError {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.causes.hash(state);
+    }
 }
 
 impl ConsumeError {
     /// Create a new empty `ConsumeError`.
     pub fn new() -> ConsumeError {
-        ConsumeError { causes: Vec::new() }
+        ConsumeError::default()
     }
 
     /// Create a new `ConsumeError` containing only `cause`.
     pub fn new_with(cause: ConsumeErrorType) -> ConsumeError {
-        ConsumeError {
-            causes: vec![cause],
-        }
+        ConsumeError::new_from(vec![cause])
     }
 
     /// Create a new `ConsumeError` containing `causes`.
     pub fn new_from(causes: Vec<ConsumeErrorType>) -> ConsumeError {
-        ConsumeError { causes }
+        ConsumeError {
+            #[cfg(feature = "origin")]
+            origins: vec![None; causes.len()],
+            severities: vec![Severity::Error; causes.len()],
+            causes,
+            expected: Vec::new(),
+        }
     }
 
     /// Mutate all the errors to move the utf-8 character index at which they were caused by `by`.
@@ -92,6 +318,83 @@ impl ConsumeError {
         self
     }
 
+    /// Mutate all the errors to move the utf-8 character index at which they were caused to the
+    /// index returned by `f`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use manger::{ ConsumeError, ConsumeErrorType::* };
+    /// assert_eq!(
+    ///     ConsumeError::new_with(InvalidValue { index: 3 }).map_indices(|index| index * 2),
+    ///     ConsumeError::new_with(InvalidValue { index: 6 })
+    /// );
+    /// ```
+    pub fn map_indices(mut self, f: impl Fn(usize) -> usize) -> Self {
+        self.causes
+            .iter_mut()
+            .for_each(|cause| *cause = cause.map_index(&f));
+        self
+    }
+
+    /// Sort the causes of this error into a deterministic order.
+    ///
+    /// The order in which causes are added depends on how the [`Consumable`][crate::Consumable]
+    /// implementations try their alternatives, which can change without changing which causes
+    /// there are. After sorting, the causes are ordered by their index, from the start of the
+    /// `source` onwards. Causes at the same index are ordered by their kind, in the same order
+    /// that is used to pick the [`primary`][ConsumeError::primary] cause: malformed formats,
+    /// exceeded limits, unclosed delimiters, missing items, invalid values, unexpected tokens and
+    /// insufficient tokens. Causes of the same kind at the same index are ordered by their message.
+    ///
+    /// The severity and origin of every cause move along with it. This order is part of the
+    /// stability guarantees of this crate, so it can be relied upon in snapshot tests, see
+    /// [`assert_error_snapshot`][crate::assert_error_snapshot].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use manger::{ ConsumeError, ConsumeErrorType::* };
+    ///
+    /// let error = ConsumeError::new_from(vec![
+    ///     InsufficientTokens { index: 2 },
+    ///     UnexpectedToken { index: 0, token: 'b' },
+    ///     InvalidValue { index: 2 },
+    ///     InvalidFormat { index: 2 },
+    /// ]);
+    ///
+    /// assert_eq!(
+    ///     error.sorted().into_causes(),
+    ///     vec![
+    ///         UnexpectedToken { index: 0, token: 'b' },
+    ///         InvalidFormat { index: 2 },
+    ///         InvalidValue { index: 2 },
+    ///         InsufficientTokens { index: 2 },
+    ///     ]
+    /// );
+    /// ```
+    pub fn sorted(mut self) -> Self {
+        let mut order = (0..self.causes.len()).collect::<Vec<_>>();
+        order.sort_by_cached_key(|&index| {
+            let cause = &self.causes[index];
+            (
+                *cause.index(),
+                std::cmp::Reverse(cause.priority()),
+                cause.to_string(),
+            )
+        });
+
+        self.causes = order.iter().map(|&index| self.causes[index]).collect();
+        self.severities = order.iter().map(|&index| self.severities[index]).collect();
+
+        #[cfg(feature = "origin")]
+        {
+            self.origins = order.iter().map(|&index| self.origins[index]).collect();
+        }
+
+        self
+    }
+
     /// Fetch a vector of the causes of this error.
     ///
     /// This consume ownership of the error.
@@ -107,14 +410,251 @@ impl ConsumeError {
     /// Pushes an extra cause for this error.
     pub fn add_cause(&mut self, cause: ConsumeErrorType) {
         self.causes.push(cause);
+        self.severities.push(Severity::Error);
+
+        #[cfg(feature = "origin")]
+        self.origins.push(None);
     }
 
-    /// Pushes all the causes for `other_err` for this error.
+    /// Pushes an extra cause with the [`Note`][Severity::Note] severity for this error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use manger::{ ConsumeError, ConsumeErrorType::*, Severity };
+    ///
+    /// let mut error = ConsumeError::new_with(InsufficientTokens { index: 4 });
+    /// error.add_note(UnexpectedToken { index: 0, token: '(' });
+    ///
+    /// assert_eq!(error.tagged_causes()[1].severity(), Severity::Note);
+    /// assert_eq!(
+    ///     error.to_string(),
+    ///     "Expected more tokens at index `4` but found none!\n\
+    ///      note: Found the token `(` at index `0`, which is unexpected!"
+    /// );
+    /// ```
+    pub fn add_note(&mut self, cause: ConsumeErrorType) {
+        self.causes.push(cause);
+        self.severities.push(Severity::Note);
+
+        #[cfg(feature = "origin")]
+        self.origins.push(None);
+    }
+
+    /// Fetch the cause that best describes why consuming failed.
+    ///
+    /// This is the error, or the note when there are no errors, with the largest index, since
+    /// that cause got the furthest into the `source`. Among causes at the same index, the kind of
+    /// cause decides, where malformed formats come first, followed by exceeded limits, unclosed
+    /// delimiters, missing items, invalid values, unexpected tokens and insufficient tokens.
+    /// Otherwise, the first cause is picked.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use manger::{ Consumable, ConsumeError, ConsumeErrorType::* };
+    /// use manger::limits::Limit;
+    ///
+    /// let error = <either::Either<(char, char), u32>>::consume_from("a")
+    ///     .map(|_| ())
+    ///     .unwrap_err();
+    ///
+    /// assert_eq!(error.causes().len(), 2);
+    /// assert_eq!(error.primary(), Some(&InsufficientTokens { index: 1 }));
+    ///
+    /// let error = ConsumeError::new_from(vec![
+    ///     LimitExceeded { index: 3, limit: Limit::Depth },
+    ///     InvalidFormat { index: 3 },
+    /// ]);
+    /// assert_eq!(error.primary(), Some(&InvalidFormat { index: 3 }));
+    /// ```
+    pub fn primary(&self) -> Option<&ConsumeErrorType> {
+        let has_errors = self.severities.contains(&Severity::Error);
+
+        self.causes
+            .iter()
+            .zip(&self.severities)
+            .filter(|(_, severity)| !has_errors || **severity == Severity::Error)
+            .rev()
+            .max_by_key(|(cause, _)| (*cause.index(), cause.priority()))
+            .map(|(cause, _)| cause)
+    }
+
+    /// Pushes all the causes, with their origins, and expectations for `other_err` for this error.
     pub fn add_causes(&mut self, other_err: ConsumeError) {
         other_err
-            .into_causes()
-            .into_iter()
-            .for_each(|cause| self.add_cause(cause));
+            .expected
+            .iter()
+            .for_each(|expectation| self.add_expectation(*expectation));
+        self.causes.extend(other_err.causes);
+        self.severities.extend(other_err.severities);
+
+        #[cfg(feature = "origin")]
+        self.origins.extend(other_err.origins);
+    }
+
+    /// Merges the error of a failed alternative into this error according to `policy`.
+    ///
+    /// This is used by [`Either`][either::Either] and the implementations generated by
+    /// [`consume_enum`][crate::consume_enum] to combine the errors of all alternatives.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use manger::{ ConsumeError, ConsumeErrorType::*, MergePolicy };
+    ///
+    /// let mut error = ConsumeError::new();
+    /// error.add_alternative(ConsumeError::new_with(InvalidValue { index: 1 }), MergePolicy::Deepest);
+    /// error.add_alternative(ConsumeError::new_with(InvalidValue { index: 3 }), MergePolicy::Deepest);
+    /// error.add_alternative(ConsumeError::new_with(InvalidValue { index: 2 }), MergePolicy::Deepest);
+    ///
+    /// assert_eq!(error, ConsumeError::new_with(InvalidValue { index: 3 }));
+    /// ```
+    pub fn add_alternative(&mut self, other_err: ConsumeError, policy: MergePolicy) {
+        match policy {
+            MergePolicy::All => self.add_causes(other_err),
+            MergePolicy::First => {
+                if self.causes.is_empty() {
+                    self.add_causes(other_err);
+                }
+            }
+            MergePolicy::Deepest => match (self.depth(), other_err.depth()) {
+                (_, None) => {}
+                (Some(depth), Some(other_depth)) if depth > other_depth => {}
+                (Some(depth), Some(other_depth)) if depth == other_depth => {
+                    self.add_causes(other_err)
+                }
+                _ => *self = other_err,
+            },
+        }
+    }
+
+    /// The largest index of the causes, or `None` when there are no causes.
+    fn depth(&self) -> Option<usize> {
+        self.causes.iter().map(|cause| *cause.index()).max()
+    }
+
+    /// Fetch a vector of the causes of this error together with their origin.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use manger::{consume_struct, Consumable};
+    ///
+    /// struct Point(u32, u32);
+    /// consume_struct!(Point => [x: u32, > ',', y: u32; (x, y)]);
+    ///
+    /// let error = <(char, Point)>::consume_from("a1;2").map(|_| ()).unwrap_err();
+    /// let cause = error.tagged_causes()[0];
+    ///
+    /// assert_eq!(*cause.cause().index(), 2);
+    /// # #[cfg(feature = "origin")]
+    /// assert!(cause.origin().unwrap().ends_with("Point"));
+    /// ```
+    pub fn tagged_causes(&self) -> Vec<TaggedCause<'_>> {
+        self.causes
+            .iter()
+            .enumerate()
+            .map(|(index, cause)| TaggedCause {
+                cause,
+                origin: self.origin_of(index),
+                severity: self.severities[index],
+            })
+            .collect()
+    }
+
+    #[cfg(feature = "origin")]
+    fn origin_of(&self, index: usize) -> Option<&'static str> {
+        self.origins[index]
+    }
+
+    #[cfg(not(feature = "origin"))]
+    fn origin_of(&self, _: usize) -> Option<&'static str> {
+        None
+    }
+
+    /// Tag all causes of this error that have no origin yet with `origin`.
+    ///
+    /// This does nothing when the `origin` feature is disabled.
+    #[cfg_attr(not(feature = "origin"), allow(unused_mut, unused_variables))]
+    pub fn tag_origin(mut self, origin: &'static str) -> Self {
+        #[cfg(feature = "origin")]
+        self.origins
+            .iter_mut()
+            .filter(|tag| tag.is_none())
+            .for_each(|tag| *tag = Some(origin));
+
+        self
+    }
+
+    /// Fetch the descriptions of what was expected instead, without duplicates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use manger::{consume_enum, Consumable, Expectation};
+    ///
+    /// enum Literal {
+    ///     Number(u32),
+    ///     Character(char),
+    /// }
+    /// consume_enum!(
+    ///     Literal {
+    ///         Number => [ > '#', value: u32; (value) ],
+    ///         Character => [ > '\'', value: char, > '\''; (value) ]
+    ///     }
+    /// );
+    ///
+    /// let error = Literal::consume_from("x").map(|_| ()).unwrap_err();
+    ///
+    /// assert_eq!(
+    ///     error.expected(),
+    ///     &[Expectation::new("'#' , u32"), Expectation::new("'\\'' , char , '\\''")]
+    /// );
+    /// assert_eq!(
+    ///     error.to_string(),
+    ///     "Found the token `x` at index `0`, which is unexpected!\n\
+    ///      Found the token `x` at index `0`, which is unexpected!\n\
+    ///      Expected one of: '#' , u32 | '\\'' , char , '\\''"
+    /// );
+    /// ```
+    pub fn expected(&self) -> &[Expectation] {
+        &self.expected
+    }
+
+    /// Pushes an extra expectation for this error, unless it is already present.
+    pub fn add_expectation(&mut self, expectation: Expectation) {
+        if !self.expected.contains(&expectation) {
+            self.expected.push(expectation);
+        }
+    }
+}
+
+/// Shows every cause on its own line, where notes are prefixed with `note: `, followed by the
+/// expectations.
+impl fmt::Display for ConsumeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut lines = self
+            .causes
+            .iter()
+            .zip(&self.severities)
+            .map(|(cause, severity)| match severity {
+                Severity::Error => cause.to_string(),
+                Severity::Note => format!("{}: {}", severity, cause),
+            })
+            .collect::<Vec<_>>();
+
+        if !self.expected.is_empty() {
+            let expected = self
+                .expected
+                .iter()
+                .map(|expectation| expectation.description())
+                .collect::<Vec<_>>();
+
+            lines.push(format!("Expected one of: {}", expected.join(" | ")));
+        }
+
+        f.write_str(&lines.join("\n"))
     }
 }
 
@@ -127,6 +667,25 @@ impl ConsumeErrorType {
             InsufficientTokens { index } => index,
             UnexpectedToken { index, token: _ } => index,
             InvalidValue { index } => index,
+            LimitExceeded { index, limit: _ } => index,
+            UnclosedDelimiter { index, .. } => index,
+            MissingItems { index, .. } => index,
+            InvalidFormat { index } => index,
+        }
+    }
+
+    /// The priority of the kind of cause when picking the [`primary`][ConsumeError::primary] cause.
+    fn priority(&self) -> u8 {
+        use ConsumeErrorType::*;
+
+        match self {
+            InsufficientTokens { .. } => 0,
+            UnexpectedToken { .. } => 1,
+            InvalidValue { .. } => 2,
+            MissingItems { .. } => 3,
+            UnclosedDelimiter { .. } => 4,
+            LimitExceeded { .. } => 5,
+            InvalidFormat { .. } => 6,
         }
     }
 
@@ -142,15 +701,55 @@ impl ConsumeErrorType {
     /// );
     /// ```
     pub fn offset(self, by: usize) -> Self {
+        self.map_index(|index| index + by)
+    }
+
+    /// Mutate self to move the utf-8 character index at which it was caused to the index returned
+    /// by `f`. Other indices within self, such as the index at which a delimiter was opened, are
+    /// moved as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use manger::ConsumeErrorType::*;
+    /// assert_eq!(
+    ///     InsufficientTokens { index: 5 }.map_index(|index| index - 1),
+    ///     InsufficientTokens { index: 4 },
+    /// );
+    /// ```
+    pub fn map_index(self, f: impl Fn(usize) -> usize) -> Self {
         use ConsumeErrorType::*;
 
         match self {
-            InsufficientTokens { index } => InsufficientTokens { index: index + by },
+            InsufficientTokens { index } => InsufficientTokens { index: f(index) },
             UnexpectedToken { index, token } => UnexpectedToken {
-                index: index + by,
+                index: f(index),
+                token,
+            },
+            InvalidValue { index } => InvalidValue { index: f(index) },
+            LimitExceeded { index, limit } => LimitExceeded {
+                index: f(index),
+                limit,
+            },
+            UnclosedDelimiter {
+                index,
+                opened_at,
+                token,
+            } => UnclosedDelimiter {
+                index: f(index),
+                opened_at: f(opened_at),
                 token,
             },
-            InvalidValue { index } => InvalidValue { index: index + by },
+            MissingItems {
+                index,
+                found,
+                required,
+            } => MissingItems {
+                index: f(index),
+                found,
+                required,
+            },
+            InvalidFormat { index } => InvalidFormat { index: f(index) },
         }
     }
 }
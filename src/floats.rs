@@ -1,77 +1,262 @@
-use crate::chars;
-use crate::common::{Digit, OneOrMore, Sign};
 use crate::error::ConsumeError;
 use crate::error::ConsumeErrorType::*;
-use crate::{consume_enum, Consumable};
-use std::str::FromStr;
+use crate::{Consumable, Expectation};
 
-enum FloatNumberStruct {
-    NoPeriod,
-    PeriodStart,
-    PeriodMiddle,
+/// The span of a decimal number in the form of `[+-]digits[.digits][(e|E)[+-]digits]`, scanned
+/// in one pass.
+struct Decimal {
+    /// The byte length of the number, which equals its length in utf-8 characters.
+    length: usize,
+    /// The utf-8 character index of the exponent marker, if there is one.
+    exponent_index: Option<usize>,
+    /// The utf-8 character index of the last digit before the period.
+    last_integer_index: usize,
+    /// The utf-8 character index of the first digit before the period which makes the number
+    /// larger than the maximum, if there is one.
+    overflow_index: Option<usize>,
 }
 
-consume_enum!(
-    FloatNumberStruct {
-        PeriodStart => [
-            : chars::Period,
-            : OneOrMore<Digit>;
-        ],
-        PeriodMiddle => [
-            : OneOrMore<Digit>,
-            : chars::Period,
-            : OneOrMore<Digit>;
-        ],
-        NoPeriod => [
-            : OneOrMore<Digit>;
-        ]
-    }
-);
+impl Decimal {
+    /// Scan the decimal number at the start of `source`, where `max` is the largest finite value.
+    ///
+    /// A period is only part of the number if it is followed by a digit and an exponent is only
+    /// part of the number if it is followed by digits.
+    fn scan(source: &str, max: f64) -> Result<Decimal, ConsumeError> {
+        let bytes = source.as_bytes();
+        let digit_at = |index: usize| {
+            bytes
+                .get(index)
+                .filter(|byte| byte.is_ascii_digit())
+                .map(|byte| byte - b'0')
+        };
 
-enum FloatStructure {
-    Float,
-    Infinity,
-    NaN,
-}
+        let mut decimal = Decimal {
+            length: 0,
+            exponent_index: None,
+            last_integer_index: 0,
+            overflow_index: None,
+        };
+        let mut index = 0;
+        let mut estimate = 0f64;
+
+        if let Some(b'-' | b'+') = bytes.first() {
+            index += 1;
+        }
+
+        let integer_start = index;
+        while let Some(digit) = digit_at(index) {
+            estimate = estimate * 10.0 + f64::from(digit);
+            if estimate > max && decimal.overflow_index.is_none() {
+                decimal.overflow_index = Some(index);
+            }
+
+            decimal.last_integer_index = index;
+            index += 1;
+        }
+        let has_integer = index > integer_start;
+
+        if bytes.get(index) == Some(&b'.') && digit_at(index + 1).is_some() {
+            index += 1;
+
+            while digit_at(index).is_some() {
+                index += 1;
+            }
+        } else if !has_integer {
+            // A digit was expected after the sign, or after a period without an integer part.
+            let expected = if bytes.get(index) == Some(&b'.') {
+                index + 1
+            } else {
+                index
+            };
 
-use crate::chars::alpha;
-consume_enum!(
-    FloatStructure {
-        Float => [
-            : Sign,
-            : FloatNumberStruct,
-            : Option<(alpha::E, OneOrMore<Digit>)>;
-        ],
-        Infinity => [
-            : Sign,
-            : alpha::I,
-            : alpha::N,
-            : alpha::F,
-            : alpha::I,
-            : alpha::N,
-            : alpha::I,
-            : alpha::T,
-            : alpha::Y;
-        ],
-        NaN => [
-            : alpha::N,
-            : alpha::A,
-            : alpha::N;
-        ]
+            return Err(ConsumeError::new_with(
+                match source[expected.min(source.len())..].chars().next() {
+                    Some(token) => UnexpectedToken {
+                        index: expected,
+                        token,
+                    },
+                    None => InsufficientTokens { index: expected },
+                },
+            ));
+        }
+
+        if let Some(b'e' | b'E') = bytes.get(index) {
+            let sign_length = match bytes.get(index + 1) {
+                Some(b'-' | b'+') => 1,
+                _ => 0,
+            };
+
+            let mut exponent_end = index + 1 + sign_length;
+            while digit_at(exponent_end).is_some() {
+                exponent_end += 1;
+            }
+
+            if exponent_end > index + 1 + sign_length {
+                decimal.exponent_index = Some(index);
+                index = exponent_end;
+            }
+        }
+
+        decimal.length = index;
+        Ok(decimal)
     }
-);
-
-impl Consumable for f32 {
-    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
-        let (_, unconsumed) = FloatNumberStruct::consume_from(source)?;
-
-        Ok((
-            <f32>::from_str(utf8_slice::till(
-                source,
-                utf8_slice::len(source) - utf8_slice::len(unconsumed),
-            ))
-            .map_err(|_| ConsumeError::new_with(InvalidValue { index: 0 }))?,
-            unconsumed,
-        ))
+
+    /// Get the utf-8 character index at which the number becomes too large.
+    fn overflow_index(&self) -> usize {
+        self.exponent_index
+            .or(self.overflow_index)
+            .unwrap_or(self.last_integer_index)
     }
 }
+
+macro_rules! impl_consume_float {
+    ( $type:ty, $test_name:ident ) => {
+        /// Consumes a decimal number of the form `[+-]digits[.digits][(e|E)[+-]digits]`, where
+        /// either the integer or the fractional digits may be left out. The number is converted
+        /// with [`from_str`][std::str::FromStr::from_str], so it is rounded to the nearest value.
+        ///
+        /// A number which is too large results in an
+        /// [`InvalidValue`][crate::ConsumeErrorType::InvalidValue] error at the exponent, or at the
+        /// digit that makes the number too large.
+        impl Consumable for $type {
+            fn expecting() -> Expectation {
+                Expectation::new("a number")
+            }
+
+            fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+                let decimal = Decimal::scan(source, <$type>::MAX as f64)?;
+                let (number, unconsumed) = source.split_at(decimal.length);
+
+                // The scanned number is valid for `from_str`, which rounds correctly.
+                match <$type as std::str::FromStr>::from_str(number) {
+                    Ok(value) if value.is_finite() => Ok((value, unconsumed)),
+                    _ => Err(ConsumeError::new_with(InvalidValue {
+                        index: decimal.overflow_index(),
+                    })),
+                }
+            }
+        }
+
+        #[test]
+        fn $test_name() {
+            use crate::ConsumeErrorType::*;
+            use crate::{Consumable, ConsumeError};
+
+            // Numbers are consumed to the same value as `from_str` gives, or fail when it is infinite.
+            let assert_same = |source: &str| match source.parse::<$type>().unwrap() {
+                value if value.is_infinite() => {
+                    assert!(<$type>::consume_from(source).is_err(), "{}", source)
+                }
+                value => assert_eq!(<$type>::consume_from(source), Ok((value, "")), "{}", source),
+            };
+
+            for source in [
+                "0",
+                "1",
+                "-1",
+                "+1",
+                "0.5",
+                ".5",
+                "-.25",
+                "1e3",
+                "1E-3",
+                "2.5e+2",
+                "123.456",
+                "3.4028234e38",
+                "1e-45",
+                "0.1",
+                "9007199254740993",
+                "0.30000000000000004",
+                "12345678901234567890123456789",
+                "00000000000000000000000001.5",
+                "2.2250738585072011e-308",
+                "2.2250738585072014e-308",
+                "4.9406564584124654e-324",
+                "2.4703282292062327e-324",
+                "2.4703282292062328e-324",
+                "1.7976931348623157e308",
+                "1.7976931348623158e308",
+                "1.17549435e-38",
+                "1.4e-45",
+                "7.006492321624085e-46",
+                "7.006492321624086e-46",
+                "3.4028235677973366e38",
+                "9007199254740992.5000000000000000000001",
+                "9007199254740993.0000000000000000000000",
+                "16777217.000000000000000000000000000001",
+                "0e999999",
+                "1e-999999",
+                "16777217",
+                "16777219",
+            ] {
+                assert_same(source);
+            }
+
+            // Digits after the kept ones only round up when they are not all zero.
+            assert_same(&format!("9007199254740993.{}", "0".repeat(900)));
+            assert_same(&format!("9007199254740993.{}1", "0".repeat(900)));
+            assert_same(&format!("16777217.{}1", "0".repeat(900)));
+
+            // A simple linear congruential generator, so the numbers are the same for every run.
+            let mut state = 0x2545F4914F6CDD1Du64;
+            let mut next = |bound: u64| {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                (state >> 33) % bound
+            };
+
+            let range = <$type>::MAX_10_EXP as u64 + 50;
+            for _ in 0..2000 {
+                let digits: String = (0..1 + next(40))
+                    .map(|_| char::from(b'0' + next(10) as u8))
+                    .collect();
+                let period = next(digits.len() as u64 + 1) as usize;
+                let source = format!(
+                    "{}.{}e{}",
+                    &digits[..period],
+                    &digits[period..],
+                    next(2 * range) as i64 - range as i64
+                );
+                assert_same(&source.replace(".e", "e"));
+            }
+
+            assert_eq!(<$type>::consume_from("1.5.2"), Ok((1.5, ".2")));
+            assert_eq!(<$type>::consume_from("1.x"), Ok((1.0, ".x")));
+            assert_eq!(<$type>::consume_from("2e"), Ok((2.0, "e")));
+            assert_eq!(<$type>::consume_from("2e+x"), Ok((2.0, "e+x")));
+
+            assert_eq!(
+                <$type>::consume_from("").unwrap_err(),
+                ConsumeError::new_with(InsufficientTokens { index: 0 })
+            );
+            assert_eq!(
+                <$type>::consume_from("-x").unwrap_err(),
+                ConsumeError::new_with(UnexpectedToken {
+                    index: 1,
+                    token: 'x'
+                })
+            );
+            assert_eq!(
+                <$type>::consume_from(".e").unwrap_err(),
+                ConsumeError::new_with(UnexpectedToken {
+                    index: 1,
+                    token: 'e'
+                })
+            );
+            assert_eq!(
+                <$type>::consume_from("-1.5e999999999999999999").unwrap_err(),
+                ConsumeError::new_with(InvalidValue { index: 4 })
+            );
+            assert_eq!(
+                <$type>::consume_from(&format!("1{}", "0".repeat(400))).unwrap_err(),
+                ConsumeError::new_with(InvalidValue {
+                    index: <$type>::MAX_10_EXP as usize + 1
+                })
+            );
+        }
+    };
+}
+
+impl_consume_float!(f32, f32_consuming);
+impl_consume_float!(f64, f64_consuming);
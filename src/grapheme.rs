@@ -0,0 +1,135 @@
+//! Types for consuming __grapheme clusters__.
+//!
+//! Most consumers in [manger][crate] work on utf-8 characters, which are unicode scalar values.
+//! A single user-perceived character, such as an emoji with a skin tone or a letter with a
+//! combining accent, can consist of multiple scalar values. This module contains a [`Grapheme`]
+//! consumer, which consumes a whole extended grapheme cluster, and a [`GraphemeIndexed`] wrapper,
+//! which reports error indices in grapheme clusters instead of utf-8 characters.
+//!
+//! This module is only available with the `grapheme` feature, which uses the
+//! [unicode-segmentation](https://crates.io/crates/unicode-segmentation) crate.
+
+use crate::error::ConsumeErrorType::*;
+use crate::{Consumable, ConsumeError};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// One extended grapheme cluster.
+///
+/// # Examples
+///
+/// ```
+/// use manger::Consumable;
+/// use manger::grapheme::Grapheme;
+///
+/// let source = "e\u{301}tude";
+///
+/// let (grapheme, unconsumed) = Grapheme::consume_from(source)?;
+/// assert_eq!(grapheme.as_str(), "e\u{301}");
+/// assert_eq!(unconsumed, "tude");
+///
+/// let (graphemes, _) = <Vec<Grapheme>>::consume_from("👍🏽!")?;
+/// assert_eq!(graphemes.len(), 2);
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Grapheme(String);
+
+impl Grapheme {
+    /// Get the utf-8 characters of the grapheme cluster.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<Grapheme> for String {
+    fn from(grapheme: Grapheme) -> String {
+        grapheme.0
+    }
+}
+
+impl Consumable for Grapheme {
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let grapheme = source
+            .graphemes(true)
+            .next()
+            .ok_or_else(|| ConsumeError::new_with(InsufficientTokens { index: 0 }))?;
+
+        Ok((Grapheme(grapheme.to_string()), &source[grapheme.len()..]))
+    }
+}
+
+/// Convert a utf-8 character index within `source` to the index of the grapheme cluster that
+/// contains that character.
+///
+/// Indices at or past the end of `source` are mapped past the last grapheme cluster in the same
+/// way.
+///
+/// # Examples
+///
+/// ```
+/// use manger::grapheme::grapheme_index;
+///
+/// let source = "a\u{301}bc";
+///
+/// assert_eq!(grapheme_index(source, 0), 0);
+/// assert_eq!(grapheme_index(source, 1), 0);
+/// assert_eq!(grapheme_index(source, 2), 1);
+/// assert_eq!(grapheme_index(source, 4), 3);
+/// ```
+pub fn grapheme_index(source: &str, index: usize) -> usize {
+    let mut characters = 0;
+    let mut graphemes = 0;
+
+    for grapheme in source.graphemes(true) {
+        characters += grapheme.chars().count();
+
+        if characters > index {
+            return graphemes;
+        }
+
+        graphemes += 1;
+    }
+
+    graphemes + (index - characters)
+}
+
+/// Wrapper which consumes a `T` and reports the indices of errors in grapheme clusters instead of
+/// utf-8 characters.
+///
+/// # Note
+///
+/// The indices of all errors within [manger][crate] are utf-8 character indices. This wrapper
+/// should therefore only be used at the outermost level of consuming, since any type around it
+/// will offset the error indices by amounts of utf-8 characters.
+///
+/// # Examples
+///
+/// ```
+/// use manger::Consumable;
+/// use manger::grapheme::{Grapheme, GraphemeIndexed};
+///
+/// let source = "👍🏽x";
+///
+/// let error = <(Grapheme, u32)>::consume_from(source).unwrap_err();
+/// assert_eq!(error.causes()[0].index(), &2);
+///
+/// let error = <GraphemeIndexed<(Grapheme, u32)>>::consume_from(source).unwrap_err();
+/// assert_eq!(error.causes()[0].index(), &1);
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct GraphemeIndexed<T>(T);
+
+impl<T> GraphemeIndexed<T> {
+    /// Unwrap to fetch the consumed value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: Consumable> Consumable for GraphemeIndexed<T> {
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        T::consume_from(source)
+            .map(|(item, unconsumed)| (GraphemeIndexed(item), unconsumed))
+            .map_err(|err| err.map_indices(|index| grapheme_index(source, index)))
+    }
+}
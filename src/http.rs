@@ -0,0 +1,807 @@
+//! Types for consuming __HTTP/1.1__ message heads.
+//!
+//! This module contains consumers for the request line, the status line, header fields and the
+//! size lines of chunked bodies, following the rules of
+//! [RFC 9112](https://www.rfc-editor.org/rfc/rfc9112). Header names and methods are [`Token`]s
+//! and quoted parameter values are [`QuotedString`]s. Every line ends with a `"\r\n"`, although a
+//! lone `"\n"` is accepted as well. Obsolete line folding is not supported.
+//!
+//! Every type renders to its canonical form, with a `"\r\n"` at the end of every line.
+//!
+//! This module is only available with the `http` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use manger::{Consumable, ConsumeSource};
+//! use manger::http::{Headers, RequestLine};
+//!
+//! let source = "GET /index.html HTTP/1.1\r\nHost: example.com\r\nAccept: */*\r\n\r\nbody";
+//!
+//! let (request, unconsumed) = source.consume::<RequestLine>()?;
+//! let (headers, unconsumed) = unconsumed.consume::<Headers>()?;
+//!
+//! assert_eq!(request.method(), "GET");
+//! assert_eq!(request.target(), "/index.html");
+//! assert_eq!(headers.get("host"), Some("example.com"));
+//! assert_eq!(unconsumed, "body");
+//! # Ok::<(), manger::ConsumeError>(())
+//! ```
+
+use crate::common::{pattern_match, CaseInsensitive};
+use crate::error::ConsumeErrorType::*;
+use crate::{Consumable, ConsumeError, ConsumeSource, Expectation, Render};
+
+/// Returns whether `token` may be part of a [`Token`].
+fn is_tchar(token: char) -> bool {
+    token.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(token)
+}
+
+/// Returns whether `token` may be part of a field value or reason phrase, which are visible
+/// characters, spaces and tabs.
+fn is_field_char(token: char) -> bool {
+    token == '\t' || !token.is_control()
+}
+
+/// Fail with an error for the character at `index` in `source`.
+fn unexpected_at(source: &str, index: usize) -> ConsumeError {
+    ConsumeError::new_with(match source.chars().nth(index) {
+        Some(token) => UnexpectedToken { index, token },
+        None => InsufficientTokens { index },
+    })
+}
+
+/// Consume a `"\r\n"` or `"\n"` from `unconsumed`, which is at `offset` within the line.
+fn consume_line_ending(unconsumed: &mut &str, offset: usize) -> Result<(), ConsumeError> {
+    if unconsumed.mut_consume_lit(&"\r\n").is_err() && unconsumed.mut_consume_lit(&'\n').is_err() {
+        return Err(unexpected_at(unconsumed, 0).offset(offset));
+    }
+
+    Ok(())
+}
+
+/// Consume the longest run of characters for which `predicate` returns `true` from `unconsumed`.
+fn consume_while<'a>(unconsumed: &mut &'a str, predicate: impl Fn(char) -> bool) -> &'a str {
+    let length = unconsumed
+        .char_indices()
+        .find(|(_, token)| !predicate(*token))
+        .map_or(unconsumed.len(), |(byte_index, _)| byte_index);
+
+    let (consumed, rest) = unconsumed.split_at(length);
+    *unconsumed = rest;
+    consumed
+}
+
+/// A token, which is a non-empty sequence of letters, digits and the characters
+/// ``!#$%&'*+-.^_`|~``.
+///
+/// Methods, header names and chunk extension names are tokens.
+///
+/// # Examples
+///
+/// ```
+/// use manger::Consumable;
+/// use manger::http::Token;
+///
+/// assert_eq!(Token::consume_from("Content-Type: text")?.0.as_str(), "Content-Type");
+/// assert!(Token::consume_from(": text").is_err());
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Token(String);
+
+impl Token {
+    /// Get the characters of the token.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Take ownership of the characters of the token.
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl Consumable for Token {
+    fn expecting() -> Expectation {
+        Expectation::new("a token")
+    }
+
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let mut unconsumed = source;
+        let token = consume_while(&mut unconsumed, is_tchar);
+
+        if token.is_empty() {
+            return Err(unexpected_at(source, 0));
+        }
+
+        Ok((Token(token.to_string()), unconsumed))
+    }
+}
+
+impl Render for Token {
+    fn render_to(&self, out: &mut String) {
+        out.push_str(&self.0);
+    }
+}
+
+/// A string between `'"'`s, where a `'\\'` escapes the next character.
+///
+/// Control characters other than a tab are not allowed. A string without a closing `'"'` results
+/// in an [`UnclosedDelimiter`][crate::ConsumeErrorType::UnclosedDelimiter].
+///
+/// # Examples
+///
+/// ```
+/// use manger::{Consumable, Render};
+/// use manger::http::QuotedString;
+///
+/// let (string, unconsumed) = QuotedString::consume_from(r#""say \"hi\""; q=1"#)?;
+///
+/// assert_eq!(string.value(), r#"say "hi""#);
+/// assert_eq!(unconsumed, "; q=1");
+/// assert_eq!(string.render(), r#""say \"hi\"""#);
+///
+/// assert!(QuotedString::consume_from("\"open").is_err());
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct QuotedString(String);
+
+impl QuotedString {
+    /// Get the unescaped value of the string.
+    pub fn value(&self) -> &str {
+        &self.0
+    }
+
+    /// Take ownership of the unescaped value of the string.
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl Consumable for QuotedString {
+    fn expecting() -> Expectation {
+        Expectation::new("a quoted string")
+    }
+
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let mut characters = source.chars();
+        if characters.next() != Some('"') {
+            return Err(unexpected_at(source, 0));
+        }
+
+        let mut value = String::new();
+        let mut index = 1;
+
+        loop {
+            match characters.next() {
+                Some('"') => return Ok((QuotedString(value), characters.as_str())),
+                Some('\\') => match characters.next() {
+                    Some(escaped) if is_field_char(escaped) => {
+                        value.push(escaped);
+                        index += 2;
+                    }
+                    Some(escaped) => {
+                        return Err(ConsumeError::new_with(UnexpectedToken {
+                            index: index + 1,
+                            token: escaped,
+                        }))
+                    }
+                    None => {
+                        index += 1;
+                        break;
+                    }
+                },
+                Some(token) if is_field_char(token) => {
+                    value.push(token);
+                    index += 1;
+                }
+                Some(token) => {
+                    return Err(ConsumeError::new_with(UnexpectedToken { index, token }))
+                }
+                None => break,
+            }
+        }
+
+        Err(ConsumeError::new_with(UnclosedDelimiter {
+            index,
+            opened_at: 0,
+            token: '"',
+        }))
+    }
+}
+
+impl Render for QuotedString {
+    fn render_to(&self, out: &mut String) {
+        out.push('"');
+        for token in self.0.chars() {
+            if token == '"' || token == '\\' {
+                out.push('\\');
+            }
+            out.push(token);
+        }
+        out.push('"');
+    }
+}
+
+/// The version of the protocol, such as `HTTP/1.1`.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{Consumable, Render};
+/// use manger::http::HttpVersion;
+///
+/// assert_eq!(HttpVersion::consume_from("HTTP/1.0")?.0, HttpVersion { major: 1, minor: 0 });
+/// assert_eq!(HttpVersion::HTTP_1_1.render(), "HTTP/1.1");
+/// assert!(HttpVersion::consume_from("http/1.1").is_err());
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub struct HttpVersion {
+    /// The digit before the period.
+    pub major: u8,
+    /// The digit after the period.
+    pub minor: u8,
+}
+
+impl HttpVersion {
+    /// The `HTTP/1.1` version.
+    pub const HTTP_1_1: HttpVersion = HttpVersion { major: 1, minor: 1 };
+}
+
+impl Consumable for HttpVersion {
+    fn expecting() -> Expectation {
+        Expectation::new("an HTTP version")
+    }
+
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let unconsumed = source.consume_lit(&"HTTP/")?;
+        let unconsumed = pattern_match("#.#", unconsumed).map_err(|err| err.offset(5))?;
+        let digits = source.as_bytes();
+
+        Ok((
+            HttpVersion {
+                major: digits[5] - b'0',
+                minor: digits[7] - b'0',
+            },
+            unconsumed,
+        ))
+    }
+}
+
+impl Render for HttpVersion {
+    fn render_to(&self, out: &mut String) {
+        out.push_str(&format!("HTTP/{}.{}", self.major, self.minor));
+    }
+}
+
+/// The first line of a request, such as `GET /index.html HTTP/1.1`, including the line ending.
+///
+/// The method is a [`Token`] and the target is any non-empty sequence of visible characters.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{Consumable, Render};
+/// use manger::http::{HttpVersion, RequestLine};
+///
+/// let request = RequestLine::consume_all("POST /api?q=1 HTTP/1.1\r\n")?;
+///
+/// assert_eq!(request.method(), "POST");
+/// assert_eq!(request.target(), "/api?q=1");
+/// assert_eq!(request.version(), HttpVersion::HTTP_1_1);
+/// assert_eq!(request.render(), "POST /api?q=1 HTTP/1.1\r\n");
+///
+/// assert!(RequestLine::consume_all("GET  / HTTP/1.1\r\n").is_err());
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct RequestLine {
+    method: Token,
+    target: String,
+    version: HttpVersion,
+}
+
+impl RequestLine {
+    /// Get the method of the request, such as `GET`.
+    pub fn method(&self) -> &str {
+        self.method.as_str()
+    }
+
+    /// Get the target of the request, which is usually a path with an optional query.
+    pub fn target(&self) -> &str {
+        &self.target
+    }
+
+    /// Get the version of the protocol.
+    pub fn version(&self) -> HttpVersion {
+        self.version
+    }
+}
+
+impl Consumable for RequestLine {
+    fn expecting() -> Expectation {
+        Expectation::new("a request line")
+    }
+
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let mut unconsumed = source;
+
+        let (method, mut offset) = unconsumed.mut_consume_by::<Token>()?;
+        offset += unconsumed
+            .mut_consume_lit(&' ')
+            .map_err(|err| err.offset(offset))?;
+
+        let target = consume_while(&mut unconsumed, |token| {
+            token.is_ascii_graphic() || !token.is_ascii()
+        });
+        if target.is_empty() {
+            return Err(unexpected_at(unconsumed, 0).offset(offset));
+        }
+        offset += target.chars().count();
+
+        offset += unconsumed
+            .mut_consume_lit(&' ')
+            .map_err(|err| err.offset(offset))?;
+        let (version, by) = unconsumed
+            .mut_consume_by::<HttpVersion>()
+            .map_err(|err| err.offset(offset))?;
+        consume_line_ending(&mut unconsumed, offset + by)?;
+
+        Ok((
+            RequestLine {
+                method,
+                target: target.to_string(),
+                version,
+            },
+            unconsumed,
+        ))
+    }
+}
+
+impl Render for RequestLine {
+    fn render_to(&self, out: &mut String) {
+        self.method.render_to(out);
+        out.push(' ');
+        out.push_str(&self.target);
+        out.push(' ');
+        self.version.render_to(out);
+        out.push_str("\r\n");
+    }
+}
+
+/// The first line of a response, such as `HTTP/1.1 404 Not Found`, including the line ending.
+///
+/// The status code consists of exactly three digits and the reason phrase may be empty.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{Consumable, Render};
+/// use manger::http::StatusLine;
+///
+/// let status = StatusLine::consume_all("HTTP/1.1 404 Not Found\r\n")?;
+///
+/// assert_eq!(status.code(), 404);
+/// assert_eq!(status.reason(), "Not Found");
+/// assert_eq!(status.render(), "HTTP/1.1 404 Not Found\r\n");
+///
+/// assert!(StatusLine::consume_all("HTTP/1.1 42 Odd\r\n").is_err());
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct StatusLine {
+    version: HttpVersion,
+    code: u16,
+    reason: String,
+}
+
+impl StatusLine {
+    /// Get the version of the protocol.
+    pub fn version(&self) -> HttpVersion {
+        self.version
+    }
+
+    /// Get the three digit status code.
+    pub fn code(&self) -> u16 {
+        self.code
+    }
+
+    /// Get the reason phrase, which may be empty.
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
+}
+
+impl Consumable for StatusLine {
+    fn expecting() -> Expectation {
+        Expectation::new("a status line")
+    }
+
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let mut unconsumed = source;
+
+        let (version, mut offset) = unconsumed.mut_consume_by::<HttpVersion>()?;
+        offset += unconsumed
+            .mut_consume_lit(&' ')
+            .map_err(|err| err.offset(offset))?;
+
+        let rest = pattern_match("###", unconsumed).map_err(|err| err.offset(offset))?;
+        let code = unconsumed[..3]
+            .parse()
+            .expect("three ASCII digits fit a u16");
+        unconsumed = rest;
+        offset += 3;
+
+        offset += unconsumed
+            .mut_consume_lit(&' ')
+            .map_err(|err| err.offset(offset))?;
+        let reason = consume_while(&mut unconsumed, is_field_char);
+        consume_line_ending(&mut unconsumed, offset + reason.chars().count())?;
+
+        Ok((
+            StatusLine {
+                version,
+                code,
+                reason: reason.to_string(),
+            },
+            unconsumed,
+        ))
+    }
+}
+
+impl Render for StatusLine {
+    fn render_to(&self, out: &mut String) {
+        self.version.render_to(out);
+        out.push_str(&format!(" {:03} ", self.code));
+        out.push_str(&self.reason);
+        out.push_str("\r\n");
+    }
+}
+
+/// A header field, such as `Content-Length: 42`, including the line ending.
+///
+/// The name is a [`Token`], which is followed by a `':'`. Spaces and tabs around the value are
+/// not part of it.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{Consumable, Render};
+/// use manger::http::Header;
+///
+/// let header = Header::consume_all("Content-Type:  text/html; charset=utf-8 \r\n")?;
+///
+/// assert_eq!(header.name(), "Content-Type");
+/// assert_eq!(header.value(), "text/html; charset=utf-8");
+/// assert_eq!(header.render(), "Content-Type: text/html; charset=utf-8\r\n");
+///
+/// assert!(Header::consume_all("Content-Type : text/html\r\n").is_err());
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Header {
+    name: Token,
+    value: String,
+}
+
+impl Header {
+    /// Get the name of the header field.
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    /// Get the value of the header field.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Returns whether the name of the header field is `name`, regardless of case.
+    pub fn is(&self, name: &str) -> bool {
+        self.name
+            .as_str()
+            .consume_lit(&CaseInsensitive(name))
+            .is_ok_and(str::is_empty)
+    }
+}
+
+impl Consumable for Header {
+    fn expecting() -> Expectation {
+        Expectation::new("a header field")
+    }
+
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let mut unconsumed = source;
+
+        let (name, mut offset) = unconsumed.mut_consume_by::<Token>()?;
+        offset += unconsumed
+            .mut_consume_lit(&':')
+            .map_err(|err| err.offset(offset))?;
+
+        let line = consume_while(&mut unconsumed, is_field_char);
+        consume_line_ending(&mut unconsumed, offset + line.chars().count())?;
+
+        Ok((
+            Header {
+                name,
+                value: line
+                    .trim_matches(|token| token == ' ' || token == '\t')
+                    .to_string(),
+            },
+            unconsumed,
+        ))
+    }
+}
+
+impl Render for Header {
+    fn render_to(&self, out: &mut String) {
+        self.name.render_to(out);
+        out.push_str(": ");
+        out.push_str(&self.value);
+        out.push_str("\r\n");
+    }
+}
+
+/// The header fields of a message, followed by the empty line that ends them.
+///
+/// # Examples
+///
+/// ```
+/// use manger::Consumable;
+/// use manger::http::Headers;
+///
+/// let (headers, unconsumed) = Headers::consume_from("Host: a\r\nVary: x\r\nvary: y\r\n\r\n{}")?;
+///
+/// assert_eq!(headers.len(), 3);
+/// assert_eq!(headers.get("HOST"), Some("a"));
+/// assert_eq!(headers.get_all("Vary").collect::<Vec<_>>(), vec!["x", "y"]);
+/// assert_eq!(unconsumed, "{}");
+///
+/// assert!(Headers::consume_from("Host: a\r\n").is_err());
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Default)]
+pub struct Headers(Vec<Header>);
+
+impl Headers {
+    /// Get the value of the first header field called `name`, regardless of case.
+    pub fn get<'a>(&'a self, name: &'a str) -> Option<&'a str> {
+        self.get_all(name).next()
+    }
+
+    /// Get the values of all header fields called `name`, regardless of case, in order of
+    /// appearance.
+    pub fn get_all<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.0
+            .iter()
+            .filter(move |header| header.is(name))
+            .map(Header::value)
+    }
+
+    /// Get the amount of header fields.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns whether there are no header fields.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Iterate over the header fields in order of appearance.
+    pub fn iter(&self) -> std::slice::Iter<'_, Header> {
+        self.0.iter()
+    }
+
+    /// Take ownership of the header fields.
+    pub fn into_vec(self) -> Vec<Header> {
+        self.0
+    }
+}
+
+impl Consumable for Headers {
+    fn expecting() -> Expectation {
+        Expectation::new("header fields")
+    }
+
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let mut unconsumed = source;
+        let mut headers = Vec::new();
+        let mut offset = 0;
+
+        while consume_line_ending(&mut unconsumed, offset).is_err() {
+            let (header, by) = unconsumed
+                .mut_consume_by::<Header>()
+                .map_err(|err| err.offset(offset))?;
+            offset += by;
+            headers.push(header);
+        }
+
+        Ok((Headers(headers), unconsumed))
+    }
+}
+
+impl Render for Headers {
+    fn render_to(&self, out: &mut String) {
+        self.0.iter().for_each(|header| header.render_to(out));
+        out.push_str("\r\n");
+    }
+}
+
+/// The line before every chunk of a chunked body, such as `1a;name=value`, including the line
+/// ending.
+///
+/// The size is hexadecimal and a size that does not fit a `u64` results in an
+/// [`InvalidValue`][crate::ConsumeErrorType::InvalidValue] at the digit that overflows. Every
+/// extension is a [`Token`] with an optional value after a `'='`, which is either a token or a
+/// [`QuotedString`].
+///
+/// # Examples
+///
+/// ```
+/// use manger::{Consumable, Render};
+/// use manger::http::ChunkSize;
+///
+/// let chunk = ChunkSize::consume_all("1A;name=\"x y\";last\r\n")?;
+///
+/// assert_eq!(chunk.size(), 26);
+/// assert_eq!(
+///     chunk.extensions(),
+///     &[("name".to_string(), Some("x y".to_string())), ("last".to_string(), None)]
+/// );
+/// assert_eq!(chunk.render(), "1a;name=\"x y\";last\r\n");
+///
+/// assert_eq!(ChunkSize::consume_all("0\r\n")?.size(), 0);
+/// assert!(ChunkSize::consume_all("10000000000000000\r\n").is_err());
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct ChunkSize {
+    size: u64,
+    extensions: Vec<(String, Option<String>)>,
+}
+
+impl ChunkSize {
+    /// Get the size of the chunk in bytes. The last chunk has a size of zero.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Get the names and optional values of the chunk extensions, in order of appearance.
+    pub fn extensions(&self) -> &[(String, Option<String>)] {
+        &self.extensions
+    }
+}
+
+impl Consumable for ChunkSize {
+    fn expecting() -> Expectation {
+        Expectation::new("a chunk size line")
+    }
+
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let mut unconsumed = source;
+
+        let digits = consume_while(&mut unconsumed, |token| token.is_ascii_hexdigit());
+        if digits.is_empty() {
+            return Err(unexpected_at(source, 0));
+        }
+
+        let mut size: u64 = 0;
+        for (index, digit) in digits.chars().enumerate() {
+            size = size
+                .checked_mul(16)
+                .and_then(|size| size.checked_add(u64::from(digit.to_digit(16)?)))
+                .ok_or_else(|| ConsumeError::new_with(InvalidValue { index }))?;
+        }
+
+        let mut offset = digits.len();
+        let mut extensions = Vec::new();
+
+        while let Ok(by) = unconsumed.mut_consume_lit(&';') {
+            offset += by;
+            let (name, by) = unconsumed
+                .mut_consume_by::<Token>()
+                .map_err(|err| err.offset(offset))?;
+            offset += by;
+
+            let value = match unconsumed.mut_consume_lit(&'=') {
+                Ok(by) => {
+                    offset += by;
+                    let (value, by) = unconsumed
+                        .mut_consume_by::<either::Either<Token, QuotedString>>()
+                        .map_err(|err| err.offset(offset))?;
+                    offset += by;
+                    Some(value.either(Token::into_string, QuotedString::into_string))
+                }
+                Err(_) => None,
+            };
+
+            extensions.push((name.into_string(), value));
+        }
+
+        consume_line_ending(&mut unconsumed, offset)?;
+
+        Ok((ChunkSize { size, extensions }, unconsumed))
+    }
+}
+
+impl Render for ChunkSize {
+    fn render_to(&self, out: &mut String) {
+        out.push_str(&format!("{:x}", self.size));
+
+        for (name, value) in &self.extensions {
+            out.push(';');
+            out.push_str(name);
+
+            if let Some(value) = value {
+                out.push('=');
+                if !value.is_empty() && value.chars().all(is_tchar) {
+                    out.push_str(value);
+                } else {
+                    QuotedString(value.clone()).render_to(out);
+                }
+            }
+        }
+
+        out.push_str("\r\n");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ChunkSize, Header, Headers, QuotedString, RequestLine, StatusLine};
+    use crate::error::ConsumeErrorType::*;
+    use crate::{Consumable, ConsumeError};
+
+    #[test]
+    fn test_http_errors() {
+        assert_eq!(
+            RequestLine::consume_from("GET /a HTTP/1.x\r\n").unwrap_err(),
+            ConsumeError::new_with(UnexpectedToken {
+                index: 14,
+                token: 'x'
+            })
+        );
+        assert_eq!(
+            StatusLine::consume_from("HTTP/1.1 200 OK").unwrap_err(),
+            ConsumeError::new_with(InsufficientTokens { index: 15 })
+        );
+        assert_eq!(
+            Headers::consume_from("A: 1\r\nB 2\r\n\r\n").unwrap_err(),
+            ConsumeError::new_with(UnexpectedToken {
+                index: 7,
+                token: ' '
+            })
+        );
+        assert_eq!(
+            QuotedString::consume_from("\"a\\").unwrap_err(),
+            ConsumeError::new_with(UnclosedDelimiter {
+                index: 3,
+                opened_at: 0,
+                token: '"'
+            })
+        );
+        assert_eq!(
+            ChunkSize::consume_from("ff;=1\r\n").unwrap_err(),
+            ConsumeError::new_with(UnexpectedToken {
+                index: 3,
+                token: '='
+            })
+        );
+    }
+
+    #[test]
+    fn test_http_line_endings() {
+        let (header, unconsumed) = Header::consume_from("X-Empty:\nrest").unwrap();
+        assert_eq!(header.value(), "");
+        assert_eq!(unconsumed, "rest");
+
+        assert_eq!(
+            Headers::consume_from("\n").unwrap(),
+            (Headers::default(), "")
+        );
+        assert!(Header::consume_from("X: a\rb\r\n").is_err());
+    }
+}
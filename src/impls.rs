@@ -1,16 +1,31 @@
+use crate::common::Integer;
 use crate::Consumable;
 use crate::ConsumeError;
+use crate::SelfConsumable;
+use std::ops::{Range, RangeInclusive};
+
+impl SelfConsumable for bool {
+    fn consume_item<'a>(source: &'a str, item: &'_ Self) -> Result<&'a str, ConsumeError> {
+        <&str>::consume_item(source, &if *item { "true" } else { "false" })
+    }
+}
 
 impl<T: Consumable> Consumable for Option<T> {
     fn consume_from(source: &str) -> Result<(Option<T>, &str), ConsumeError> {
-        Ok(match <T>::consume_from(source) {
-            Err(_) => (None, source),
-            Ok((item, unconsumed)) => (Some(item), unconsumed),
+        crate::trace::attempt::<Self, _>(source, || {
+            Ok(match <T>::consume_from(source) {
+                Err(_) => (None, source),
+                Ok((item, unconsumed)) => (Some(item), unconsumed),
+            })
         })
     }
 }
 
 impl<T: Consumable> Consumable for Box<T> {
+    fn expecting() -> crate::Expectation {
+        T::expecting()
+    }
+
     fn consume_from(s: &str) -> Result<(Box<T>, &str), ConsumeError> {
         <T>::consume_from(s).map(|(item, unconsumed)| (Box::new(item), unconsumed))
     }
@@ -18,15 +33,89 @@ impl<T: Consumable> Consumable for Box<T> {
 
 impl<T: Consumable> Consumable for Vec<T> {
     fn consume_from(s: &str) -> Result<(Vec<T>, &str), ConsumeError> {
-        let mut sequence = Vec::new();
-        let mut last_unconsumed = s;
+        crate::trace::attempt::<Self, _>(s, || crate::limits::repeat(s, 0))
+    }
+}
 
-        while let Ok((extra_coordinate_pair, unconsumed)) = T::consume_from(last_unconsumed) {
-            sequence.push(extra_coordinate_pair);
-            last_unconsumed = unconsumed;
-        }
+/// Consume the bounds of a range, which are separated by `separator`.
+///
+/// When the end of the range is missing, the error expects the end of the range.
+fn consume_bounds<'a, T: Integer>(
+    source: &'a str,
+    separator: &str,
+) -> Result<((T, T), &'a str), ConsumeError> {
+    let (start, unconsumed) = T::consume_from(source)?;
+    let offset = crate::strs::consumed_length(source, unconsumed);
+
+    let unconsumed =
+        <&str>::consume_item(unconsumed, &separator).map_err(|err| err.offset(offset))?;
+    let offset = offset + separator.chars().count();
+
+    let (end, unconsumed) = T::consume_from(unconsumed).map_err(|err| {
+        let mut err = err.offset(offset);
+        err.add_expectation(crate::Expectation::new("the end of the range"));
+        err
+    })?;
+
+    Ok(((start, end), unconsumed))
+}
+
+/// Consumes two integers separated by `..`, such as `3..7`.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{Consumable, ConsumeErrorType};
+///
+/// assert_eq!(<std::ops::Range<i32>>::consume_from("-3..7;")?, (-3..7, ";"));
+///
+/// let error = <std::ops::Range<u32>>::consume_from("3..").unwrap_err();
+/// assert_eq!(error.causes(), vec![&ConsumeErrorType::InsufficientTokens { index: 3 }]);
+/// assert_eq!(
+///     error.to_string(),
+///     "Expected more tokens at index `3` but found none!\n\
+///      Expected one of: the end of the range"
+/// );
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+impl<T: Integer> Consumable for Range<T> {
+    fn expecting() -> crate::Expectation {
+        crate::Expectation::new("a range")
+    }
+
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        crate::trace::attempt::<Self, _>(source, || {
+            consume_bounds(source, "..").map(|((start, end), unconsumed)| (start..end, unconsumed))
+        })
+    }
+}
+
+/// Consumes two integers separated by `..=`, such as `1..=9`.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{Consumable, ConsumeErrorType};
+///
+/// assert_eq!(<std::ops::RangeInclusive<u8>>::consume_from("1..=9")?, (1..=9, ""));
+///
+/// // A range which excludes its end is not inclusive.
+/// assert_eq!(
+///     <std::ops::RangeInclusive<u8>>::consume_from("1..9").unwrap_err().causes(),
+///     vec![&ConsumeErrorType::UnexpectedToken { index: 3, token: '9' }]
+/// );
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+impl<T: Integer> Consumable for RangeInclusive<T> {
+    fn expecting() -> crate::Expectation {
+        crate::Expectation::new("an inclusive range")
+    }
 
-        Ok((sequence, last_unconsumed))
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        crate::trace::attempt::<Self, _>(source, || {
+            consume_bounds(source, "..=")
+                .map(|((start, end), unconsumed)| (start..=end, unconsumed))
+        })
     }
 }
 
@@ -69,3 +158,69 @@ consume_concat!(A, B, C, D, E, F, G);
 consume_concat!(A, B, C, D, E, F, G, H);
 consume_concat!(A, B, C, D, E, F, G, H, I);
 consume_concat!(A, B, C, D, E, F, G, H, I, J);
+
+#[cfg(test)]
+mod tests {
+    use crate::error::ConsumeErrorType::*;
+    use crate::{Consumable, Expectation, Render};
+    use std::ops::{Range, RangeInclusive};
+
+    #[test]
+    fn test_range() {
+        assert_eq!(<Range<u32>>::consume_from("3..7"), Ok((3..7, "")));
+        assert_eq!(<Range<i8>>::consume_from("-8..-1x"), Ok((-8..-1, "x")));
+
+        // An empty range is still a range.
+        let (range, _) = <Range<u32>>::consume_from("7..3").unwrap();
+        assert_eq!((range.start, range.end), (7, 3));
+
+        let error = <Range<u32>>::consume_from("12..x").unwrap_err();
+        assert_eq!(
+            error.causes(),
+            vec![&UnexpectedToken {
+                index: 4,
+                token: 'x'
+            }]
+        );
+        assert_eq!(
+            error.expected(),
+            &[Expectation::new("the end of the range")]
+        );
+
+        assert_eq!(
+            <Range<u32>>::consume_from("3.7").unwrap_err().causes(),
+            vec![&UnexpectedToken {
+                index: 2,
+                token: '7'
+            }]
+        );
+        assert_eq!(
+            <Range<u32>>::consume_from("1..=9").unwrap_err().causes(),
+            vec![&UnexpectedToken {
+                index: 3,
+                token: '='
+            }]
+        );
+        assert_eq!(
+            <Range<u8>>::consume_from("1..300").unwrap_err().causes(),
+            vec![&InvalidValue { index: 5 }]
+        );
+        assert_eq!((3..7).render(), "3..7");
+    }
+
+    #[test]
+    fn test_range_inclusive() {
+        assert_eq!(
+            <RangeInclusive<u64>>::consume_from("0..=10,"),
+            Ok((0..=10, ","))
+        );
+        assert_eq!(
+            <RangeInclusive<u64>>::consume_from("0..=")
+                .unwrap_err()
+                .causes(),
+            vec![&InsufficientTokens { index: 4 }]
+        );
+        assert!(<RangeInclusive<u64>>::consume_from("..=3").is_err());
+        assert_eq!((-1..=1).render(), "-1..=1");
+    }
+}
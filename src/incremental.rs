@@ -0,0 +1,386 @@
+//! __Incremental__ re-consuming of a document after an edit.
+//!
+//! Editor integrations re-consume a document after every edit, while most of the document stays
+//! the same. An [`IncrementalParser<T>`] consumes a document as a list of `T` items, like
+//! [`consume_iter`][crate::Consumable::consume_iter], and keeps the span of every item. After an
+//! [`edit`][IncrementalParser::edit], only the items that are damaged by the edit are consumed
+//! again. As soon as consuming reaches the start of an item after the edit again, the remaining
+//! items are reused with their spans shifted.
+//!
+//! This assumes that an item only depends on its own text and the text directly around it, which
+//! holds for most items at the top level of a document such as statements or lines. Consuming
+//! falls back to the whole rest of the document when it never reaches such a start, and
+//! [`reparse`][IncrementalParser::reparse] consumes the whole document again.
+//!
+//! # Examples
+//!
+//! ```
+//! use manger::consume_struct;
+//! use manger::incremental::IncrementalParser;
+//!
+//! struct Statement {
+//!     name: char,
+//!     value: u32,
+//! }
+//! consume_struct!(
+//!     Statement => [
+//!         name: char,
+//!         > '=',
+//!         value: u32,
+//!         > ';';
+//!     ]
+//! );
+//!
+//! let mut parser = <IncrementalParser<Statement>>::new("a=1;b=2;c=3;");
+//! assert_eq!(parser.items().len(), 3);
+//!
+//! // Replace the `2` by `42`.
+//! parser.edit(6..7, "42");
+//!
+//! assert_eq!(parser.source(), "a=1;b=42;c=3;");
+//! assert_eq!(parser.items()[1].1.value, 42);
+//! assert_eq!(parser.items()[2].0, 9..13);
+//!
+//! // Only the second statement was consumed again.
+//! assert_eq!(parser.reconsumed(), 1..2);
+//! ```
+
+use crate::{context, Consumable, ConsumeError};
+use std::ops::Range;
+
+/// A document consumed as a list of `T` items, which is re-consumed incrementally after edits.
+///
+/// All spans and indices are utf-8 character indices within the whole document.
+///
+/// # Examples
+///
+/// ```
+/// use manger::incremental::IncrementalParser;
+///
+/// let mut parser = <IncrementalParser<char>>::new("abcdefgh");
+/// parser.edit(1..2, "xy");
+///
+/// let items = parser.items().iter().map(|(_, c)| *c).collect::<String>();
+/// assert_eq!(items, "axycdefgh");
+///
+/// // The items around the edit were consumed again, the others were reused.
+/// assert_eq!(parser.reconsumed(), 0..4);
+/// assert_eq!(parser.items()[4].0, 4..5);
+/// ```
+#[derive(Debug, Clone)]
+pub struct IncrementalParser<T: Consumable> {
+    source: String,
+    items: Vec<(Range<usize>, T)>,
+    error: Option<ConsumeError>,
+    reconsumed: Range<usize>,
+}
+
+impl<T: Consumable> IncrementalParser<T> {
+    /// Consume the whole `source`.
+    pub fn new(source: impl Into<String>) -> Self {
+        let mut parser = IncrementalParser {
+            source: source.into(),
+            items: Vec::new(),
+            error: None,
+            reconsumed: 0..0,
+        };
+        parser.reparse();
+        parser
+    }
+
+    /// Get the whole document.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Get the consumed items with their spans.
+    pub fn items(&self) -> &[(Range<usize>, T)] {
+        &self.items
+    }
+
+    /// Take ownership of the consumed items with their spans.
+    pub fn into_items(self) -> Vec<(Range<usize>, T)> {
+        self.items
+    }
+
+    /// Get the part of the document after the last item, which could not be consumed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use manger::chars::Space;
+    /// use manger::incremental::IncrementalParser;
+    ///
+    /// let mut parser = <IncrementalParser<(u8, Option<Space>)>>::new("1 2 x");
+    /// assert_eq!(parser.unconsumed(), "x");
+    ///
+    /// parser.edit(4..5, "3");
+    /// assert_eq!(parser.unconsumed(), "");
+    /// assert!(parser.error().is_none());
+    /// ```
+    pub fn unconsumed(&self) -> &str {
+        let end = self.items.last().map_or(0, |(span, _)| span.end);
+        utf8_slice::from(&self.source, end)
+    }
+
+    /// Get the error which stopped consuming before the end of the document, if any.
+    ///
+    /// The indices of the error are within the whole document.
+    pub fn error(&self) -> Option<&ConsumeError> {
+        self.error.as_ref()
+    }
+
+    /// Get the indices within [`items`][IncrementalParser::items] of the items that were consumed
+    /// during the last edit or reparse. All other items were reused.
+    pub fn reconsumed(&self) -> Range<usize> {
+        self.reconsumed.clone()
+    }
+
+    /// Consume the whole document again.
+    pub fn reparse(&mut self) {
+        self.items.clear();
+        self.consume_from(0, Vec::new());
+    }
+
+    /// Replace the characters within `range` by `replacement` and re-consume the items that are
+    /// damaged by it.
+    ///
+    /// The `range` is given in utf-8 character indices of the document before the edit. A `range`
+    /// which is not within the document, such as a stale range of an editor, is clamped to the
+    /// document and the whole document is consumed again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use manger::incremental::IncrementalParser;
+    ///
+    /// let mut parser = <IncrementalParser<u32>>::new("1,2,3");
+    /// assert_eq!(parser.items().len(), 1);
+    ///
+    /// // Removing the separators merges the numbers into one.
+    /// parser.edit(1..2, "");
+    /// parser.edit(2..3, "");
+    /// assert_eq!(parser.items()[0].1, 123);
+    ///
+    /// // A range past the end of the document is clamped to it.
+    /// parser.edit(5..8, "4");
+    /// assert_eq!(parser.source(), "1234");
+    /// assert_eq!(parser.items()[0].1, 1234);
+    /// ```
+    pub fn edit(&mut self, range: Range<usize>, replacement: &str) {
+        let length = self.source.chars().count();
+        let end = range.end.min(length);
+        let stale = range.start > end || range.end > length;
+        let range = range.start.min(end)..end;
+
+        let start = byte_index(&self.source, range.start);
+        let end = byte_index(&self.source, range.end);
+        self.source.replace_range(start..end, replacement);
+
+        if stale {
+            self.reparse();
+            return;
+        }
+
+        let inserted = replacement.chars().count();
+        let shift = |index: usize| index + inserted - (range.end - range.start);
+
+        // An item which ends at the edit could have continued into the replacement.
+        let damaged = self
+            .items
+            .iter()
+            .position(|(span, _)| span.end >= range.start)
+            .unwrap_or(self.items.len());
+
+        // Items which start after the edit can be reused. These do not start directly at the edit,
+        // since they could depend on the character before them.
+        let reusable = self
+            .items
+            .iter()
+            .position(|(span, _)| span.start > range.end)
+            .unwrap_or(self.items.len())
+            .max(damaged);
+
+        let mut reused: Vec<_> = self.items.drain(reusable..).collect();
+        for (span, _) in reused.iter_mut() {
+            *span = shift(span.start)..shift(span.end);
+        }
+        self.items.truncate(damaged);
+
+        let position = self.items.last().map_or(0, |(span, _)| span.end);
+        self.consume_from(position, reused);
+    }
+
+    /// Consume items starting at the utf-8 character index `position`, until the start of one of
+    /// the `reused` items is reached.
+    fn consume_from(&mut self, mut position: usize, reused: Vec<(Range<usize>, T)>) {
+        let first = self.items.len();
+        let mut reused = reused.into_iter().peekable();
+        let source = self.source.as_str();
+        let mut unconsumed = utf8_slice::from(source, position);
+
+        self.error = None;
+
+        loop {
+            while reused.peek().is_some_and(|(span, _)| span.start < position) {
+                reused.next();
+            }
+
+            if reused
+                .peek()
+                .is_some_and(|(span, _)| span.start == position)
+            {
+                self.reconsumed = first..self.items.len();
+                self.items.extend(reused);
+                self.error = self.tail_error();
+                return;
+            }
+
+            if unconsumed.is_empty() {
+                break;
+            }
+
+            match context::scope(source, || T::consume_from(unconsumed)) {
+                // An item that consumes nothing would be consumed forever.
+                Ok((item, rest)) if rest.len() < unconsumed.len() => {
+                    let end = position + crate::strs::consumed_length(unconsumed, rest);
+                    self.items.push((position..end, item));
+                    position = end;
+                    unconsumed = rest;
+                }
+                Ok(_) => break,
+                Err(err) => {
+                    self.error = Some(err.offset(position));
+                    break;
+                }
+            }
+        }
+
+        self.reconsumed = first..self.items.len();
+    }
+
+    /// Get the error of consuming an item after the last item.
+    fn tail_error(&self) -> Option<ConsumeError> {
+        let end = self.items.last().map_or(0, |(span, _)| span.end);
+        let unconsumed = utf8_slice::from(&self.source, end);
+
+        if unconsumed.is_empty() {
+            return None;
+        }
+
+        context::scope(&self.source, || T::consume_from(unconsumed))
+            .err()
+            .map(|err| err.offset(end))
+    }
+}
+
+/// Get the byte index of the utf-8 character index `index` within `source`.
+fn byte_index(source: &str, index: usize) -> usize {
+    source
+        .char_indices()
+        .nth(index)
+        .map_or(source.len(), |(byte, _)| byte)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IncrementalParser;
+    use crate::chars::Space;
+    use crate::error::ConsumeErrorType::*;
+    use crate::{Consumable, ConsumeError};
+
+    #[derive(Debug, PartialEq)]
+    struct Line(String);
+
+    impl Consumable for Line {
+        fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+            match source.find('\n') {
+                Some(end) => Ok((Line(source[..end].to_string()), &source[end + 1..])),
+                None => Err(ConsumeError::new_with(InsufficientTokens {
+                    index: source.chars().count(),
+                })),
+            }
+        }
+    }
+
+    fn lines(parser: &IncrementalParser<Line>) -> Vec<&str> {
+        parser
+            .items()
+            .iter()
+            .map(|(_, line)| line.0.as_str())
+            .collect()
+    }
+
+    fn assert_consistent(parser: &IncrementalParser<Line>) {
+        let fresh = <IncrementalParser<Line>>::new(parser.source());
+        assert_eq!(parser.items(), fresh.items());
+        assert_eq!(parser.unconsumed(), fresh.unconsumed());
+    }
+
+    #[test]
+    fn test_incremental_edits() {
+        let mut parser = <IncrementalParser<Line>>::new("ab\ncd\nëf\n");
+        assert_eq!(lines(&parser), vec!["ab", "cd", "ëf"]);
+
+        parser.edit(4..5, "x\ny");
+        assert_eq!(lines(&parser), vec!["ab", "cx", "y", "ëf"]);
+        assert_eq!(parser.reconsumed(), 1..3);
+        assert_eq!(parser.items()[3].0, 8..11);
+        assert_consistent(&parser);
+
+        parser.edit(2..3, "");
+        assert_eq!(lines(&parser), vec!["abcx", "y", "ëf"]);
+        assert_eq!(parser.reconsumed(), 0..1);
+        assert_consistent(&parser);
+
+        parser.edit(10..10, "gh\n");
+        assert_eq!(lines(&parser), vec!["abcx", "y", "ëf", "gh"]);
+        assert_eq!(parser.reconsumed(), 2..4);
+        assert_consistent(&parser);
+
+        parser.edit(0..13, "");
+        assert!(parser.items().is_empty());
+        assert_consistent(&parser);
+    }
+
+    #[test]
+    fn test_incremental_stale_edits() {
+        let mut parser = <IncrementalParser<Line>>::new("ab\ncd\n");
+
+        parser.edit(5..20, "x\n");
+        assert_eq!(parser.source(), "ab\ncdx\n");
+        assert_eq!(parser.reconsumed(), 0..2);
+        assert_consistent(&parser);
+
+        #[allow(clippy::reversed_empty_ranges)]
+        parser.edit(3..2, "y");
+        assert_eq!(parser.source(), "aby\ncdx\n");
+        assert_consistent(&parser);
+
+        parser.edit(30..40, "z\n");
+        assert_eq!(lines(&parser), vec!["aby", "cdx", "z"]);
+        assert_consistent(&parser);
+    }
+
+    #[test]
+    fn test_incremental_errors() {
+        let mut parser = <IncrementalParser<(u8, Option<Space>)>>::new("1 2 300 4");
+        assert_eq!(parser.items().len(), 2);
+        assert_eq!(parser.unconsumed(), "300 4");
+        assert!(parser.error().is_some());
+
+        parser.edit(4..6, "");
+        assert_eq!(parser.items().len(), 4);
+        assert!(parser.error().is_none());
+
+        parser.edit(0..0, "x");
+        assert!(parser.items().is_empty());
+        assert_eq!(
+            parser.error().map(|err| err.causes()),
+            Some(vec![&UnexpectedToken {
+                index: 0,
+                token: 'x'
+            }])
+        );
+    }
+}
@@ -0,0 +1,349 @@
+//! Types for consuming __indentation-sensitive__ blocks.
+//!
+//! Formats such as YAML and Python group lines into blocks by their indentation. The
+//! [`Indented<T>`] consumer consumes such a block, where every line contains an item `T`. The
+//! indentation of the block is available to `T` through [`IndentContext`], which allows for
+//! nesting blocks. The [`Dedent`] consumer checks whether the block ends after the current line.
+//!
+//! The indentation of a line is the amount of `' '` and `'\t'` characters at the start of the
+//! line. Lines which only contain those characters are skipped between the items of a block.
+//!
+//! # Examples
+//!
+//! ```
+//! use manger::{consume_struct, Consumable};
+//! use manger::chars;
+//! use manger::common::NoneOf;
+//! use manger::indent::Indented;
+//!
+//! manger::char_set!(LineEnd => "\r\n");
+//!
+//! struct Node(String, Vec<Node>);
+//! consume_struct!(
+//!     Node => [
+//!         name: Vec<NoneOf<LineEnd>>,
+//!         children: Option<(chars::NewLine, Indented<Node>)>;
+//!         (
+//!             name.into_iter().map(char::from).collect(),
+//!             children.map_or(Vec::new(), |(_, children)| children.into_vec())
+//!         )
+//!     ]
+//! );
+//!
+//! let tree = Node::consume_all("root\n  a\n    b\n  c")?;
+//!
+//! assert_eq!(tree.0, "root");
+//! assert_eq!(tree.1.len(), 2);
+//! assert_eq!(tree.1[0].1[0].0, "b");
+//! # Ok::<(), manger::ConsumeError>(())
+//! ```
+
+use crate::context;
+use crate::error::ConsumeErrorType::*;
+use crate::{Consumable, ConsumeError, ConsumeSource, LiteralConsumable, Render};
+use std::cell::Cell;
+
+thread_local! {
+    /// The indentation of the innermost block that is being consumed.
+    static LEVEL: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Restores the previous indentation level, also when unwinding.
+struct LevelGuard(usize);
+
+impl Drop for LevelGuard {
+    fn drop(&mut self) {
+        LEVEL.with(|level| level.set(self.0));
+    }
+}
+
+/// Access to the indentation level of the block that is being consumed.
+///
+/// The level is `0` outside of any block. [`Indented<T>`] sets the level to the indentation of its
+/// block while consuming its items.
+///
+/// # Examples
+///
+/// ```
+/// use manger::indent::IndentContext;
+///
+/// assert_eq!(IndentContext::level(), 0);
+///
+/// IndentContext::with_level(4, || {
+///     assert_eq!(IndentContext::level(), 4);
+/// });
+///
+/// assert_eq!(IndentContext::level(), 0);
+/// ```
+#[derive(Debug)]
+pub struct IndentContext;
+
+impl IndentContext {
+    /// Get the indentation level of the innermost block that is being consumed.
+    pub fn level() -> usize {
+        LEVEL.with(Cell::get)
+    }
+
+    /// Set the indentation level to `level` for the duration of `f`.
+    pub fn with_level<R>(level: usize, f: impl FnOnce() -> R) -> R {
+        let _guard = LevelGuard(LEVEL.with(|current| current.replace(level)));
+
+        f()
+    }
+}
+
+fn is_blank(token: char) -> bool {
+    token == ' ' || token == '\t'
+}
+
+/// Get the indentation of the line that starts at `source`.
+fn indentation(source: &str) -> usize {
+    source.len() - source.trim_start_matches(is_blank).len()
+}
+
+/// Strip a `"\n"` or `"\r\n"` from the start of `source`.
+fn strip_line_ending(source: &str) -> Option<&str> {
+    source
+        .strip_prefix('\n')
+        .or_else(|| source.strip_prefix("\r\n"))
+}
+
+/// Skip the line ending at the start of `source` and any following blank lines.
+///
+/// Returns the amount of skipped characters and the start of the next line with content, or
+/// `None` if `source` does not start with a line ending.
+fn next_line(source: &str) -> Option<(usize, &str)> {
+    let mut line = strip_line_ending(source)?;
+
+    while let Some(rest) = strip_line_ending(&line[indentation(line)..]) {
+        line = rest;
+    }
+
+    Some((source.len() - line.len(), line))
+}
+
+fn unexpected_token(source: &str, index: usize) -> ConsumeError {
+    ConsumeError::new_with(match utf8_slice::from(source, index).chars().next() {
+        Some(token) => UnexpectedToken { index, token },
+        None => InsufficientTokens { index },
+    })
+}
+
+/// A block of lines that are indented deeper than the enclosing block.
+///
+/// The block has to start at the start of a line, just as
+/// [`StartOfLine`][crate::common::StartOfLine]. The indentation of the first line sets the level
+/// of the block, which has to be deeper than [`IndentContext::level`]. Every line of the block is
+/// then consumed as a `T` while [`IndentContext::level`] returns the level of the block.
+///
+/// The block continues as long as the next line with content has the same indentation. The block
+/// ends before the line ending of the last item, so the enclosing consumer can continue with the
+/// next line. A next line that is indented deeper than the block, but was not consumed by `T`,
+/// results in an error.
+///
+/// # Examples
+///
+/// ```
+/// use manger::Consumable;
+/// use manger::indent::Indented;
+///
+/// let (block, unconsumed) = <Indented<u32>>::consume_from("  1\n\n  2\n3")?;
+///
+/// assert_eq!(block.level(), 2);
+/// assert_eq!(block.items(), &[1, 2]);
+/// assert_eq!(unconsumed, "\n3");
+///
+/// assert!(<Indented<u32>>::consume_from("1").is_err());
+/// assert!(<Indented<u32>>::consume_from("  1\n      2").is_err());
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Indented<T> {
+    level: usize,
+    items: Vec<T>,
+}
+
+impl<T> Indented<T> {
+    /// Get the indentation of the lines of the block.
+    pub fn level(&self) -> usize {
+        self.level
+    }
+
+    /// Get the items of the block in order of appearance.
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+
+    /// Take ownership of the items of the block.
+    pub fn into_vec(self) -> Vec<T> {
+        self.items
+    }
+}
+
+impl<T> IntoIterator for Indented<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.into_iter()
+    }
+}
+
+/// Renders every item on its own line, indented with [`level`][Indented::level] spaces.
+impl<T: Render> Render for Indented<T> {
+    fn render_to(&self, out: &mut String) {
+        for (index, item) in self.items.iter().enumerate() {
+            if index > 0 {
+                out.push('\n');
+            }
+            out.extend(std::iter::repeat(' ').take(self.level));
+            item.render_to(out);
+        }
+    }
+}
+
+impl<T: Consumable> Consumable for Indented<T> {
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        if !context::is_start_of_line(source) {
+            return Err(unexpected_token(source, 0));
+        }
+
+        let level = indentation(source);
+        if level <= IndentContext::level() {
+            return Err(unexpected_token(source, level));
+        }
+
+        let mut items = Vec::new();
+        let mut unconsumed = &source[level..];
+        let mut offset = level;
+
+        loop {
+            let (item, by) = IndentContext::with_level(level, || unconsumed.mut_consume_by::<T>())
+                .map_err(|err| err.offset(offset))?;
+            offset += by;
+            items.push(item);
+
+            let (skipped, line) = match next_line(unconsumed) {
+                Some(next) => next,
+                None => break,
+            };
+
+            let line_level = indentation(line);
+            if line_level > level && line_level < line.len() {
+                return Err(unexpected_token(unconsumed, skipped + line_level).offset(offset));
+            }
+            if line_level != level || line_level == line.len() {
+                break;
+            }
+
+            unconsumed = &line[level..];
+            offset += skipped + level;
+        }
+
+        Ok((Indented { level, items }, unconsumed))
+    }
+}
+
+/// End of the block that is being consumed.
+///
+/// Will succeed in consumation if the next line with content is indented less than
+/// [`IndentContext::level`], or if no line with content follows. Blank lines are skipped. It does
+/// not consume any tokens.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{consume_enum, Consumable};
+/// use manger::indent::{Dedent, Indented};
+///
+/// // Only the last item of a block can be a `0`.
+/// enum Item {
+///     Last,
+///     Number(u32),
+/// }
+/// consume_enum!(
+///     Item {
+///         Last => [ > '0', : Dedent; ],
+///         Number => [ number: u32 { |number| number != 0 }; (number) ]
+///     }
+/// );
+///
+/// assert!(<Indented<Item>>::consume_all(" 1\n 2\n 0").is_ok());
+/// assert!(<Indented<Item>>::consume_all(" 1\n 0\n 2").is_err());
+///
+/// assert_eq!(<(u32, Dedent)>::consume_from("5\n\n")?.1, "\n\n");
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+pub struct Dedent;
+
+impl Consumable for Dedent {
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let (skipped, line) = match next_line(source) {
+            Some(next) => next,
+            None if source.is_empty() => return Ok((Dedent, source)),
+            None => return Err(unexpected_token(source, 0)),
+        };
+
+        let line_level = indentation(line);
+        if line_level == line.len() || line_level < IndentContext::level() {
+            Ok((Dedent, source))
+        } else {
+            Err(unexpected_token(source, skipped + line_level))
+        }
+    }
+}
+
+impl LiteralConsumable for Dedent {}
+
+impl Render for Dedent {
+    fn render_to(&self, _: &mut String) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Dedent, IndentContext, Indented};
+    use crate::error::ConsumeErrorType::*;
+    use crate::{Consumable, ConsumeError};
+
+    #[test]
+    fn test_indented() {
+        let (block, unconsumed) =
+            <Indented<char>>::consume_from("\ta\n\tb\r\n \t\n\tc\nd").unwrap();
+        assert_eq!(block.into_vec(), vec!['a', 'b', 'c']);
+        assert_eq!(unconsumed, "\nd");
+
+        let (block, unconsumed) = <Indented<char>>::consume_from("  a\n b").unwrap();
+        assert_eq!(block.items(), &['a']);
+        assert_eq!(unconsumed, "\n b");
+
+        assert_eq!(
+            <Indented<char>>::consume_from("  a\n   b").unwrap_err(),
+            ConsumeError::new_with(UnexpectedToken {
+                index: 7,
+                token: 'b'
+            })
+        );
+        assert_eq!(
+            IndentContext::with_level(2, || <Indented<char>>::consume_from("  a")).unwrap_err(),
+            ConsumeError::new_with(UnexpectedToken {
+                index: 2,
+                token: 'a'
+            })
+        );
+        assert_eq!(
+            <Indented<u32>>::consume_from("  x").unwrap_err().causes()[0].index(),
+            &2
+        );
+    }
+
+    #[test]
+    fn test_dedent() {
+        assert!(Dedent::consume_from("").is_ok());
+        assert!(Dedent::consume_from("\n  \n").is_ok());
+        assert!(Dedent::consume_from("x").is_err());
+        assert!(Dedent::consume_from("\nx").is_err());
+        assert!(IndentContext::with_level(2, || Dedent::consume_from("\n x")).is_ok());
+        assert!(IndentContext::with_level(2, || Dedent::consume_from("\n  x")).is_err());
+    }
+}
@@ -0,0 +1,456 @@
+//! Runtime __information__ on the grammar of types defined with the macros.
+//!
+//! Prefixing a [`consume_struct`][crate::consume_struct!] or [`consume_enum`][crate::consume_enum!]
+//! definition with `#[info]` implements [`GrammarInfo`], which describes the consumed grammar as a
+//! [`RuleInfo`]. A rule lists its alternatives, where each alternative is a sequence of
+//! [`GrammarItem`]s. These are either literal text or a type that is consumed.
+//!
+//! This allows for generic tooling over grammars, such as documentation, pretty expectations in
+//! errors or completion engines. [`GrammarInfo::suggest`] lists what could have continued
+//! consuming where a `source` failed. The [`Display`][std::fmt::Display] of a [`RuleInfo`] gives a
+//! summary of the rule in EBNF.
+//!
+//! # Examples
+//!
+//! ```
+//! use manger::consume_struct;
+//! use manger::info::{GrammarInfo, ItemKind};
+//!
+//! struct Assignment {
+//!     name: char,
+//!     value: u32,
+//! }
+//! consume_struct!(
+//!     #[info]
+//!     Assignment => [
+//!         > "let ",
+//!         name: char,
+//!         > '=',
+//!         value: u32;
+//!     ]
+//! );
+//!
+//! let rule = Assignment::grammar_info();
+//! let items = &rule.alternatives()[0];
+//!
+//! assert_eq!(items[0].kind(), ItemKind::Literal);
+//! assert_eq!(items[0].text(), r#""let ""#);
+//! assert_eq!(items[1].kind(), ItemKind::Type);
+//! assert_eq!(items[1].name(), Some("name"));
+//! assert_eq!(items[1].text(), "char");
+//!
+//! assert_eq!(rule.to_string(), r#"Assignment = "let " , char , '=' , u32 ;"#);
+//! ```
+
+use crate::{Consumable, ConsumeError};
+use std::fmt;
+
+/// Consume an item from the start of a `source`, returning the unconsumed part.
+pub type Consumer = fn(&str) -> Result<&str, ConsumeError>;
+
+/// Trait for types which can describe their grammar at runtime.
+///
+/// This is implemented by the [`consume_struct`][crate::consume_struct!] and
+/// [`consume_enum`][crate::consume_enum!] macros with the `#[info]` option.
+///
+/// # Examples
+///
+/// ```
+/// use manger::consume_enum;
+/// use manger::info::GrammarInfo;
+///
+/// enum Toggle {
+///     On,
+///     Off,
+/// }
+/// consume_enum!(
+///     #[info]
+///     Toggle {
+///         On => [ > "on"; ],
+///         Off => [ > "off"; ]
+///     }
+/// );
+///
+/// let rule = Toggle::grammar_info();
+///
+/// assert_eq!(rule.name(), "Toggle");
+/// assert_eq!(rule.alternatives().len(), 2);
+/// assert_eq!(rule.to_string(), r#"Toggle = "on" | "off" ;"#);
+/// ```
+pub trait GrammarInfo: Consumable {
+    /// Get the rule which is consumed by this type.
+    fn grammar_info() -> RuleInfo;
+
+    /// List the items which could have continued consuming `source` at the point where it
+    /// failed.
+    ///
+    /// Every alternative of the rule consumes its items one by one until an item fails. The items
+    /// that failed furthest into the `source` are the suggestions, each listed once. Alternatives
+    /// which consume all their items give no suggestions. An item which fails somewhere within
+    /// itself is suggested at its start. This is the foundation for tab-completion or "expected one
+    /// of" hints.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use manger::consume_enum;
+    /// use manger::info::{GrammarInfo, ItemKind};
+    ///
+    /// enum Command {
+    ///     Get(char),
+    ///     Set(char, u32),
+    ///     Quit,
+    /// }
+    /// consume_enum!(
+    ///     #[info]
+    ///     Command {
+    ///         Get => [ > "get ", key: char; (key) ],
+    ///         Set => [ > "set ", key: char, > '=', value: u32; (key, value) ],
+    ///         Quit => [ > "quit"; ]
+    ///     }
+    /// );
+    ///
+    /// let texts = |source| {
+    ///     Command::suggest(source)
+    ///         .into_iter()
+    ///         .map(|expected| expected.text())
+    ///         .collect::<Vec<_>>()
+    /// };
+    ///
+    /// assert_eq!(texts(""), vec![r#""get ""#, r#""set ""#, r#""quit""#]);
+    /// assert_eq!(texts("set x"), vec!["'='"]);
+    ///
+    /// let expected = Command::suggest("set x=");
+    /// assert_eq!(expected[0].index(), 6);
+    /// assert_eq!(expected[0].kind(), ItemKind::Type);
+    /// assert_eq!(expected[0].to_string(), "u32");
+    /// ```
+    fn suggest(source: &str) -> Vec<Expected> {
+        let mut furthest = 0;
+        let mut suggestions: Vec<Expected> = Vec::new();
+
+        for alternative in Self::grammar_info().alternatives() {
+            let mut unconsumed = source;
+
+            for item in alternative {
+                match item.consume_from(unconsumed) {
+                    Ok(rest) => unconsumed = rest,
+                    Err(_) => {
+                        let expected = Expected {
+                            index: crate::strs::consumed_length(source, unconsumed),
+                            kind: item.kind(),
+                            text: item.text(),
+                        };
+
+                        if expected.index > furthest {
+                            furthest = expected.index;
+                            suggestions.clear();
+                        }
+                        if expected.index == furthest && !suggestions.contains(&expected) {
+                            suggestions.push(expected);
+                        }
+                        break;
+                    }
+                }
+            }
+        }
+
+        suggestions
+    }
+}
+
+/// An item which could have continued consuming at a certain utf-8 character index, as given by
+/// [`GrammarInfo::suggest`].
+///
+/// # Examples
+///
+/// ```
+/// use manger::consume_struct;
+/// use manger::info::{GrammarInfo, ItemKind};
+///
+/// struct Negative(u32);
+/// consume_struct!(
+///     #[info]
+///     Negative => [ > '-', value: u32; (value) ]
+/// );
+///
+/// let expected = Negative::suggest("+5");
+///
+/// assert_eq!(expected.len(), 1);
+/// assert_eq!(expected[0].index(), 0);
+/// assert_eq!(expected[0].kind(), ItemKind::Literal);
+/// assert_eq!(expected[0].text(), "'-'");
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Expected {
+    index: usize,
+    kind: ItemKind,
+    text: &'static str,
+}
+
+impl Expected {
+    /// Get the utf-8 character index within the `source` where the item was expected.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Get whether a literal or a type was expected.
+    pub fn kind(&self) -> ItemKind {
+        self.kind
+    }
+
+    /// Get the literal or type as written in the macro.
+    pub fn text(&self) -> &'static str {
+        self.text
+    }
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.text)
+    }
+}
+
+/// Whether a [`GrammarItem`] is literal text or a consumed type.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum ItemKind {
+    /// Literal text, given as `> EXPR` in the macros.
+    Literal,
+
+    /// A type, given as `: TYPE` or `name: TYPE` in the macros.
+    Type,
+}
+
+/// A single item within a sequence of a [`RuleInfo`].
+///
+/// The text of an item is the literal or type as written in the macro. Next to its description,
+/// an item can consume itself from a `source` with [`consume_from`][GrammarItem::consume_from].
+/// Conditions on a type are not checked when consuming this way.
+///
+/// # Examples
+///
+/// ```
+/// use manger::info::GrammarItem;
+/// use manger::{Consumable, ConsumeSource};
+///
+/// let item = GrammarItem::literal("'+'", |source| source.consume_lit(&'+'));
+///
+/// assert_eq!(item.to_string(), "'+'");
+/// assert_eq!(item.consume_from("+1"), Ok("1"));
+/// assert!(item.consume_from("1").is_err());
+///
+/// let item = GrammarItem::typed(Some("value"), "u8", |source| {
+///     u8::consume_from(source).map(|(_, unconsumed)| unconsumed)
+/// });
+///
+/// assert_eq!(item.name(), Some("value"));
+/// assert_eq!(item.consume_from("12;"), Ok(";"));
+/// ```
+#[derive(Clone, Copy)]
+pub struct GrammarItem {
+    kind: ItemKind,
+    name: Option<&'static str>,
+    text: &'static str,
+    consumer: Consumer,
+}
+
+impl GrammarItem {
+    /// Create an item of literal `text`, which is consumed with `consumer`.
+    pub fn literal(text: &'static str, consumer: Consumer) -> Self {
+        GrammarItem {
+            kind: ItemKind::Literal,
+            name: None,
+            text,
+            consumer,
+        }
+    }
+
+    /// Create an item of the type named `text`, which is consumed with `consumer` and saved as
+    /// `name`.
+    pub fn typed(name: Option<&'static str>, text: &'static str, consumer: Consumer) -> Self {
+        GrammarItem {
+            kind: ItemKind::Type,
+            name,
+            text,
+            consumer,
+        }
+    }
+
+    /// Get whether this item is a literal or a type.
+    pub fn kind(&self) -> ItemKind {
+        self.kind
+    }
+
+    /// Get the name the item is saved as, if any.
+    pub fn name(&self) -> Option<&'static str> {
+        self.name
+    }
+
+    /// Get the literal or type as written in the macro.
+    pub fn text(&self) -> &'static str {
+        self.text
+    }
+
+    /// Consume this item from the start of `source`, returning the unconsumed part.
+    pub fn consume_from<'a>(&self, source: &'a str) -> Result<&'a str, ConsumeError> {
+        (self.consumer)(source)
+    }
+}
+
+impl fmt::Debug for GrammarItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GrammarItem")
+            .field("kind", &self.kind)
+            .field("name", &self.name)
+            .field("text", &self.text)
+            .finish()
+    }
+}
+
+impl fmt::Display for GrammarItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.text)
+    }
+}
+
+/// The grammar of a type, as a list of alternatives which are sequences of [`GrammarItem`]s.
+///
+/// This is displayed as an EBNF rule.
+///
+/// # Examples
+///
+/// ```
+/// use manger::info::{GrammarItem, RuleInfo};
+/// use manger::ConsumeSource;
+///
+/// let rule = RuleInfo::new(
+///     "Sign",
+///     vec![
+///         vec![GrammarItem::literal("'+'", |source| source.consume_lit(&'+'))],
+///         vec![GrammarItem::literal("'-'", |source| source.consume_lit(&'-'))],
+///     ],
+/// );
+///
+/// assert_eq!(rule.to_string(), "Sign = '+' | '-' ;");
+/// ```
+#[derive(Debug, Clone)]
+pub struct RuleInfo {
+    name: &'static str,
+    alternatives: Vec<Vec<GrammarItem>>,
+}
+
+impl RuleInfo {
+    /// Create a rule called `name` with a sequence of items for every alternative.
+    pub fn new(name: &'static str, alternatives: Vec<Vec<GrammarItem>>) -> Self {
+        RuleInfo { name, alternatives }
+    }
+
+    /// Get the name of the rule.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Get the sequence of items of every alternative.
+    pub fn alternatives(&self) -> &[Vec<GrammarItem>] {
+        &self.alternatives
+    }
+}
+
+impl fmt::Display for RuleInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} = ", self.name)?;
+
+        for (index, alternative) in self.alternatives.iter().enumerate() {
+            if index > 0 {
+                f.write_str(" | ")?;
+            }
+
+            for (index, item) in alternative.iter().enumerate() {
+                if index > 0 {
+                    f.write_str(" , ")?;
+                }
+                write!(f, "{}", item)?;
+            }
+        }
+
+        f.write_str(" ;")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GrammarInfo, ItemKind};
+    use crate::{consume_enum, consume_struct};
+
+    #[derive(Debug, PartialEq)]
+    enum Expr {
+        Sum(Box<Expr>, Box<Expr>),
+        Num(u32),
+    }
+
+    consume_enum!(
+        #[info]
+        Expr {
+            Sum => { left_assoc, precedence = 1 } [
+                lhs: Self, > '+', rhs: Self;
+                (Box::new(lhs), Box::new(rhs))
+            ],
+            Num => [ value: u32; (value) ]
+        }
+    );
+
+    #[allow(dead_code)]
+    struct List {
+        items: Vec<u8>,
+    }
+
+    consume_struct!(
+        #[info]
+        List => [
+            > '[',
+            items: u8 [ ',' ],
+            > ']';
+        ]
+    );
+
+    #[test]
+    fn test_grammar_info() {
+        let rule = Expr::grammar_info();
+        assert_eq!(rule.to_string(), "Expr = Expr , '+' , Expr | u32 ;");
+
+        let sum = &rule.alternatives()[0];
+        assert_eq!(
+            sum.iter().map(|item| item.name()).collect::<Vec<_>>(),
+            vec![Some("lhs"), None, Some("rhs")]
+        );
+        assert_eq!(sum[0].consume_from("1+2"), Ok(""));
+        assert_eq!(sum[1].kind(), ItemKind::Literal);
+
+        let rule = List::grammar_info();
+        assert_eq!(
+            rule.to_string(),
+            "List = '[' , [ u8 , { ',' , u8 } ] , ']' ;"
+        );
+        assert_eq!(rule.alternatives()[0][1].consume_from("1,2]"), Ok("]"));
+    }
+
+    #[test]
+    fn test_suggest() {
+        let texts = |source| {
+            Expr::suggest(source)
+                .into_iter()
+                .map(|expected| (expected.index(), expected.text()))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(texts("x"), vec![(0, "Expr"), (0, "u32")]);
+        assert_eq!(texts("1+2"), vec![(3, "'+'")]);
+        assert_eq!(
+            List::suggest("[1,2")
+                .into_iter()
+                .map(|e| e.text())
+                .collect::<Vec<_>>(),
+            vec!["']'"]
+        );
+    }
+}
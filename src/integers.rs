@@ -1,33 +1,89 @@
-use crate::common::{Digit, OneOrMore, Sign};
-use crate::{ConsumeError, ConsumeErrorType};
+use crate::common::{FromDigits, OverflowMode};
+use crate::{ConsumeError, ConsumeErrorType, Render, SelfConsumable};
+
+macro_rules! impl_from_digits {
+    ( $type:ty, $signed:literal ) => {
+        impl FromDigits for $type {
+            const SIGNED: bool = $signed;
+
+            fn zero() -> Self {
+                0
+            }
+
+            fn push_digit(self, digit: u8, negative: bool, mode: OverflowMode) -> Option<Self> {
+                let digit = digit as $type;
+
+                match (mode, negative) {
+                    (OverflowMode::Checked, false) => {
+                        self.checked_mul(10).and_then(|num| num.checked_add(digit))
+                    }
+                    (OverflowMode::Checked, true) => {
+                        self.checked_mul(10).and_then(|num| num.checked_sub(digit))
+                    }
+                    (OverflowMode::Saturating, false) => {
+                        Some(self.saturating_mul(10).saturating_add(digit))
+                    }
+                    (OverflowMode::Saturating, true) => {
+                        Some(self.saturating_mul(10).saturating_sub(digit))
+                    }
+                    (OverflowMode::Wrapping, false) => {
+                        Some(self.wrapping_mul(10).wrapping_add(digit))
+                    }
+                    (OverflowMode::Wrapping, true) => {
+                        Some(self.wrapping_mul(10).wrapping_sub(digit))
+                    }
+                }
+            }
+        }
+    };
+}
+
+/// Consume the canonical form of the integer `item` from the start of `source`.
+///
+/// Fails if the digits are followed by another digit, because then `source` starts with a
+/// different integer.
+pub(crate) fn consume_integer_item<'a>(
+    source: &'a str,
+    item: &impl Render,
+) -> Result<&'a str, ConsumeError> {
+    let literal = item.render();
+    let unconsumed = <&str>::consume_item(source, &literal.as_str())?;
+
+    match unconsumed.chars().next() {
+        Some(token) if token.is_ascii_digit() => {
+            Err(ConsumeError::new_with(ConsumeErrorType::UnexpectedToken {
+                index: literal.len(),
+                token,
+            }))
+        }
+        _ => Ok(unconsumed),
+    }
+}
 
 macro_rules! impl_consume_uint {
     ( $type: ty, $test_name:ident$(, $plus_maxvalue:literal )? ) => {
         impl $crate::Consumable for $type {
-            fn consume_from(s: &str) -> Result<(Self, &str), ConsumeError> {
-                let (digits, unconsumed) = OneOrMore::<Digit>::consume_from(s)?;
-
-                let mut num: $type = 0;
-
-                for digit in digits.into_iter() {
-                    let digit = digit.value();
+            fn expecting() -> $crate::Expectation {
+                $crate::Expectation::new("an integer")
+            }
 
-                    num = num
-                        .checked_mul(10)
-                        .and_then(|num| num.checked_add(digit))
-                        .ok_or(ConsumeError::new_with(ConsumeErrorType::InvalidValue {
-                            index: 0,
-                        }))?;
-                }
+            fn consume_from(s: &str) -> Result<(Self, &str), ConsumeError> {
+                <$type as FromDigits>::consume_digits(s, OverflowMode::Checked)
+            }
+        }
 
-                Ok((num, unconsumed))
+        impl SelfConsumable for $type {
+            fn consume_item<'a>(source: &'a str, item: &'_ Self) -> Result<&'a str, ConsumeError> {
+                consume_integer_item(source, item)
             }
         }
 
+        impl_from_digits!($type, false);
+
         #[test]
         fn $test_name() {
             use crate::ConsumeErrorType::*;
-            use crate::{ ConsumeError, Consumable };
+            use crate::{ ConsumeError, Consumable, SelfConsumable };
 
             for i in <$type>::MIN..(<$type>::MIN + 10) {
                 assert_eq!(i, <$type>::consume_from(&format!("{}", i)).expect("MIN TEST FAILED").0);
@@ -39,20 +95,29 @@ macro_rules! impl_consume_uint {
 
             assert_eq!(
                 <$type>::consume_from("").unwrap_err(),
-                ConsumeError::new_from(
-                        vec![InsufficientTokens { index: 0 }; 10]
-                    )
+                ConsumeError::new_with(InsufficientTokens { index: 0 })
             );
             assert_eq!(
                 <$type>::consume_from("-123").unwrap_err(),
-                ConsumeError::new_from(
-                    vec![UnexpectedToken { index: 0, token: '-' }; 10]
-                )
+                ConsumeError::new_with(UnexpectedToken { index: 0, token: '-' })
+            );
+            assert_eq!(<$type>::consume_item("7x", &7), Ok("x"));
+            assert_eq!(
+                <$type>::consume_item("70", &7).unwrap_err(),
+                ConsumeError::new_with(UnexpectedToken { index: 1, token: '0' })
             );
             $(
             assert_eq!(
                 <$type>::consume_from($plus_maxvalue).unwrap_err(),
-                ConsumeError::new_with(InvalidValue { index: 0 })
+                ConsumeError::new_with(InvalidValue { index: $plus_maxvalue.len() - 1 })
+            );
+            assert_eq!(
+                <$type as crate::common::Integer>::consume_with($plus_maxvalue, OverflowMode::Saturating),
+                Ok((<$type>::MAX, ""))
+            );
+            assert_eq!(
+                <$type as crate::common::Integer>::consume_with($plus_maxvalue, OverflowMode::Wrapping),
+                Ok((0, ""))
             );
             )?
         }
@@ -62,32 +127,27 @@ macro_rules! impl_consume_uint {
 macro_rules! impl_consume_int {
     ( $type: ty, $test_name:ident$(, $plus_maxvalue:literal, $min_minvalue:literal )? ) => {
         impl $crate::Consumable for $type {
-            fn consume_from(s: &str) -> Result<(Self, &str), ConsumeError> {
-                let (sign, unconsumed) = Sign::consume_from(s)?;
-                let (digits, unconsumed) = OneOrMore::<Digit>::consume_from(unconsumed)?;
-
-                let mut num: $type = 0;
-                let normal = sign.normal::<$type>();
-
-                for digit in digits.into_iter() {
-                    let digit = normal * digit.value::<$type>();
+            fn expecting() -> $crate::Expectation {
+                $crate::Expectation::new("an integer")
+            }
 
-                    num = num
-                        .checked_mul(10)
-                        .and_then(|num| num.checked_add(digit))
-                        .ok_or(ConsumeError::new_with(ConsumeErrorType::InvalidValue {
-                            index: 0,
-                        }))?;
-                }
+            fn consume_from(s: &str) -> Result<(Self, &str), ConsumeError> {
+                <$type as FromDigits>::consume_digits(s, OverflowMode::Checked)
+            }
+        }
 
-                Ok((num, unconsumed))
+        impl SelfConsumable for $type {
+            fn consume_item<'a>(source: &'a str, item: &'_ Self) -> Result<&'a str, ConsumeError> {
+                consume_integer_item(source, item)
             }
         }
 
+        impl_from_digits!($type, true);
+
         #[test]
             fn $test_name() {
                 use crate::ConsumeErrorType::*;
-                use crate::{ ConsumeError, Consumable };
+                use crate::{ ConsumeError, Consumable, SelfConsumable };
 
                 for i in <$type>::MIN..(<$type>::MIN + 10) {
                     assert_eq!(i, <$type>::consume_from(&format!("{}", i)).expect("MIN TEST FAILED").0);
@@ -103,24 +163,33 @@ macro_rules! impl_consume_int {
 
                 assert_eq!(
                     <$type>::consume_from("").unwrap_err(),
-                    ConsumeError::new_from(
-                        vec![InsufficientTokens { index: 0 }; 10]
-                    )
+                    ConsumeError::new_with(InsufficientTokens { index: 0 })
                 );
                 assert_eq!(
                     <$type>::consume_from("a123").unwrap_err(),
-                    ConsumeError::new_from(
-                        vec![UnexpectedToken { index: 0, token: 'a' }; 10]
-                    )
+                    ConsumeError::new_with(UnexpectedToken { index: 0, token: 'a' })
+                );
+                assert_eq!(<$type>::consume_item("-7x", &-7), Ok("x"));
+                assert_eq!(
+                    <$type>::consume_item("-70", &-7).unwrap_err(),
+                    ConsumeError::new_with(UnexpectedToken { index: 2, token: '0' })
                 );
                 $(
                 assert_eq!(
                     <$type>::consume_from($plus_maxvalue).unwrap_err(),
-                    ConsumeError::new_with(InvalidValue { index: 0 })
+                    ConsumeError::new_with(InvalidValue { index: $plus_maxvalue.len() - 1 })
                 );
                 assert_eq!(
                     <$type>::consume_from($min_minvalue).unwrap_err(),
-                    ConsumeError::new_with(InvalidValue { index: 0 })
+                    ConsumeError::new_with(InvalidValue { index: $min_minvalue.len() - 1 })
+                );
+                assert_eq!(
+                    <$type as crate::common::Integer>::consume_with($min_minvalue, OverflowMode::Saturating),
+                    Ok((<$type>::MIN, ""))
+                );
+                assert_eq!(
+                    <$type as crate::common::Integer>::consume_with($plus_maxvalue, OverflowMode::Wrapping),
+                    Ok((<$type>::MIN, ""))
                 );
                 )?
             }
@@ -0,0 +1,607 @@
+//! Types for consuming __JSON__ values.
+//!
+//! The [`Json`] type is implemented with the same combinators that are available to every user of
+//! [manger][crate] and supports the full [JSON](https://www.json.org/json-en.html) grammar,
+//! including escape sequences and surrogate pairs. At most [`MAX_NESTING`] arrays and objects
+//! can be nested, so that deeply nested input cannot overflow the stack.
+//!
+//! This module is only available with the `json` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use manger::Consumable;
+//! use manger::json::Json;
+//!
+//! let source = r#"{ "name": "manger", "tags": ["parser", "é"], "version": 0.1 }"#;
+//! let json = Json::consume_all(source)?;
+//!
+//! assert_eq!(json.get("name").and_then(Json::as_str), Some("manger"));
+//! assert_eq!(json.get("version").and_then(Json::as_f64), Some(0.1));
+//! assert_eq!(
+//!     json.get("tags").and_then(Json::as_array).map(|tags| tags.len()),
+//!     Some(2)
+//! );
+//! # Ok::<(), manger::ConsumeError>(())
+//! ```
+
+use crate::chars::{self, alpha};
+#[cfg(feature = "deep-recursion")]
+use crate::common::DeepRecursion;
+use crate::common::{Digit, OneOrMore};
+use crate::error::ConsumeErrorType::*;
+use crate::limits::Limit;
+use crate::{consume_enum, consume_struct, Consumable, ConsumeError};
+use either::Either;
+use std::cell::Cell;
+
+/// The maximum amount of arrays and objects that can be nested within a [`Json`] value.
+///
+/// Consuming a value that nests deeper fails with a
+/// [`LimitExceeded`][crate::ConsumeErrorType::LimitExceeded] error at the start of the first array
+/// or object that is too deep.
+///
+/// # Examples
+///
+/// ```
+/// use manger::Consumable;
+/// use manger::json::{Json, MAX_NESTING};
+///
+/// let nested = |depth: usize| format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+///
+/// assert!(Json::consume_all(&nested(MAX_NESTING)).is_ok());
+/// assert!(Json::consume_all(&nested(MAX_NESTING + 1)).is_err());
+/// ```
+pub const MAX_NESTING: usize = 64;
+
+thread_local! {
+    /// The amount of arrays and objects that are being consumed on this thread.
+    static NESTING: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Restores the previous nesting, also when unwinding.
+struct NestingGuard(usize);
+
+impl Drop for NestingGuard {
+    fn drop(&mut self) {
+        NESTING.with(|nesting| nesting.set(self.0));
+    }
+}
+
+/// An array or object, which fails if it is nested more than [`MAX_NESTING`] levels deep.
+///
+/// With the `deep-recursion` feature, it is consumed as a `DeepRecursion`, since a single level of
+/// nesting takes many stack frames.
+struct Nested<T>(T);
+
+impl<T: Consumable> Consumable for Nested<T> {
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let nesting = NESTING.with(Cell::get);
+
+        if nesting >= MAX_NESTING {
+            return Err(ConsumeError::new_with(LimitExceeded {
+                index: 0,
+                limit: Limit::Depth,
+            }));
+        }
+
+        let _guard = NestingGuard(nesting);
+        NESTING.with(|current| current.set(nesting + 1));
+
+        #[cfg(feature = "deep-recursion")]
+        let consumed = DeepRecursion::<T>::consume_from(source)
+            .map(|(item, unconsumed)| (item.into_inner(), unconsumed));
+        #[cfg(not(feature = "deep-recursion"))]
+        let consumed = T::consume_from(source);
+
+        consumed.map(|(item, unconsumed)| (Nested(item), unconsumed))
+    }
+}
+
+/// Whitespace as allowed by JSON, which is a space, tab, line feed or carriage return.
+struct JsonWhitespace;
+
+consume_struct!(
+    JsonWhitespace => [
+        : char { |token: char| matches!(token, ' ' | '\t' | '\n' | '\r') };
+    ]
+);
+
+/// A JSON value.
+///
+/// Objects keep their members in the order they appeared in the `source`. Arrays and objects can
+/// be nested at most [`MAX_NESTING`] levels deep.
+///
+/// # Note
+///
+/// Consuming a [`Json`] value does not consume whitespace before or after the value. Whitespace
+/// within arrays and objects is consumed.
+///
+/// # Examples
+///
+/// ```
+/// use manger::Consumable;
+/// use manger::json::Json;
+///
+/// let (json, unconsumed) = Json::consume_from("[1, true, null] tail")?;
+///
+/// assert_eq!(
+///     json,
+///     Json::Array(vec![Json::Number(1.0), Json::Bool(true), Json::Null])
+/// );
+/// assert_eq!(unconsumed, " tail");
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub enum Json {
+    /// The `null` literal.
+    Null,
+
+    /// The `true` or `false` literals.
+    Bool(bool),
+
+    /// A number, which is always stored as a [`f64`].
+    Number(f64),
+
+    /// A string with all escape sequences resolved.
+    String(String),
+
+    /// An array of values.
+    Array(Vec<Json>),
+
+    /// An object with its members in order of appearance.
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    /// Returns whether the value is `null`.
+    pub fn is_null(&self) -> bool {
+        matches!(self, Json::Null)
+    }
+
+    /// Returns the boolean if the value is a [`Json::Bool`].
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Json::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns the number if the value is a [`Json::Number`].
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Json::Number(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns the string if the value is a [`Json::String`].
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the items if the value is a [`Json::Array`].
+    pub fn as_array(&self) -> Option<&Vec<Json>> {
+        match self {
+            Json::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    /// Returns the members if the value is a [`Json::Object`].
+    pub fn as_object(&self) -> Option<&Vec<(String, Json)>> {
+        match self {
+            Json::Object(members) => Some(members),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of the first member named `key` if the value is a [`Json::Object`].
+    pub fn get(&self, key: &str) -> Option<&Json> {
+        self.as_object()?
+            .iter()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value)
+    }
+}
+
+consume_enum!(
+    Json {
+        Null => [
+            > "null";
+        ],
+        Bool => [
+            value: JsonBool;
+            (value == JsonBool::True)
+        ],
+        Number => [
+            number: JsonNumber;
+            (number.0)
+        ],
+        String => [
+            string: JsonString;
+            (string.0)
+        ],
+        Array => [
+            array: Nested<JsonArray>;
+            (array.0 .0)
+        ],
+        Object => [
+            object: Nested<JsonObject>;
+            (object.0 .0)
+        ]
+    }
+);
+
+#[derive(PartialEq)]
+enum JsonBool {
+    True,
+    False,
+}
+
+consume_enum!(
+    JsonBool {
+        True => [ > "true"; ],
+        False => [ > "false"; ]
+    }
+);
+
+struct JsonNumber(f64);
+
+enum IntegerPart {
+    Zero,
+    NonZero,
+}
+
+consume_enum!(
+    IntegerPart {
+        Zero => [
+            > '0';
+        ],
+        NonZero => [
+            : char { |token: char| ('1'..='9').contains(&token) },
+            : Vec<Digit>;
+        ]
+    }
+);
+
+type NumberStructure = (
+    Option<chars::Hyphen>,
+    IntegerPart,
+    Option<(chars::Period, OneOrMore<Digit>)>,
+    Option<(
+        alpha::E,
+        Option<Either<chars::Plus, chars::Hyphen>>,
+        OneOrMore<Digit>,
+    )>,
+);
+
+impl Consumable for JsonNumber {
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let (_, unconsumed) = NumberStructure::consume_from(source)?;
+        let length = utf8_slice::len(source) - utf8_slice::len(unconsumed);
+
+        // Every JSON number is also an `f64`, so this consumes the whole number.
+        let (number, rest) = f64::consume_from(utf8_slice::till(source, length))?;
+        if !rest.is_empty() {
+            return Err(ConsumeError::new_with(InvalidValue {
+                index: length - utf8_slice::len(rest),
+            }));
+        }
+
+        Ok((JsonNumber(number), unconsumed))
+    }
+}
+
+struct HexDigit(u16);
+
+consume_struct!(
+    HexDigit => [
+        token: char { |token: char| token.is_ascii_hexdigit() };
+        (token.to_digit(16).unwrap_or_default() as u16)
+    ]
+);
+
+enum JsonChar {
+    Plain(char),
+    Escaped(char),
+    CodeUnit(u16),
+}
+
+consume_enum!(
+    JsonChar {
+        Plain => [
+            token: char { |token: char| token != '"' && token != '\\' && token >= '\u{20}' };
+            (token)
+        ],
+        Escaped => [
+            > '\\',
+            escapee: JsonEscapee;
+            (escapee.0)
+        ],
+        CodeUnit => [
+            > "\\u",
+            a: HexDigit,
+            b: HexDigit,
+            c: HexDigit,
+            d: HexDigit;
+            (a.0 << 12 | b.0 << 8 | c.0 << 4 | d.0)
+        ]
+    }
+);
+
+struct JsonEscapee(char);
+
+impl Consumable for JsonEscapee {
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let (token, unconsumed) = char::consume_from(source)?;
+
+        let escapee = match token {
+            '"' => '"',
+            '\\' => '\\',
+            '/' => '/',
+            'b' => '\u{8}',
+            'f' => '\u{c}',
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            _ => return Err(ConsumeError::new_with(UnexpectedToken { index: 0, token })),
+        };
+
+        Ok((JsonEscapee(escapee), unconsumed))
+    }
+}
+
+struct JsonString(String);
+
+impl Consumable for JsonString {
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let ((_, characters, _), unconsumed) =
+            <(chars::DoubleQuotes, Vec<JsonChar>, chars::DoubleQuotes)>::consume_from(source)?;
+
+        let mut string = String::with_capacity(characters.len());
+
+        // The index of the current character and a leading surrogate with the index of its escape,
+        // which has to be followed by a trailing surrogate.
+        let mut index = 1;
+        let mut leading: Option<(u16, usize)> = None;
+
+        for character in characters {
+            let (token, length) = match character {
+                JsonChar::CodeUnit(code_unit) => match leading.take() {
+                    Some((high, high_index)) => match std::char::decode_utf16([high, code_unit])
+                        .next()
+                    {
+                        Some(Ok(token)) => (token, 6),
+                        _ => {
+                            return Err(ConsumeError::new_with(InvalidValue { index: high_index }))
+                        }
+                    },
+                    None if (0xD800..0xDC00).contains(&code_unit) => {
+                        leading = Some((code_unit, index));
+                        index += 6;
+                        continue;
+                    }
+                    None => match char::from_u32(code_unit.into()) {
+                        Some(token) => (token, 6),
+                        None => return Err(ConsumeError::new_with(InvalidValue { index })),
+                    },
+                },
+                _ if leading.is_some() => break,
+                JsonChar::Plain(token) => (token, 1),
+                JsonChar::Escaped(token) => (token, 2),
+            };
+
+            string.push(token);
+            index += length;
+        }
+
+        if let Some((_, leading_index)) = leading {
+            return Err(ConsumeError::new_with(InvalidValue {
+                index: leading_index,
+            }));
+        }
+
+        Ok((JsonString(string), unconsumed))
+    }
+}
+
+struct JsonArray(Vec<Json>);
+
+struct JsonArrayItems(Vec<Json>);
+
+struct JsonArrayItem(Json);
+
+consume_struct!(
+    JsonArray => [
+        > '[',
+        : Vec<JsonWhitespace>,
+        items: Option<JsonArrayItems>,
+        : Vec<JsonWhitespace>,
+        > ']';
+        (items.map_or_else(Vec::new, |items| items.0))
+    ]
+);
+
+consume_struct!(
+    JsonArrayItems => [
+        head: Json,
+        tail: Vec<JsonArrayItem>;
+        (std::iter::once(head).chain(tail.into_iter().map(|item| item.0)).collect())
+    ]
+);
+
+consume_struct!(
+    JsonArrayItem => [
+        : Vec<JsonWhitespace>,
+        > ',',
+        : Vec<JsonWhitespace>,
+        item: Json;
+        (item)
+    ]
+);
+
+struct JsonObject(Vec<(String, Json)>);
+
+struct JsonObjectMembers(Vec<(String, Json)>);
+
+struct JsonObjectMember(String, Json);
+
+struct JsonObjectTailMember(JsonObjectMember);
+
+consume_struct!(
+    JsonObject => [
+        > '{',
+        : Vec<JsonWhitespace>,
+        members: Option<JsonObjectMembers>,
+        : Vec<JsonWhitespace>,
+        > '}';
+        (members.map_or_else(Vec::new, |members| members.0))
+    ]
+);
+
+consume_struct!(
+    JsonObjectMembers => [
+        head: JsonObjectMember,
+        tail: Vec<JsonObjectTailMember>;
+        (
+            std::iter::once(head)
+                .chain(tail.into_iter().map(|member| member.0))
+                .map(|JsonObjectMember(key, value)| (key, value))
+                .collect()
+        )
+    ]
+);
+
+consume_struct!(
+    JsonObjectMember => [
+        key: JsonString,
+        : Vec<JsonWhitespace>,
+        > ':',
+        : Vec<JsonWhitespace>,
+        value: Json;
+        (key.0, value)
+    ]
+);
+
+consume_struct!(
+    JsonObjectTailMember => [
+        : Vec<JsonWhitespace>,
+        > ',',
+        : Vec<JsonWhitespace>,
+        member: JsonObjectMember;
+        (member)
+    ]
+);
+
+#[cfg(test)]
+mod tests {
+    use super::{Json, JsonString, MAX_NESTING};
+    use crate::Consumable;
+
+    #[test]
+    fn test_json_literals() {
+        assert_eq!(Json::consume_all("null").unwrap(), Json::Null);
+        assert_eq!(Json::consume_all("true").unwrap(), Json::Bool(true));
+        assert_eq!(Json::consume_all("false").unwrap(), Json::Bool(false));
+        assert!(Json::consume_all("nul").is_err());
+    }
+
+    #[test]
+    fn test_json_numbers() {
+        assert_eq!(Json::consume_all("0").unwrap(), Json::Number(0.0));
+        assert_eq!(Json::consume_all("-12.5").unwrap(), Json::Number(-12.5));
+        assert_eq!(Json::consume_all("1e3").unwrap(), Json::Number(1000.0));
+        assert_eq!(Json::consume_all("2.5E-1").unwrap(), Json::Number(0.25));
+        assert_eq!(Json::consume_from("012").unwrap().1, "12");
+        assert!(Json::consume_all("-").is_err());
+        assert!(Json::consume_all("1.").is_err());
+    }
+
+    #[test]
+    fn test_json_strings() {
+        assert_eq!(
+            Json::consume_all(r#""a\"b\\c\/d\n""#).unwrap(),
+            Json::String("a\"b\\c/d\n".to_string())
+        );
+        assert_eq!(
+            Json::consume_all(r#""é😀""#).unwrap(),
+            Json::String("é😀".to_string())
+        );
+        assert_eq!(
+            Json::consume_all(r#""\ud83d\ude00 \u00e9""#).unwrap(),
+            Json::String("😀 é".to_string())
+        );
+        assert_eq!(
+            Json::consume_all("\"\u{7f}\u{9f}\"").unwrap(),
+            Json::String("\u{7f}\u{9f}".to_string())
+        );
+        assert!(Json::consume_all(r#""\ud83d""#).is_err());
+        assert!(Json::consume_all(r#""\x""#).is_err());
+        assert!(Json::consume_all("\"a\nb\"").is_err());
+        assert!(Json::consume_all("\"\u{1f}\"").is_err());
+        assert!(Json::consume_all("\"abc").is_err());
+    }
+
+    #[test]
+    fn test_json_string_error_indices() {
+        let index = |source: &str| {
+            let error = JsonString::consume_from(source).err()?;
+
+            error.causes().first().map(|cause| *cause.index())
+        };
+
+        assert_eq!(index(r#""ab\ud83d""#), Some(3));
+        assert_eq!(index(r#""ab\ud83dc""#), Some(3));
+        assert_eq!(index(r#""\n\ud83d\u0041""#), Some(3));
+        assert_eq!(index(r#""a\ude00""#), Some(2));
+    }
+
+    #[test]
+    fn test_json_whitespace() {
+        assert!(Json::consume_all("[ 1,\t2 ,\r\n3 ]").is_ok());
+        assert!(Json::consume_all("[\u{a0}1]").is_err());
+        assert!(Json::consume_all("[\u{c}1]").is_err());
+    }
+
+    #[test]
+    fn test_json_deep_nesting() {
+        let nested =
+            |depth: usize| format!("{}null{}", "[{\"a\":".repeat(depth), "}]".repeat(depth));
+
+        assert!(Json::consume_all(&nested(MAX_NESTING / 2)).is_ok());
+
+        assert!(Json::consume_all(&nested(MAX_NESTING / 2 + 1)).is_err());
+        assert!(Json::consume_all(&nested(100_000)).is_err());
+    }
+
+    #[test]
+    fn test_json_nesting() {
+        assert_eq!(
+            Json::consume_all(r#"{"a": [1, {"b": []}, {}], "c" : null}"#).unwrap(),
+            Json::Object(vec![
+                (
+                    "a".to_string(),
+                    Json::Array(vec![
+                        Json::Number(1.0),
+                        Json::Object(vec![("b".to_string(), Json::Array(vec![]))]),
+                        Json::Object(vec![]),
+                    ])
+                ),
+                ("c".to_string(), Json::Null),
+            ])
+        );
+        assert_eq!(
+            Json::consume_all("[[[[]]]]").unwrap(),
+            Json::Array(vec![Json::Array(vec![Json::Array(vec![Json::Array(
+                vec![]
+            )])])])
+        );
+        assert!(Json::consume_all("[1,]").is_err());
+        assert!(Json::consume_all(r#"{"a" 1}"#).is_err());
+        assert!(Json::consume_all("[1").is_err());
+    }
+}
@@ -77,7 +77,12 @@
 //! Here you can see how what that looks like:
 //!
 //! > Since [`Vec<T>`][std::vec::Vec] will consume instances of type `T` until it finds a error, it
-//! can never fail itself. You are therefore safe to unwrap the result.
+//! > can never fail itself, unless a maximum amount of repetitions is enforced with
+//! > [`limits`]. You are therefore safe to unwrap the result.
+//! >
+//! > An instance that consumes nothing, such as a `None` within a `Vec<Option<T>>`, also stops the
+//! > repetition and is not kept. Otherwise, it would be consumed over and over again at the same
+//! > position. The same holds for [`consume_iter`][Consumable::consume_iter].
 //!
 //! ```
 //! use manger::{ Consumable, consume_struct };
@@ -144,7 +149,7 @@
 //! type. This will consume either 0 or 1 of type `T`.
 //!
 //! > Since [`Option<T>`][std::option::Option] will consume a instance of type `T` if it finds no error, it
-//! can never fail itself. You are therefore safe to unwrap the result.
+//! > can never fail itself. You are therefore safe to unwrap the result.
 //!
 //! ```
 //! use manger::consume_struct;
@@ -204,6 +209,10 @@
 //! );
 //! ```
 //!
+//! Every level of nesting uses the call stack. If the `source` can be nested very deeply, such as
+//! with untrusted input, use `DeepRecursion<T>` of the `deep-recursion` feature instead of
+//! `Box<T>` to avoid a stack overflow.
+//!
 //! ### Whitespace
 //!
 //! For whitespace we can use the [Whitespace][crate::common::Whitespace] struct. This will consume any
@@ -227,7 +236,76 @@
 //! relationship. This option is preferred if we do not care about which option is selected.
 
 #[doc(inline)]
-pub use error::{ConsumeError, ConsumeErrorType};
+pub use cursor::Cursor;
+
+#[doc(inline)]
+pub use error::{ConsumeError, ConsumeErrorType, Expectation, MergePolicy, Severity, TaggedCause};
+
+/// Attribute to generate the [`Consumable`] implementation of a `struct` or `enum` from an
+/// [EBNF](https://en.wikipedia.org/wiki/Extended_Backus–Naur_form) rule. Only available with
+/// the `grammar` feature.
+///
+/// The rule is translated into an invocation of [`consume_struct`] or [`consume_enum`], so the
+/// item behaves exactly as if it was written with those macros. The rule is written as follows.
+///
+/// - It optionally starts with `name :=`, which is only there for documentation, and optionally
+///   ends with a `;`.
+/// - A `'c'` or `"str"` literal is consumed and thrown away, like `> 'c'` in the macros.
+/// - A Rust type, such as `u32` or `Vec<char>`, is consumed and thrown away, while `name: Type`
+///   saves it into the field `name`. For tuple structs and variants, the saved items fill the
+///   fields in order.
+/// - A term suffixed with `*`, `+` or `?` is consumed into a [`Vec`],
+///   [`OneOrMore`][crate::common::OneOrMore] or [`Option`] respectively.
+/// - Terms within `( ... )` are consumed into a tuple. Character literals within a group are
+///   consumed as a [`Char`][crate::chars::Char].
+/// - The alternatives of an `enum` are separated by `|`, and each alternative starts with the
+///   variant it fills as `Variant =>`.
+///
+/// The options `render`, `ebnf` and `info` can follow the rule, which are passed on to the
+/// macros. Generic types are not supported.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{grammar, Consumable};
+///
+/// #[grammar("expr := left: u32 rest: ('+' u32)*")]
+/// struct Sum {
+///     left: u32,
+///     rest: Vec<(manger::chars::Char<'+'>, u32)>,
+/// }
+///
+/// impl Sum {
+///     fn value(&self) -> u32 {
+///         self.left + self.rest.iter().map(|(_, right)| right).sum::<u32>()
+///     }
+/// }
+///
+/// let (sum, unconsumed) = Sum::consume_from("1+2+3;")?;
+/// assert_eq!(sum.value(), 6);
+/// assert_eq!(unconsumed, ";");
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+///
+/// An `enum` with a tuple variant and a unit variant.
+///
+/// ```
+/// use manger::{grammar, Consumable};
+///
+/// #[derive(Debug, PartialEq)]
+/// #[grammar(r#"Toggle => "toggle" | Set => "set " value: u8 "%"; "#, render)]
+/// enum Command {
+///     Toggle,
+///     Set(u8),
+/// }
+///
+/// assert_eq!(Command::consume_all("set 80%")?, Command::Set(80));
+/// assert_eq!(Command::consume_all("toggle")?, Command::Toggle);
+/// assert!(Command::consume_all("set 80").is_err());
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[cfg(feature = "grammar")]
+pub use manger_macro::grammar;
 
 /// Trait that defines whether a trait can be interpretted for a `source` string or not. It is the
 /// trait that defines most behaviour for [manger][crate].
@@ -278,6 +356,17 @@ pub use error::{ConsumeError, ConsumeErrorType};
 ///
 /// assert_eq!(product, -60);
 /// ```
+///
+/// # Panics
+///
+/// None of the implementations within [manger][crate] panic, whatever the `source` string is. This
+/// also holds for the implementations generated by [`consume_struct`] and [`consume_enum`], as long
+/// as the conditions and mappings within the definition do not panic. Custom implementations are
+/// expected to uphold the same guarantee.
+///
+/// A grammar that recurses through a [`Box<T>`] can still overflow the stack on deeply nested
+/// input, which aborts the process. Use `DeepRecursion<T>` of the `deep-recursion` feature or a
+/// maximum depth from the [`limits`] for such grammars.
 pub trait Consumable: Sized {
     /// Attempt consume from `source` to form an item of `Self`. When consuming is
     /// succesful, it returns the item along with the unconsumed part of the source.
@@ -307,6 +396,35 @@ pub trait Consumable: Sized {
     /// ```
     fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError>;
 
+    /// Get a description of what `Self` consumes, which is used within errors.
+    ///
+    /// By default, this is the name of the type. [`consume_struct`] and [`consume_enum`] describe
+    /// the sequence they consume and the built-in types give a short description.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use manger::{consume_struct, lit, Consumable, Expectation};
+    ///
+    /// lit!("let" => Let);
+    /// assert_eq!(Let::expecting(), Expectation::new("`let`"));
+    ///
+    /// struct Binding(char, u32);
+    /// consume_struct!(
+    ///     Binding => [
+    ///         : Let,
+    ///         name: char,
+    ///         > '=',
+    ///         value: u32;
+    ///         (name, value)
+    ///     ]
+    /// );
+    /// assert_eq!(Binding::expecting(), Expectation::new("Let , char , '=' , u32"));
+    /// ```
+    fn expecting() -> Expectation {
+        Expectation::new(std::any::type_name::<Self>())
+    }
+
     /// Attempt consume from `source` to form an item of `Self`. When consuming is
     /// succesful, it returns the item along with the unconsumed part of the source
     /// and the amount of consumed characters.
@@ -333,15 +451,16 @@ pub trait Consumable: Sized {
     /// # Ok::<(), manger::ConsumeError>(())
     /// ```
     fn consume_how_many_from(source: &str) -> Result<(Self, &str, usize), ConsumeError> {
-        let start_len = utf8_slice::len(source);
         let (item, unconsumed) = Self::consume_from(source)?;
-        let end_len = utf8_slice::len(unconsumed);
+        let length = strs::consumed_length(source, unconsumed);
 
-        Ok((item, unconsumed, start_len - end_len))
+        Ok((item, unconsumed, length))
     }
 
     /// Fetch a iterator of `source` to inorderly consume items of `Self`.
     ///
+    /// The iterator ends when an item cannot be consumed, or when an item consumes nothing.
+    ///
     /// # Examples
     ///
     /// ```
@@ -368,7 +487,43 @@ pub trait Consumable: Sized {
     fn consume_iter<'a>(source: &'a str) -> ConsumeIter<'a, Self> {
         ConsumeIter {
             phantom: std::marker::PhantomData,
+            source,
             unconsumed: source,
+            finished: false,
+        }
+    }
+
+    /// Fetch a iterator over the parts of `source` between the occurences of `delimiter`,
+    /// starting from the end, which consumes every part as a whole as an item of `Self`.
+    ///
+    /// The iterator ends when a part cannot be consumed as a whole. The iterator is double-ended,
+    /// so [`next_back`][DoubleEndedIterator::next_back] consumes the parts from the start
+    /// instead. This is useful for formats which are structured by their suffix, such as file
+    /// extensions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use manger::Consumable;
+    ///
+    /// let mut versions = u32::rsplit_consume_iter("1.22.333", '.');
+    /// assert_eq!(versions.next(), Some(333));
+    /// assert_eq!(versions.next_back(), Some(1));
+    /// assert_eq!(versions.next(), Some(22));
+    /// assert_eq!(versions.next(), None);
+    ///
+    /// let extensions: Vec<String> = <Vec<char>>::rsplit_consume_iter("archive.tar.gz", '.')
+    ///     .take(2)
+    ///     .map(|extension| extension.into_iter().collect())
+    ///     .collect();
+    /// assert_eq!(extensions, vec!["gz", "tar"]);
+    /// ```
+    fn rsplit_consume_iter(source: &str, delimiter: char) -> RSplitConsumeIter<'_, Self> {
+        RSplitConsumeIter {
+            phantom: std::marker::PhantomData,
+            source,
+            parts: source.rsplit(delimiter),
+            finished: false,
         }
     }
 
@@ -391,20 +546,269 @@ pub trait Consumable: Sized {
     /// # Ok::<(), manger::ConsumeError>(())
     /// ```
     fn consume_all(source: &str) -> Result<Self, ConsumeError> {
-        <(Self, crate::common::End)>::consume_from(source).map(|((item, _), _)| item)
+        context::scope(source, || {
+            <(Self, crate::common::End)>::consume_from(source)
+        })
+        .map(|((item, _), _)| item)
+    }
+
+    /// Parse an item of Self from the whole `source`.
+    ///
+    /// This is the same as [`consume_all`][Consumable::consume_all] and fails when not all of
+    /// `source` is consumed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use manger::Consumable;
+    ///
+    /// assert_eq!(u32::parse_exact("42")?, 42);
+    /// assert!(u32::parse_exact("42 ").is_err());
+    /// # Ok::<(), manger::ConsumeError>(())
+    /// ```
+    fn parse_exact(source: &str) -> Result<Self, ConsumeError> {
+        Self::consume_all(source)
+    }
+
+    /// Parse an item of Self from the start of `source`.
+    ///
+    /// This is the same as [`consume_from`][Consumable::consume_from], but registers `source` as
+    /// the whole input within [`context`], so consumers that depend on their position work as
+    /// expected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use manger::Consumable;
+    /// use manger::common::Start;
+    ///
+    /// let (value, unconsumed) = <(Start, u32)>::parse_prefix("42 is the answer")?;
+    ///
+    /// assert_eq!(value.1, 42);
+    /// assert_eq!(unconsumed, " is the answer");
+    /// # Ok::<(), manger::ConsumeError>(())
+    /// ```
+    fn parse_prefix(source: &str) -> Result<(Self, &str), ConsumeError> {
+        context::scope(source, || Self::consume_from(source))
+    }
+
+    /// Parse an item of Self from the start of `source`, where `source` starts at the utf-8
+    /// character index `offset` of a larger document.
+    ///
+    /// This is the same as [`parse_prefix`][Consumable::parse_prefix], but all indices within the
+    /// error are moved by `offset`. So they are indices within the larger document instead of
+    /// within `source`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use manger::{Consumable, ConsumeErrorType};
+    ///
+    /// let document = "width = 42\nheight = x";
+    /// let value = &document[20..];
+    ///
+    /// let error = u32::consume_from_at(value, 20).unwrap_err();
+    ///
+    /// assert_eq!(
+    ///     error.causes(),
+    ///     vec![&ConsumeErrorType::UnexpectedToken { index: 20, token: 'x' }]
+    /// );
+    /// ```
+    fn consume_from_at(source: &str, offset: usize) -> Result<(Self, &str), ConsumeError> {
+        Self::parse_prefix(source).map_err(|err| err.offset(offset))
+    }
+
+    /// Consume an item of Self from the start of `source`, while recording every attempt to
+    /// consume into a [`ParseTrace`][trace::ParseTrace] tree.
+    ///
+    /// This is the same as [`consume_from`][Consumable::consume_from], but also returns the tree
+    /// of attempts, which is useful for finding out why a `source` is not consumed as expected.
+    /// Only available with the `debug-trace` feature, so other builds do not pay for the
+    /// recording.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use manger::{consume_struct, Consumable};
+    /// use manger::trace::Outcome;
+    ///
+    /// struct Pair(u32, Option<u32>);
+    /// consume_struct!(Pair => [a: u32, b: Option<u32>; (a, b)]);
+    ///
+    /// let (result, trace) = Pair::consume_debug_from("12;");
+    ///
+    /// assert_eq!(result.map(|(_, unconsumed)| unconsumed), Ok(";"));
+    /// assert_eq!(trace.span(), 0..2);
+    ///
+    /// let option = &trace.children()[0];
+    /// assert_eq!(option.span(), 2..2);
+    /// assert_eq!(option.outcome(), &Outcome::Consumed);
+    /// ```
+    #[cfg(feature = "debug-trace")]
+    fn consume_debug_from(source: &str) -> (Result<(Self, &str), ConsumeError>, trace::ParseTrace) {
+        trace::record_session::<Self, _>(source, || Self::consume_from(source))
+    }
+
+    /// Consume an item of Self from the start of `source`, while enforcing `limits`.
+    ///
+    /// This is the same as [`parse_prefix`][Consumable::parse_prefix], but fails with a
+    /// [`LimitExceeded`][ConsumeErrorType::LimitExceeded] error when consuming exceeds one of the
+    /// [`Limits`][limits::Limits]. At most one character more than the maximum length is looked
+    /// at.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use manger::{Consumable, ConsumeErrorType};
+    /// use manger::limits::{Limit, Limits};
+    ///
+    /// let limits = Limits {
+    ///     max_repetitions: Some(2),
+    ///     ..Limits::default()
+    /// };
+    ///
+    /// let (digits, unconsumed) = <Vec<u8>>::consume_from_with_limits("4 2", limits)?;
+    /// assert_eq!((digits, unconsumed), (vec![4], " 2"));
+    ///
+    /// let error = <Vec<char>>::consume_from_with_limits("abc", limits).unwrap_err();
+    /// assert_eq!(
+    ///     error.causes(),
+    ///     vec![&ConsumeErrorType::LimitExceeded { index: 2, limit: Limit::Repetitions }]
+    /// );
+    /// # Ok::<(), manger::ConsumeError>(())
+    /// ```
+    fn consume_from_with_limits(
+        source: &str,
+        limits: limits::Limits,
+    ) -> Result<(Self, &str), ConsumeError> {
+        let limited = limits::limit_length(source, limits);
+        let max_length = limits.max_length.unwrap_or(usize::MAX);
+
+        match limits::scope(limits, || {
+            context::scope(source, || Self::consume_how_many_from(limited))
+        }) {
+            Ok((_, _, by)) if by > max_length => {
+                Err(ConsumeError::new_with(ConsumeErrorType::LimitExceeded {
+                    index: max_length,
+                    limit: limits::Limit::Length,
+                }))
+            }
+            Ok((item, unconsumed, _)) => Ok((item, &source[limited.len() - unconsumed.len()..])),
+            Err(mut err) => {
+                if limited.len() < source.len()
+                    && err.causes().iter().any(|cause| *cause.index() > max_length)
+                {
+                    err.add_cause(ConsumeErrorType::LimitExceeded {
+                        index: max_length,
+                        limit: limits::Limit::Length,
+                    });
+                }
+
+                Err(err)
+            }
+        }
+    }
+
+    /// Consume items of `Self` from the whole `source`, collecting all the errors on the way.
+    ///
+    /// Items are consumed as a [`Recovered<Self, R>`][crate::common::Recovered], so every item
+    /// that fails to consume is skipped by consuming a recovery type `R` and its error is
+    /// recorded. When `R` fails as well, the characters are skipped one by one until consuming is
+    /// possible again. Only one error is recorded for those skipped characters.
+    ///
+    /// The indices of the returned errors are relative to the start of `source`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use manger::{char_set, Consumable};
+    /// use manger::chars;
+    /// use manger::common::NoneOf;
+    ///
+    /// char_set!(Semicolon => ";");
+    ///
+    /// type Statement = (u32, chars::Semicolon);
+    /// type SkipStatement = (Vec<NoneOf<Semicolon>>, chars::Semicolon);
+    ///
+    /// let (statements, errors) =
+    ///     Statement::consume_all_diagnostics::<SkipStatement>("1;two;3;4");
+    ///
+    /// assert_eq!(statements.len(), 2);
+    /// assert_eq!(errors.len(), 2);
+    /// assert_eq!(errors[0].causes()[0].index(), &2);
+    /// assert_eq!(errors[1].causes()[0].index(), &9);
+    /// ```
+    fn consume_all_diagnostics<R: Consumable>(source: &str) -> (Vec<Self>, Vec<ConsumeError>) {
+        let mut items = Vec::new();
+        let mut errors = Vec::new();
+
+        context::scope(source, || {
+            let mut unconsumed = source;
+            let mut offset = 0;
+            let mut syncing = false;
+
+            while let Some(token) = unconsumed.chars().next() {
+                match <crate::common::Recovered<Self, R>>::consume_how_many_from(unconsumed) {
+                    Ok((recovered, rest, by)) if by > 0 => {
+                        match recovered.into_result() {
+                            Ok(item) => items.push(item),
+                            Err(err) => errors.push(err.offset(offset)),
+                        }
+
+                        unconsumed = rest;
+                        offset += by;
+                        syncing = false;
+                    }
+                    result => {
+                        if !syncing {
+                            errors.push(
+                                result
+                                    .err()
+                                    .unwrap_or_else(|| {
+                                        ConsumeError::new_with(ConsumeErrorType::UnexpectedToken {
+                                            index: 0,
+                                            token,
+                                        })
+                                    })
+                                    .offset(offset),
+                            );
+                        }
+
+                        unconsumed = utf8_slice::from(unconsumed, 1);
+                        offset += 1;
+                        syncing = true;
+                    }
+                }
+            }
+        });
+
+        (items, errors)
     }
 }
 
 /// Trait which allows for consuming of instances and literals from a string.
 ///
 /// This trait should be mostly used for types with a bijection to a string representation,
-/// which includes the `char` and `&str`. This does not include floating points, because
-/// "42" and "4.2e1" will both consume to 42.
+/// which includes the `char`, `&str`, `bool` and the integer types. This does not include
+/// floating points, because "42" and "4.2e1" will both consume to 42.
 ///
 /// # Note
 ///
-/// For the reason mentioned before, this is not implemented for `f32` and `f64`. Similarly,
-/// this is also not implemented for `u8`, `u16`, `u32`, `u64`, `i8`, `i1
+/// For the reason mentioned before, this is not implemented for `f32` and `f64`. The integer
+/// types only consume their canonical form, which is the form [`Render`] gives. So `42u32` does
+/// not consume `"042"` or `"+42"`, and it does not consume `"420"` either, because an integer
+/// literal may not be followed by another digit.
+///
+/// ```
+/// use manger::ConsumeSource;
+///
+/// assert_eq!("42,-7".consume_lit(&42u32)?.consume_lit(&',')?.consume_lit(&-7i8)?, "");
+/// assert_eq!("true!".consume_lit(&true)?, "!");
+///
+/// assert!("420".consume_lit(&42u32).is_err());
+/// assert!("+42".consume_lit(&42u32).is_err());
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
 pub trait SelfConsumable {
     /// Attempt to consume a literal `item` from a `source` string. When consuming
     /// is succesful, it will return the unconsumed part of the `source`. When consuming
@@ -439,9 +843,115 @@ pub trait SelfConsumable {
     fn consume_item<'a>(source: &'a str, item: &'_ Self) -> Result<&'a str, ConsumeError>;
 }
 
+/// Marker trait for zero-sized consumers, such as [`chars::Space`] or the types generated by
+/// [`lit`], which can also be used as a literal.
+///
+/// Every `LiteralConsumable` is [`SelfConsumable`], where consuming the literal consumes an item
+/// of the type and throws it away. This allows for passing a zero-sized consumer wherever a
+/// literal is expected, such as [`ConsumeSource::consume_lit`] and the `> EXPR` instruction of
+/// [`consume_struct`] and [`consume_enum`].
+///
+/// # Examples
+///
+/// ```
+/// use manger::{lit, ConsumeSource};
+/// use manger::chars::{Comma, Space};
+///
+/// lit! {
+///     "let" => Let
+/// }
+///
+/// let unconsumed = "let x, y".consume_lit(&Let)?.consume_lit(&Space::new())?;
+/// assert_eq!(unconsumed, "x, y");
+///
+/// let unconsumed = unconsumed.consume_lit(&'x')?.consume_lit(&Comma::new())?;
+/// assert_eq!(unconsumed, " y");
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+pub trait LiteralConsumable: Consumable {}
+
+impl<T: LiteralConsumable> SelfConsumable for T {
+    fn consume_item<'a>(source: &'a str, _: &'_ Self) -> Result<&'a str, ConsumeError> {
+        T::consume_from(source).map(|(_, unconsumed)| unconsumed)
+    }
+}
+
+/// Trait that defines the inverse of [`Consumable`]: turning an item back into a string.
+///
+/// Rendering an item and consuming the result should give back an equal item. Tokens which are
+/// not saved in the item, such as literals and unnamed properties within [`consume_struct`] and
+/// [`consume_enum`], are rendered in their canonical form. Therefore, rendering does not always
+/// give back the exact `source` the item was consumed from.
+///
+/// # Implementation
+///
+/// Both [`consume_struct`] and [`consume_enum`] can generate an implementation of [`Render`] from
+/// the same description used to consume, by prefixing the description with `#[render]`.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{consume_struct, Consumable, Render};
+/// use manger::chars;
+///
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+/// consume_struct!(
+///     #[render]
+///     Point => [
+///         > '(',
+///         x: i32,
+///         > ',',
+///         : Vec<chars::Space>,
+///         y: i32,
+///         > ')';
+///     ]
+/// );
+///
+/// let point = Point::consume_all("(3,   -4)")?;
+///
+/// assert_eq!(point.render(), "(3,-4)");
+/// assert_eq!((point, ';', 42u8).render(), "(3,-4);42");
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+pub trait Render {
+    /// Append the string representation of `self` to `out`.
+    ///
+    /// This is the core function to implement when implementing [`Render`](#).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use manger::Render;
+    ///
+    /// let mut out = String::from("answer: ");
+    /// 42u32.render_to(&mut out);
+    ///
+    /// assert_eq!(out, "answer: 42");
+    /// ```
+    fn render_to(&self, out: &mut String);
+
+    /// Get the string representation of `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use manger::Render;
+    ///
+    /// assert_eq!((-5i32, Some('!'), vec!["ab", "c"]).render(), "-5!abc");
+    /// ```
+    fn render(&self) -> String {
+        let mut out = String::new();
+        self.render_to(&mut out);
+        out
+    }
+}
+
 /// Trait that exposes some functions for easier consuming syntax on `&str`.
 ///
-/// ConsumeSource is only implemented for `&str`.
+/// ConsumeSource is implemented for `&str` and for [`Cursor`], which also tracks its position.
 pub trait ConsumeSource: Sized {
     /// A shorthand for the [`consume_item`](trait.SelfConsumable.html#tymethod.consume_item).
     /// Here the `source` is `self` and the `item` is `literal`.
@@ -565,9 +1075,104 @@ pub trait ConsumeSource: Sized {
     /// # Ok::<(), manger::ConsumeError>(())
     /// ```
     fn mut_consume_by<T: Consumable>(&mut self) -> Result<(T, usize), ConsumeError>;
+
+    /// Save the current state of `self`, so that it can be restored later with
+    /// [`restore`][ConsumeSource::restore].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use manger::ConsumeSource;
+    ///
+    /// let mut source = "42, 43";
+    ///
+    /// let checkpoint = source.checkpoint();
+    /// source.mut_consume::<u32>()?;
+    ///
+    /// if source.mut_consume_lit(&';').is_err() {
+    ///     source.restore(checkpoint);
+    /// }
+    ///
+    /// assert_eq!(source, "42, 43");
+    /// # Ok::<(), manger::ConsumeError>(())
+    /// ```
+    fn checkpoint(&self) -> Self;
+
+    /// Reset `self` to a state saved with [`checkpoint`][ConsumeSource::checkpoint].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use manger::ConsumeSource;
+    ///
+    /// let mut source = "abc";
+    ///
+    /// let checkpoint = source.checkpoint();
+    /// source.mut_consume::<char>()?;
+    /// assert_eq!(source, "bc");
+    ///
+    /// source.restore(checkpoint);
+    /// assert_eq!(source, "abc");
+    /// # Ok::<(), manger::ConsumeError>(())
+    /// ```
+    fn restore(&mut self, checkpoint: Self);
+
+    /// Attempt to consume with `f`. When `f` fails, `self` is restored to the state it had
+    /// before `f` was called. When `f` succeeds, `self` keeps the changes made by `f`.
+    ///
+    /// This allows for trying multiple strategies after each other, without having to juggle
+    /// the original `source` manually.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use manger::ConsumeSource;
+    ///
+    /// let mut source = "(42)";
+    ///
+    /// let bracketed = source.attempt(|source| {
+    ///     source.mut_consume_lit(&'[')?;
+    ///     let value = source.mut_consume::<u32>()?;
+    ///     source.mut_consume_lit(&']')?;
+    ///     Ok(value)
+    /// });
+    /// assert!(bracketed.is_err());
+    /// assert_eq!(source, "(42)");
+    ///
+    /// let parenthesized = source.attempt(|source| {
+    ///     source.mut_consume_lit(&'(')?;
+    ///     let value = source.mut_consume::<u32>()?;
+    ///     source.mut_consume_lit(&')')?;
+    ///     Ok(value)
+    /// })?;
+    /// assert_eq!(parenthesized, 42);
+    /// assert_eq!(source, "");
+    /// # Ok::<(), manger::ConsumeError>(())
+    /// ```
+    fn attempt<T>(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> Result<T, ConsumeError>,
+    ) -> Result<T, ConsumeError> {
+        let checkpoint = self.checkpoint();
+        let result = f(self);
+
+        if result.is_err() {
+            self.restore(checkpoint);
+        }
+
+        result
+    }
 }
 
-impl<'s> ConsumeSource for &'s str {
+impl ConsumeSource for &str {
+    fn checkpoint(&self) -> Self {
+        self
+    }
+
+    fn restore(&mut self, checkpoint: Self) {
+        *self = checkpoint;
+    }
+
     fn consume_lit<T: SelfConsumable>(self, item: &T) -> Result<Self, ConsumeError> {
         <T>::consume_item(self, item)
     }
@@ -584,20 +1189,19 @@ impl<'s> ConsumeSource for &'s str {
     }
 
     fn mut_consume_lit<T: SelfConsumable>(&mut self, literal: &T) -> Result<usize, ConsumeError> {
-        let length = utf8_slice::len(self);
-
         let unconsumed = self.consume_lit(literal)?;
+        let length = strs::consumed_length(self, unconsumed);
         *self = unconsumed;
 
-        Ok(length - utf8_slice::len(self))
+        Ok(length)
     }
 
     fn mut_consume_by<T: Consumable>(&mut self) -> Result<(T, usize), ConsumeError> {
-        let length = utf8_slice::len(self);
         let (item, unconsumed) = self.consume()?;
+        let length = strs::consumed_length(self, unconsumed);
         *self = unconsumed;
 
-        Ok((item, length - utf8_slice::len(self)))
+        Ok((item, length))
     }
 }
 
@@ -634,7 +1238,78 @@ where
     T: Consumable,
 {
     phantom: std::marker::PhantomData<T>,
+    source: &'a str,
     unconsumed: &'a str,
+    finished: bool,
+}
+
+impl<'a, T> ConsumeIter<'a, T>
+where
+    T: Consumable,
+{
+    /// Create an iterator which starts consuming at the utf-8 character index `offset` within
+    /// `source`. When `offset` is past the end of `source`, the iterator starts at the end.
+    ///
+    /// Together with [`position`][ConsumeIter::position], this allows for resuming after the
+    /// iterator ended, such as after skipping a malformed region of the `source`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use manger::{ Consumable, ConsumeIter };
+    /// use manger::chars;
+    ///
+    /// let source = "1,2,x,4,";
+    ///
+    /// let mut iter = <(u32, chars::Comma)>::consume_iter(source);
+    /// assert_eq!(iter.by_ref().count(), 2);
+    /// assert_eq!(iter.position(), 4);
+    /// assert_eq!(iter.unconsumed(), "x,4,");
+    ///
+    /// // Skip the malformed item and resume.
+    /// let mut iter = <ConsumeIter<(u32, chars::Comma)>>::with_offset(source, iter.position() + 2);
+    /// assert_eq!(iter.next().map(|(value, _)| value), Some(4));
+    /// assert_eq!(iter.position(), 8);
+    /// ```
+    pub fn with_offset(source: &'a str, offset: usize) -> Self {
+        let unconsumed = source
+            .char_indices()
+            .nth(offset)
+            .map_or("", |(index, _)| &source[index..]);
+
+        ConsumeIter {
+            phantom: std::marker::PhantomData,
+            source,
+            unconsumed,
+            finished: false,
+        }
+    }
+
+    /// Fetch the utf-8 character index within the `source` at which the next item will be
+    /// consumed.
+    pub fn position(&self) -> usize {
+        strs::consumed_length(self.source, self.unconsumed)
+    }
+
+    /// Fetch the part of the `source` that has not been consumed yet.
+    pub fn unconsumed(&self) -> &'a str {
+        self.unconsumed
+    }
+}
+
+// This is implemented manually, so it does not require `T` to implement `Clone` as well.
+impl<T> Clone for ConsumeIter<'_, T>
+where
+    T: Consumable,
+{
+    fn clone(&self) -> Self {
+        ConsumeIter {
+            phantom: std::marker::PhantomData,
+            source: self.source,
+            unconsumed: self.unconsumed,
+            finished: self.finished,
+        }
+    }
 }
 
 impl<'a, T> Iterator for ConsumeIter<'a, T>
@@ -643,13 +1318,120 @@ where
 {
     type Item = T;
     fn next(&mut self) -> Option<Self::Item> {
-        let (item_option, unconsumed) = <Option<T>>::consume_from(self.unconsumed).unwrap();
-        self.unconsumed = unconsumed;
+        if self.finished {
+            return None;
+        }
+
+        let result = context::scope(self.source, || T::consume_from(self.unconsumed));
 
-        item_option
+        match result {
+            // An item that consumes nothing would be returned forever.
+            Ok((item, unconsumed)) if unconsumed.len() < self.unconsumed.len() => {
+                self.unconsumed = unconsumed;
+                Some(item)
+            }
+            _ => {
+                self.finished = true;
+                None
+            }
+        }
+    }
+
+    /// Every item consumes at least one byte of the unconsumed part of the `source`.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.finished {
+            (0, Some(0))
+        } else {
+            (0, Some(self.unconsumed.len()))
+        }
     }
 }
 
+/// Once an item cannot be consumed, the iterator keeps returning `None`.
+impl<T> std::iter::FusedIterator for ConsumeIter<'_, T> where T: Consumable {}
+
+/// Iterator over the parts of a `source` between the occurences of a delimiter for a
+/// `Consumable` type `T`, which starts from the end.
+///
+/// This is created by [`Consumable::rsplit_consume_iter`].
+#[derive(Debug)]
+pub struct RSplitConsumeIter<'a, T>
+where
+    T: Consumable,
+{
+    phantom: std::marker::PhantomData<T>,
+    source: &'a str,
+    parts: std::str::RSplit<'a, char>,
+    finished: bool,
+}
+
+// This is implemented manually, so it does not require `T` to implement `Clone` as well.
+impl<T> Clone for RSplitConsumeIter<'_, T>
+where
+    T: Consumable,
+{
+    fn clone(&self) -> Self {
+        RSplitConsumeIter {
+            phantom: std::marker::PhantomData,
+            source: self.source,
+            parts: self.parts.clone(),
+            finished: self.finished,
+        }
+    }
+}
+
+impl<T> RSplitConsumeIter<'_, T>
+where
+    T: Consumable,
+{
+    fn consume_part(&mut self, part: Option<&str>) -> Option<T> {
+        let item = part.and_then(|part| context::scope(self.source, || T::consume_all(part)).ok());
+
+        self.finished = item.is_none();
+        item
+    }
+}
+
+impl<T> Iterator for RSplitConsumeIter<'_, T>
+where
+    T: Consumable,
+{
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        let part = self.parts.next();
+        self.consume_part(part)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.finished {
+            (0, Some(0))
+        } else {
+            (0, self.parts.size_hint().1)
+        }
+    }
+}
+
+impl<T> DoubleEndedIterator for RSplitConsumeIter<'_, T>
+where
+    T: Consumable,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        let part = self.parts.next_back();
+        self.consume_part(part)
+    }
+}
+
+/// Once a part cannot be consumed, the iterator keeps returning `None`.
+impl<T> std::iter::FusedIterator for RSplitConsumeIter<'_, T> where T: Consumable {}
+
 /// A wrapper to have default [FromStr][std::str::FromStr] behaviour.
 ///
 /// # Examples
@@ -673,7 +1455,7 @@ where
 /// assert_eq!(num, -42);
 /// # Ok::<(), manger::ConsumeError>(())
 /// ```
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
 pub struct Parser<T>
 where
     T: Consumable + Sized,
@@ -714,13 +1496,223 @@ where
     }
 }
 
+/// A wrapper for an item `T` which was consumed from a whole `source`.
+///
+/// This allows for [`TryFrom<&str>`][std::convert::TryFrom], which behaves like
+/// [`parse_exact`][Consumable::parse_exact].
+///
+/// # Examples
+///
+/// ```
+/// use manger::Complete;
+/// use std::convert::TryFrom;
+///
+/// let Complete(value) = Complete::<i32>::try_from("-42")?;
+/// assert_eq!(value, -42);
+///
+/// assert!(Complete::<i32>::try_from("-42!").is_err());
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Complete<T>(pub T);
+
+impl<T> Complete<T> {
+    /// Unwrap to fetch the consumed item.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: Consumable> std::convert::TryFrom<&str> for Complete<T> {
+    type Error = ConsumeError;
+
+    fn try_from(source: &str) -> Result<Self, Self::Error> {
+        T::parse_exact(source).map(Complete)
+    }
+}
+
+#[cfg(feature = "num-bigint")]
+mod bigint;
 pub mod chars;
 pub mod common;
+pub mod config;
+pub mod constant;
+pub mod context;
+pub mod coverage;
+pub mod csv;
+mod cursor;
+#[cfg(feature = "serde")]
+pub mod de;
+pub mod dynamic;
 mod either;
 mod enum_macro;
 mod error;
 mod floats;
+#[cfg(feature = "grapheme")]
+pub mod grapheme;
+#[cfg(feature = "http")]
+pub mod http;
 mod impls;
+pub mod incremental;
+pub mod indent;
+pub mod info;
 mod integers;
+#[cfg(feature = "json")]
+pub mod json;
+pub mod limits;
+#[cfg(feature = "logs")]
+pub mod logs;
+#[cfg(feature = "markdown")]
+pub mod markdown;
+#[cfg(feature = "nom")]
+pub mod nom_adapter;
+#[cfg(feature = "regex")]
+pub mod regex;
+mod render;
+pub mod scan;
+#[cfg(feature = "async")]
+pub mod stream;
 mod strs;
 mod struct_macro;
+pub mod testing;
+pub mod tokens;
+pub mod trace;
+
+#[cfg(test)]
+mod tests {
+    use crate::chars::{self, alpha, Alphabetic};
+    use crate::common::*;
+    use crate::config::{Comment, Config, KeyValue, SectionHeader};
+    use crate::csv::{CsvField, CsvRecord};
+    use crate::indent::{Dedent, Indented};
+    use crate::Consumable;
+    use either::Either;
+
+    /// Characters that are likely to reach the edge cases of the built-in consumers.
+    const ALPHABET: &[char] = &[
+        '0', '1', '9', '-', '+', '.', 'e', 'E', 'i', 'n', 'f', 'a', 'N', 'x', ' ', '\t', '\n',
+        '\r', '"', '\\', ',', ';', '#', '=', '[', ']', '{', '}', ':', 'é', '😀', '\u{301}',
+    ];
+
+    /// Deterministic sources from a xorshift generator, so failures can be reproduced.
+    fn sources(count: usize) -> impl Iterator<Item = String> {
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as usize
+        };
+
+        (0..count).map(move |_| {
+            let len = next() % 24;
+            (0..len)
+                .map(|_| ALPHABET[next() % ALPHABET.len()])
+                .collect()
+        })
+    }
+
+    fn exercise<T: Consumable>(source: &str) {
+        let _ = T::consume_from(source);
+        let _ = T::consume_all(source);
+        let _ = T::parse_prefix(source);
+        let _ = T::consume_iter(source).take(32).count();
+        let _ = T::consume_all_diagnostics::<char>(source);
+    }
+
+    macro_rules! exercise_all {
+        ( $source:expr; $( $type:ty ),+ $(,)? ) => {
+            $( exercise::<$type>($source); )+
+        };
+    }
+
+    #[test]
+    fn test_no_panics() {
+        for source in sources(1000) {
+            exercise_all!(
+                &source;
+                u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, char,
+                Digit, Sign, Whitespace, End, Empty, Start, StartOfLine, EndOfLine,
+                chars::Hyphen, alpha::E, Alphabetic, Vec<Digit>, OneOrMore<char>, Option<u8>,
+                Box<i8>, Either<u8, char>, (Sign, u8, chars::Period), SeparatedBy<u8, chars::Comma>,
+                Line<u32>, Lines<i32>, Row<u8>, Recovered<i16, (Vec<char>, End)>,
+                FromStrConsumer<f64>, Indented<char>, Indented<Line<u8>>, Dedent,
+                Comment, SectionHeader, KeyValue, Config, CsvField, CsvRecord,
+            );
+
+            #[cfg(feature = "json")]
+            exercise::<crate::json::Json>(&source);
+            #[cfg(feature = "grapheme")]
+            exercise::<crate::grapheme::Grapheme>(&source);
+            #[cfg(feature = "http")]
+            exercise_all!(
+                &source;
+                crate::http::RequestLine, crate::http::StatusLine, crate::http::Headers,
+                crate::http::ChunkSize,
+            );
+            #[cfg(feature = "logs")]
+            exercise_all!(
+                &source;
+                crate::logs::CommonLogEntry, crate::logs::CombinedLogEntry,
+                crate::logs::SyslogMessage,
+            );
+            #[cfg(feature = "markdown")]
+            exercise_all!(&source; crate::markdown::Inline, crate::markdown::Inlines);
+        }
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_no_panics_when_deeply_nested() {
+        for open in ["[", "{\"a\":", "[{\"a\":"] {
+            let source = open.repeat(100_000);
+
+            assert!(crate::json::Json::consume_all(&source).is_err());
+            assert!(crate::json::Json::parse_prefix(&source).is_err());
+        }
+    }
+
+    #[test]
+    fn test_zero_width_repetition() {
+        assert_eq!(
+            <Vec<Option<char>>>::consume_from("ab"),
+            Ok((vec![Some('a'), Some('b')], ""))
+        );
+        assert_eq!(<Vec<Option<u8>>>::consume_from("x"), Ok((vec![], "x")));
+        assert_eq!(<Vec<Empty>>::consume_from("abc"), Ok((vec![], "abc")));
+        assert_eq!(
+            <OneOrMore<Option<u8>>>::consume_from("x").map(|(items, _)| items.len()),
+            Ok(1)
+        );
+
+        assert_eq!(<Option<char>>::consume_iter("ab").count(), 2);
+        assert_eq!(End::consume_iter("").count(), 0);
+    }
+
+    #[test]
+    fn test_consume_iter_with_offset() {
+        let mut iter = <crate::ConsumeIter<'_, char>>::with_offset("ëaö", 1);
+        assert_eq!(iter.position(), 1);
+        assert_eq!(iter.next(), Some('a'));
+        assert_eq!(iter.unconsumed(), "ö");
+        assert_eq!(iter.position(), 2);
+
+        let mut iter = <crate::ConsumeIter<'_, char>>::with_offset("ëa", 5);
+        assert_eq!(iter.position(), 2);
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn test_fused_iterators() {
+        let mut iter = u8::consume_iter("1x");
+        assert_eq!(iter.next(), Some(1));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.size_hint(), (0, Some(0)));
+        assert_eq!(iter.next(), None);
+
+        let mut iter = u8::rsplit_consume_iter("1.x.3", '.');
+        assert_eq!(iter.next(), Some(3));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+    }
+}
@@ -0,0 +1,411 @@
+//! Resource __limits__ for consuming untrusted input.
+//!
+//! A `source` from an untrusted origin can be crafted to nest very deeply or to repeat an item
+//! very often. [`Consumable::consume_from_with_limits`] consumes with the given [`Limits`], which
+//! are enforced by:
+//!
+//! - the implementations generated by [`consume_struct`][crate::consume_struct!] and
+//!   [`consume_enum`][crate::consume_enum!], which count towards the maximum depth,
+//! - [`Vec<T>`] and [`OneOrMore<T>`][crate::common::OneOrMore], which count towards the maximum
+//!   amount of repetitions,
+//! - and the entry point itself, which enforces the maximum consumed length.
+//!
+//! Exceeding a limit results in a [`LimitExceeded`][crate::ConsumeErrorType::LimitExceeded] error.
+//! Hand-written recursive consumers can count towards the maximum depth with [`nest`].
+//!
+//! # Examples
+//!
+//! ```
+//! use manger::{consume_enum, Consumable, ConsumeErrorType};
+//! use manger::limits::{Limit, Limits};
+//!
+//! #[derive(Debug)]
+//! enum Nested {
+//!     Leaf,
+//!     Group(Box<Nested>),
+//! }
+//! consume_enum!(
+//!     Nested {
+//!         Leaf => [ > 'x'; ],
+//!         Group => [ > '(', inner: Box<Nested>, > ')'; (inner) ]
+//!     }
+//! );
+//!
+//! let limits = Limits {
+//!     max_depth: Some(3),
+//!     ..Limits::default()
+//! };
+//!
+//! assert!(Nested::consume_from_with_limits("((x))", limits).is_ok());
+//!
+//! let error = Nested::consume_from_with_limits("(((x)))", limits).unwrap_err();
+//! assert!(error.causes().contains(&&ConsumeErrorType::LimitExceeded {
+//!     index: 3,
+//!     limit: Limit::Depth,
+//! }));
+//! ```
+
+use crate::error::ConsumeErrorType::*;
+use crate::{Consumable, ConsumeError};
+use std::cell::Cell;
+use std::fmt;
+
+thread_local! {
+    /// The limits registered with `scope` and the current depth.
+    static STATE: Cell<State> = const { Cell::new(State { limits: Limits::new(), depth: 0 }) };
+}
+
+/// The registered limits along with the current depth.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct State {
+    limits: Limits,
+    depth: usize,
+}
+
+/// Restores the previous state, also when unwinding.
+struct StateGuard(State);
+
+impl Drop for StateGuard {
+    fn drop(&mut self) {
+        STATE.with(|state| state.set(self.0));
+    }
+}
+
+/// Get the limits and depth of the current thread.
+pub(crate) fn state() -> State {
+    STATE.with(Cell::get)
+}
+
+/// Set the limits and depth to `state` for the duration of `f`.
+pub(crate) fn with_state<R>(state: State, f: impl FnOnce() -> R) -> R {
+    let _guard = StateGuard(STATE.with(|current| current.replace(state)));
+
+    f()
+}
+
+/// The kind of limit in a [`LimitExceeded`][crate::ConsumeErrorType::LimitExceeded] error.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Limit {
+    /// The maximum depth of nested consumers.
+    Depth,
+    /// The maximum amount of repetitions of a single item.
+    Repetitions,
+    /// The maximum amount of consumed characters.
+    Length,
+}
+
+impl fmt::Display for Limit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Limit::Depth => "depth",
+            Limit::Repetitions => "repetitions",
+            Limit::Length => "length",
+        })
+    }
+}
+
+/// Limits on the resources used while consuming.
+///
+/// Every limit is optional, where `None` means that there is no limit. The [`Default`] is to have
+/// no limits at all.
+///
+/// # Examples
+///
+/// ```
+/// use manger::Consumable;
+/// use manger::limits::Limits;
+///
+/// let limits = Limits {
+///     max_repetitions: Some(3),
+///     ..Limits::default()
+/// };
+///
+/// assert!(<Vec<char>>::consume_from_with_limits("abc", limits).is_ok());
+/// assert!(<Vec<char>>::consume_from_with_limits("abcd", limits).is_err());
+///
+/// let limits = Limits {
+///     max_length: Some(8),
+///     ..Limits::default()
+/// };
+///
+/// assert!(u64::consume_from_with_limits("12345678", limits).is_ok());
+/// assert!(u64::consume_from_with_limits("123456789", limits).is_err());
+/// ```
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Limits {
+    /// The maximum depth of nested consumers that count towards the depth, such as the ones
+    /// generated by the [`consume_struct`][crate::consume_struct!] and
+    /// [`consume_enum`][crate::consume_enum!] macros.
+    pub max_depth: Option<usize>,
+    /// The maximum amount of items in a single [`Vec<T>`] or
+    /// [`OneOrMore<T>`][crate::common::OneOrMore]. This also applies to the repetitions within
+    /// other consumers that are built on them.
+    pub max_repetitions: Option<usize>,
+    /// The maximum amount of utf-8 characters that are consumed.
+    pub max_length: Option<usize>,
+}
+
+impl Limits {
+    /// Create limits without any limit.
+    pub const fn new() -> Self {
+        Limits {
+            max_depth: None,
+            max_repetitions: None,
+            max_length: None,
+        }
+    }
+
+    /// Get the limits that are currently enforced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use manger::limits::{self, Limits};
+    ///
+    /// let limits = Limits {
+    ///     max_depth: Some(16),
+    ///     ..Limits::default()
+    /// };
+    ///
+    /// assert_eq!(Limits::current(), Limits::new());
+    /// limits::scope(limits, || assert_eq!(Limits::current(), limits));
+    /// ```
+    pub fn current() -> Self {
+        state().limits
+    }
+}
+
+/// Enforce `limits` for the duration of `f`.
+///
+/// Within `f`, the depth starts at zero. The previous limits and depth are restored once `f`
+/// returns. [`consume_from_with_limits`][Consumable::consume_from_with_limits] calls this
+/// function automatically.
+pub fn scope<R>(limits: Limits, f: impl FnOnce() -> R) -> R {
+    with_state(State { limits, depth: 0 }, f)
+}
+
+/// Call `f` one level deeper, failing if that exceeds the maximum depth.
+///
+/// This is used by the implementations generated by the [`consume_struct`][crate::consume_struct!]
+/// and [`consume_enum`][crate::consume_enum!] macros, but can also be used by hand-written
+/// recursive consumers. The error has index `0`, since nothing has been consumed yet.
+///
+/// # Examples
+///
+/// ```
+/// use manger::limits::{self, Limit, Limits};
+/// use manger::ConsumeErrorType;
+///
+/// let limits = Limits {
+///     max_depth: Some(1),
+///     ..Limits::default()
+/// };
+///
+/// limits::scope(limits, || {
+///     assert_eq!(limits::nest(|| Ok(1)), Ok(1));
+///     assert_eq!(
+///         limits::nest(|| limits::nest(|| Ok(2))).unwrap_err().causes(),
+///         vec![&ConsumeErrorType::LimitExceeded { index: 0, limit: Limit::Depth }]
+///     );
+/// });
+/// ```
+pub fn nest<R>(f: impl FnOnce() -> Result<R, ConsumeError>) -> Result<R, ConsumeError> {
+    let state = state();
+
+    match state.limits.max_depth {
+        None => f(),
+        Some(max_depth) if state.depth >= max_depth => Err(ConsumeError::new_with(LimitExceeded {
+            index: 0,
+            limit: Limit::Depth,
+        })),
+        Some(_) => with_state(
+            State {
+                depth: state.depth + 1,
+                ..state
+            },
+            f,
+        ),
+    }
+}
+
+/// Consume items of `T` until that fails or an item consumes nothing, where `count` items have
+/// already been consumed.
+///
+/// Fails if the total amount of items exceeds the maximum amount of repetitions. The index of the
+/// error is the start of the first item that exceeds it.
+pub(crate) fn repeat<T: Consumable>(
+    source: &str,
+    count: usize,
+) -> Result<(Vec<T>, &str), ConsumeError> {
+    repeat_while(source, count, |_| true)
+}
+
+/// Consume items of `T` like [`repeat`], but stop before an item when `condition` does not hold
+/// for the unconsumed part of the source.
+pub(crate) fn repeat_while<T: Consumable>(
+    source: &str,
+    count: usize,
+    condition: impl Fn(&str) -> bool,
+) -> Result<(Vec<T>, &str), ConsumeError> {
+    let mut items = Vec::new();
+    let unconsumed = repeat_each(source, count, condition, |item| items.push(item))?;
+
+    Ok((items, unconsumed))
+}
+
+/// Consume items of `T` like [`repeat_while`], but pass every item to `each` instead of collecting
+/// them.
+pub(crate) fn repeat_each<T: Consumable>(
+    source: &str,
+    count: usize,
+    condition: impl Fn(&str) -> bool,
+    mut each: impl FnMut(T),
+) -> Result<&str, ConsumeError> {
+    let max_repetitions = Limits::current().max_repetitions;
+    let mut repetitions = count;
+    let mut unconsumed = source;
+    let mut offset = 0;
+
+    while condition(unconsumed) {
+        // An item that consumes nothing would be consumed forever, so it ends the repetition.
+        let Ok((item, rest, by @ 1..)) = T::consume_how_many_from(unconsumed) else {
+            break;
+        };
+
+        if max_repetitions.is_some_and(|max| repetitions >= max) {
+            return Err(ConsumeError::new_with(LimitExceeded {
+                index: offset,
+                limit: Limit::Repetitions,
+            }));
+        }
+
+        each(item);
+        repetitions += 1;
+        unconsumed = rest;
+        offset += by;
+    }
+
+    Ok(unconsumed)
+}
+
+/// Get the part of `source` that may be consumed with the maximum length of `limits`.
+///
+/// One character more than the maximum is kept, so consuming past the maximum can be detected. A
+/// maximum of [`usize::MAX`] can never be exceeded, so it keeps the whole `source`.
+pub(crate) fn limit_length(source: &str, limits: Limits) -> &str {
+    match limits
+        .max_length
+        .and_then(|max_length| max_length.checked_add(1))
+        .and_then(|kept| source.char_indices().nth(kept))
+    {
+        Some((index, _)) => &source[..index],
+        None => source,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Limit, Limits};
+    use crate::common::OneOrMore;
+    use crate::{Consumable, ConsumeErrorType};
+
+    enum Nested {
+        Leaf,
+        Group(Box<Nested>),
+    }
+    crate::consume_enum!(
+        Nested {
+            Leaf => [ > 'x'; ],
+            Group => [ > '(', inner: Box<Nested>, > ')'; (inner) ]
+        }
+    );
+
+    impl Nested {
+        fn depth(&self) -> usize {
+            let mut nested = self;
+            let mut depth = 0;
+
+            while let Nested::Group(inner) = nested {
+                nested = inner;
+                depth += 1;
+            }
+
+            depth
+        }
+    }
+
+    #[test]
+    fn test_depth() {
+        let limits = Limits {
+            max_depth: Some(100),
+            ..Limits::default()
+        };
+        let nested = |depth| format!("{}x{}", "(".repeat(depth), ")".repeat(depth));
+
+        assert_eq!(
+            Nested::consume_from_with_limits(&nested(99), limits).map(|(nested, _)| nested.depth()),
+            Ok(99)
+        );
+        assert!(Nested::consume_from_with_limits(&nested(100), limits)
+            .map(|_| ())
+            .unwrap_err()
+            .causes()
+            .contains(&&ConsumeErrorType::LimitExceeded {
+                index: 100,
+                limit: Limit::Depth
+            }));
+    }
+
+    #[test]
+    fn test_repetitions() {
+        let limits = Limits {
+            max_repetitions: Some(2),
+            ..Limits::default()
+        };
+
+        assert!(<OneOrMore<char>>::consume_from_with_limits("ab", limits).is_ok());
+        assert_eq!(
+            <OneOrMore<char>>::consume_from_with_limits("abc", limits)
+                .unwrap_err()
+                .causes(),
+            vec![&ConsumeErrorType::LimitExceeded {
+                index: 2,
+                limit: Limit::Repetitions
+            }]
+        );
+    }
+
+    #[test]
+    fn test_length() {
+        let limits = Limits {
+            max_length: Some(3),
+            ..Limits::default()
+        };
+
+        assert_eq!(
+            <Vec<char>>::consume_from_with_limits("ab", limits),
+            Ok((vec!['a', 'b'], ""))
+        );
+        assert!(<Vec<char>>::consume_from_with_limits("abcdef", limits)
+            .unwrap_err()
+            .causes()
+            .contains(&&ConsumeErrorType::LimitExceeded {
+                index: 3,
+                limit: Limit::Length
+            }));
+        assert_eq!(
+            <(char, char)>::consume_from_with_limits("abcdef", limits),
+            Ok((('a', 'b'), "cdef"))
+        );
+
+        let limits = Limits {
+            max_length: Some(usize::MAX),
+            ..Limits::default()
+        };
+
+        assert_eq!(
+            <Vec<char>>::consume_from_with_limits("abc", limits),
+            Ok((vec!['a', 'b', 'c'], ""))
+        );
+    }
+}
@@ -0,0 +1,870 @@
+//! Types for consuming __log lines__.
+//!
+//! This module contains consumers for the access logs of web servers in the
+//! [Common Log Format](https://httpd.apache.org/docs/current/logs.html#common) and the Combined
+//! Log Format, and for [RFC 5424](https://www.rfc-editor.org/rfc/rfc5424) syslog messages. Every
+//! type consumes a single line without its line ending, so a whole log can be consumed with
+//! [`Lines`][crate::common::Lines].
+//!
+//! Fields with the nil value `-` are `None`. Both formats use a [`Timestamp`], which is checked
+//! to be within range.
+//!
+//! This module is only available with the `logs` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use manger::Consumable;
+//! use manger::common::Lines;
+//! use manger::logs::CombinedLogEntry;
+//!
+//! let source = concat!(
+//!     "127.0.0.1 - frank [10/Oct/2000:13:55:36 -0700] \"GET /a.gif HTTP/1.0\" 200 2326 ",
+//!     "\"http://example.com/\" \"Mozilla/4.08\"\n",
+//!     "::1 - - [10/Oct/2000:13:56:01 -0700] \"GET /b HTTP/1.0\" 404 - \"-\" \"curl/7.1\"\n",
+//! );
+//!
+//! let entries = <Lines<CombinedLogEntry>>::consume_all(source)?.into_vec();
+//!
+//! assert_eq!(entries[0].common.user.as_deref(), Some("frank"));
+//! assert_eq!(entries[0].common.timestamp.minute, 55);
+//! assert_eq!(entries[1].common.status, 404);
+//! assert_eq!(entries[1].common.size, None);
+//! assert!(entries[1].common.ip().map_or(false, |ip| ip.is_loopback()));
+//! # Ok::<(), manger::ConsumeError>(())
+//! ```
+
+use crate::error::ConsumeErrorType::*;
+use crate::{Consumable, ConsumeError, ConsumeSource, Cursor, Expectation, Render};
+use std::net::IpAddr;
+
+/// The abbreviated names of the months in the Common Log Format.
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// A non-empty run of characters other than whitespace.
+struct Word(String);
+
+impl Consumable for Word {
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let length = source.find(char::is_whitespace).unwrap_or(source.len());
+
+        if length == 0 {
+            return Err(ConsumeError::new_with(match source.chars().next() {
+                Some(token) => UnexpectedToken { index: 0, token },
+                None => InsufficientTokens { index: 0 },
+            }));
+        }
+
+        Ok((Word(source[..length].to_string()), &source[length..]))
+    }
+}
+
+/// Exactly `N` ASCII digits.
+struct Digits<const N: usize>(u32);
+
+impl<const N: usize> Consumable for Digits<N> {
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let mut value = 0;
+        let mut characters = source.chars();
+
+        for index in 0..N {
+            match characters.next() {
+                Some(token) if token.is_ascii_digit() => value = value * 10 + (token as u32 - 48),
+                Some(token) => {
+                    return Err(ConsumeError::new_with(UnexpectedToken { index, token }))
+                }
+                None => return Err(ConsumeError::new_with(InsufficientTokens { index })),
+            }
+        }
+
+        Ok((Digits(value), characters.as_str()))
+    }
+}
+
+/// A string between `'"'`s, where a `'\\'` escapes the next character.
+struct Quoted(String);
+
+impl Consumable for Quoted {
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let mut characters = source.chars();
+        match characters.next() {
+            Some('"') => {}
+            Some(token) => return Err(ConsumeError::new_with(UnexpectedToken { index: 0, token })),
+            None => return Err(ConsumeError::new_with(InsufficientTokens { index: 0 })),
+        }
+
+        let mut value = String::new();
+        let mut index = 1;
+
+        while let Some(token) = characters.next() {
+            match token {
+                '"' => return Ok((Quoted(value), characters.as_str())),
+                '\n' | '\r' => break,
+                '\\' => match characters.next() {
+                    Some(escaped) => {
+                        value.push(escaped);
+                        index += 2;
+                    }
+                    None => {
+                        index += 1;
+                        break;
+                    }
+                },
+                _ => {
+                    value.push(token);
+                    index += 1;
+                }
+            }
+        }
+
+        Err(ConsumeError::new_with(UnclosedDelimiter {
+            index,
+            opened_at: 0,
+            token: '"',
+        }))
+    }
+}
+
+/// Convert the nil value `-` to `None`.
+fn non_nil(value: String) -> Option<String> {
+    if value == "-" {
+        None
+    } else {
+        Some(value)
+    }
+}
+
+/// Consume `N` digits from `cursor` and check that the value is within `range`.
+fn consume_within<const N: usize>(
+    cursor: &mut Cursor<'_>,
+    range: std::ops::RangeInclusive<u32>,
+) -> Result<u32, ConsumeError> {
+    let index = cursor.position();
+    let Digits(value) = cursor.mut_consume::<Digits<N>>()?;
+
+    if !range.contains(&value) {
+        return Err(ConsumeError::new_with(InvalidValue { index }));
+    }
+
+    Ok(value)
+}
+
+fn render_quoted(value: Option<&str>, out: &mut String) {
+    out.push('"');
+    for token in value.unwrap_or("-").chars() {
+        if token == '"' || token == '\\' {
+            out.push('\\');
+        }
+        out.push(token);
+    }
+    out.push('"');
+}
+
+/// A point in time with the offset from UTC at which it was logged.
+///
+/// # Examples
+///
+/// ```
+/// use manger::Consumable;
+/// use manger::logs::{CommonLogEntry, Timestamp};
+///
+/// let entry = CommonLogEntry::consume_all(
+///     "10.0.0.1 - - [01/Feb/2021:09:30:00 +0100] \"GET / HTTP/1.1\" 200 512",
+/// )?;
+///
+/// assert_eq!(
+///     entry.timestamp,
+///     Timestamp {
+///         year: 2021,
+///         month: 2,
+///         day: 1,
+///         hour: 9,
+///         minute: 30,
+///         second: 0,
+///         microsecond: 0,
+///         offset_minutes: 60,
+///     }
+/// );
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Default)]
+pub struct Timestamp {
+    /// The year, from 0 to 9999.
+    pub year: u16,
+    /// The month, from 1 to 12.
+    pub month: u8,
+    /// The day of the month, from 1 to 31.
+    pub day: u8,
+    /// The hour, from 0 to 23.
+    pub hour: u8,
+    /// The minute, from 0 to 59.
+    pub minute: u8,
+    /// The second, from 0 to 60 to allow for leap seconds.
+    pub second: u8,
+    /// The fraction of the second in microseconds.
+    pub microsecond: u32,
+    /// The offset from UTC in minutes.
+    pub offset_minutes: i16,
+}
+
+impl Timestamp {
+    /// Consume the time of day, `HH:MM:SS`.
+    fn consume_time(&mut self, cursor: &mut Cursor<'_>) -> Result<(), ConsumeError> {
+        self.hour = consume_within::<2>(cursor, 0..=23)? as u8;
+        cursor.mut_consume_lit(&':')?;
+        self.minute = consume_within::<2>(cursor, 0..=59)? as u8;
+        cursor.mut_consume_lit(&':')?;
+        self.second = consume_within::<2>(cursor, 0..=60)? as u8;
+
+        Ok(())
+    }
+
+    /// Consume the offset from UTC, which is a sign followed by `hh`, the `separator` and `mm`.
+    fn consume_offset(
+        &mut self,
+        cursor: &mut Cursor<'_>,
+        separator: &str,
+    ) -> Result<(), ConsumeError> {
+        let negative = match cursor.mut_consume::<char>()? {
+            '+' => false,
+            '-' => true,
+            token => {
+                return Err(ConsumeError::new_with(UnexpectedToken {
+                    index: cursor.position() - 1,
+                    token,
+                }))
+            }
+        };
+
+        let hours = consume_within::<2>(cursor, 0..=23)?;
+        cursor.mut_consume_lit(&separator)?;
+        let minutes = consume_within::<2>(cursor, 0..=59)?;
+
+        let offset = (hours * 60 + minutes) as i16;
+        self.offset_minutes = if negative { -offset } else { offset };
+
+        Ok(())
+    }
+
+    /// Consume a timestamp of the form `[10/Oct/2000:13:55:36 -0700]`.
+    fn consume_clf(cursor: &mut Cursor<'_>) -> Result<Self, ConsumeError> {
+        let mut timestamp = Timestamp::default();
+
+        cursor.mut_consume_lit(&'[')?;
+        timestamp.day = consume_within::<2>(cursor, 1..=31)? as u8;
+        cursor.mut_consume_lit(&'/')?;
+
+        let index = cursor.position();
+        timestamp.month = MONTHS
+            .iter()
+            .position(|month| cursor.mut_consume_lit(month).is_ok())
+            .ok_or_else(|| ConsumeError::new_with(InvalidValue { index }))?
+            as u8
+            + 1;
+
+        cursor.mut_consume_lit(&'/')?;
+        timestamp.year = consume_within::<4>(cursor, 0..=9999)? as u16;
+        cursor.mut_consume_lit(&':')?;
+        timestamp.consume_time(cursor)?;
+        cursor.mut_consume_lit(&' ')?;
+        timestamp.consume_offset(cursor, "")?;
+        cursor.mut_consume_lit(&']')?;
+
+        Ok(timestamp)
+    }
+
+    /// Consume a timestamp of the form `2003-10-11T22:14:15.003Z`, as in RFC 3339.
+    fn consume_rfc3339(cursor: &mut Cursor<'_>) -> Result<Self, ConsumeError> {
+        let mut timestamp = Timestamp {
+            year: consume_within::<4>(cursor, 0..=9999)? as u16,
+            ..Timestamp::default()
+        };
+
+        cursor.mut_consume_lit(&'-')?;
+        timestamp.month = consume_within::<2>(cursor, 1..=12)? as u8;
+        cursor.mut_consume_lit(&'-')?;
+        timestamp.day = consume_within::<2>(cursor, 1..=31)? as u8;
+        cursor.mut_consume_lit(&'T')?;
+        timestamp.consume_time(cursor)?;
+
+        if cursor.mut_consume_lit(&'.').is_ok() {
+            let mut digits = 0;
+            while let Ok(Digits(digit)) = cursor.attempt(|cursor| cursor.mut_consume::<Digits<1>>())
+            {
+                if digits == 6 {
+                    return Err(ConsumeError::new_with(InvalidValue {
+                        index: cursor.position() - 1,
+                    }));
+                }
+                timestamp.microsecond = timestamp.microsecond * 10 + digit;
+                digits += 1;
+            }
+
+            if digits == 0 {
+                cursor.mut_consume::<Digits<1>>()?;
+            }
+            timestamp.microsecond *= 10u32.pow(6 - digits);
+        }
+
+        if cursor.mut_consume_lit(&'Z').is_err() {
+            timestamp.consume_offset(cursor, ":")?;
+        }
+
+        Ok(timestamp)
+    }
+
+    fn render_offset(&self, separator: &str, out: &mut String) {
+        let sign = if self.offset_minutes < 0 { '-' } else { '+' };
+        let offset = self.offset_minutes.unsigned_abs();
+        out.push_str(&format!(
+            "{}{:02}{}{:02}",
+            sign,
+            offset / 60,
+            separator,
+            offset % 60
+        ));
+    }
+
+    fn render_clf(&self, out: &mut String) {
+        out.push_str(&format!(
+            "[{:02}/{}/{:04}:{:02}:{:02}:{:02} ",
+            self.day,
+            MONTHS[usize::from(self.month.clamp(1, 12)) - 1],
+            self.year,
+            self.hour,
+            self.minute,
+            self.second
+        ));
+        self.render_offset("", out);
+        out.push(']');
+    }
+
+    fn render_rfc3339(&self, out: &mut String) {
+        out.push_str(&format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        ));
+
+        if self.microsecond != 0 {
+            let fraction = format!("{:06}", self.microsecond);
+            out.push('.');
+            out.push_str(fraction.trim_end_matches('0'));
+        }
+
+        if self.offset_minutes == 0 {
+            out.push('Z');
+        } else {
+            self.render_offset(":", out);
+        }
+    }
+}
+
+/// An entry in the Common Log Format, such as
+/// `127.0.0.1 - frank [10/Oct/2000:13:55:36 -0700] "GET /a.gif HTTP/1.0" 200 2326`.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{Consumable, Render};
+/// use manger::logs::CommonLogEntry;
+///
+/// let source = r#"example.com - - [10/Oct/2000:13:55:36 -0700] "GET / HTTP/1.0" 304 -"#;
+/// let entry = CommonLogEntry::consume_all(source)?;
+///
+/// assert_eq!(entry.host, "example.com");
+/// assert_eq!(entry.ip(), None);
+/// assert_eq!(entry.request, "GET / HTTP/1.0");
+/// assert_eq!(entry.render(), source);
+///
+/// assert!(CommonLogEntry::consume_all(&source.replace("Oct", "Okt")).is_err());
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct CommonLogEntry {
+    /// The address or host name of the client.
+    pub host: String,
+    /// The identity of the client according to `identd`.
+    pub ident: Option<String>,
+    /// The authenticated user.
+    pub user: Option<String>,
+    /// The time at which the request was received.
+    pub timestamp: Timestamp,
+    /// The request line, without the quotes.
+    pub request: String,
+    /// The status code of the response.
+    pub status: u16,
+    /// The size of the response body in bytes, if there was a body.
+    pub size: Option<u64>,
+}
+
+impl CommonLogEntry {
+    /// Get the address of the client, if the host is an IPv4 or IPv6 address.
+    pub fn ip(&self) -> Option<IpAddr> {
+        self.host.parse().ok()
+    }
+
+    fn consume_cursor(cursor: &mut Cursor<'_>) -> Result<Self, ConsumeError> {
+        let Word(host) = cursor.mut_consume()?;
+        cursor.mut_consume_lit(&' ')?;
+        let Word(ident) = cursor.mut_consume()?;
+        cursor.mut_consume_lit(&' ')?;
+        let Word(user) = cursor.mut_consume()?;
+        cursor.mut_consume_lit(&' ')?;
+        let timestamp = Timestamp::consume_clf(cursor)?;
+        cursor.mut_consume_lit(&' ')?;
+        let Quoted(request) = cursor.mut_consume()?;
+        cursor.mut_consume_lit(&' ')?;
+        let status = consume_within::<3>(cursor, 100..=999)? as u16;
+        cursor.mut_consume_lit(&' ')?;
+        let size = match cursor.mut_consume_lit(&'-') {
+            Ok(_) => None,
+            Err(_) => Some(cursor.mut_consume::<u64>()?),
+        };
+
+        Ok(CommonLogEntry {
+            host,
+            ident: non_nil(ident),
+            user: non_nil(user),
+            timestamp,
+            request,
+            status,
+            size,
+        })
+    }
+}
+
+impl Consumable for CommonLogEntry {
+    fn expecting() -> Expectation {
+        Expectation::new("a Common Log Format entry")
+    }
+
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let mut cursor = Cursor::new(source);
+        let entry = Self::consume_cursor(&mut cursor)?;
+
+        Ok((entry, cursor.unconsumed()))
+    }
+}
+
+impl Render for CommonLogEntry {
+    fn render_to(&self, out: &mut String) {
+        out.push_str(&self.host);
+        for field in [&self.ident, &self.user] {
+            out.push(' ');
+            out.push_str(field.as_deref().unwrap_or("-"));
+        }
+        out.push(' ');
+        self.timestamp.render_clf(out);
+        out.push(' ');
+        render_quoted(Some(&self.request), out);
+        out.push_str(&format!(" {} ", self.status));
+        match self.size {
+            Some(size) => out.push_str(&size.to_string()),
+            None => out.push('-'),
+        }
+    }
+}
+
+/// An entry in the Combined Log Format, which is an entry in the Common Log Format followed by
+/// the quoted referer and user agent.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{Consumable, Render};
+/// use manger::logs::CombinedLogEntry;
+///
+/// let source = concat!(
+///     r#"10.0.0.1 - - [10/Oct/2000:13:55:36 +0000] "GET / HTTP/1.0" 200 7 "-" "#,
+///     r#""Agent \"X\"""#
+/// );
+/// let entry = CombinedLogEntry::consume_all(source)?;
+///
+/// assert_eq!(entry.referer, None);
+/// assert_eq!(entry.user_agent.as_deref(), Some("Agent \"X\""));
+/// assert_eq!(entry.render(), source);
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct CombinedLogEntry {
+    /// The fields of the Common Log Format.
+    pub common: CommonLogEntry,
+    /// The page that linked to the request.
+    pub referer: Option<String>,
+    /// The user agent of the client.
+    pub user_agent: Option<String>,
+}
+
+impl Consumable for CombinedLogEntry {
+    fn expecting() -> Expectation {
+        Expectation::new("a Combined Log Format entry")
+    }
+
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let mut cursor = Cursor::new(source);
+
+        let common = CommonLogEntry::consume_cursor(&mut cursor)?;
+        cursor.mut_consume_lit(&' ')?;
+        let Quoted(referer) = cursor.mut_consume()?;
+        cursor.mut_consume_lit(&' ')?;
+        let Quoted(user_agent) = cursor.mut_consume()?;
+
+        Ok((
+            CombinedLogEntry {
+                common,
+                referer: non_nil(referer),
+                user_agent: non_nil(user_agent),
+            },
+            cursor.unconsumed(),
+        ))
+    }
+}
+
+impl Render for CombinedLogEntry {
+    fn render_to(&self, out: &mut String) {
+        self.common.render_to(out);
+        out.push(' ');
+        render_quoted(self.referer.as_deref(), out);
+        out.push(' ');
+        render_quoted(self.user_agent.as_deref(), out);
+    }
+}
+
+/// The header of an RFC 5424 syslog message, such as
+/// `<34>1 2003-10-11T22:14:15.003Z host su - ID47`.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{Consumable, Render};
+/// use manger::logs::SyslogHeader;
+///
+/// let source = "<165>1 2003-08-24T05:14:15.000003-07:00 192.0.2.1 myproc 8710 - rest";
+/// let (header, unconsumed) = SyslogHeader::consume_from(source)?;
+///
+/// assert_eq!((header.facility, header.severity), (20, 5));
+/// assert_eq!(header.timestamp.map(|timestamp| timestamp.microsecond), Some(3));
+/// assert_eq!(header.proc_id.as_deref(), Some("8710"));
+/// assert_eq!(header.msg_id, None);
+/// assert_eq!(unconsumed, " rest");
+/// assert_eq!(header.render(), &source[..source.len() - 5]);
+///
+/// assert!(SyslogHeader::consume_from("<192>1 - - - - -").is_err());
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct SyslogHeader {
+    /// The facility, from 0 to 23.
+    pub facility: u8,
+    /// The severity, from 0 (emergency) to 7 (debug).
+    pub severity: u8,
+    /// The version of the protocol, which is `1` for RFC 5424.
+    pub version: u8,
+    /// The time at which the message was created.
+    pub timestamp: Option<Timestamp>,
+    /// The host name or address of the machine that sent the message.
+    pub hostname: Option<String>,
+    /// The application that sent the message.
+    pub app_name: Option<String>,
+    /// The process that sent the message.
+    pub proc_id: Option<String>,
+    /// The type of message.
+    pub msg_id: Option<String>,
+}
+
+impl SyslogHeader {
+    fn consume_cursor(cursor: &mut Cursor<'_>) -> Result<Self, ConsumeError> {
+        cursor.mut_consume_lit(&'<')?;
+        let index = cursor.position();
+        let priority = cursor.mut_consume::<u8>()?;
+        if priority > 191 || cursor.position() - index > 3 {
+            return Err(ConsumeError::new_with(InvalidValue { index }));
+        }
+        cursor.mut_consume_lit(&'>')?;
+
+        let index = cursor.position();
+        let version = cursor.mut_consume::<u8>()?;
+        if version == 0 || cursor.position() - index > 2 {
+            return Err(ConsumeError::new_with(InvalidValue { index }));
+        }
+        cursor.mut_consume_lit(&' ')?;
+
+        let timestamp = match cursor.mut_consume_lit(&'-') {
+            Ok(_) => None,
+            Err(_) => Some(Timestamp::consume_rfc3339(cursor)?),
+        };
+
+        let mut fields = Vec::with_capacity(4);
+        for _ in 0..4 {
+            cursor.mut_consume_lit(&' ')?;
+            let Word(field) = cursor.mut_consume()?;
+            fields.push(non_nil(field));
+        }
+        let mut fields = fields.into_iter();
+
+        Ok(SyslogHeader {
+            facility: priority / 8,
+            severity: priority % 8,
+            version,
+            timestamp,
+            hostname: fields.next().flatten(),
+            app_name: fields.next().flatten(),
+            proc_id: fields.next().flatten(),
+            msg_id: fields.next().flatten(),
+        })
+    }
+}
+
+impl Consumable for SyslogHeader {
+    fn expecting() -> Expectation {
+        Expectation::new("a syslog header")
+    }
+
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let mut cursor = Cursor::new(source);
+        let header = Self::consume_cursor(&mut cursor)?;
+
+        Ok((header, cursor.unconsumed()))
+    }
+}
+
+impl Render for SyslogHeader {
+    fn render_to(&self, out: &mut String) {
+        out.push_str(&format!(
+            "<{}>{} ",
+            self.facility * 8 + self.severity,
+            self.version
+        ));
+
+        match &self.timestamp {
+            Some(timestamp) => timestamp.render_rfc3339(out),
+            None => out.push('-'),
+        }
+
+        for field in [&self.hostname, &self.app_name, &self.proc_id, &self.msg_id] {
+            out.push(' ');
+            out.push_str(field.as_deref().unwrap_or("-"));
+        }
+    }
+}
+
+/// An element of the structured data of a syslog message, such as
+/// `[exampleSDID@32473 iut="3" eventSource="Application"]`.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct SdElement {
+    /// The identifier of the element.
+    pub id: String,
+    /// The names and unescaped values of the parameters, in order of appearance.
+    pub params: Vec<(String, String)>,
+}
+
+/// A name within structured data, which excludes `'='`, `' '`, `']'` and `'"'`.
+struct SdName(String);
+
+impl Consumable for SdName {
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let length = source
+            .find(|token: char| "= ]\"".contains(token) || !token.is_ascii_graphic())
+            .unwrap_or(source.len());
+
+        if length == 0 {
+            return Err(ConsumeError::new_with(match source.chars().next() {
+                Some(token) => UnexpectedToken { index: 0, token },
+                None => InsufficientTokens { index: 0 },
+            }));
+        }
+
+        Ok((SdName(source[..length].to_string()), &source[length..]))
+    }
+}
+
+impl SdElement {
+    fn consume_cursor(cursor: &mut Cursor<'_>) -> Result<Self, ConsumeError> {
+        cursor.mut_consume_lit(&'[')?;
+        let SdName(id) = cursor.mut_consume()?;
+        let mut params = Vec::new();
+
+        while cursor.mut_consume_lit(&' ').is_ok() {
+            let SdName(name) = cursor.mut_consume()?;
+            cursor.mut_consume_lit(&'=')?;
+            let Quoted(value) = cursor.mut_consume()?;
+            params.push((name, value));
+        }
+
+        cursor.mut_consume_lit(&']')?;
+
+        Ok(SdElement { id, params })
+    }
+
+    fn render_to(&self, out: &mut String) {
+        out.push('[');
+        out.push_str(&self.id);
+        for (name, value) in &self.params {
+            out.push(' ');
+            out.push_str(name);
+            out.push_str("=\"");
+            for token in value.chars() {
+                if token == '"' || token == '\\' || token == ']' {
+                    out.push('\\');
+                }
+                out.push(token);
+            }
+            out.push('"');
+        }
+        out.push(']');
+    }
+}
+
+/// An RFC 5424 syslog message, which is a [`SyslogHeader`] followed by structured data and an
+/// optional message.
+///
+/// The message spans the rest of the line and a leading byte order mark is removed from it.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{Consumable, Render};
+/// use manger::logs::SyslogMessage;
+///
+/// let source = concat!(
+///     "<165>1 2003-10-11T22:14:15.003Z host evntslog - ID47 ",
+///     "[exampleSDID@32473 iut=\"3\" eventSource=\"Application\"] An application event"
+/// );
+/// let message = SyslogMessage::consume_all(source)?;
+///
+/// assert_eq!(message.header.app_name.as_deref(), Some("evntslog"));
+/// assert_eq!(message.structured_data[0].id, "exampleSDID@32473");
+/// assert_eq!(
+///     message.structured_data[0].params[1],
+///     ("eventSource".to_string(), "Application".to_string())
+/// );
+/// assert_eq!(message.message.as_deref(), Some("An application event"));
+/// assert_eq!(message.render(), source);
+///
+/// let message = SyslogMessage::consume_all("<13>1 - - - - - -")?;
+/// assert!(message.structured_data.is_empty());
+/// assert_eq!(message.message, None);
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct SyslogMessage {
+    /// The header of the message.
+    pub header: SyslogHeader,
+    /// The elements of the structured data, which is empty for the nil value `-`.
+    pub structured_data: Vec<SdElement>,
+    /// The free-form message.
+    pub message: Option<String>,
+}
+
+impl Consumable for SyslogMessage {
+    fn expecting() -> Expectation {
+        Expectation::new("a syslog message")
+    }
+
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let mut cursor = Cursor::new(source);
+
+        let header = SyslogHeader::consume_cursor(&mut cursor)?;
+        cursor.mut_consume_lit(&' ')?;
+
+        let mut structured_data = Vec::new();
+        if cursor.mut_consume_lit(&'-').is_err() {
+            structured_data.push(SdElement::consume_cursor(&mut cursor)?);
+            while cursor.unconsumed().starts_with('[') {
+                structured_data.push(SdElement::consume_cursor(&mut cursor)?);
+            }
+        }
+
+        let unconsumed = cursor.unconsumed();
+        let (message, unconsumed) = match unconsumed.strip_prefix(' ') {
+            Some(rest) => {
+                let length = rest.find(['\n', '\r']);
+                let (message, unconsumed) = rest.split_at(length.unwrap_or(rest.len()));
+                let message = message.strip_prefix('\u{feff}').unwrap_or(message);
+
+                (Some(message.to_string()), unconsumed)
+            }
+            None => (None, unconsumed),
+        };
+
+        Ok((
+            SyslogMessage {
+                header,
+                structured_data,
+                message,
+            },
+            unconsumed,
+        ))
+    }
+}
+
+impl Render for SyslogMessage {
+    fn render_to(&self, out: &mut String) {
+        self.header.render_to(out);
+        out.push(' ');
+
+        if self.structured_data.is_empty() {
+            out.push('-');
+        }
+        self.structured_data
+            .iter()
+            .for_each(|element| element.render_to(out));
+
+        if let Some(message) = &self.message {
+            out.push(' ');
+            out.push_str(message);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CommonLogEntry, SyslogHeader, SyslogMessage};
+    use crate::error::ConsumeErrorType::*;
+    use crate::{Consumable, ConsumeError, Render};
+
+    #[test]
+    fn test_timestamp_errors() {
+        assert_eq!(
+            CommonLogEntry::consume_from("h - - [32/Oct/2000:13:55:36 -0700] \"\" 200 0")
+                .unwrap_err(),
+            ConsumeError::new_with(InvalidValue { index: 7 })
+        );
+        assert_eq!(
+            SyslogHeader::consume_from("<1>1 2003-10-11T25:14:15Z h a p m").unwrap_err(),
+            ConsumeError::new_with(InvalidValue { index: 16 })
+        );
+        assert_eq!(
+            SyslogHeader::consume_from("<1>1 2003-10-11T22:14:15.1234567Z h a p m").unwrap_err(),
+            ConsumeError::new_with(InvalidValue { index: 31 })
+        );
+        assert_eq!(
+            CommonLogEntry::consume_from("h - - [10/Oct/2000:13:55:36 -0700] \"GET 200 0")
+                .unwrap_err(),
+            ConsumeError::new_with(UnclosedDelimiter {
+                index: 45,
+                opened_at: 35,
+                token: '"'
+            })
+        );
+    }
+
+    #[test]
+    fn test_syslog_round_trip() {
+        let source =
+            "<0>12 2003-10-11T22:14:15.5+05:30 h a p m [a b=\"\\]\\\"\"][c] \u{feff}msg\nnext";
+        let (message, unconsumed) = SyslogMessage::consume_from(source).unwrap();
+
+        assert_eq!(message.header.version, 12);
+        assert_eq!(message.header.timestamp.unwrap().offset_minutes, 330);
+        assert_eq!(message.structured_data[0].params[0].1, "]\"");
+        assert_eq!(message.message.as_deref(), Some("msg"));
+        assert_eq!(unconsumed, "\nnext");
+        assert_eq!(
+            message.render(),
+            "<0>12 2003-10-11T22:14:15.5+05:30 h a p m [a b=\"\\]\\\"\"][c] msg"
+        );
+    }
+}
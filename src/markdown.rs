@@ -0,0 +1,488 @@
+//! Types for consuming a subset of __Markdown__ inline syntax.
+//!
+//! The [`Inline`] type consumes code spans, links, strong emphasis, emphasis and text. These are
+//! tried in that order at every position, so a code span takes precedence over the emphasis it
+//! may contain. When a construct cannot be closed, consuming backtracks and its opening
+//! delimiter becomes text, just like in [CommonMark](https://spec.commonmark.org/). The
+//! [`Inlines`] type consumes a whole paragraph of inlines.
+//!
+//! Failed attempts are remembered per position, so consuming stays quadratic for text with
+//! many unclosed delimiters. Only the basic rules of CommonMark are followed: reference links,
+//! images, autolinks and HTML are not supported.
+//!
+//! This module is only available with the `markdown` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use manger::{Consumable, Render};
+//! use manger::markdown::{Inline, Inlines};
+//!
+//! let inlines = Inlines::consume_all("Use *`manger`* for **fast [parsing](https://docs.rs)**")?;
+//!
+//! assert_eq!(
+//!     inlines.inlines(),
+//!     &[
+//!         Inline::Text("Use ".to_string()),
+//!         Inline::Emphasis(vec![Inline::Code("manger".to_string())]),
+//!         Inline::Text(" for ".to_string()),
+//!         Inline::Strong(vec![
+//!             Inline::Text("fast ".to_string()),
+//!             Inline::Link {
+//!                 text: vec![Inline::Text("parsing".to_string())],
+//!                 url: "https://docs.rs".to_string(),
+//!             },
+//!         ]),
+//!     ]
+//! );
+//! assert_eq!(inlines.plain_text(), "Use manger for fast parsing");
+//!
+//! // An unclosed delimiter is text.
+//! assert_eq!(
+//!     Inlines::consume_all("2 * 3 = 6")?.inlines(),
+//!     &[Inline::Text("2 * 3 = 6".to_string())]
+//! );
+//! # Ok::<(), manger::ConsumeError>(())
+//! ```
+
+use crate::error::ConsumeErrorType::*;
+use crate::{limits, Consumable, ConsumeError, Expectation, Render};
+use std::collections::HashMap;
+
+/// The characters that can start or end a construct, or escape another character.
+const SPECIAL: &str = "\\`*_[]";
+
+/// A single inline element of Markdown.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub enum Inline {
+    /// Text without markup, with escapes resolved.
+    Text(String),
+
+    /// A code span between backticks, such as `` `code` ``.
+    Code(String),
+
+    /// Emphasis between single `'*'` or `'_'` delimiters.
+    Emphasis(Vec<Inline>),
+
+    /// Strong emphasis between double `"**"` or `"__"` delimiters.
+    Strong(Vec<Inline>),
+
+    /// A link of the form `[text](url)`.
+    Link {
+        /// The inlines that form the text of the link.
+        text: Vec<Inline>,
+        /// The destination of the link.
+        url: String,
+    },
+}
+
+/// The inline elements of a paragraph.
+///
+/// Consumes the whole `source`, so it never fails unless a [limit][crate::limits] is exceeded.
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Default)]
+pub struct Inlines(Vec<Inline>);
+
+impl Inline {
+    /// Get the text without any markup.
+    pub fn plain_text(&self) -> String {
+        let mut text = String::new();
+        self.push_plain_text(&mut text);
+        text
+    }
+
+    fn push_plain_text(&self, out: &mut String) {
+        match self {
+            Inline::Text(text) | Inline::Code(text) => out.push_str(text),
+            Inline::Emphasis(inlines)
+            | Inline::Strong(inlines)
+            | Inline::Link { text: inlines, .. } => inlines
+                .iter()
+                .for_each(|inline| inline.push_plain_text(out)),
+        }
+    }
+}
+
+impl Inlines {
+    /// Get the inline elements in order of appearance.
+    pub fn inlines(&self) -> &[Inline] {
+        &self.0
+    }
+
+    /// Take ownership of the inline elements.
+    pub fn into_vec(self) -> Vec<Inline> {
+        self.0
+    }
+
+    /// Get the text without any markup.
+    pub fn plain_text(&self) -> String {
+        let mut text = String::new();
+        self.0
+            .iter()
+            .for_each(|inline| inline.push_plain_text(&mut text));
+        text
+    }
+}
+
+/// The constructs that are attempted at every position, in order of precedence.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+enum Construct {
+    Code,
+    Link,
+    Strong(char),
+    Emphasis(char),
+}
+
+const CONSTRUCTS: [Construct; 6] = [
+    Construct::Code,
+    Construct::Link,
+    Construct::Strong('*'),
+    Construct::Strong('_'),
+    Construct::Emphasis('*'),
+    Construct::Emphasis('_'),
+];
+
+/// Consumes inlines from `source`, remembering the attempted constructs by byte position.
+struct Parser<'s> {
+    source: &'s str,
+    attempts: HashMap<(usize, Construct), Option<(Inline, usize)>>,
+}
+
+impl<'s> Parser<'s> {
+    fn new(source: &'s str) -> Self {
+        Parser {
+            source,
+            attempts: HashMap::new(),
+        }
+    }
+
+    /// Consume inlines from `position` until the end of the `source` or the `closing` delimiter.
+    /// Returns the inlines and the position after them.
+    fn inlines(
+        &mut self,
+        mut position: usize,
+        closing: Option<&str>,
+    ) -> Result<(Vec<Inline>, usize), ConsumeError> {
+        let mut inlines = Vec::new();
+
+        while position < self.source.len() {
+            let rest = &self.source[position..];
+
+            // A closing delimiter that is followed by the same character is part of a longer
+            // delimiter, such as the `"**"` within `*a **b** c*`.
+            if let Some(closing) = closing {
+                if let Some(after) = rest.strip_prefix(closing) {
+                    if !after.starts_with(&closing[..1]) {
+                        break;
+                    }
+                }
+            }
+
+            if let Some((inline, end)) = self.construct(position)? {
+                inlines.push(inline);
+                position = end;
+                continue;
+            }
+
+            let (text, length) = literal_text(rest);
+            match inlines.last_mut() {
+                Some(Inline::Text(previous)) => previous.push_str(&text),
+                _ => inlines.push(Inline::Text(text)),
+            }
+            position += length;
+        }
+
+        Ok((inlines, position))
+    }
+
+    /// Attempt every construct at `position` in order of precedence.
+    fn construct(&mut self, position: usize) -> Result<Option<(Inline, usize)>, ConsumeError> {
+        for construct in CONSTRUCTS.iter() {
+            if let Some(attempt) = self.attempt(position, *construct)? {
+                return Ok(Some(attempt));
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn attempt(
+        &mut self,
+        position: usize,
+        construct: Construct,
+    ) -> Result<Option<(Inline, usize)>, ConsumeError> {
+        if let Some(attempt) = self.attempts.get(&(position, construct)) {
+            return Ok(attempt.clone());
+        }
+
+        let attempt = limits::nest(|| match construct {
+            Construct::Code => Ok(self.code(position)),
+            Construct::Link => self.link(position),
+            Construct::Strong(delimiter) => Ok(self
+                .delimited(position, &delimiter.to_string().repeat(2))?
+                .map(|(inlines, end)| (Inline::Strong(inlines), end))),
+            Construct::Emphasis(delimiter) => Ok(self
+                .delimited(position, &delimiter.to_string())?
+                .map(|(inlines, end)| (Inline::Emphasis(inlines), end))),
+        })?;
+
+        self.attempts.insert((position, construct), attempt.clone());
+        Ok(attempt)
+    }
+
+    /// A code span starts with a run of backticks and ends with a run of the same length.
+    fn code(&self, position: usize) -> Option<(Inline, usize)> {
+        let rest = &self.source[position..];
+        let fence = rest.len() - rest.trim_start_matches('`').len();
+        if fence == 0 {
+            return None;
+        }
+
+        let mut start = fence;
+        loop {
+            let offset = rest[start..].find('`')? + start;
+            let run = rest[offset..].len() - rest[offset..].trim_start_matches('`').len();
+
+            if run == fence {
+                let content = &rest[fence..offset];
+                let content = match content.strip_prefix(' ').and_then(|c| c.strip_suffix(' ')) {
+                    Some(stripped) if !content.trim().is_empty() => stripped,
+                    _ => content,
+                };
+
+                return Some((Inline::Code(content.to_string()), position + offset + run));
+            }
+
+            start = offset + run;
+        }
+    }
+
+    /// A link is its text between `'['` and `']'`, directly followed by a url between `'('` and
+    /// `')'` without whitespace.
+    fn link(&mut self, position: usize) -> Result<Option<(Inline, usize)>, ConsumeError> {
+        if !self.source[position..].starts_with('[') {
+            return Ok(None);
+        }
+
+        let (text, end) = self.inlines(position + 1, Some("]"))?;
+        let rest = match self.source[end..].strip_prefix("](") {
+            Some(rest) => rest,
+            None => return Ok(None),
+        };
+
+        let length = match rest.find(|token: char| token == ')' || token.is_whitespace()) {
+            Some(length) if rest[length..].starts_with(')') => length,
+            _ => return Ok(None),
+        };
+
+        Ok(Some((
+            Inline::Link {
+                text,
+                url: rest[..length].to_string(),
+            },
+            end + 2 + length + 1,
+        )))
+    }
+
+    /// Emphasis starts and ends with `delimiter`, where the inner inlines do not start or end with
+    /// whitespace.
+    fn delimited(
+        &mut self,
+        position: usize,
+        delimiter: &str,
+    ) -> Result<Option<(Vec<Inline>, usize)>, ConsumeError> {
+        let rest = &self.source[position..];
+        match rest.strip_prefix(delimiter) {
+            Some(inner) if !inner.starts_with(char::is_whitespace) => {}
+            _ => return Ok(None),
+        }
+
+        let start = position + delimiter.len();
+        let (inlines, end) = self.inlines(start, Some(delimiter))?;
+
+        if inlines.is_empty()
+            || !self.source[end..].starts_with(delimiter)
+            || self.source[start..end].ends_with(char::is_whitespace)
+        {
+            return Ok(None);
+        }
+
+        Ok(Some((inlines, end + delimiter.len())))
+    }
+}
+
+/// Get the text of one character, resolving an escape, followed by all characters that are not
+/// special. Returns the text and its length in bytes.
+fn literal_text(source: &str) -> (String, usize) {
+    let mut characters = source.chars();
+    let mut text = String::new();
+
+    match characters.next() {
+        Some('\\') => match characters.clone().next() {
+            Some(escaped) if escaped.is_ascii_punctuation() => {
+                characters.next();
+                text.push(escaped);
+            }
+            _ => text.push('\\'),
+        },
+        Some(token) => text.push(token),
+        None => {}
+    }
+
+    let rest = characters.as_str();
+    let run = rest
+        .find(|token| SPECIAL.contains(token))
+        .unwrap_or(rest.len());
+    text.push_str(&rest[..run]);
+
+    (text, source.len() - rest.len() + run)
+}
+
+impl Consumable for Inline {
+    fn expecting() -> Expectation {
+        Expectation::new("a Markdown inline")
+    }
+
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        if source.is_empty() {
+            return Err(ConsumeError::new_with(InsufficientTokens { index: 0 }));
+        }
+
+        let (inline, end) = match Parser::new(source).construct(0)? {
+            Some(attempt) => attempt,
+            None => {
+                let (text, length) = literal_text(source);
+                (Inline::Text(text), length)
+            }
+        };
+
+        Ok((inline, &source[end..]))
+    }
+}
+
+impl Consumable for Inlines {
+    fn expecting() -> Expectation {
+        Expectation::new("Markdown inlines")
+    }
+
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let (inlines, end) = Parser::new(source).inlines(0, None)?;
+        Ok((Inlines(inlines), &source[end..]))
+    }
+}
+
+/// Renders the inline as Markdown, escaping the special characters within text.
+impl Render for Inline {
+    fn render_to(&self, out: &mut String) {
+        match self {
+            Inline::Text(text) => {
+                for token in text.chars() {
+                    if SPECIAL.contains(token) {
+                        out.push('\\');
+                    }
+                    out.push(token);
+                }
+            }
+            Inline::Code(code) => {
+                let longest = code
+                    .split(|token| token != '`')
+                    .map(str::len)
+                    .max()
+                    .unwrap_or(0);
+                let fence = "`".repeat(longest + 1);
+                let padding = if code.starts_with('`') || code.ends_with('`') {
+                    " "
+                } else {
+                    ""
+                };
+
+                out.push_str(&fence);
+                out.push_str(padding);
+                out.push_str(code);
+                out.push_str(padding);
+                out.push_str(&fence);
+            }
+            Inline::Emphasis(inlines) => {
+                out.push('*');
+                inlines.render_to(out);
+                out.push('*');
+            }
+            Inline::Strong(inlines) => {
+                out.push_str("**");
+                inlines.render_to(out);
+                out.push_str("**");
+            }
+            Inline::Link { text, url } => {
+                out.push('[');
+                text.render_to(out);
+                out.push_str("](");
+                out.push_str(url);
+                out.push(')');
+            }
+        }
+    }
+}
+
+impl Render for Inlines {
+    fn render_to(&self, out: &mut String) {
+        self.0.render_to(out);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Inline, Inlines};
+    use crate::{Consumable, Render};
+
+    fn text(text: &str) -> Inline {
+        Inline::Text(text.to_string())
+    }
+
+    #[test]
+    fn test_markdown_precedence() {
+        assert_eq!(
+            Inlines::consume_all("*a **b** c*").unwrap().into_vec(),
+            vec![Inline::Emphasis(vec![
+                text("a "),
+                Inline::Strong(vec![text("b")]),
+                text(" c"),
+            ])]
+        );
+        assert_eq!(
+            Inlines::consume_all("**a**b**").unwrap().into_vec(),
+            vec![Inline::Strong(vec![text("a")]), text("b**")]
+        );
+        assert_eq!(
+            Inlines::consume_all("*a `*` b*").unwrap().into_vec(),
+            vec![Inline::Emphasis(vec![
+                text("a "),
+                Inline::Code("*".to_string()),
+                text(" b"),
+            ])]
+        );
+        assert_eq!(
+            Inlines::consume_all("[a](b c) \\*d\\*").unwrap().into_vec(),
+            vec![text("[a](b c) *d*")]
+        );
+        assert_eq!(
+            Inlines::consume_all("`` a`b ``").unwrap().into_vec(),
+            vec![Inline::Code("a`b".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_markdown_render() {
+        for source in ["*a* **b** [c](d)", "`` `x` `` \\*y\\*"] {
+            assert_eq!(Inlines::consume_all(source).unwrap().render(), source);
+        }
+
+        let (inline, unconsumed) = Inline::consume_from("*a* b").unwrap();
+        assert_eq!(inline, Inline::Emphasis(vec![text("a")]));
+        assert_eq!(unconsumed, " b");
+    }
+
+    #[test]
+    fn test_markdown_unclosed_delimiters() {
+        let source = "*a ".repeat(2000);
+        assert_eq!(Inlines::consume_all(&source).unwrap().plain_text(), source);
+    }
+}
@@ -0,0 +1,221 @@
+//! An adapter to use [__nom__](https://docs.rs/nom) parsers as a [`Consumable`].
+//!
+//! A nom parser is a function, while a [`Consumable`] is a type. The [`nom_parser`] macro
+//! therefore defines a zero-sized type implementing [`NomParser`] for a nom parser. The
+//! [`FromNom<P>`] consumer runs the parser `P` and can be used within tuples and the
+//! [`consume_struct`][crate::consume_struct] and [`consume_enum`][crate::consume_enum] macros,
+//! just like any other [`Consumable`]. This allows for gradually migrating from nom.
+//!
+//! Errors of nom are translated into a [`ConsumeError`] at the utf-8 character index where the
+//! parser failed. Only available with the `nom` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use manger::{consume_struct, nom_parser, Consumable};
+//! use manger::nom_adapter::FromNom;
+//! use nom::character::complete::hex_digit1;
+//! use nom::combinator::map_res;
+//!
+//! nom_parser! {
+//!     /// A hexadecimal number parsed by nom.
+//!     pub Hex => map_res(hex_digit1, |digits| u32::from_str_radix(digits, 16)) => u32;
+//! }
+//!
+//! struct Address(u32);
+//! consume_struct!(
+//!     Address => [
+//!         > "0x",
+//!         hex: FromNom<Hex>;
+//!         (hex.into_item())
+//!     ]
+//! );
+//!
+//! let (address, unconsumed) = Address::consume_from("0xff;")?;
+//! assert_eq!(address.0, 255);
+//! assert_eq!(unconsumed, ";");
+//!
+//! let error = Address::consume_from("0xyz").map(|_| ()).unwrap_err();
+//! assert_eq!(*error.causes()[0].index(), 2);
+//! # Ok::<(), manger::ConsumeError>(())
+//! ```
+
+#[doc(hidden)]
+pub use nom;
+
+use crate::error::ConsumeErrorType::*;
+use crate::strs::consumed_length;
+use crate::{Consumable, ConsumeError};
+use std::marker::PhantomData;
+
+/// A nom parser over `&str`, which is used by [`FromNom<P>`].
+///
+/// This is most easily implemented with the [`nom_parser`] macro.
+pub trait NomParser {
+    /// The output of the parser.
+    type Output;
+
+    /// Run the parser on `input`.
+    fn parse(input: &str) -> nom::IResult<&str, Self::Output>;
+}
+
+/// Consumer which runs the nom parser `P`.
+///
+/// When the parser fails, the error is at the index of the input at which nom reports the error.
+/// When nom needs more input, the error is at the end of the `source`.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{nom_parser, Consumable, ConsumeErrorType};
+/// use manger::nom_adapter::FromNom;
+/// use nom::bytes::complete::tag;
+/// use nom::character::complete::alpha1;
+/// use nom::sequence::preceded;
+///
+/// nom_parser! {
+///     Hashtag => preceded(tag("#"), alpha1) => String;
+/// }
+///
+/// let (hashtag, unconsumed) = <FromNom<Hashtag>>::consume_from("#rust!")?;
+/// assert_eq!(hashtag.item(), "rust");
+/// assert_eq!(unconsumed, "!");
+///
+/// let error = <FromNom<Hashtag>>::consume_from("#1").map(|_| ()).unwrap_err();
+/// assert_eq!(
+///     error.into_causes(),
+///     vec![ConsumeErrorType::UnexpectedToken { index: 1, token: '1' }]
+/// );
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct FromNom<P: NomParser> {
+    item: P::Output,
+    phantom: PhantomData<P>,
+}
+
+impl<P: NomParser> FromNom<P> {
+    /// Get the output of the parser.
+    pub fn item(&self) -> &P::Output {
+        &self.item
+    }
+
+    /// Take ownership of the output of the parser.
+    pub fn into_item(self) -> P::Output {
+        self.item
+    }
+}
+
+impl<P: NomParser> Consumable for FromNom<P> {
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        crate::trace::attempt::<Self, _>(source, || match P::parse(source) {
+            Ok((unconsumed, item)) => Ok((
+                FromNom {
+                    item,
+                    phantom: PhantomData,
+                },
+                unconsumed,
+            )),
+            Err(nom::Err::Error(err)) | Err(nom::Err::Failure(err)) => {
+                let index = consumed_length(source, err.input);
+
+                Err(ConsumeError::new_with(match err.input.chars().next() {
+                    Some(token) => UnexpectedToken { index, token },
+                    None => InsufficientTokens { index },
+                }))
+            }
+            Err(nom::Err::Incomplete(_)) => Err(ConsumeError::new_with(InsufficientTokens {
+                index: source.chars().count(),
+            })),
+        })
+    }
+}
+
+/// A macro used to define zero-sized types implementing [`NomParser`] for nom parsers.
+///
+/// Every definition gives the parser as an expression, followed by the type of its output. The
+/// output is converted into this type with [`Into`], which allows for parsers that borrow from
+/// the input, such as a `&str` output converted into a `String`.
+///
+/// # Examples
+///
+/// ```
+/// use manger::nom_parser;
+/// use manger::nom_adapter::NomParser;
+/// use nom::character::complete::{alpha1, digit1};
+///
+/// nom_parser! {
+///     Word => alpha1 => String;
+///     pub Digits => digit1 => String
+/// }
+///
+/// assert_eq!(Word::parse("abc1"), Ok(("1", "abc".to_string())));
+/// assert!(Digits::parse("abc1").is_err());
+/// ```
+#[macro_export]
+macro_rules! nom_parser {
+    ( $( $( #[$attr:meta] )* $vis:vis $name:ident => $parser:expr => $output:ty );+ $(;)? ) => {
+        $(
+            $( #[$attr] )*
+            #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+            $vis struct $name;
+
+            impl $crate::nom_adapter::NomParser for $name {
+                type Output = $output;
+
+                fn parse(
+                    input: &str,
+                ) -> $crate::nom_adapter::nom::IResult<&str, Self::Output> {
+                    let mut parser = $parser;
+
+                    $crate::nom_adapter::nom::Parser::parse(&mut parser, input).map(
+                        |(unconsumed, output)| (unconsumed, ::std::convert::Into::into(output)),
+                    )
+                }
+            }
+        )+
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FromNom;
+    use crate::error::ConsumeErrorType::*;
+    use crate::{Consumable, ConsumeError};
+    use nom::character::{complete, streaming};
+
+    nom_parser! {
+        Complete => complete::digit1 => String;
+        Streaming => streaming::digit1 => String;
+    }
+
+    #[test]
+    fn test_from_nom_errors() {
+        assert_eq!(
+            <FromNom<Complete>>::consume_from("ë").map(|_| ()),
+            Err(ConsumeError::new_with(UnexpectedToken {
+                index: 0,
+                token: 'ë'
+            }))
+        );
+        assert_eq!(
+            <FromNom<Complete>>::consume_from("").map(|_| ()),
+            Err(ConsumeError::new_with(InsufficientTokens { index: 0 }))
+        );
+        assert_eq!(
+            <FromNom<Streaming>>::consume_from("ë12").map(|_| ()),
+            Err(ConsumeError::new_with(UnexpectedToken {
+                index: 0,
+                token: 'ë'
+            }))
+        );
+        assert_eq!(
+            <FromNom<Streaming>>::consume_from("12").map(|_| ()),
+            Err(ConsumeError::new_with(InsufficientTokens { index: 2 }))
+        );
+        assert_eq!(
+            <FromNom<Complete>>::consume_from("12a").map(|(digits, _)| digits.into_item()),
+            Ok("12".to_string())
+        );
+    }
+}
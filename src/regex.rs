@@ -0,0 +1,208 @@
+//! Types for consuming a token with a __regular expression__.
+//!
+//! Some tokens are easier to describe with a regular expression than with combinators. The
+//! [`Regex<P, T>`] consumer consumes the match of the pattern `P` at the start of the `source`, so
+//! an existing regular expression can be embedded within a grammar. Patterns are defined with the
+//! [`regex_pattern`][crate::regex_pattern!] macro and compiled once, when they are first used.
+//!
+//! This module is only available with the `regex` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use manger::{consume_struct, regex_pattern, Consumable};
+//! use manger::regex::Regex;
+//!
+//! regex_pattern! {
+//!     Name => r"[a-z_]+";
+//!     Semver => r"(?P<major>\d+)\.(?P<minor>\d+)\.(?P<patch>\d+)";
+//! }
+//!
+//! struct Dependency {
+//!     name: Regex<Name>,
+//!     version: Regex<Semver>,
+//! }
+//! consume_struct!(
+//!     Dependency => [
+//!         name: Regex<Name>,
+//!         > " = ",
+//!         version: Regex<Semver>;
+//!     ]
+//! );
+//!
+//! let dependency = Dependency::consume_all("manger = 0.1.12")?;
+//!
+//! assert_eq!(dependency.name.item(), "manger");
+//! assert_eq!(dependency.version.name("minor"), Some("1"));
+//! assert_eq!(dependency.version.group(3), Some("12"));
+//! # Ok::<(), manger::ConsumeError>(())
+//! ```
+
+use crate::error::ConsumeErrorType::*;
+use crate::{Consumable, ConsumeError};
+use std::marker::PhantomData;
+use std::ops::Range;
+use std::str::FromStr;
+
+#[doc(no_inline)]
+pub use ::regex::Regex as CompiledRegex;
+
+/// Trait for a regular expression that can be used with [`Regex<P, T>`].
+///
+/// This trait is usually implemented with the [`regex_pattern`][crate::regex_pattern!] macro.
+/// When implemented manually, the compiled regular expression should only match at the start of
+/// the input, for example by starting the pattern with `^`.
+pub trait RegexPattern {
+    /// Get the compiled regular expression, which is anchored at the start of the input.
+    fn regex() -> &'static CompiledRegex;
+}
+
+/// Define a type that implements [`RegexPattern`][crate::regex::RegexPattern].
+///
+/// Every definition consists of a name and a string literal with the pattern in the syntax of the
+/// [`regex`](https://docs.rs/regex) crate. The pattern is anchored at the start of the input and
+/// compiled once, when it is first used. Invalid patterns panic at that moment.
+///
+/// This macro is only available with the `regex` feature.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{regex_pattern, Consumable};
+/// use manger::regex::Regex;
+///
+/// regex_pattern! {
+///     /// Hexadecimal color, such as `#ff8000`.
+///     pub HexColor => "#[0-9a-fA-F]{6}";
+/// }
+///
+/// let (color, unconsumed) = <Regex<HexColor>>::consume_from("#ff8000;")?;
+///
+/// assert_eq!(color.as_str(), "#ff8000");
+/// assert_eq!(unconsumed, ";");
+///
+/// assert!(<Regex<HexColor>>::consume_from(" #ff8000").is_err());
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[macro_export]
+macro_rules! regex_pattern {
+    ( $( $( #[$attr:meta] )* $vis:vis $name:ident => $pattern:literal );+ $(;)? ) => {
+        $(
+            $( #[$attr] )*
+            #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+            $vis struct $name;
+
+            impl $crate::regex::RegexPattern for $name {
+                fn regex() -> &'static $crate::regex::CompiledRegex {
+                    static REGEX: ::std::sync::OnceLock<$crate::regex::CompiledRegex> =
+                        ::std::sync::OnceLock::new();
+
+                    REGEX.get_or_init(|| {
+                        $crate::regex::CompiledRegex::new(concat!("^(?:", $pattern, ")"))
+                            .expect(concat!("invalid regex pattern for ", stringify!($name)))
+                    })
+                }
+            }
+        )+
+    };
+}
+
+/// Consumes the match of the regular expression `P` at the start of the `source`.
+///
+/// The matched text is turned into a `T` with its [`FromStr`] implementation, which is a
+/// [`String`] by default. When it fails, consuming fails with an
+/// [`InvalidValue`][crate::ConsumeErrorType::InvalidValue]. The matched text and its capture
+/// groups are kept as well.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{regex_pattern, Consumable};
+/// use manger::regex::Regex;
+///
+/// regex_pattern!(Number => r"[0-9]+(_[0-9]+)*");
+///
+/// let (number, _) = <Regex<Number>>::consume_from("1_000_000")?;
+/// assert_eq!(number.item(), "1_000_000");
+///
+/// regex_pattern!(Byte => r"[0-9]+");
+/// assert!(<Regex<Byte, u8>>::consume_from("255").is_ok());
+/// assert!(<Regex<Byte, u8>>::consume_from("256").is_err());
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Regex<P, T = String> {
+    item: T,
+    matched: String,
+    groups: Vec<Option<Range<usize>>>,
+    phantom: PhantomData<P>,
+}
+
+impl<P, T> Regex<P, T> {
+    /// Get a reference to the consumed item.
+    pub fn item(&self) -> &T {
+        &self.item
+    }
+
+    /// Unwrap to fetch the consumed item.
+    pub fn into_inner(self) -> T {
+        self.item
+    }
+
+    /// Get the text matched by the regular expression.
+    pub fn as_str(&self) -> &str {
+        &self.matched
+    }
+
+    /// Get the text matched by the capture group at `index`.
+    ///
+    /// The group at index `0` is the whole match. Returns `None` if the group does not exist or
+    /// did not participate in the match.
+    pub fn group(&self, index: usize) -> Option<&str> {
+        self.groups
+            .get(index)?
+            .clone()
+            .map(|range| &self.matched[range])
+    }
+}
+
+impl<P: RegexPattern, T> Regex<P, T> {
+    /// Get the text matched by the capture group with the name `name`.
+    ///
+    /// Returns `None` if the group does not exist or did not participate in the match.
+    pub fn name(&self, name: &str) -> Option<&str> {
+        let index = P::regex()
+            .capture_names()
+            .position(|group| group == Some(name))?;
+
+        self.group(index)
+    }
+}
+
+impl<P: RegexPattern, T: FromStr> Consumable for Regex<P, T> {
+    fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+        let captures = P::regex().captures(source).ok_or_else(|| {
+            ConsumeError::new_with(match source.chars().next() {
+                Some(token) => UnexpectedToken { index: 0, token },
+                None => InsufficientTokens { index: 0 },
+            })
+        })?;
+
+        let matched = captures.get(0).map_or("", |matched| matched.as_str());
+        let item =
+            T::from_str(matched).map_err(|_| ConsumeError::new_with(InvalidValue { index: 0 }))?;
+
+        Ok((
+            Regex {
+                item,
+                matched: matched.to_string(),
+                groups: captures
+                    .iter()
+                    .map(|group| group.map(|group| group.range()))
+                    .collect(),
+                phantom: PhantomData,
+            },
+            &source[matched.len()..],
+        ))
+    }
+}
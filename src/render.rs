@@ -0,0 +1,191 @@
+use crate::Render;
+use either::Either;
+
+macro_rules! render_display {
+    ( $( $type:ty ),+ ) => {
+        $(
+            impl Render for $type {
+                fn render_to(&self, out: &mut String) {
+                    use std::fmt::Write;
+
+                    write!(out, "{}", self).expect("writing to a String cannot fail");
+                }
+            }
+        )+
+    };
+}
+
+render_display!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+impl Render for char {
+    fn render_to(&self, out: &mut String) {
+        out.push(*self);
+    }
+}
+
+impl Render for str {
+    fn render_to(&self, out: &mut String) {
+        out.push_str(self);
+    }
+}
+
+impl Render for String {
+    fn render_to(&self, out: &mut String) {
+        out.push_str(self);
+    }
+}
+
+impl<T: Render + ?Sized> Render for &T {
+    fn render_to(&self, out: &mut String) {
+        (**self).render_to(out);
+    }
+}
+
+impl<T: Render> Render for Option<T> {
+    fn render_to(&self, out: &mut String) {
+        if let Some(item) = self {
+            item.render_to(out);
+        }
+    }
+}
+
+impl<T: Render> Render for Box<T> {
+    fn render_to(&self, out: &mut String) {
+        (**self).render_to(out);
+    }
+}
+
+impl<T: Render> Render for Vec<T> {
+    fn render_to(&self, out: &mut String) {
+        self.iter().for_each(|item| item.render_to(out));
+    }
+}
+
+/// Renders the start, followed by `..` and the end.
+impl<T: Render> Render for std::ops::Range<T> {
+    fn render_to(&self, out: &mut String) {
+        self.start.render_to(out);
+        out.push_str("..");
+        self.end.render_to(out);
+    }
+}
+
+/// Renders the start, followed by `..=` and the end.
+impl<T: Render> Render for std::ops::RangeInclusive<T> {
+    fn render_to(&self, out: &mut String) {
+        self.start().render_to(out);
+        out.push_str("..=");
+        self.end().render_to(out);
+    }
+}
+
+impl<L: Render, R: Render> Render for Either<L, R> {
+    fn render_to(&self, out: &mut String) {
+        match self {
+            Either::Left(left) => left.render_to(out),
+            Either::Right(right) => right.render_to(out),
+        }
+    }
+}
+
+macro_rules! render_concat {
+    ( $( $type_ident:ident => $index:tt ),+ ) => {
+        impl<$( $type_ident ),+> Render for ($( $type_ident ),+)
+        where
+            $( $type_ident: Render ),+
+        {
+            fn render_to(&self, out: &mut String) {
+                $( self.$index.render_to(out); )+
+            }
+        }
+    };
+}
+
+render_concat!(A => 0, B => 1);
+render_concat!(A => 0, B => 1, C => 2);
+render_concat!(A => 0, B => 1, C => 2, D => 3);
+render_concat!(A => 0, B => 1, C => 2, D => 3, E => 4);
+render_concat!(A => 0, B => 1, C => 2, D => 3, E => 4, F => 5);
+render_concat!(A => 0, B => 1, C => 2, D => 3, E => 4, F => 5, G => 6);
+render_concat!(A => 0, B => 1, C => 2, D => 3, E => 4, F => 5, G => 6, H => 7);
+render_concat!(A => 0, B => 1, C => 2, D => 3, E => 4, F => 5, G => 6, H => 7, I => 8);
+render_concat!(A => 0, B => 1, C => 2, D => 3, E => 4, F => 5, G => 6, H => 7, I => 8, J => 9);
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{OneOrMore, SeparatedBy, Sign};
+    use crate::{chars, consume_enum, consume_struct, Consumable, Render};
+
+    #[derive(Debug, PartialEq)]
+    struct Call {
+        name: char,
+        sign: Sign,
+        arguments: Vec<u32>,
+    }
+
+    consume_struct!(
+        #[render]
+        Call => [
+            name: char,
+            > '(',
+            : Vec<chars::Space>,
+            sign: Sign,
+            arguments: u32 [ ", " ],
+            > ')';
+        ]
+    );
+
+    #[derive(Debug, PartialEq)]
+    struct Range {
+        start: u32,
+        end: u32,
+        length: u32,
+    }
+
+    consume_struct!(
+        #[render]
+        Range => [
+            start: u32,
+            > "..",
+            end: u32 { |end| end >= start };
+            { start, end, length: end - start }
+        ]
+    );
+
+    #[derive(Debug, PartialEq)]
+    enum Shape {
+        Circle(u32),
+        Rectangle { width: u32, height: u32 },
+        Empty,
+    }
+
+    consume_enum!(
+        #[render]
+        Shape {
+            Circle => [ > "circle ", radius: u32; (radius) ],
+            Rectangle => [ > "rect ", width: u32, : chars::Space, height: u32; ],
+            Empty => [ > "empty"; ]
+        }
+    );
+
+    fn round_trip<T: Consumable + Render>(source: &str) -> String {
+        T::consume_all(source).unwrap().render()
+    }
+
+    #[test]
+    fn test_render_round_trip() {
+        assert_eq!(round_trip::<Call>("f(-1, 2)"), "f(-1, 2)");
+        assert_eq!(round_trip::<Call>("g(   3)"), "g(3)");
+        assert_eq!(round_trip::<Shape>("circle 5"), "circle 5");
+        assert_eq!(round_trip::<Shape>("rect 3 4"), "rect 3 4");
+        assert_eq!(round_trip::<Shape>("empty"), "empty");
+        assert_eq!(round_trip::<Range>("3..5"), "3..5");
+        assert_eq!(Range::consume_all("3..5").map(|range| range.length), Ok(2));
+        assert_eq!(round_trip::<OneOrMore<chars::Plus>>("+++"), "+++");
+        assert_eq!(
+            round_trip::<SeparatedBy<u8, chars::Comma>>("1,2,3"),
+            "1,2,3"
+        );
+        assert_eq!(round_trip::<(i32, chars::Colon, Option<u8>)>("-1:"), "-1:");
+    }
+}
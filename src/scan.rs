@@ -0,0 +1,217 @@
+//! Types for consuming with a __format string__, similar to `sscanf`.
+//!
+//! The [`scan`][crate::scan!] macro extracts values of [`Consumable`] types from a `source`
+//! string, without defining a `struct`. The format string contains a `{}` placeholder for every
+//! type. All other characters of the format string are literals, where `{{` and `}}` stand for a
+//! literal `{` and `}`. A lone `{` or `}` makes the format string invalid. The [`Scanner`] does the
+//! work behind the macro.
+
+use crate::error::ConsumeErrorType::*;
+use crate::{Consumable, ConsumeError, ConsumeSource};
+
+/// Consume values from a `source` string according to a format string.
+///
+/// The macro takes the `source`, a format string with a `{}` placeholder for every value and the
+/// [`Consumable`][crate::Consumable] types of the values. It returns a tuple of the values along
+/// with the unconsumed part of the `source`, or the error with the index at which consuming
+/// failed.
+///
+/// # Examples
+///
+/// ```
+/// use manger::scan;
+///
+/// let ((x, y), unconsumed) = scan!("(3, -4) + 1", "({}, {})", u32, i32)?;
+///
+/// assert_eq!((x, y), (3, -4));
+/// assert_eq!(unconsumed, " + 1");
+///
+/// let error = scan!("(3; -4)", "({}, {})", u32, i32).unwrap_err();
+/// assert_eq!(error.causes()[0].index(), &2);
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+///
+/// A format string which is invalid or has a different amount of placeholders than types results
+/// in an [`InvalidFormat`][crate::ConsumeErrorType::InvalidFormat] error.
+///
+/// ```
+/// use manger::{scan, ConsumeErrorType};
+///
+/// let error = scan!("1 2", "{} 2", u8, u8).unwrap_err();
+/// assert_eq!(error.causes(), vec![&ConsumeErrorType::InvalidFormat { index: 3 }]);
+/// ```
+#[macro_export]
+macro_rules! scan {
+    ( $source:expr, $format:expr, $( $cons_type:ty ),+ $(,)? ) => {
+        (|| {
+            let mut scanner = $crate::scan::Scanner::new($format, $source)?;
+            let items = ( $( scanner.value::<$cons_type>()?, )+ );
+
+            Ok::<_, $crate::ConsumeError>((items, scanner.finish()?))
+        })()
+    };
+}
+
+/// Consumes a `source` string according to a format string with `{}` placeholders.
+///
+/// The literals of the format string are consumed as soon as possible: the literal before the
+/// first placeholder by [`new`][Scanner::new], and the literal after a placeholder by
+/// [`value`][Scanner::value].
+///
+/// # Examples
+///
+/// ```
+/// use manger::scan::Scanner;
+///
+/// let mut scanner = Scanner::new("{{{}}} = {}", "{x} = 5")?;
+///
+/// assert_eq!(scanner.value::<char>()?, 'x');
+/// assert_eq!(scanner.value::<u8>()?, 5);
+/// assert_eq!(scanner.finish()?, "");
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Scanner<'f, 's> {
+    format: &'f str,
+    unconsumed: &'s str,
+    offset: usize,
+    at_placeholder: bool,
+}
+
+impl<'f, 's> Scanner<'f, 's> {
+    /// Create a scanner for `source` and consume the literal before the first placeholder.
+    ///
+    /// Fails when the `format` contains a lone `{` or `}`.
+    pub fn new(format: &'f str, source: &'s str) -> Result<Self, ConsumeError> {
+        let mut chars = format.chars();
+        while let Some(token) = chars.next() {
+            match (token, chars.clone().next()) {
+                ('{', Some('{' | '}')) | ('}', Some('}')) => {
+                    chars.next();
+                }
+                ('{' | '}', _) => {
+                    return Err(ConsumeError::new_with(InvalidFormat { index: 0 }));
+                }
+                _ => {}
+            }
+        }
+
+        let mut scanner = Scanner {
+            format,
+            unconsumed: source,
+            offset: 0,
+            at_placeholder: false,
+        };
+
+        scanner.consume_literal()?;
+        Ok(scanner)
+    }
+
+    /// Consume the literal of the format string up to the next placeholder or the end.
+    fn consume_literal(&mut self) -> Result<(), ConsumeError> {
+        let mut literal = String::new();
+        let mut chars = self.format.char_indices();
+        let mut rest = "";
+        self.at_placeholder = false;
+
+        while let Some((index, token)) = chars.next() {
+            let next = self.format[index + token.len_utf8()..].chars().next();
+
+            match (token, next) {
+                ('{', Some('}')) => {
+                    rest = &self.format[index + 2..];
+                    self.at_placeholder = true;
+                    break;
+                }
+                ('{', Some('{')) | ('}', Some('}')) => {
+                    literal.push(token);
+                    chars.next();
+                }
+                _ => literal.push(token),
+            }
+        }
+
+        self.format = rest;
+
+        let offset = self.offset;
+        self.offset += self
+            .unconsumed
+            .mut_consume_lit(&literal.as_str())
+            .map_err(|err| err.offset(offset))?;
+
+        Ok(())
+    }
+
+    /// Consume the value for the next placeholder, followed by the literal after the placeholder.
+    ///
+    /// Fails with an [`InvalidFormat`][crate::ConsumeErrorType::InvalidFormat] error if there are
+    /// no placeholders left.
+    pub fn value<T: Consumable>(&mut self) -> Result<T, ConsumeError> {
+        if !self.at_placeholder {
+            return Err(ConsumeError::new_with(InvalidFormat { index: self.offset }));
+        }
+
+        let offset = self.offset;
+        let (item, by) = self
+            .unconsumed
+            .mut_consume_by::<T>()
+            .map_err(|err| err.offset(offset))?;
+        self.offset += by;
+
+        self.consume_literal()?;
+        Ok(item)
+    }
+
+    /// Get the part of the `source` that has not been consumed.
+    ///
+    /// Fails with an [`InvalidFormat`][crate::ConsumeErrorType::InvalidFormat] error if there are
+    /// placeholders left.
+    pub fn finish(self) -> Result<&'s str, ConsumeError> {
+        if self.at_placeholder {
+            return Err(ConsumeError::new_with(InvalidFormat { index: self.offset }));
+        }
+
+        Ok(self.unconsumed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Scanner;
+    use crate::error::ConsumeErrorType::*;
+    use crate::ConsumeError;
+
+    #[test]
+    fn test_scan() {
+        assert_eq!(
+            scan!("é: 1, 2", "é: {}, {}", u8, i64).unwrap(),
+            ((1, 2), "")
+        );
+        assert_eq!(scan!("x", "{}", char).unwrap(), (('x',), ""));
+        assert_eq!(
+            scan!("é: 1; 2", "é: {}, {}", u8, i64).unwrap_err().causes()[0].index(),
+            &4
+        );
+    }
+
+    #[test]
+    fn test_invalid_format() {
+        assert_eq!(
+            scan!("1 2", "{} 2", u8, u8).unwrap_err().causes(),
+            vec![&InvalidFormat { index: 3 }]
+        );
+        assert_eq!(
+            Scanner::new("{}", "1").and_then(Scanner::finish),
+            Err(ConsumeError::new_with(InvalidFormat { index: 0 }))
+        );
+        assert_eq!(
+            scan!("{1}", "{{}", u8).unwrap_err().causes(),
+            vec![&InvalidFormat { index: 0 }]
+        );
+        assert_eq!(
+            scan!("1}", "{}}", u8).unwrap_err().causes(),
+            vec![&InvalidFormat { index: 0 }]
+        );
+        assert_eq!(scan!("{1}", "{{{}}}", u8).unwrap(), ((1,), ""));
+    }
+}
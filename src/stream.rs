@@ -0,0 +1,495 @@
+//! Consuming items from an __asynchronous__ source as data arrives.
+//!
+//! Services which read from sockets or pipes receive their data in chunks and do not want to
+//! block while waiting for the rest of an item. An [`AsyncStreamConsumer<R, T>`] reads from an
+//! [`AsyncBufRead`] `R` and yields the consumed items of `T` as a [`Stream`], just like
+//! [`consume_iter`][crate::Consumable::consume_iter] does for a `&str`.
+//!
+//! Consuming an item is retried when more data arrives while it is incomplete. An item is
+//! incomplete when consuming it fails with an `InsufficientTokens` error at the end of the
+//! buffered data, or when it consumes all buffered data, since it could continue in the data that
+//! has not yet arrived. At the end of the source, items are consumed as usual.
+//!
+//! An incomplete item is consumed again from its start, so the consumer does not retry after every
+//! read. It retries once the buffered data has doubled since the last attempt, or when the source
+//! has no more data available for now. This keeps the work linear in the size of an item when
+//! data arrives faster than it is consumed, while an item that is complete is never held back.
+//!
+//! Only available with the `async` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use futures::executor::block_on;
+//! use futures::stream::TryStreamExt;
+//! use manger::chars::Char;
+//! use manger::stream::AsyncConsumable;
+//!
+//! // Numbers separated by commas, which can be split over any number of reads.
+//! let reader: &[u8] = b"1,22,333,";
+//!
+//! let numbers: Vec<(u32, Char<','>)> = block_on(
+//!     <(u32, Char<','>)>::consume_stream(reader).try_collect()
+//! )?;
+//! let numbers: Vec<u32> = numbers.into_iter().map(|(number, _)| number).collect();
+//!
+//! assert_eq!(numbers, vec![1, 22, 333]);
+//! # Ok::<(), manger::stream::StreamError>(())
+//! ```
+
+use crate::error::ConsumeErrorType::{self, *};
+use crate::{context, Consumable, ConsumeError};
+use futures_core::Stream;
+use futures_io::AsyncBufRead;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// An error which occurs while consuming from an asynchronous source.
+#[derive(Debug, thiserror::Error)]
+pub enum StreamError {
+    /// Reading from the source failed.
+    #[error("Failed to read from the source: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// Consuming an item failed. The indices of the error are utf-8 character indices within all
+    /// data read from the source.
+    #[error("{0}")]
+    Consume(ConsumeError),
+}
+
+/// Trait which allows a [`Consumable`] type to be consumed from an asynchronous source.
+///
+/// This is implemented for all [`Consumable`] types.
+pub trait AsyncConsumable: Consumable {
+    /// Consume items of `Self` from `reader` as a [`Stream`].
+    ///
+    /// This is a shorthand for [`AsyncStreamConsumer::new`].
+    fn consume_stream<R: AsyncBufRead + Unpin>(reader: R) -> AsyncStreamConsumer<R, Self> {
+        AsyncStreamConsumer::new(reader)
+    }
+}
+
+impl<T: Consumable> AsyncConsumable for T {}
+
+impl From<ConsumeError> for StreamError {
+    fn from(err: ConsumeError) -> Self {
+        StreamError::Consume(err)
+    }
+}
+
+/// A [`Stream`] of items of `T` consumed from the [`AsyncBufRead`] `R`.
+///
+/// The stream ends after the first error, or when all data has been read and consumed.
+///
+/// # Examples
+///
+/// ```
+/// use futures::executor::block_on;
+/// use futures::stream::StreamExt;
+/// use manger::stream::{AsyncStreamConsumer, StreamError};
+///
+/// let reader: &[u8] = b"abc\xFF";
+/// let mut consumer = AsyncStreamConsumer::<_, char>::new(reader);
+///
+/// block_on(async {
+///     assert_eq!(consumer.next().await.map(Result::ok), Some(Some('a')));
+///     assert_eq!(consumer.next().await.map(Result::ok), Some(Some('b')));
+///     assert_eq!(consumer.next().await.map(Result::ok), Some(Some('c')));
+///
+///     // Invalid utf-8 ends the stream with an error.
+///     assert!(matches!(consumer.next().await, Some(Err(StreamError::Consume(_)))));
+///     assert!(consumer.next().await.is_none());
+/// });
+/// ```
+#[derive(Debug)]
+pub struct AsyncStreamConsumer<R, T> {
+    reader: R,
+    buffer: String,
+    undecoded: Vec<u8>,
+    consumed: usize,
+    invalid: Option<usize>,
+    attempted: usize,
+    eof: bool,
+    done: bool,
+    phantom: PhantomData<fn() -> T>,
+}
+
+impl<R: AsyncBufRead + Unpin, T: Consumable> AsyncStreamConsumer<R, T> {
+    /// Create a consumer which reads from `reader`.
+    pub fn new(reader: R) -> Self {
+        AsyncStreamConsumer {
+            reader,
+            buffer: String::new(),
+            undecoded: Vec::new(),
+            consumed: 0,
+            invalid: None,
+            attempted: 0,
+            eof: false,
+            done: false,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Get the data that has been read, but not yet consumed.
+    pub fn buffered(&self) -> &str {
+        &self.buffer
+    }
+
+    /// Get the amount of utf-8 characters that have been consumed.
+    pub fn consumed(&self) -> usize {
+        self.consumed
+    }
+
+    /// Take back the reader. Data that has been read, but not yet consumed, is lost.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Returns whether no more data follows the buffered data, because the end of the source or
+    /// invalid utf-8 was reached.
+    fn is_complete(&self) -> bool {
+        self.eof || self.invalid.is_some()
+    }
+
+    /// Returns whether consuming from the buffered data should be attempted. An incomplete item is
+    /// only attempted again once the buffered data doubled, or when no more data is available for
+    /// now and the buffered data grew at all.
+    fn should_attempt(&self, pending: bool) -> bool {
+        self.attempted == 0
+            || self.is_complete()
+            || self.buffer.len() >= 2 * self.attempted
+            || (pending && self.buffer.len() > self.attempted)
+    }
+
+    /// Attempt to consume an item from the buffered data, returning `None` when the item is
+    /// incomplete.
+    fn consume_buffered(&mut self) -> Option<Result<T, ConsumeError>> {
+        let source = self.buffer.as_str();
+        let complete = self.is_complete();
+
+        let result = match context::scope(source, || T::consume_from(source)) {
+            Ok((_, rest)) if rest.is_empty() && !complete => None,
+            Ok((item, rest)) if rest.len() < source.len() => {
+                let length = crate::strs::consumed_length(source, rest);
+                self.buffer.drain(..source.len() - rest.len());
+                self.consumed += length;
+                Some(Ok(item))
+            }
+            // An item that consumes nothing would be consumed forever.
+            Ok(_) => Some(Err(ConsumeError::new_with(InvalidValue { index: 0 }))),
+            Err(err) if !complete && is_incomplete(&err, source.chars().count()) => None,
+            Err(err) => Some(Err(err)),
+        };
+
+        self.attempted = match result {
+            None => self.buffer.len(),
+            Some(_) => 0,
+        };
+
+        result
+    }
+
+    /// Attempt to consume an item and turn it into an item of the stream.
+    fn poll_buffered(&mut self) -> Option<Result<T, StreamError>> {
+        let result = self.consume_buffered()?;
+        self.done = result.is_err();
+
+        let consumed = self.consumed;
+        Some(result.map_err(|err| StreamError::Consume(err.offset(consumed))))
+    }
+
+    /// Decode the valid utf-8 at the start of the undecoded bytes into the buffer, and remember
+    /// the index of invalid utf-8 after it.
+    fn decode(&mut self) {
+        let (valid, invalid) = match std::str::from_utf8(&self.undecoded) {
+            Ok(valid) => (valid.len(), false),
+            // An error without length is an incomplete character at the end.
+            Err(err) => (err.valid_up_to(), err.error_len().is_some() || self.eof),
+        };
+
+        let bytes: Vec<u8> = self.undecoded.drain(..valid).collect();
+        self.buffer
+            .push_str(std::str::from_utf8(&bytes).expect("decoded bytes are valid utf-8"));
+
+        if invalid {
+            self.invalid = Some(self.consumed + self.buffer.chars().count());
+        }
+    }
+}
+
+impl<R: AsyncBufRead + Unpin, T: Consumable> Stream for AsyncStreamConsumer<R, T> {
+    type Item = Result<T, StreamError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if this.done {
+                return Poll::Ready(None);
+            }
+
+            if !this.buffer.is_empty() {
+                if this.should_attempt(false) {
+                    if let Some(result) = this.poll_buffered() {
+                        return Poll::Ready(Some(result));
+                    }
+                }
+            } else if let Some(index) = this.invalid {
+                this.done = true;
+                return Poll::Ready(Some(Err(StreamError::Consume(ConsumeError::new_with(
+                    InvalidValue { index },
+                )))));
+            } else if this.eof {
+                this.done = true;
+                return Poll::Ready(None);
+            }
+
+            let bytes = match Pin::new(&mut this.reader).poll_fill_buf(cx) {
+                Poll::Pending => {
+                    // An item could be complete, while the source waits for it to be consumed.
+                    if !this.buffer.is_empty() && this.should_attempt(true) {
+                        if let Some(result) = this.poll_buffered() {
+                            return Poll::Ready(Some(result));
+                        }
+                    }
+
+                    return Poll::Pending;
+                }
+                Poll::Ready(Err(err)) => {
+                    this.done = true;
+                    return Poll::Ready(Some(Err(StreamError::Io(err))));
+                }
+                Poll::Ready(Ok(bytes)) => bytes,
+            };
+
+            let length = bytes.len();
+            this.eof = length == 0;
+            this.undecoded.extend_from_slice(bytes);
+            Pin::new(&mut this.reader).consume(length);
+
+            this.decode();
+        }
+    }
+}
+
+/// Returns whether `err` was caused by the buffered data of `length` characters ending too soon.
+fn is_incomplete(err: &ConsumeError, length: usize) -> bool {
+    err.causes().into_iter().any(|cause| match cause {
+        ConsumeErrorType::InsufficientTokens { index } => *index >= length,
+        _ => false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AsyncConsumable, StreamError};
+    use crate::chars::{Char, Space};
+    use crate::common::LengthPrefixed;
+    use crate::error::ConsumeErrorType::*;
+    use crate::limits::Limit;
+    use crate::{Consumable, ConsumeError};
+    use futures::executor::block_on;
+    use futures::stream::{StreamExt, TryStreamExt};
+    use futures_io::{AsyncBufRead, AsyncRead};
+    use std::cell::Cell;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    /// A reader which returns its chunks one at a time, and is pending before every chunk unless
+    /// it is `ready`.
+    struct Chunks {
+        chunks: Vec<&'static [u8]>,
+        pending: bool,
+        ready: bool,
+    }
+
+    impl Chunks {
+        fn new(chunks: Vec<&'static [u8]>) -> Self {
+            Chunks {
+                chunks,
+                pending: true,
+                ready: false,
+            }
+        }
+
+        fn ready(chunks: Vec<&'static [u8]>) -> Self {
+            Chunks {
+                chunks,
+                pending: false,
+                ready: true,
+            }
+        }
+    }
+
+    impl AsyncRead for Chunks {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut [u8],
+        ) -> Poll<std::io::Result<usize>> {
+            let chunk = match self.as_mut().poll_fill_buf(cx) {
+                Poll::Ready(Ok(chunk)) => chunk,
+                Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+                Poll::Pending => return Poll::Pending,
+            };
+
+            let amount = chunk.len().min(buf.len());
+            buf[..amount].copy_from_slice(&chunk[..amount]);
+            self.consume(amount);
+
+            Poll::Ready(Ok(amount))
+        }
+    }
+
+    impl AsyncBufRead for Chunks {
+        fn poll_fill_buf(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+        ) -> Poll<std::io::Result<&[u8]>> {
+            let this = self.get_mut();
+
+            if this.pending {
+                this.pending = false;
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+
+            Poll::Ready(Ok(this.chunks.first().copied().unwrap_or(&[])))
+        }
+
+        fn consume(self: Pin<&mut Self>, amount: usize) {
+            let this = self.get_mut();
+
+            if amount > 0 {
+                this.chunks[0] = &this.chunks[0][amount..];
+
+                if this.chunks[0].is_empty() {
+                    this.chunks.remove(0);
+                    this.pending = !this.ready;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_stream_chunks() {
+        let reader = Chunks::new(vec![b"12", b"3 4", b"5", b"6 7"]);
+        let items: Vec<(u32, Option<Space>)> =
+            block_on(<(u32, Option<Space>)>::consume_stream(reader).try_collect()).unwrap();
+        let items: Vec<u32> = items.into_iter().map(|(number, _)| number).collect();
+        assert_eq!(items, vec![123, 456, 7]);
+
+        let reader = Chunks::new(vec![b"a", b"b\xC3", b"\xAB"]);
+        let items: Vec<char> = block_on(char::consume_stream(reader).try_collect()).unwrap();
+        assert_eq!(items, vec!['a', 'b', 'ë']);
+    }
+
+    #[test]
+    fn test_stream_frames() {
+        type Frame = LengthPrefixed<Vec<char>, 8>;
+
+        let reader = Chunks::new(vec![b"5:he", b"llo3:a", b"bc0", b":"]);
+        let frames: Vec<Frame> = block_on(Frame::consume_stream(reader).try_collect()).unwrap();
+        let frames: Vec<String> = frames
+            .iter()
+            .map(|frame| frame.item().iter().collect())
+            .collect();
+        assert_eq!(frames, vec!["hello", "abc", ""]);
+
+        // A frame which is too large is rejected before its body arrives.
+        let reader = Chunks::new(vec![b"1", b"00:", b"abc"]);
+        let frames: Vec<_> = block_on(Frame::consume_stream(reader).collect());
+        match &frames[..] {
+            [Err(StreamError::Consume(err))] => assert_eq!(
+                err.causes(),
+                vec![&LimitExceeded {
+                    index: 0,
+                    limit: Limit::Length
+                }]
+            ),
+            other => panic!("unexpected frames {:?}", other),
+        }
+    }
+
+    thread_local! {
+        static ATTEMPTS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    /// A frame which counts how often consuming it was attempted.
+    #[derive(Debug)]
+    struct Counted(LengthPrefixed<Vec<char>, 4096>);
+
+    impl Consumable for Counted {
+        fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+            ATTEMPTS.with(|attempts| attempts.set(attempts.get() + 1));
+            Consumable::consume_from(source).map(|(frame, rest)| (Counted(frame), rest))
+        }
+    }
+
+    #[test]
+    fn test_stream_retries() {
+        let mut chunks: Vec<&'static [u8]> = vec![b"2000:"];
+        chunks.extend(vec![&b"x"[..]; 2000]);
+
+        let frames: Vec<Counted> =
+            block_on(Counted::consume_stream(Chunks::ready(chunks)).try_collect()).unwrap();
+        assert_eq!(frames[0].0.item().len(), 2000);
+        assert!(ATTEMPTS.with(Cell::get) < 20);
+
+        // A complete item is consumed while the source has no more data for now.
+        let mut consumer = <(u32, Char<','>)>::consume_stream(Chunks::new(vec![b"12345", b"6,"]));
+        let item = block_on(consumer.next()).unwrap().unwrap();
+        assert_eq!(item.0, 123456);
+    }
+
+    #[test]
+    fn test_stream_read() {
+        use futures::io::AsyncReadExt;
+
+        let mut reader = Chunks::new(vec![b"ab", b"c"]);
+        let mut read = String::new();
+        block_on(reader.read_to_string(&mut read)).unwrap();
+        assert_eq!(read, "abc");
+    }
+
+    #[test]
+    fn test_stream_errors() {
+        let reader = Chunks::new(vec![b"1,2", b",x,3,"]);
+        let items: Vec<_> = block_on(<(u8, Char<','>)>::consume_stream(reader).collect());
+        assert_eq!(items.len(), 3);
+        assert!(items[..2].iter().all(Result::is_ok));
+        match &items[2] {
+            Err(StreamError::Consume(err)) => {
+                assert_eq!(
+                    err.causes(),
+                    vec![&UnexpectedToken {
+                        index: 4,
+                        token: 'x'
+                    }]
+                )
+            }
+            other => panic!("unexpected item {:?}", other),
+        }
+
+        // The last item is incomplete at the end of the source.
+        let reader = Chunks::new(vec![b"1,", b"2"]);
+        let items: Vec<_> = block_on(<(u8, Char<','>)>::consume_stream(reader).collect());
+        assert_eq!(items.len(), 2);
+        match &items[1] {
+            Err(StreamError::Consume(err)) => {
+                assert_eq!(err.causes(), vec![&InsufficientTokens { index: 3 }])
+            }
+            other => panic!("unexpected item {:?}", other),
+        }
+
+        let reader = Chunks::new(vec![b"ab\xC3"]);
+        let items: Vec<_> = block_on(char::consume_stream(reader).collect());
+        match &items[..] {
+            [Ok('a'), Ok('b'), Err(StreamError::Consume(err))] => {
+                assert_eq!(err.causes(), vec![&InvalidValue { index: 2 }])
+            }
+            other => panic!("unexpected items {:?}", other),
+        }
+    }
+}
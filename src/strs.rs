@@ -2,32 +2,272 @@ use crate::error::ConsumeError;
 use crate::error::ConsumeErrorType::*;
 use crate::SelfConsumable;
 
+/// Split the first utf-8 character from `source`.
+///
+/// With the `ascii-fast-path` feature, an ASCII character is split by looking at the first byte
+/// only.
+pub(crate) fn split_first_char(source: &str) -> Option<(char, &str)> {
+    #[cfg(feature = "ascii-fast-path")]
+    if let Some(byte) = source.as_bytes().first().filter(|byte| byte.is_ascii()) {
+        debug_assert!(
+            source.is_char_boundary(1),
+            "an ASCII byte is a whole character"
+        );
+        return Some((char::from(*byte), &source[1..]));
+    }
+
+    let token = source.chars().next()?;
+    Some((token, utf8_slice::from(source, 1)))
+}
+
+/// Get the amount of utf-8 characters that were consumed from `source`, where `unconsumed` is the
+/// end of `source`.
+///
+/// Only the consumed part is counted, so this does not depend on the length of `unconsumed`.
+pub(crate) fn consumed_length(source: &str, unconsumed: &str) -> usize {
+    source[..source.len() - unconsumed.len()].chars().count()
+}
+
+/// Consumes the literal by comparing bytes. Since both strings are valid utf-8, a matching prefix
+/// always ends at a character boundary.
+///
+/// When the literal does not match, the error is at the utf-8 character index of the first token
+/// that differs and contains that token from the `source`.
 impl SelfConsumable for &str {
     fn consume_item<'a>(source: &'a str, item: &'_ Self) -> Result<&'a str, ConsumeError> {
-        let mut unconsumed = source;
+        if let Some(unconsumed) = source.strip_prefix(item) {
+            return Ok(unconsumed);
+        }
 
-        for (index, token) in item.chars().enumerate() {
-            if let Some(next_char) = unconsumed.chars().next() {
-                if token != next_char {
-                    return Err(ConsumeError::new_with(UnexpectedToken { index, token }));
+        let mut tokens = source.chars();
+        for (index, expected) in item.chars().enumerate() {
+            match tokens.next() {
+                Some(token) if token == expected => {}
+                Some(token) => {
+                    return Err(ConsumeError::new_with(UnexpectedToken { index, token }))
                 }
-            } else {
-                return Err(ConsumeError::new_with(InsufficientTokens { index }));
+                None => return Err(ConsumeError::new_with(InsufficientTokens { index })),
             }
-
-            unconsumed = utf8_slice::from(unconsumed, 1);
         }
 
-        Ok(unconsumed)
+        unreachable!("`source` starts with `item` when all tokens match")
     }
 }
 
+/// Consumes the literal like a `&str` does.
+///
+/// This allows for literals that are only known at runtime within
+/// [`consume_struct`][crate::consume_struct] and [`consume_enum`][crate::consume_enum], since the
+/// `> EXPR` instruction accepts any expression.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{consume_struct, Consumable};
+///
+/// fn delimiter() -> String {
+///     ":".repeat(2)
+/// }
+///
+/// struct Path(u32, u32);
+/// consume_struct!(
+///     Path => [
+///         first: u32,
+///         > delimiter(),
+///         second: u32;
+///         (first, second)
+///     ]
+/// );
+///
+/// assert!(Path::consume_all("4::2").is_ok());
+/// assert!(Path::consume_all("4:2").is_err());
+/// ```
+impl SelfConsumable for String {
+    fn consume_item<'a>(source: &'a str, item: &'_ Self) -> Result<&'a str, ConsumeError> {
+        <&str>::consume_item(source, &item.as_str())
+    }
+}
+
+/// A macro used to define zero-sized types for literal strings, such as keywords.
+///
+/// Every type consumes its literal string exactly and renders it again. The literal is also
+/// available through the associated `LITERAL` constant, and can be consumed in a `const` context
+/// with the associated `try_consume_const` function.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{lit, Consumable, Render};
+/// use manger::chars::Space;
+///
+/// lit! {
+///     /// The `let` keyword.
+///     "let" => pub Let;
+///     "=" => Assign
+/// }
+///
+/// type Binding = (Let, Space, char, Space, Assign, Space, u32);
+///
+/// let (binding, unconsumed) = Binding::consume_from("let x = 42;")?;
+/// assert_eq!(unconsumed, ";");
+/// assert_eq!(binding.render(), "let x = 42");
+/// assert_eq!(Let::LITERAL, "let");
+///
+/// assert!(Let::consume_from("lettuce").is_ok());
+/// assert!(Let::consume_from("le").is_err());
+///
+/// // The literal can also be consumed in a `const` context.
+/// const _: () = assert!(Let::try_consume_const("let x").is_ok());
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[macro_export]
+macro_rules! lit {
+    ( $( $( #[$attr:meta] )* $literal:literal => $vis:vis $name:ident );+ $(;)? ) => {
+        $(
+            $( #[$attr] )*
+            #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+            $vis struct $name;
+
+            #[allow(dead_code)]
+            impl $name {
+                /// The literal string which is consumed and rendered.
+                pub const LITERAL: &'static str = $literal;
+
+                /// Consume the same as `consume_from`, but in a `const` context.
+                pub const fn try_consume_const(
+                    source: &str,
+                ) -> ::std::result::Result<(Self, &str), $crate::ConsumeErrorType> {
+                    match $crate::constant::consume_literal(source, $literal) {
+                        Ok(unconsumed) => Ok(($name, unconsumed)),
+                        Err(err) => Err(err),
+                    }
+                }
+            }
+
+            impl $crate::Consumable for $name {
+                fn expecting() -> $crate::Expectation {
+                    $crate::Expectation::new(concat!("`", $literal, "`"))
+                }
+
+                fn consume_from(
+                    source: &str,
+                ) -> ::std::result::Result<(Self, &str), $crate::ConsumeError> {
+                    <&str as $crate::SelfConsumable>::consume_item(source, &$literal)
+                        .map(|unconsumed| ($name, unconsumed))
+                }
+            }
+
+            impl $crate::Render for $name {
+                fn render_to(&self, out: &mut String) {
+                    out.push_str($literal);
+                }
+            }
+
+            impl $crate::LiteralConsumable for $name {}
+        )+
+    };
+}
+
+/// A macro used to define zero-sized types for fixed tags, such as header names of a protocol.
+///
+/// Every tag is matched exactly, like with [`lit`][crate::lit], or regardless of case when the
+/// invocation starts with `case_insensitive;`. The matching uses
+/// [`CaseInsensitive`][crate::common::CaseInsensitive] in that case. A tag always renders as
+/// written and is also available through the associated `TAG` constant.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{tags, Consumable, Render};
+///
+/// tags! {
+///     case_insensitive;
+///     /// The `Content-Length` header name.
+///     pub ContentLength => "Content-Length",
+///     Host => "Host",
+/// }
+///
+/// tags! {
+///     Get => "GET",
+/// }
+///
+/// let (_, unconsumed) = ContentLength::consume_from("content-length: 42")?;
+/// assert_eq!(unconsumed, ": 42");
+/// assert_eq!(Host::consume_from("HOST:")?.0.render(), "Host");
+/// assert_eq!(ContentLength::TAG, "Content-Length");
+///
+/// assert!(Get::consume_from("get").is_err());
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+#[macro_export]
+macro_rules! tags {
+    ( case_insensitive; $( $( #[$attr:meta] )* $vis:vis $name:ident => $tag:literal ),+ $(,)? ) => {
+        $(
+            $crate::tags!( @tag [ $crate::common::CaseInsensitive($tag) ] $( #[$attr] )* $vis $name => $tag );
+        )+
+    };
+    ( $( $( #[$attr:meta] )* $vis:vis $name:ident => $tag:literal ),+ $(,)? ) => {
+        $(
+            $crate::tags!( @tag [ $tag ] $( #[$attr] )* $vis $name => $tag );
+        )+
+    };
+
+    ( @tag [ $matcher:expr ] $( #[$attr:meta] )* $vis:vis $name:ident => $tag:literal ) => {
+        $( #[$attr] )*
+        #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+        $vis struct $name;
+
+        #[allow(dead_code)]
+        impl $name {
+            /// The tag as it is rendered.
+            pub const TAG: &'static str = $tag;
+        }
+
+        impl $crate::Consumable for $name {
+            fn expecting() -> $crate::Expectation {
+                $crate::Expectation::new(concat!("`", $tag, "`"))
+            }
+
+            fn consume_from(
+                source: &str,
+            ) -> ::std::result::Result<(Self, &str), $crate::ConsumeError> {
+                $crate::SelfConsumable::consume_item(source, &$matcher)
+                    .map(|unconsumed| ($name, unconsumed))
+            }
+        }
+
+        impl $crate::Render for $name {
+            fn render_to(&self, out: &mut String) {
+                out.push_str($tag);
+            }
+        }
+
+        impl $crate::LiteralConsumable for $name {}
+    };
+}
+
 #[cfg(test)]
 mod tests {
     use crate::SelfConsumable;
 
     #[test]
     fn test_strs_self_consume() {
+        use crate::ConsumeError;
+        use crate::ConsumeErrorType::*;
+
         assert_eq!(<&str>::consume_item("ABCDEF", &"ABC"), Ok("DEF"));
+        assert_eq!(<&str>::consume_item("ëa", &"ëa"), Ok(""));
+        assert_eq!(<&str>::consume_item("", &""), Ok(""));
+        assert_eq!(
+            <&str>::consume_item("ëbc", &"ëac"),
+            Err(ConsumeError::new_with(UnexpectedToken {
+                index: 1,
+                token: 'b'
+            }))
+        );
+        assert_eq!(
+            <&str>::consume_item("ëa", &"ëab"),
+            Err(ConsumeError::new_with(InsufficientTokens { index: 2 }))
+        );
     }
 }
@@ -74,107 +74,651 @@
 /// The ENBF syntax is as follows:
 /// > Please note that the syntax ignores interproduction rule.
 /// ```enbf
-/// syntax = struct_name, "=>", "[",
+/// syntax = { option }, struct_name, [ generics ], "=>", "[",
 ///             {(instruction, ",")}*,
 ///             instruction, ";",
-///             [ "(", RUST_EXPR*, ")" ], # RUST_EXPR is an arbitrary rust expression it can use all
-///                                       # the RUST_IDENT defined in the previous section.
+///             [ mapping ],
 ///          "]";
 ///
+/// mapping = "(", RUST_EXPR*, ")"        # RUST_EXPR is an arbitrary rust expression it can use all
+///                                       # the RUST_IDENT defined in the previous section.
+///         | "{", field, { ",", field }, "}";
+///
+/// field = RUST_IDENT, [ ":", RUST_EXPR ]; # Without an expression, the field takes the saved data
+///                                         # with the same name.
+///
+/// option = "#[", ( "render" | "ebnf" | "info" ), "]";
+///
 /// instruction = expr_instruction | type_instruction;
 ///
 /// expr_instruction = ">", RUST_EXPR;    # RUST_EXPR is an arbitrary rust expression. It should
-///                                       # return a instance of a type that has the `Consumable`
-///                                       # trait.
+///                                       # return a instance of a type that has the
+///                                       # `SelfConsumable` trait.
+///
+/// type_instruction = [ RUST_IDENT ], ":", RUST_TYPE, [ separator ]; # RUST_IDENT is an arbitrary
+///                                                                   # rust identity an it will
+///                                                                   # assigned to that property
+///                                                                   # if no tuple syntax is
+///                                                                   # defined.
+///                                                                   # RUST_TYPE is an arbitrary
+///                                                                   # rust type that implements
+///                                                                   # `Consumable`.
+///
+/// separator = "[", RUST_EXPR, "]"; # RUST_EXPR is a constant rust expression. It should return a
+///                                  # instance of a type that has the `SelfConsumable` trait.
 ///
-/// type_instruction = [ RUST_IDENT ], ":", RUST_TYPE; # RUST_IDENT is an arbitrary rust identity
-///                                                    # an it will assigned to that property if no
-///                                                    # tuple syntax is defined.
-///                                                    # RUST_TYPE is an arbitrary rust type that
-///                                                    # implements `Consumable`.
+/// generics = "<", RUST_IDENT, { ",", RUST_IDENT }, ">",
+///            [ "where", RUST_WHERE_PREDICATES ]; # RUST_WHERE_PREDICATES are arbitrary rust where
+///                                                # predicates, which are added to the
+///                                                # implementation.
+/// ```
+///
+/// # Separated lists
+///
+/// Suffixing the type of an instruction with `[ SEPARATOR ]` consumes zero or more instances of
+/// that type, separated by the literal `SEPARATOR`. The instances are saved as a
+/// [`Vec`][std::vec::Vec]. This uses the [`SeparatedBy`][crate::common::SeparatedBy] combinator,
+/// so a trailing separator is not consumed. The separator has to be a constant expression, since
+/// it cannot use the data saved by other instructions.
+///
+/// ```
+/// use manger::{ consume_struct, Consumable };
+///
+/// #[derive(PartialEq, Debug)]
+/// struct Call {
+///     name: char,
+///     arguments: Vec<i32>,
+/// }
+/// consume_struct!(
+///     Call => [
+///         name: char,
+///         > '(',
+///         arguments: i32 [ ", " ],
+///         > ')';
+///     ]
+/// );
+///
+/// assert_eq!(
+///     Call::consume_from("f(1, -2, 3)")?.0,
+///     Call { name: 'f', arguments: vec![1, -2, 3] }
+/// );
+/// assert_eq!(Call::consume_from("g()")?.0.arguments, vec![]);
+/// assert!(Call::consume_from("h(1, )").is_err());
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+///
+/// # Literals
+///
+/// The `> EXPR` instruction consumes any expression of a type that implements
+/// [`SelfConsumable`][crate::SelfConsumable], not just a `char` or `&str`. This includes the
+/// literal wrappers [`CaseInsensitive`][crate::common::CaseInsensitive] and
+/// [`Keyword`][crate::common::Keyword], as well as integers and runtime `String`s.
+///
+/// ```
+/// use manger::{ consume_struct, Consumable };
+/// use manger::common::{ CaseInsensitive, Keyword };
+/// use manger::chars::Space;
+///
+/// #[derive(PartialEq, Debug)]
+/// struct Select {
+///     column: char,
+/// }
+/// consume_struct!(
+///     Select => [
+///         > CaseInsensitive("select"),
+///         : Space,
+///         column: char,
+///         : Space,
+///         > Keyword("from"),
+///         > " t";
+///     ]
+/// );
+///
+/// assert_eq!(Select::consume_from("SELECT x from t")?.0, Select { column: 'x' });
+/// assert!(Select::consume_from("select x fromt").is_err());
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+///
+/// # Mappings
+///
+/// The `struct` is constructed from the saved data in one of three ways:
+///
+/// 1. A tuple mapping, such as `(left + right)`, constructs a tuple `struct`.
+/// 2. A named mapping, such as `{ left, total: left + right }`, constructs a `struct` with named
+///    fields. Fields without an expression take the saved data with the same name.
+/// 3. Without a mapping, a `struct` with named fields is constructed from all the saved data.
+///
+/// ```
+/// use manger::{ consume_struct, Consumable };
+///
+/// #[derive(PartialEq, Debug)]
+/// struct Sum {
+///     left: i32,
+///     right: i32,
+///     total: i32,
+/// }
+/// consume_struct!(
+///     Sum => [
+///         left: i32,
+///         > '+',
+///         right: i32;
+///         { left, right, total: left + right }
+///     ]
+/// );
+///
+/// assert_eq!(
+///     Sum::consume_from("2+-5")?.0,
+///     Sum { left: 2, right: -5, total: -3 }
+/// );
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+///
+/// # Rendering
+///
+/// Prefixing the definition with `#[render]` also implements [`Render`][crate::Render] for the
+/// `struct`, which turns an instance back into a string. Literals are rendered as given, saved
+/// properties are rendered from the instance and unnamed properties are rendered from the
+/// [`Default`] of their type. The conditions are ignored. Since the properties have to be taken
+/// back out of the instance, a mapping can only list names of properties. Fields with an
+/// expression within a named mapping are not rendered, so their saved data cannot be rendered
+/// either.
+///
+/// ```
+/// use manger::{ consume_struct, Consumable, Render };
+/// use manger::common::Whitespace;
+///
+/// #[derive(PartialEq, Debug)]
+/// struct Assignment(char, Vec<u32>);
+/// consume_struct!(
+///     #[render]
+///     Assignment => [
+///         name: char,
+///         : Vec<Whitespace>,
+///         > '=',
+///         : Whitespace,
+///         values: u32 [ ',' ];
+///         (name, values)
+///     ]
+/// );
+///
+/// let assignment = Assignment::consume_all("x  = 1,2,3")?;
+///
+/// assert_eq!(assignment.render(), "x= 1,2,3");
+/// assert_eq!(Assignment::consume_all(&assignment.render())?, assignment);
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+///
+/// # Grammar
+///
+/// Prefixing the definition with `#[ebnf]` adds an associated constant `EBNF` to the `struct`,
+/// which describes the consumed grammar as an
+/// [EBNF](https://en.wikipedia.org/wiki/Extended_Backus–Naur_form) rule. Types are named as they
+/// are written in the definition and literals are given as their rust expression. Conditions are
+/// not part of the grammar.
+///
+/// Prefixing the definition with `#[info]` implements [`GrammarInfo`][crate::info::GrammarInfo],
+/// which gives the same grammar at runtime as a list of items.
+///
+/// ```
+/// use manger::consume_struct;
+/// use manger::common::Whitespace;
+///
+/// struct Assignment(char, Vec<u32>);
+/// consume_struct!(
+///     #[ebnf]
+///     Assignment => [
+///         name: char { |name: char| name.is_alphabetic() },
+///         : Vec<Whitespace>,
+///         > '=',
+///         values: u32 [ ',' ];
+///         (name, values)
+///     ]
+/// );
+///
+/// assert_eq!(
+///     Assignment::EBNF,
+///     "Assignment = char , Vec<Whitespace> , '=' , [ u32 , { ',' , u32 } ] ;"
+/// );
+/// ```
+///
+/// # Generics
+///
+/// Generic `struct`s can be consumed by listing the generic parameters after the name of the
+/// `struct`. Any bounds on the parameters are given in a `where` clause.
+///
+/// ```
+/// use manger::{ consume_struct, Consumable };
+///
+/// #[derive(PartialEq, Debug)]
+/// struct Encased<T>(T);
+/// consume_struct!(
+///     Encased<T> where T: Consumable + Clone => [
+///         > '(',
+///         value: T,
+///         > ')';
+///         (value)
+///     ]
+/// );
+///
+/// assert_eq!(<Encased<u32>>::consume_from("(42)")?.0, Encased(42));
+/// assert_eq!(<Encased<char>>::consume_from("(x)")?.0, Encased('x'));
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+///
+/// # Common mistakes
+///
+/// Mistakes within the macro are reported at the token where the macro stopped matching. Most
+/// mistakes fall into one of the following categories.
+///
+/// The list of instructions has to end with a semicolon before the mapping.
+///
+/// ```compile_fail
+/// use manger::consume_struct;
+///
+/// struct EncasedInteger(i32);
+/// consume_struct!(
+///     EncasedInteger => [
+///         > '(',
+///         value: i32,
+///         > ')'
+///         (value)
+///     ]
+/// );
+/// ```
+///
+/// Saving data requires a colon between the name and the type.
+///
+/// ```compile_fail
+/// use manger::consume_struct;
+///
+/// struct EncasedInteger(i32);
+/// consume_struct!(
+///     EncasedInteger => [
+///         > '(',
+///         value i32,
+///         > ')';
+///         (value)
+///     ]
+/// );
+/// ```
+///
+/// The instructions are given between brackets, not braces.
+///
+/// ```compile_fail
+/// use manger::consume_struct;
+///
+/// struct EncasedInteger(i32);
+/// consume_struct!(
+///     EncasedInteger => {
+///         > '(',
+///         value: i32,
+///         > ')';
+///         (value)
+///     }
+/// );
 /// ```
 ///
 /// # Note
 ///
 /// 1. Although this macro works without importing any __manger__ traits, they will also not be
-/// imported afterwards. Importing traits should still be done if methods of the trait
-/// are supposed to be used afterwards.
+///    imported afterwards. Importing traits should still be done if methods of the trait
+///    are supposed to be used afterwards.
 ///
 /// 2. This macro assumed that we are in the same module as the `enum` mentioned
-/// was defined. Some undefined behaviour might occur if this macro is called
-/// outside of the module the `enum` was created.
+///    was defined. Some undefined behaviour might occur if this macro is called
+///    outside of the module the `enum` was created.
 #[macro_export]
 macro_rules! consume_struct {
+    ( #[$option:ident] $( $rest:tt )* ) => {
+        $crate::consume_struct!( $( $rest )* );
+        $crate::consume_struct!( @entry $option $( $rest )* );
+    };
+    ( $struct_name:ident $( $rest:tt )* ) => {
+        $crate::consume_struct!( @entry impl $struct_name $( $rest )* );
+    };
+
+    // The `$mode` selects whether `Consumable` (`impl`), `Render` (`render`) or the `EBNF`
+    // constant (`ebnf`) is implemented. Other options are skipped.
+    ( @entry $mode:tt #[$option:ident] $( $rest:tt )* ) => {
+        $crate::consume_struct!( @entry $mode $( $rest )* );
+    };
+    ( @entry $mode:tt $struct_name:ident => $body:tt ) => {
+        $crate::consume_struct!( @$mode [] $struct_name [] => $body );
+    };
+    ( @entry $mode:tt $struct_name:ident < $( $generic:ident ),+ $(,)? > => $body:tt ) => {
+        $crate::consume_struct!( @$mode [ $( $generic ),+ ] $struct_name [] => $body );
+    };
+    ( @entry $mode:tt $struct_name:ident < $( $generic:ident ),+ $(,)? > where $( $rest:tt )* ) => {
+        $crate::consume_struct!( @where $mode [ $( $generic ),+ ] $struct_name [] $( $rest )* );
+    };
+
+    // Collect the tokens of the where clause up until the `=>`.
+    ( @where $mode:tt [ $( $generic:ident ),+ ] $struct_name:ident [ $( $clause:tt )* ] => $body:tt ) => {
+        $crate::consume_struct!(
+            @$mode [ $( $generic ),+ ] $struct_name [ $( $clause )* ] => $body
+        );
+    };
+    ( @where $mode:tt [ $( $generic:ident ),+ ] $struct_name:ident [ $( $clause:tt )* ] $next:tt $( $rest:tt )* ) => {
+        $crate::consume_struct!(
+            @where $mode [ $( $generic ),+ ] $struct_name [ $( $clause )* $next ] $( $rest )*
+        );
+    };
+
     (
-        $struct_name:ident => [
+        @impl [ $( $generic:ident ),* ] $struct_name:ident [ $( $clause:tt )* ] => [
             $(
-                $( $( $prop_name:ident )?: $cons_type:ty $( { $cons_condition:expr } )?)?
+                $( $( $prop_name:ident )?: $cons_type:ty $( [ $separator:expr ] )? $( { $cons_condition:expr } )?)?
                 $( > $cons_expr:expr )?
             ),*
             ;
             $( ( $( $prop:expr ),* ) )?
+            $( { $( $field:ident $( : $field_value:expr )? ),* $(,)? } )?
         ] ) => {
-        impl $crate::Consumable for $struct_name {
+        impl<$( $generic ),*> $crate::Consumable for $struct_name<$( $generic ),*>
+        where
+            $( $clause )*
+        {
+            fn expecting() -> $crate::Expectation {
+                $crate::Expectation::new($crate::consume_struct!(
+                    @ebnf_sequence
+                    $( ( $( $cons_type $( , [ $separator ] )? )? $( > $cons_expr )? ) ),*
+                ))
+            }
+
             fn consume_from(source: &str) -> Result<(Self, &str), $crate::ConsumeError> {
-                let mut unconsumed = source;
-                let mut offset = 0;
+                $crate::trace::attempt::<Self, _>(source, || $crate::limits::nest(|| {
+                    $crate::coverage::register::<Self>(&[
+                        $(
+                            $crate::consume_struct!(
+                                @coverage_point $( $( $prop_name )?: $cons_type )? $( > $cons_expr )?
+                            )
+                        ),*
+                    ]);
+                    let mut point = 0;
+                    let mut unconsumed = source;
+                    let mut offset = 0;
 
-                $(
                     $(
-                        $( let $prop_name = )?
-                        $crate::ConsumeSource::mut_consume_by::<$cons_type>(&mut unconsumed)
                         $(
-                            .and_then(
-                                |(item, by)| {
-                                    if ($cons_condition)(item) {
-                                        Ok((item, by))
-                                    } else {
-                                        Err(
-                                            $crate::ConsumeError::new_with(
-                                                $crate::ConsumeErrorType::InvalidValue { index: offset }
+                            $( let $prop_name = )?
+                            $crate::consume_struct!( @consume unconsumed, $cons_type $( , [ $separator ] )? )
+                            $(
+                                .and_then(
+                                    |(item, by)| {
+                                        if ($cons_condition)(item) {
+                                            Ok((item, by))
+                                        } else {
+                                            Err(
+                                                $crate::ConsumeError::new_with(
+                                                    $crate::ConsumeErrorType::InvalidValue { index: offset }
+                                                )
                                             )
-                                        )
+                                        }
                                     }
-                                }
-                            )
+                                )
+                            )?
+                                .map(|(prop, by)| {
+                                    #[allow(unused_assignments)]
+                                    { offset += by };
+
+                                    prop
+                                })
+                                .map_err( |err| err.offset(offset) )?;
                         )?
-                            .map(|(prop, by)| {
-                                #[allow(unused_assignments)]
-                                { offset += by };
 
-                                prop
-                            })
-                            .map_err( |err| err.offset(offset) )?;
+                        $(
+                            $crate::ConsumeSource::mut_consume_lit(&mut unconsumed, &$cons_expr)
+                                .map(|by| {
+                                    #[allow(unused_assignments)]
+                                    { offset += by };
+                                })
+                                .map_err( |err| err.offset(offset) )?;
+                        )?
+
+                        $crate::coverage::hit::<Self>(point);
+                        #[allow(unused_assignments)]
+                        { point += 1 };
+                    )+
+
+                    Ok(
+                        (
+                            $crate::consume_struct!(
+                                @internal $struct_name,
+                                $( $( $( $prop_name, )* )? )?
+                                $( => ( $( $prop ),* ) )?
+                                $( => { $( $field $( : $field_value )? ),* } )?
+                            ),
+                            unconsumed
+                        )
+                    )
+                })
+                .map_err(|err| err.tag_origin(::std::any::type_name::<Self>())))
+            }
+        }
+    };
+
+    // The name of an item within the coverage report.
+    ( @coverage_point $prop_name:ident : $cons_type:ty ) => {
+        concat!(stringify!($prop_name), ": ", stringify!($cons_type))
+    };
+    ( @coverage_point : $cons_type:ty ) => {
+        stringify!($cons_type)
+    };
+    ( @coverage_point > $cons_expr:expr ) => {
+        stringify!($cons_expr)
+    };
+
+    ( @consume $unconsumed:ident, $cons_type:ty ) => {
+        $crate::ConsumeSource::mut_consume_by::<$cons_type>(&mut $unconsumed)
+    };
+    ( @consume $unconsumed:ident, $cons_type:ty, [ $separator:expr ] ) => {{
+        struct Separator;
+
+        impl $crate::Consumable for Separator {
+            fn consume_from(source: &str) -> Result<(Self, &str), $crate::ConsumeError> {
+                $crate::ConsumeSource::consume_lit(source, &$separator)
+                    .map(|unconsumed| (Separator, unconsumed))
+            }
+        }
+
+        $crate::ConsumeSource::mut_consume_by::<
+            $crate::common::SeparatedBy<$cons_type, Separator>
+        >(&mut $unconsumed)
+            .map(|(items, by)| (items.into_vec(), by))
+    }};
+
+    ( @internal $struct_name:ident, $( $prop_name:ident, )* => ( $( $prop:expr ),* ) ) => {
+        $struct_name ( $( $prop ),* )
+    };
+    (
+        @internal $struct_name:ident, $( $prop_name:ident, )*
+        => { $( $field:ident $( : $field_value:expr )? ),* }
+    ) => {
+        $struct_name { $( $field: $crate::consume_enum!( @field $field $( : $field_value )? ) ),* }
+    };
+    ( @internal $struct_name:ident, $( $prop_name:ident, )* ) => {
+        $struct_name { $( $prop_name ),* }
+    };
+
+    (
+        @render [ $( $generic:ident ),* ] $struct_name:ident [ $( $clause:tt )* ] => [
+            $(
+                $( $( $prop_name:ident )?: $cons_type:ty $( [ $separator:expr ] )? $( { $cons_condition:expr } )?)?
+                $( > $cons_expr:expr )?
+            ),*
+            ;
+            $( ( $( $tuple_field:ident ),* $(,)? ) )?
+            $( { $( $field:ident $( : $field_value:expr )? ),* $(,)? } )?
+        ] ) => {
+        impl<$( $generic ),*> $crate::Render for $struct_name<$( $generic ),*>
+        where
+            $( $clause )*
+        {
+            fn render_to(&self, out: &mut String) {
+                $crate::consume_struct!(
+                    @render_bind self, $struct_name,
+                    $( $( $( $prop_name, )* )? )?
+                    $( => ( $( $tuple_field ),* ) )?
+                    $( => { $( $field $( : $field_value )? ),* } )?
+                );
+
+                $(
+                    $(
+                        $crate::consume_struct!(
+                            @render_item out, [ $( $prop_name )? ] $cons_type $( , [ $separator ] )?
+                        );
                     )?
 
                     $(
-                        $crate::ConsumeSource::mut_consume_lit(&mut unconsumed, &$cons_expr)
-                            .map(|by| {
-                                #[allow(unused_assignments)]
-                                { offset += by };
-                            })
-                            .map_err( |err| err.offset(offset) )?;
+                        $crate::Render::render_to(&$cons_expr, out);
                     )?
                 )+
+            }
+        }
+    };
+    ( @render $( $rest:tt )* ) => {
+        compile_error!(
+            "Render can only be generated when the mapping only lists the names of properties"
+        );
+    };
 
-                Ok(
-                    (
-                        $crate::consume_struct!(
-                            @internal $struct_name,
-                            $( $( $( $prop_name, )* )? )?
-                            $( => ( $( $prop ),* ) )?
-                        ),
-                        unconsumed
-                    )
+    ( @render_bind $value:expr, $struct_name:ident, $( $prop_name:ident, )* => ( $( $field:ident ),* ) ) => {
+        #[allow(unused_variables)]
+        let $struct_name ( $( $field ),* ) = $value;
+    };
+    (
+        @render_bind $value:expr, $struct_name:ident, $( $prop_name:ident, )*
+        => { $( $field:ident $( : $field_value:expr )? ),* }
+    ) => {
+        #[allow(unused_variables)]
+        let $struct_name {
+            $( $field: $crate::consume_enum!( @render_field $field $( : $field_value )? ) ),*
+        } = $value;
+    };
+    ( @render_bind $value:expr, $struct_name:ident, $( $prop_name:ident, )* ) => {
+        #[allow(unused_variables)]
+        let $struct_name { $( $prop_name ),* } = $value;
+    };
+
+    ( @render_item $out:ident, [ $prop_name:ident ] $cons_type:ty ) => {
+        $crate::Render::render_to($prop_name, $out);
+    };
+    ( @render_item $out:ident, [ $prop_name:ident ] $cons_type:ty, [ $separator:expr ] ) => {
+        for (index, item) in $prop_name.iter().enumerate() {
+            if index > 0 {
+                $crate::Render::render_to(&$separator, $out);
+            }
+            $crate::Render::render_to(item, $out);
+        }
+    };
+    ( @render_item $out:ident, [] $cons_type:ty ) => {
+        $crate::Render::render_to(&<$cons_type as ::std::default::Default>::default(), $out);
+    };
+    ( @render_item $out:ident, [] $cons_type:ty, [ $separator:expr ] ) => {};
+
+    (
+        @ebnf [ $( $generic:ident ),* ] $struct_name:ident [ $( $clause:tt )* ] => [
+            $(
+                $( $( $prop_name:ident )?: $cons_type:ty $( [ $separator:expr ] )? $( { $cons_condition:expr } )?)?
+                $( > $cons_expr:expr )?
+            ),*
+            ;
+            $( ( $( $prop:expr ),* ) )?
+            $( { $( $field:ident $( : $field_value:expr )? ),* $(,)? } )?
+        ] ) => {
+        impl<$( $generic ),*> $struct_name<$( $generic ),*>
+        where
+            $( $clause )*
+        {
+            /// The grammar that is consumed, as an EBNF rule.
+            pub const EBNF: &str = concat!(
+                stringify!($struct_name),
+                " = ",
+                $crate::consume_struct!(
+                    @ebnf_sequence
+                    $( ( $( $cons_type $( , [ $separator ] )? )? $( > $cons_expr )? ) ),*
+                ),
+                " ;"
+            );
+        }
+    };
+
+    (
+        @info [ $( $generic:ident ),* ] $struct_name:ident [ $( $clause:tt )* ] => [
+            $(
+                $( $( $prop_name:ident )?: $cons_type:ty $( [ $separator:expr ] )? $( { $cons_condition:expr } )?)?
+                $( > $cons_expr:expr )?
+            ),*
+            ;
+            $( ( $( $prop:expr ),* ) )?
+            $( { $( $field:ident $( : $field_value:expr )? ),* $(,)? } )?
+        ] ) => {
+        impl<$( $generic ),*> $crate::info::GrammarInfo for $struct_name<$( $generic ),*>
+        where
+            $( $clause )*
+        {
+            fn grammar_info() -> $crate::info::RuleInfo {
+                $crate::info::RuleInfo::new(
+                    stringify!($struct_name),
+                    vec![$crate::consume_struct!(
+                        @info_sequence
+                        $(
+                            (
+                                $( [ $( $prop_name )? ] $cons_type $( , [ $separator ] )? )?
+                                $( > $cons_expr )?
+                            )
+                        ),*
+                    )],
                 )
             }
         }
     };
 
-    ( @internal $struct_name:ident, $( $prop_name:ident, )* => ( $( $prop:expr ),* ) ) => {
-        $struct_name ( $( $prop ),* )
+    ( @info_sequence $( $item:tt ),* ) => {
+        vec![ $( $crate::consume_struct!( @info_item $item ) ),* ]
     };
-    ( @internal $struct_name:ident, $( $prop_name:ident, )* ) => {
-        $struct_name { $( $prop_name, ),* }
+
+    ( @info_item ( > $cons_expr:expr ) ) => {
+        $crate::info::GrammarItem::literal(
+            stringify!($cons_expr),
+            |source| $crate::ConsumeSource::consume_lit(source, &$cons_expr),
+        )
+    };
+    ( @info_item ( [ $( $prop_name:ident )? ] $cons_type:ty $( , [ $separator:expr ] )? ) ) => {
+        $crate::info::GrammarItem::typed(
+            $crate::consume_struct!( @info_name $( $prop_name )? ),
+            $crate::consume_struct!( @ebnf_item ( $cons_type $( , [ $separator ] )? ) ),
+            |source| {
+                let mut unconsumed = source;
+                $crate::consume_struct!( @consume unconsumed, $cons_type $( , [ $separator ] )? )
+                    .map(|_| unconsumed)
+            },
+        )
+    };
+
+    ( @info_name ) => {
+        None
+    };
+    ( @info_name $prop_name:ident ) => {
+        Some(stringify!($prop_name))
+    };
+
+    ( @ebnf_sequence $first:tt $( , $rest:tt )* ) => {
+        concat!(
+            $crate::consume_struct!( @ebnf_item $first )
+            $( , " , ", $crate::consume_struct!( @ebnf_item $rest ) )*
+        )
+    };
+
+    ( @ebnf_item ( > $cons_expr:expr ) ) => {
+        stringify!($cons_expr)
+    };
+    ( @ebnf_item ( $cons_type:ty ) ) => {
+        stringify!($cons_type)
+    };
+    ( @ebnf_item ( $cons_type:ty, [ $separator:expr ] ) ) => {
+        concat!(
+            "[ ", stringify!($cons_type), " , { ", stringify!($separator), " , ", stringify!($cons_type), " } ]"
+        )
     };
 }
@@ -0,0 +1,327 @@
+//! Assertions for __testing__ grammars.
+//!
+//! The [`assert_consumes`][crate::assert_consumes] macro checks that a [`Consumable`] type
+//! consumes an example input within a `#[test]`. When it does not, the panic message contains the
+//! error along with an excerpt of the input which points to where consuming failed. The
+//! [`static_assert_consumes`][crate::static_assert_consumes] macro does the same at compile-time
+//! for the types which can be consumed in a `const` context, see the
+//! [`constant`][crate::constant] module.
+//!
+//! The [`assert_error_snapshot`][crate::assert_error_snapshot] macro checks that consuming an
+//! input fails with exactly the expected error message, with the causes in their
+//! [deterministic order][crate::ConsumeError::sorted].
+
+use crate::context::Position;
+use crate::{Consumable, ConsumeError};
+
+/// Assert that a [`Consumable`][crate::Consumable] type consumes an example input.
+///
+/// The macro takes the type, the input and optionally the part of the input which should be left
+/// unconsumed with `rest = "..."`. By default, the whole input should be consumed. It evaluates
+/// to the consumed item.
+///
+/// # Panics
+///
+/// Panics when consuming fails or leaves another part of the input unconsumed. The message
+/// contains the error or the unconsumed part, along with an excerpt of the input that points to
+/// where consuming stopped.
+///
+/// # Examples
+///
+/// ```
+/// use manger::assert_consumes;
+/// use manger::chars::Space;
+///
+/// let number = assert_consumes!(u32, "42");
+/// assert_eq!(number, 42);
+///
+/// assert_consumes!((u32, Space, u32), "1 2 3", rest = " 3");
+/// ```
+///
+/// A failure shows where consuming stopped.
+///
+/// ```should_panic
+/// use manger::assert_consumes;
+///
+/// // `(u32, char, u32)` failed to consume the input:
+/// // Found the token `+` at index `3`, which is unexpected!
+/// //  --> line 2, column 1
+/// //   |
+/// // 2 | +x
+/// //   | ^
+/// assert_consumes!((u32, char, u32), "12\n+x");
+/// ```
+#[macro_export]
+macro_rules! assert_consumes {
+    ( $cons_type:ty, $source:expr $(,)? ) => {
+        $crate::testing::assert_consumes::<$cons_type>($source, "")
+    };
+    ( $cons_type:ty, $source:expr, rest = $rest:expr $(,)? ) => {
+        $crate::testing::assert_consumes::<$cons_type>($source, $rest)
+    };
+}
+
+/// Assert at compile-time that a type consumes an example input in a `const` context.
+///
+/// This takes the same arguments as [`assert_consumes`][crate::assert_consumes], but only works
+/// for types with a `const fn try_consume_const`, see the [`constant`][crate::constant] module.
+/// The input and `rest` have to be constant expressions.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{lit, static_assert_consumes};
+/// use manger::chars::CharRange;
+///
+/// lit! {
+///     "let" => Let
+/// }
+///
+/// static_assert_consumes!(Let, "let");
+/// static_assert_consumes!(CharRange<'a', 'z'>, "xyz", rest = "yz");
+/// ```
+///
+/// A failure is a compile error.
+///
+/// ```compile_fail
+/// use manger::static_assert_consumes;
+/// use manger::chars::Char;
+///
+/// static_assert_consumes!(Char<'a'>, "b");
+/// ```
+#[macro_export]
+macro_rules! static_assert_consumes {
+    ( $cons_type:ty, $source:expr $(,)? ) => {
+        $crate::static_assert_consumes!($cons_type, $source, rest = "");
+    };
+    ( $cons_type:ty, $source:expr, rest = $rest:expr $(,)? ) => {
+        const _: () = match <$cons_type>::try_consume_const($source) {
+            Ok((_, unconsumed)) => assert!(
+                $crate::constant::str_eq(unconsumed, $rest),
+                concat!(
+                    "`",
+                    stringify!($cons_type),
+                    "` did not leave `",
+                    stringify!($rest),
+                    "` unconsumed from ",
+                    stringify!($source)
+                )
+            ),
+            Err(_) => panic!(concat!(
+                "`",
+                stringify!($cons_type),
+                "` failed to consume ",
+                stringify!($source)
+            )),
+        };
+    };
+}
+
+/// Assert that consuming a [`Consumable`][crate::Consumable] type from an input fails with an
+/// expected error, also known as a golden error.
+///
+/// The macro takes the type, the input and the snapshot of the error. The snapshot is the error
+/// message of the [`sorted`][crate::ConsumeError::sorted] error, so it does not change when only
+/// the order in which the alternatives of a grammar are tried changes.
+///
+/// # Panics
+///
+/// Panics when consuming succeeds or fails with another error. The message contains the expected
+/// and the found snapshot, along with an excerpt of the input that points to where consuming
+/// failed.
+///
+/// # Examples
+///
+/// ```
+/// use manger::assert_error_snapshot;
+///
+/// assert_error_snapshot!(
+///     either::Either<(char, char), u32>,
+///     "a",
+///     "Found the token `a` at index `0`, which is unexpected!\n\
+///      Expected more tokens at index `1` but found none!"
+/// );
+/// ```
+#[macro_export]
+macro_rules! assert_error_snapshot {
+    ( $cons_type:ty, $source:expr, $snapshot:expr $(,)? ) => {
+        $crate::testing::assert_error_snapshot::<$cons_type>($source, $snapshot)
+    };
+}
+
+/// Consume `T` from `source` and assert that it fails with an error with the message `snapshot`,
+/// which is used by [`assert_error_snapshot`][crate::assert_error_snapshot].
+///
+/// # Panics
+///
+/// Panics with a message containing an excerpt of `source` when the assertion fails.
+#[track_caller]
+pub fn assert_error_snapshot<T: Consumable>(source: &str, snapshot: &str) {
+    let name = std::any::type_name::<T>();
+
+    match crate::context::scope(source, || T::consume_from(source)) {
+        Ok((_, unconsumed)) => {
+            let index = crate::strs::consumed_length(source, unconsumed);
+            panic!(
+                "`{}` consumed the input, but an error was expected:\n{}",
+                name,
+                excerpt(source, index)
+            )
+        }
+        Err(err) => {
+            let err = err.sorted();
+            let found = err.to_string();
+
+            if found != snapshot {
+                panic!(
+                    "`{}` failed with another error than the snapshot:\n  expected: {:?}\n     found: {:?}\n{}",
+                    name,
+                    snapshot,
+                    found,
+                    excerpt(source, error_index(&err))
+                )
+            }
+        }
+    }
+}
+
+/// Consume `T` from `source` and assert that `rest` is left unconsumed, which is used by
+/// [`assert_consumes`][crate::assert_consumes].
+///
+/// # Panics
+///
+/// Panics with a message containing an excerpt of `source` when the assertion fails.
+#[track_caller]
+pub fn assert_consumes<T: Consumable>(source: &str, rest: &str) -> T {
+    let name = std::any::type_name::<T>();
+
+    match crate::context::scope(source, || T::consume_from(source)) {
+        Ok((item, unconsumed)) if unconsumed == rest => item,
+        Ok((_, unconsumed)) => {
+            let index = crate::strs::consumed_length(source, unconsumed);
+            panic!(
+                "`{}` did not leave the expected part of the input unconsumed:\n  expected: {:?}\n     found: {:?}\n{}",
+                name,
+                rest,
+                unconsumed,
+                excerpt(source, index)
+            )
+        }
+        Err(err) => panic!(
+            "`{}` failed to consume the input:\n{}\n{}",
+            name,
+            err,
+            excerpt(source, error_index(&err))
+        ),
+    }
+}
+
+/// Get the index at which `err` occurred, which is the index of the first cause.
+fn error_index(err: &ConsumeError) -> usize {
+    err.causes().first().map_or(0, |cause| *cause.index())
+}
+
+/// Show the line of `source` which contains the utf-8 character index `index`, with a marker
+/// below the character at that index.
+///
+/// # Examples
+///
+/// ```
+/// use manger::testing::excerpt;
+///
+/// assert_eq!(
+///     excerpt("let x = 1;\nlet y = ;", 19),
+///     " --> line 2, column 9\n  |\n2 | let y = ;\n  |         ^"
+/// );
+/// ```
+pub fn excerpt(source: &str, index: usize) -> String {
+    let position = Position::from_index(source, index);
+    let line = source.lines().nth(position.line() - 1).unwrap_or("");
+    let number = position.line().to_string();
+    let gutter = " ".repeat(number.len());
+
+    format!(
+        "{gutter}--> line {}, column {}\n{gutter} |\n{} | {}\n{gutter} | {}^",
+        position.line(),
+        position.column(),
+        number,
+        line,
+        " ".repeat(position.column() - 1),
+        gutter = gutter
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::excerpt;
+    use crate::chars::Space;
+    use std::panic::catch_unwind;
+
+    fn panic_message(f: impl FnOnce() + std::panic::UnwindSafe) -> String {
+        let payload = catch_unwind(f).unwrap_err();
+        payload
+            .downcast_ref::<String>()
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn test_excerpt() {
+        assert_eq!(
+            excerpt("ab", 0),
+            " --> line 1, column 1\n  |\n1 | ab\n  | ^"
+        );
+        assert_eq!(
+            excerpt("ab", 2),
+            " --> line 1, column 3\n  |\n1 | ab\n  |   ^"
+        );
+        assert_eq!(excerpt("", 0), " --> line 1, column 1\n  |\n1 | \n  | ^");
+        assert_eq!(
+            excerpt("a\n\n\n\n\n\n\n\n\nëb", 11),
+            "  --> line 10, column 2\n   |\n10 | ëb\n   |  ^"
+        );
+    }
+
+    #[test]
+    fn test_assert_consumes() {
+        assert_eq!(assert_consumes!(Vec<char>, "ab"), vec!['a', 'b']);
+        assert_eq!(assert_consumes!(u8, "12;", rest = ";"), 12);
+
+        let message = panic_message(|| {
+            assert_consumes!((u32, Space, u32), "1 x");
+        });
+        assert!(message.starts_with("`(u32, manger::chars::Char<' '>, u32)` failed"));
+        assert!(message.ends_with(" --> line 1, column 3\n  |\n1 | 1 x\n  |   ^"));
+
+        let message = panic_message(|| {
+            assert_consumes!(u32, "12 3");
+        });
+        assert!(message.contains("expected: \"\"\n     found: \" 3\""));
+        assert!(message.ends_with("1 | 12 3\n  |   ^"));
+    }
+
+    #[test]
+    fn test_assert_error_snapshot() {
+        type Sign = either::Either<(char, char), crate::chars::Char<'-'>>;
+
+        assert_error_snapshot!(
+            Sign,
+            "+",
+            "Found the token `+` at index `0`, which is unexpected!\n\
+             Expected more tokens at index `1` but found none!"
+        );
+
+        let message = panic_message(|| {
+            assert_error_snapshot!(u32, "12", "");
+        });
+        assert!(message.starts_with("`u32` consumed the input"));
+
+        let message = panic_message(|| {
+            assert_error_snapshot!(u32, "x", "");
+        });
+        assert!(message.contains("expected: \"\"\n     found: \"Found the token `x`"));
+        assert!(message.ends_with("1 | x\n  | ^"));
+    }
+
+    static_assert_consumes!(crate::common::Digit, "12", rest = "2");
+}
@@ -0,0 +1,455 @@
+//! A separate __lexing__ pass, which turns a `source` into [`Token`]s before consuming them.
+//!
+//! Large grammars often become simpler when the characters of a `source` are first grouped into
+//! tokens, such as numbers, identifiers and operators. The kinds of tokens are defined as a
+//! [`Consumable`] type, most often an `enum` using [`consume_enum`][crate::consume_enum], and
+//! [`tokenize`] turns a `source` into a `Vec<Token<K>>` which keeps the span of every token.
+//!
+//! The tokens are then consumed by types implementing [`TokenConsumable`], which mirrors
+//! [`Consumable`] but consumes from a [`TokenStream`] instead of a `&str`. All indices in errors
+//! are still utf-8 character indices within the original `source`. Expressions with prefix, infix
+//! and postfix operators can be consumed with a [`Pratt`][pratt::Pratt] parser.
+//!
+//! # Examples
+//!
+//! ```
+//! use manger::consume_enum;
+//! use manger::common::Whitespace;
+//! use manger::tokens::{tokenize, TokenConsumable, TokenStream};
+//! use manger::ConsumeError;
+//!
+//! #[derive(Debug, Clone, PartialEq)]
+//! enum Kind {
+//!     Number(u32),
+//!     Plus,
+//! }
+//! consume_enum!(
+//!     Kind {
+//!         Number => [ value: u32; (value) ],
+//!         Plus => [ > '+'; ]
+//!     }
+//! );
+//!
+//! #[derive(Debug)]
+//! struct Sum(u32);
+//!
+//! impl TokenConsumable<Kind> for Sum {
+//!     fn consume_tokens<'t>(
+//!         tokens: TokenStream<'t, Kind>,
+//!     ) -> Result<(Self, TokenStream<'t, Kind>), ConsumeError> {
+//!         let number = |kind: &Kind| match kind {
+//!             Kind::Number(value) => Some(*value),
+//!             _ => None,
+//!         };
+//!
+//!         let (mut sum, mut tokens) = tokens.next_map(number)?;
+//!         while let Ok(rest) = tokens.expect(&Kind::Plus) {
+//!             let (value, rest) = rest.next_map(number)?;
+//!             sum += value;
+//!             tokens = rest;
+//!         }
+//!
+//!         Ok((Sum(sum), tokens))
+//!     }
+//! }
+//!
+//! let tokens = tokenize::<Kind, Vec<Whitespace>>("1 + 20 + 300")?;
+//! assert_eq!(tokens.len(), 5);
+//! assert_eq!(tokens[2].span(), 4..6);
+//!
+//! let Sum(sum) = Sum::consume_all_tokens(&tokens)?;
+//! assert_eq!(sum, 321);
+//!
+//! // The error points to the `+` at the end of the source.
+//! let tokens = tokenize::<Kind, Vec<Whitespace>>("1 + 2 +")?;
+//! let error = Sum::consume_all_tokens(&tokens).unwrap_err();
+//! assert_eq!(error.causes()[0].index(), &7);
+//! # Ok::<(), manger::ConsumeError>(())
+//! ```
+
+use crate::error::ConsumeErrorType::*;
+use crate::{context, Consumable, ConsumeError};
+use std::ops::Range;
+
+pub mod pratt;
+
+/// A token of kind `K` along with its span within the `source`.
+///
+/// The span is given in utf-8 character indices.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Token<K> {
+    kind: K,
+    span: Range<usize>,
+}
+
+impl<K> Token<K> {
+    /// Create a new token of `kind` spanning `span` within the `source`.
+    pub fn new(kind: K, span: Range<usize>) -> Self {
+        Token { kind, span }
+    }
+
+    /// Get the kind of the token.
+    pub fn kind(&self) -> &K {
+        &self.kind
+    }
+
+    /// Take ownership of the kind of the token.
+    pub fn into_kind(self) -> K {
+        self.kind
+    }
+
+    /// Get the span of the token within the `source`.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+}
+
+/// Turn a `source` into a list of tokens of kind `K`.
+///
+/// Before every token and at the end of the `source`, `S` is consumed and thrown away. This is
+/// most often [`Vec<Whitespace>`][crate::common::Whitespace] or [`Empty`][crate::common::Empty]
+/// to skip nothing.
+///
+/// # Errors
+///
+/// Returns the error of consuming `K` when no token could be consumed before the end of the
+/// `source`. A token which consumes nothing gives an `InvalidValue` error, since it would be
+/// consumed forever.
+///
+/// # Examples
+///
+/// ```
+/// use manger::common::{Empty, Whitespace};
+/// use manger::tokens::tokenize;
+///
+/// let tokens = tokenize::<char, Vec<Whitespace>>(" a b\tc ")?;
+/// let kinds = tokens.iter().map(|token| *token.kind()).collect::<String>();
+/// assert_eq!(kinds, "abc");
+/// assert_eq!(tokens[2].span(), 5..6);
+///
+/// let error = tokenize::<u8, Empty>("12x").unwrap_err();
+/// assert_eq!(error.causes()[0].index(), &2);
+/// # Ok::<(), manger::ConsumeError>(())
+/// ```
+pub fn tokenize<K: Consumable, S: Consumable>(source: &str) -> Result<Vec<Token<K>>, ConsumeError> {
+    let mut tokens = Vec::new();
+    let mut position = 0;
+    let mut unconsumed = source;
+
+    loop {
+        if let Ok((_, rest)) = context::scope(source, || S::consume_from(unconsumed)) {
+            position += crate::strs::consumed_length(unconsumed, rest);
+            unconsumed = rest;
+        }
+
+        if unconsumed.is_empty() {
+            return Ok(tokens);
+        }
+
+        let (kind, rest) = context::scope(source, || K::consume_from(unconsumed))
+            .map_err(|err| err.offset(position))?;
+
+        if rest.len() == unconsumed.len() {
+            return Err(ConsumeError::new_with(InvalidValue { index: position }));
+        }
+
+        let end = position + crate::strs::consumed_length(unconsumed, rest);
+        tokens.push(Token::new(kind, position..end));
+        position = end;
+        unconsumed = rest;
+    }
+}
+
+/// The unconsumed part of a list of tokens.
+///
+/// Besides the tokens, the stream remembers the index at which the tokens end, so that errors at
+/// the end of the tokens can still point into the `source`.
+#[derive(Debug)]
+pub struct TokenStream<'t, K> {
+    tokens: &'t [Token<K>],
+    end: usize,
+}
+
+impl<K> Clone for TokenStream<'_, K> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K> Copy for TokenStream<'_, K> {}
+
+impl<'t, K> TokenStream<'t, K> {
+    /// Create a stream over `tokens`, which ends at the end of the last token.
+    pub fn new(tokens: &'t [Token<K>]) -> Self {
+        let end = tokens.last().map_or(0, |token| token.span.end);
+        TokenStream { tokens, end }
+    }
+
+    /// Create a stream over `tokens`, which ends at the utf-8 character index `end`.
+    ///
+    /// This is most often the length of the `source`, so that errors at the end of the tokens
+    /// point after any trailing whitespace.
+    pub fn with_end(tokens: &'t [Token<K>], end: usize) -> Self {
+        TokenStream { tokens, end }
+    }
+
+    /// Get the unconsumed tokens.
+    pub fn tokens(&self) -> &'t [Token<K>] {
+        self.tokens
+    }
+
+    /// Returns whether all tokens have been consumed.
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    /// Get the utf-8 character index of the next token, or of the end when all tokens have been
+    /// consumed.
+    pub fn position(&self) -> usize {
+        self.tokens
+            .first()
+            .map_or(self.end, |token| token.span.start)
+    }
+
+    /// Consume the next token.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InsufficientTokens` error when all tokens have been consumed.
+    pub fn next_token(self) -> Result<(&'t Token<K>, Self), ConsumeError> {
+        match self.tokens.split_first() {
+            Some((token, tokens)) => Ok((token, TokenStream { tokens, ..self })),
+            None => Err(ConsumeError::new_with(InsufficientTokens {
+                index: self.end,
+            })),
+        }
+    }
+
+    /// Consume the next token and map its kind with `f`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidValue` error when `f` returns `None`, or an `InsufficientTokens` error
+    /// when all tokens have been consumed.
+    pub fn next_map<T>(
+        self,
+        f: impl FnOnce(&'t K) -> Option<T>,
+    ) -> Result<(T, Self), ConsumeError> {
+        let (token, rest) = self.next_token()?;
+        match f(&token.kind) {
+            Some(value) => Ok((value, rest)),
+            None => Err(ConsumeError::new_with(InvalidValue {
+                index: token.span.start,
+            })),
+        }
+    }
+
+    /// Consume the next token if it is of `kind`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidValue` error when the next token is of another kind, or an
+    /// `InsufficientTokens` error when all tokens have been consumed.
+    pub fn expect(self, kind: &K) -> Result<Self, ConsumeError>
+    where
+        K: PartialEq,
+    {
+        self.next_map(|next| (next == kind).then_some(()))
+            .map(|((), rest)| rest)
+    }
+}
+
+/// Trait which allows a type to be consumed from a [`TokenStream`], just as
+/// [`Consumable`] consumes from a `&str`.
+///
+/// Implementations exist for [`Token<K>`] itself, which consumes any single token, and for
+/// `Option<T>`, `Vec<T>`, `Box<T>` and tuples of up to 6 items.
+pub trait TokenConsumable<K>: Sized {
+    /// Attempt to consume `Self` from the start of `tokens`, returning the item along with the
+    /// unconsumed tokens.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error with utf-8 character indices within the original `source`.
+    fn consume_tokens<'t>(
+        tokens: TokenStream<'t, K>,
+    ) -> Result<(Self, TokenStream<'t, K>), ConsumeError>;
+
+    /// Attempt to consume `Self` from all `tokens`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of consuming `Self`, or an `InvalidValue` error at the first token that
+    /// is left unconsumed.
+    fn consume_all_tokens(tokens: &[Token<K>]) -> Result<Self, ConsumeError> {
+        let (item, rest) = Self::consume_tokens(TokenStream::new(tokens))?;
+
+        if rest.is_empty() {
+            Ok(item)
+        } else {
+            Err(ConsumeError::new_with(InvalidValue {
+                index: rest.position(),
+            }))
+        }
+    }
+}
+
+impl<K: Clone> TokenConsumable<K> for Token<K> {
+    fn consume_tokens<'t>(
+        tokens: TokenStream<'t, K>,
+    ) -> Result<(Self, TokenStream<'t, K>), ConsumeError> {
+        tokens
+            .next_token()
+            .map(|(token, rest)| (token.clone(), rest))
+    }
+}
+
+impl<K, T: TokenConsumable<K>> TokenConsumable<K> for Option<T> {
+    fn consume_tokens<'t>(
+        tokens: TokenStream<'t, K>,
+    ) -> Result<(Self, TokenStream<'t, K>), ConsumeError> {
+        Ok(match T::consume_tokens(tokens) {
+            Ok((item, rest)) => (Some(item), rest),
+            Err(_) => (None, tokens),
+        })
+    }
+}
+
+impl<K, T: TokenConsumable<K>> TokenConsumable<K> for Vec<T> {
+    fn consume_tokens<'t>(
+        mut tokens: TokenStream<'t, K>,
+    ) -> Result<(Self, TokenStream<'t, K>), ConsumeError> {
+        let mut items = Vec::new();
+
+        // An item that consumes no tokens would be consumed forever.
+        while let Ok((item, rest)) = T::consume_tokens(tokens) {
+            if rest.tokens.len() == tokens.tokens.len() {
+                break;
+            }
+
+            items.push(item);
+            tokens = rest;
+        }
+
+        Ok((items, tokens))
+    }
+}
+
+impl<K, T: TokenConsumable<K>> TokenConsumable<K> for Box<T> {
+    fn consume_tokens<'t>(
+        tokens: TokenStream<'t, K>,
+    ) -> Result<(Self, TokenStream<'t, K>), ConsumeError> {
+        T::consume_tokens(tokens).map(|(item, rest)| (Box::new(item), rest))
+    }
+}
+
+macro_rules! tuple_token_consumable {
+    ( $( $ty:ident ),+ ) => {
+        impl<K, $( $ty: TokenConsumable<K> ),+> TokenConsumable<K> for ( $( $ty, )+ ) {
+            #[allow(non_snake_case)]
+            fn consume_tokens<'t>(
+                tokens: TokenStream<'t, K>,
+            ) -> Result<(Self, TokenStream<'t, K>), ConsumeError> {
+                $( let ($ty, tokens) = $ty::consume_tokens(tokens)?; )+
+                Ok((( $( $ty, )+ ), tokens))
+            }
+        }
+    };
+}
+
+tuple_token_consumable!(A);
+tuple_token_consumable!(A, B);
+tuple_token_consumable!(A, B, C);
+tuple_token_consumable!(A, B, C, D);
+tuple_token_consumable!(A, B, C, D, E);
+tuple_token_consumable!(A, B, C, D, E, F);
+
+#[cfg(test)]
+mod tests {
+    use super::{tokenize, Token, TokenConsumable, TokenStream};
+    use crate::common::{Empty, Whitespace};
+    use crate::consume_enum;
+    use crate::error::ConsumeErrorType::*;
+    use crate::ConsumeError;
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum Kind {
+        Number(u32),
+        Open,
+        Close,
+    }
+    consume_enum!(
+        Kind {
+            Number => [ value: u32; (value) ],
+            Open => [ > '('; ],
+            Close => [ > ')'; ]
+        }
+    );
+
+    #[derive(Debug, PartialEq)]
+    struct Group(Vec<u32>);
+
+    impl TokenConsumable<Kind> for Group {
+        fn consume_tokens<'t>(
+            tokens: TokenStream<'t, Kind>,
+        ) -> Result<(Self, TokenStream<'t, Kind>), ConsumeError> {
+            let mut tokens = tokens.expect(&Kind::Open)?;
+            let mut numbers = Vec::new();
+            while let Ok((value, rest)) = tokens.next_map(|kind| match kind {
+                Kind::Number(value) => Some(*value),
+                _ => None,
+            }) {
+                numbers.push(value);
+                tokens = rest;
+            }
+            let tokens = tokens.expect(&Kind::Close)?;
+            Ok((Group(numbers), tokens))
+        }
+    }
+
+    #[test]
+    fn test_tokenize() {
+        let tokens = tokenize::<Kind, Vec<Whitespace>>(" (1 ëë 2)").unwrap_err();
+        assert_eq!(tokens.causes()[0].index(), &4);
+
+        let tokens = tokenize::<Kind, Vec<Whitespace>>(" (1  23) ").unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::new(Kind::Open, 1..2),
+                Token::new(Kind::Number(1), 2..3),
+                Token::new(Kind::Number(23), 5..7),
+                Token::new(Kind::Close, 7..8),
+            ]
+        );
+
+        assert_eq!(tokenize::<Kind, Empty>("").unwrap(), vec![]);
+
+        let error = tokenize::<Empty, Empty>("a").unwrap_err();
+        assert_eq!(error.causes(), vec![&InvalidValue { index: 0 }]);
+    }
+
+    #[test]
+    fn test_token_consumable() {
+        let tokens = tokenize::<Kind, Vec<Whitespace>>("(1 2) () (3").unwrap();
+
+        let (groups, rest) = <Vec<Group>>::consume_tokens(TokenStream::new(&tokens)).unwrap();
+        assert_eq!(groups, vec![Group(vec![1, 2]), Group(vec![])]);
+        assert_eq!(rest.position(), 9);
+
+        let error = <(Group, Group, Group)>::consume_all_tokens(&tokens).unwrap_err();
+        assert_eq!(error.causes(), vec![&InsufficientTokens { index: 11 }]);
+
+        let stream = TokenStream::with_end(&tokens[..0], 20);
+        let error = Group::consume_tokens(stream).unwrap_err();
+        assert_eq!(error.causes(), vec![&InsufficientTokens { index: 20 }]);
+
+        let error = <Vec<Group>>::consume_all_tokens(&tokens).unwrap_err();
+        assert_eq!(error.causes(), vec![&InvalidValue { index: 9 }]);
+
+        let (open, rest) =
+            <Option<Token<Kind>>>::consume_tokens(TokenStream::new(&tokens)).unwrap();
+        assert_eq!(open.map(Token::into_kind), Some(Kind::Open));
+        assert_eq!(rest.tokens().len(), tokens.len() - 1);
+    }
+}
@@ -0,0 +1,326 @@
+//! A __Pratt parser__ for expressions with prefix, infix and postfix operators over [`Token`]s.
+//!
+//! A [`Pratt`] parser is built from a callback that turns a single token into an operand, and a
+//! list of operators with their binding powers. Every operator has a callback to build the
+//! expression from its operands.
+//!
+//! The binding powers decide how tightly an operator holds on to its operands. An infix operator
+//! has a left and a right binding power, where an operator with a left binding power lower than
+//! its right one is left associative, and right associative otherwise. Operators with higher
+//! binding powers bind tighter.
+//!
+//! # Examples
+//!
+//! ```
+//! use manger::consume_enum;
+//! use manger::common::Whitespace;
+//! use manger::tokens::{tokenize, pratt::Pratt};
+//!
+//! #[derive(Debug, Clone, PartialEq)]
+//! enum Kind {
+//!     Number(i32),
+//!     Op(char),
+//! }
+//! consume_enum!(
+//!     Kind {
+//!         Number => [ value: u16; (value.into()) ],
+//!         Op => [ op: char; (op) ]
+//!     }
+//! );
+//!
+//! let calculator = Pratt::new(|kind: &Kind| match kind {
+//!     Kind::Number(value) => Some(*value),
+//!     _ => None,
+//! })
+//! .group(Kind::Op('('), Kind::Op(')'))
+//! .infix(Kind::Op('+'), 1, 2, |_, lhs, rhs| lhs + rhs)
+//! .infix(Kind::Op('-'), 1, 2, |_, lhs, rhs| lhs - rhs)
+//! .infix(Kind::Op('*'), 3, 4, |_, lhs, rhs| lhs * rhs)
+//! .infix(Kind::Op('^'), 8, 7, |_, lhs, rhs| lhs.pow(rhs as u32))
+//! .prefix(Kind::Op('-'), 5, |_, rhs| -rhs)
+//! .postfix(Kind::Op('!'), 9, |_, lhs| (1..=lhs).product());
+//!
+//! let value = |source| calculator.consume_all_tokens(&tokenize::<Kind, Vec<Whitespace>>(source)?);
+//!
+//! assert_eq!(value("1 + 2 * 3")?, 7);
+//! assert_eq!(value("10 - 4 - 3")?, 3);
+//! assert_eq!(value("2 ^ 3 ^ 2")?, 512);
+//! assert_eq!(value("-(1 + 2) * 3!")?, -18);
+//!
+//! // The error points to the missing operand at the end of the source.
+//! assert_eq!(value("1 + 2 *").unwrap_err().causes()[0].index(), &7);
+//! # Ok::<(), manger::ConsumeError>(())
+//! ```
+
+use super::{Token, TokenStream};
+use crate::error::ConsumeErrorType::*;
+use crate::ConsumeError;
+
+type Operand<K, T> = Box<dyn Fn(&K) -> Option<T>>;
+type Unary<K, T> = Box<dyn Fn(&Token<K>, T) -> T>;
+type Binary<K, T> = Box<dyn Fn(&Token<K>, T, T) -> T>;
+
+/// A Pratt parser, which consumes expressions of `T` from tokens of kind `K`.
+///
+/// Operators are matched by comparing the kind of a token with the kind given to
+/// [`prefix`][Pratt::prefix], [`infix`][Pratt::infix] or [`postfix`][Pratt::postfix]. When an
+/// operator is added more than once, the first one is used.
+pub struct Pratt<K, T> {
+    operand: Operand<K, T>,
+    groups: Vec<(K, K)>,
+    prefix: Vec<(K, u8, Unary<K, T>)>,
+    infix: Vec<(K, u8, u8, Binary<K, T>)>,
+    postfix: Vec<(K, u8, Unary<K, T>)>,
+}
+
+impl<K: PartialEq, T> Pratt<K, T> {
+    /// Create a parser without operators, where `operand` turns the kind of a single token into an
+    /// operand. Tokens for which `operand` returns `None` are not operands.
+    pub fn new(operand: impl Fn(&K) -> Option<T> + 'static) -> Self {
+        Pratt {
+            operand: Box::new(operand),
+            groups: Vec::new(),
+            prefix: Vec::new(),
+            infix: Vec::new(),
+            postfix: Vec::new(),
+        }
+    }
+
+    /// Add a group, where an expression between `open` and `close` is an operand.
+    pub fn group(mut self, open: K, close: K) -> Self {
+        self.groups.push((open, close));
+        self
+    }
+
+    /// Add a prefix operator, which holds on to the expression after it with the binding power
+    /// `right`.
+    pub fn prefix(
+        mut self,
+        kind: K,
+        right: u8,
+        build: impl Fn(&Token<K>, T) -> T + 'static,
+    ) -> Self {
+        self.prefix.push((kind, right, Box::new(build)));
+        self
+    }
+
+    /// Add an infix operator, which holds on to the expression before it with the binding power
+    /// `left` and on to the expression after it with the binding power `right`.
+    pub fn infix(
+        mut self,
+        kind: K,
+        left: u8,
+        right: u8,
+        build: impl Fn(&Token<K>, T, T) -> T + 'static,
+    ) -> Self {
+        self.infix.push((kind, left, right, Box::new(build)));
+        self
+    }
+
+    /// Add a postfix operator, which holds on to the expression before it with the binding power
+    /// `left`.
+    pub fn postfix(
+        mut self,
+        kind: K,
+        left: u8,
+        build: impl Fn(&Token<K>, T) -> T + 'static,
+    ) -> Self {
+        self.postfix.push((kind, left, Box::new(build)));
+        self
+    }
+
+    /// Attempt to consume an expression from the start of `tokens`, returning the expression
+    /// along with the unconsumed tokens.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidValue` error at a token which is neither an operand nor a prefix
+    /// operator where an operand was expected, and an `InsufficientTokens` error when the tokens
+    /// end there. Groups which are not closed give an `InvalidValue` or `InsufficientTokens` error
+    /// where the closing token was expected.
+    pub fn consume_tokens<'t>(
+        &self,
+        tokens: TokenStream<'t, K>,
+    ) -> Result<(T, TokenStream<'t, K>), ConsumeError> {
+        self.expression(tokens, 0)
+    }
+
+    /// Attempt to consume an expression from all `tokens`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of [`consume_tokens`][Pratt::consume_tokens], or an `InvalidValue` error
+    /// at the first token that is left unconsumed.
+    pub fn consume_all_tokens(&self, tokens: &[Token<K>]) -> Result<T, ConsumeError> {
+        let (expression, rest) = self.consume_tokens(TokenStream::new(tokens))?;
+
+        if rest.is_empty() {
+            Ok(expression)
+        } else {
+            Err(ConsumeError::new_with(InvalidValue {
+                index: rest.position(),
+            }))
+        }
+    }
+
+    /// Consume an expression of which all operators have a binding power of at least `minimum`.
+    fn expression<'t>(
+        &self,
+        tokens: TokenStream<'t, K>,
+        minimum: u8,
+    ) -> Result<(T, TokenStream<'t, K>), ConsumeError> {
+        crate::limits::nest(|| Ok(self.nested_expression(tokens, minimum)))
+            .map_err(|err| err.offset(tokens.position()))?
+    }
+
+    fn nested_expression<'t>(
+        &self,
+        tokens: TokenStream<'t, K>,
+        minimum: u8,
+    ) -> Result<(T, TokenStream<'t, K>), ConsumeError> {
+        let (token, mut tokens) = tokens.next_token()?;
+
+        let mut lhs = if let Some((_, right, build)) =
+            self.prefix.iter().find(|(kind, ..)| kind == token.kind())
+        {
+            let (rhs, rest) = self.expression(tokens, *right)?;
+            tokens = rest;
+            build(token, rhs)
+        } else if let Some((_, close)) = self.groups.iter().find(|(open, _)| open == token.kind()) {
+            let (inner, rest) = self.expression(tokens, 0)?;
+            tokens = rest.expect(close)?;
+            inner
+        } else if let Some(operand) = (self.operand)(token.kind()) {
+            operand
+        } else {
+            return Err(ConsumeError::new_with(InvalidValue {
+                index: token.span.start,
+            }));
+        };
+
+        while let Ok((token, rest)) = tokens.next_token() {
+            if let Some((_, left, build)) =
+                self.postfix.iter().find(|(kind, ..)| kind == token.kind())
+            {
+                if *left < minimum {
+                    break;
+                }
+
+                lhs = build(token, lhs);
+                tokens = rest;
+            } else if let Some((_, left, right, build)) =
+                self.infix.iter().find(|(kind, ..)| kind == token.kind())
+            {
+                if *left < minimum {
+                    break;
+                }
+
+                let (rhs, rest) = self.expression(rest, *right)?;
+                lhs = build(token, lhs, rhs);
+                tokens = rest;
+            } else {
+                break;
+            }
+        }
+
+        Ok((lhs, tokens))
+    }
+}
+
+impl<K, T> std::fmt::Debug for Pratt<K, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Pratt")
+            .field("groups", &self.groups.len())
+            .field("prefix", &self.prefix.len())
+            .field("infix", &self.infix.len())
+            .field("postfix", &self.postfix.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Pratt;
+    use crate::common::Whitespace;
+    use crate::error::ConsumeErrorType::*;
+    use crate::limits::{self, Limit, Limits};
+    use crate::tokens::{tokenize, TokenStream};
+
+    fn parser() -> Pratt<char, String> {
+        Pratt::new(|kind: &char| kind.is_alphanumeric().then(|| kind.to_string()))
+            .group('(', ')')
+            .infix('+', 1, 2, |_, lhs, rhs| format!("({} + {})", lhs, rhs))
+            .infix('*', 3, 4, |_, lhs, rhs| format!("({} * {})", lhs, rhs))
+            .infix('=', 2, 1, |_, lhs, rhs| format!("({} = {})", lhs, rhs))
+            .prefix('-', 5, |_, rhs| format!("(-{})", rhs))
+            .postfix('!', 7, |_, lhs| format!("({}!)", lhs))
+            .postfix('?', 0, |token, lhs| {
+                format!("({}?@{})", lhs, token.span().start)
+            })
+    }
+
+    fn parse(source: &str) -> Result<String, crate::ConsumeError> {
+        parser().consume_all_tokens(&tokenize::<char, Vec<Whitespace>>(source)?)
+    }
+
+    #[test]
+    fn test_pratt_precedence() {
+        assert_eq!(parse("a").unwrap(), "a");
+        assert_eq!(parse("a + b * c").unwrap(), "(a + (b * c))");
+        assert_eq!(parse("a * b + c").unwrap(), "((a * b) + c)");
+        assert_eq!(parse("a + b + c").unwrap(), "((a + b) + c)");
+        assert_eq!(parse("a = b = c + d").unwrap(), "(a = (b = (c + d)))");
+        assert_eq!(parse("-a! * -b").unwrap(), "((-(a!)) * (-b))");
+        assert_eq!(parse("(a + b) * c").unwrap(), "((a + b) * c)");
+        assert_eq!(parse("a + b ?").unwrap(), "((a + b)?@6)");
+        assert_eq!(parse("--((a))").unwrap(), "(-(-a))");
+    }
+
+    #[test]
+    fn test_pratt_errors() {
+        assert_eq!(
+            parse("a +").unwrap_err().causes(),
+            vec![&InsufficientTokens { index: 3 }]
+        );
+        assert_eq!(
+            parse("a + * b").unwrap_err().causes(),
+            vec![&InvalidValue { index: 4 }]
+        );
+        assert_eq!(
+            parse("(a + b").unwrap_err().causes(),
+            vec![&InsufficientTokens { index: 6 }]
+        );
+        assert_eq!(
+            parse("(a b)").unwrap_err().causes(),
+            vec![&InvalidValue { index: 3 }]
+        );
+        assert_eq!(
+            parse("a b").unwrap_err().causes(),
+            vec![&InvalidValue { index: 2 }]
+        );
+
+        let tokens = tokenize::<char, Vec<Whitespace>>("a + b c").unwrap();
+        let (expression, rest) = parser().consume_tokens(TokenStream::new(&tokens)).unwrap();
+        assert_eq!(expression, "(a + b)");
+        assert_eq!(rest.position(), 6);
+    }
+
+    #[test]
+    fn test_pratt_depth_limit() {
+        let limits = Limits {
+            max_depth: Some(8),
+            ..Limits::default()
+        };
+
+        limits::scope(limits, || {
+            assert!(parse("((((a))))").is_ok());
+            assert_eq!(
+                parse("(((((((((a)))))))))").unwrap_err().causes(),
+                vec![&LimitExceeded {
+                    index: 8,
+                    limit: Limit::Depth
+                }]
+            );
+        });
+    }
+}
@@ -0,0 +1,347 @@
+//! __Tracing__ of consume attempts for debugging grammars.
+//!
+//! Every attempt to consume by the implementations generated by
+//! [`consume_struct`][crate::consume_struct!] and [`consume_enum`][crate::consume_enum!] and by
+//! the core combinators, such as [`Vec<T>`], [`Option<T>`] and
+//! [`Either<L, R>`][either::Either], goes through [`attempt`]. Two cargo features make these
+//! attempts visible. Without them, [`attempt`] calls the consumer directly.
+//!
+//! With the `trace` feature enabled, every attempt is instrumented with a `tracing` span at the
+//! `TRACE` level. The span records the name of the type and, within a [`context::scope`], the
+//! utf-8 character index at which the attempt started. An event within the span records either
+//! the amount of consumed utf-8 characters or the error. Running the consumer under a `tracing`
+//! subscriber, such as the one of `tracing-subscriber`, therefore prints a trace of the nested
+//! attempts.
+//!
+//! With the `debug-trace` feature enabled,
+//! [`Consumable::consume_debug_from`][crate::Consumable::consume_debug_from] returns the attempts
+//! as a [`ParseTrace`] tree, which tools can inspect or render.
+//!
+//! [`context::scope`]: crate::context::scope
+
+use crate::ConsumeError;
+#[cfg(feature = "debug-trace")]
+use crate::ConsumeErrorType;
+#[cfg(feature = "debug-trace")]
+use std::{cell::RefCell, fmt, ops::Range};
+
+/// Run `consume` as an attempt of `T` to consume from `source`.
+///
+/// Hand-written consumers can use this to show up in the traces as well.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{Consumable, ConsumeError};
+/// use manger::trace;
+///
+/// struct Answer;
+///
+/// impl Consumable for Answer {
+///     fn consume_from(source: &str) -> Result<(Self, &str), ConsumeError> {
+///         trace::attempt::<Self, _>(source, || {
+///             let (_, unconsumed) = <(u32, char)>::consume_from(source)?;
+///             Ok((Answer, unconsumed))
+///         })
+///     }
+/// }
+///
+/// assert!(Answer::consume_from("42!").is_ok());
+/// ```
+#[inline(always)]
+pub fn attempt<'s, T: ?Sized, R>(
+    source: &'s str,
+    consume: impl FnOnce() -> Result<(R, &'s str), ConsumeError>,
+) -> Result<(R, &'s str), ConsumeError> {
+    record::<T, R>(source, || instrument::<T, R>(source, consume))
+}
+
+#[cfg(not(feature = "trace"))]
+#[inline(always)]
+#[allow(clippy::extra_unused_type_parameters)]
+fn instrument<'s, T: ?Sized, R>(
+    _source: &'s str,
+    consume: impl FnOnce() -> Result<(R, &'s str), ConsumeError>,
+) -> Result<(R, &'s str), ConsumeError> {
+    consume()
+}
+
+#[cfg(feature = "trace")]
+fn instrument<'s, T: ?Sized, R>(
+    source: &'s str,
+    consume: impl FnOnce() -> Result<(R, &'s str), ConsumeError>,
+) -> Result<(R, &'s str), ConsumeError> {
+    let span = tracing::trace_span!(
+        "consume",
+        r#type = std::any::type_name::<T>(),
+        offset = tracing::field::Empty
+    );
+    if span.is_disabled() {
+        return consume();
+    }
+
+    if let Some(position) = crate::context::position(source) {
+        span.record("offset", position.index());
+    }
+    let _entered = span.enter();
+
+    let result = consume();
+    match &result {
+        Ok((_, unconsumed)) => tracing::trace!(
+            consumed = crate::strs::consumed_length(source, unconsumed),
+            "consumed"
+        ),
+        Err(error) => tracing::trace!(%error, "failed"),
+    }
+
+    result
+}
+
+#[cfg(not(feature = "debug-trace"))]
+#[inline(always)]
+#[allow(clippy::extra_unused_type_parameters)]
+fn record<'s, T: ?Sized, R>(
+    _source: &'s str,
+    consume: impl FnOnce() -> Result<(R, &'s str), ConsumeError>,
+) -> Result<(R, &'s str), ConsumeError> {
+    consume()
+}
+
+/// The outcome of an attempt within a [`ParseTrace`].
+#[cfg(feature = "debug-trace")]
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub enum Outcome {
+    /// The attempt consumed the span of the node.
+    Consumed,
+
+    /// The attempt failed with these causes. Their indices are utf-8 character indices within the
+    /// whole input.
+    Failed(Vec<ConsumeErrorType>),
+}
+
+/// A tree of the attempts to consume made by
+/// [`Consumable::consume_debug_from`][crate::Consumable::consume_debug_from].
+///
+/// Every node is one [`attempt`] and its children are the attempts made while consuming it, in
+/// order. Only attempts that go through [`attempt`] show up in the tree, so consumers such as
+/// tuples and primitive types are part of the node of their parent.
+///
+/// The `Display` implementation shows one attempt per line, indented by its depth.
+///
+/// # Examples
+///
+/// ```
+/// use manger::{consume_enum, Consumable};
+/// use manger::trace::Outcome;
+///
+/// #[derive(Debug)]
+/// enum Value {
+///     Number(u32),
+///     List(Vec<u32>),
+/// }
+/// consume_enum!(
+///     Value {
+///         Number => [ value: u32; (value) ],
+///         List => [ > '[', values: Vec<u32>, > ']'; (values) ]
+///     }
+/// );
+///
+/// let (result, trace) = Value::consume_debug_from("[1]");
+///
+/// assert!(result.is_ok());
+/// assert!(trace.type_name().ends_with("Value"));
+/// assert_eq!(trace.span(), 0..3);
+/// assert_eq!(trace.outcome(), &Outcome::Consumed);
+///
+/// let list = &trace.children()[0];
+/// assert_eq!(list.type_name(), "alloc::vec::Vec<u32>");
+/// assert_eq!(list.span(), 1..2);
+/// ```
+#[cfg(feature = "debug-trace")]
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct ParseTrace {
+    type_name: &'static str,
+    span: Range<usize>,
+    outcome: Outcome,
+    children: Vec<ParseTrace>,
+}
+
+#[cfg(feature = "debug-trace")]
+impl ParseTrace {
+    /// Get the name of the type that attempted to consume, as given by [`std::any::type_name`].
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// Get the utf-8 character indices within the whole input that were consumed. For failed
+    /// attempts, the span is empty and starts where the attempt started.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// Get the outcome of the attempt.
+    pub fn outcome(&self) -> &Outcome {
+        &self.outcome
+    }
+
+    /// Get the attempts made while consuming, in order.
+    pub fn children(&self) -> &[ParseTrace] {
+        &self.children
+    }
+
+    fn fmt_depth(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        write!(
+            f,
+            "{:indent$}{} {:?}",
+            "",
+            self.type_name,
+            self.span,
+            indent = depth * 2
+        )?;
+
+        match &self.outcome {
+            Outcome::Consumed => writeln!(f, " consumed")?,
+            Outcome::Failed(causes) => writeln!(f, " failed: {:?}", causes)?,
+        }
+
+        self.children
+            .iter()
+            .try_for_each(|child| child.fmt_depth(f, depth + 1))
+    }
+}
+
+#[cfg(feature = "debug-trace")]
+impl fmt::Display for ParseTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_depth(f, 0)
+    }
+}
+
+/// The input of a recording session and the children of the attempts that are in progress.
+#[cfg(feature = "debug-trace")]
+struct Recording {
+    address: usize,
+    input: String,
+    stack: Vec<Vec<ParseTrace>>,
+}
+
+#[cfg(feature = "debug-trace")]
+impl Recording {
+    /// Get the utf-8 character index of `source` within the input, or `0` if `source` is not part
+    /// of the input.
+    fn index_of(&self, source: &str) -> usize {
+        let offset = (source.as_ptr() as usize).wrapping_sub(self.address);
+
+        self.input
+            .get(..offset)
+            .map_or(0, |consumed| consumed.chars().count())
+    }
+}
+
+#[cfg(feature = "debug-trace")]
+thread_local! {
+    static RECORDING: RefCell<Option<Recording>> = const { RefCell::new(None) };
+}
+
+/// Restores the previous recording session, also when unwinding.
+#[cfg(feature = "debug-trace")]
+struct RecordingGuard(Option<Recording>);
+
+#[cfg(feature = "debug-trace")]
+impl Drop for RecordingGuard {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        RECORDING.with(|recording| *recording.borrow_mut() = previous);
+    }
+}
+
+#[cfg(feature = "debug-trace")]
+fn record<'s, T: ?Sized, R>(
+    source: &'s str,
+    consume: impl FnOnce() -> Result<(R, &'s str), ConsumeError>,
+) -> Result<(R, &'s str), ConsumeError> {
+    let start = RECORDING.with(|recording| {
+        recording.borrow_mut().as_mut().map(|recording| {
+            recording.stack.push(Vec::new());
+            recording.index_of(source)
+        })
+    });
+
+    let start = match start {
+        Some(start) => start,
+        None => return consume(),
+    };
+
+    let result = consume();
+
+    let (span, outcome) = match &result {
+        Ok((_, unconsumed)) => (
+            start..start + crate::strs::consumed_length(source, unconsumed),
+            Outcome::Consumed,
+        ),
+        Err(error) => (
+            start..start,
+            Outcome::Failed(
+                error
+                    .causes()
+                    .into_iter()
+                    .map(|cause| cause.offset(start))
+                    .collect(),
+            ),
+        ),
+    };
+
+    RECORDING.with(|recording| {
+        if let Some(recording) = recording.borrow_mut().as_mut() {
+            let children = recording.stack.pop().unwrap_or_default();
+            let node = ParseTrace {
+                type_name: std::any::type_name::<T>(),
+                span,
+                outcome,
+                children,
+            };
+
+            if let Some(siblings) = recording.stack.last_mut() {
+                siblings.push(node);
+            }
+        }
+    });
+
+    result
+}
+
+/// Consume `T` from `source` with `consume` while recording all attempts.
+#[cfg(feature = "debug-trace")]
+pub(crate) fn record_session<'s, T, R>(
+    source: &'s str,
+    consume: impl FnOnce() -> Result<(R, &'s str), ConsumeError>,
+) -> (Result<(R, &'s str), ConsumeError>, ParseTrace) {
+    let previous = RECORDING.with(|recording| {
+        recording.borrow_mut().replace(Recording {
+            address: source.as_ptr() as usize,
+            input: source.to_string(),
+            stack: vec![Vec::new()],
+        })
+    });
+    let guard = RecordingGuard(previous);
+
+    let result = record::<T, R>(source, consume);
+
+    let mut roots = RECORDING.with(|recording| {
+        recording
+            .borrow_mut()
+            .as_mut()
+            .and_then(|recording| recording.stack.pop())
+            .unwrap_or_default()
+    });
+    drop(guard);
+
+    let mut root = roots.pop().expect("the session records its root attempt");
+
+    // The root attempt wraps the attempt of the generated implementation of `T`, if it has one.
+    if root.children.len() == 1 && root.children[0].type_name == root.type_name {
+        root = root.children.remove(0);
+    }
+
+    (result, root)
+}
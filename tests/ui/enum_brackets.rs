@@ -0,0 +1,14 @@
+use manger::consume_enum;
+
+enum Answer {
+    Yes,
+    No,
+}
+consume_enum!(
+    Answer [
+        Yes => [ > "yes"; ],
+        No => [ > "no"; ]
+    ]
+);
+
+fn main() {}
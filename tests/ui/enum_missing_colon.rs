@@ -0,0 +1,17 @@
+use manger::consume_enum;
+
+enum Pair {
+    Numbers(u32, u32),
+}
+consume_enum!(
+    Pair {
+        Numbers => [
+            first: u32,
+            > ',',
+            second u32;
+            (first, second)
+        ]
+    }
+);
+
+fn main() {}
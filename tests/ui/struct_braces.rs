@@ -0,0 +1,13 @@
+use manger::consume_struct;
+
+struct EncasedInteger(i32);
+consume_struct!(
+    EncasedInteger => {
+        > '(',
+        value: i32,
+        > ')';
+        (value)
+    }
+);
+
+fn main() {}